@@ -2,14 +2,19 @@ use std::sync::Arc;
 
 use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
 use aries_vcx_ledger::ledger::indy_vdr_ledger::{DefaultIndyLedgerRead, DefaultIndyLedgerWrite};
-use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
-
-use crate::handlers::{
-    connection::ServiceConnections, credential_definition::ServiceCredentialDefinitions,
-    did_exchange::DidcommHandlerDidExchange, holder::ServiceCredentialsHolder,
-    issuer::ServiceCredentialsIssuer, out_of_band::ServiceOutOfBand, prover::ServiceProver,
-    revocation_registry::ServiceRevocationRegistries, schema::ServiceSchemas,
-    verifier::ServiceVerifier,
+use aries_vcx_wallet::wallet::{
+    askar::askar_tenant_manager::AskarTenantManager, base_wallet::BaseWallet,
+};
+
+use crate::{
+    error::{AgentError, AgentErrorKind, AgentResult},
+    handlers::{
+        connection::ServiceConnections, credential_definition::ServiceCredentialDefinitions,
+        did_exchange::DidcommHandlerDidExchange, holder::ServiceCredentialsHolder,
+        issuer::ServiceCredentialsIssuer, out_of_band::ServiceOutOfBand, prover::ServiceProver,
+        revocation_registry::ServiceRevocationRegistries, schema::ServiceSchemas,
+        tenants::ServiceTenants, verifier::ServiceVerifier,
+    },
 };
 
 pub struct Agent<W> {
@@ -28,6 +33,7 @@ pub struct Agent<W> {
     pub(super) prover: Arc<ServiceProver<W>>,
     pub(super) out_of_band: Arc<ServiceOutOfBand<W>>,
     pub(super) did_exchange: Arc<DidcommHandlerDidExchange<W>>,
+    pub(super) tenants: Option<Arc<ServiceTenants>>,
 }
 
 // Note: We do this manually, otherwise compiler is requesting us to implement Clone for generic
@@ -51,6 +57,7 @@ impl<W> Clone for Agent<W> {
             prover: self.prover.clone(),
             out_of_band: self.out_of_band.clone(),
             did_exchange: self.did_exchange.clone(),
+            tenants: self.tenants.clone(),
         }
     }
 }
@@ -119,4 +126,16 @@ impl<T: BaseWallet> Agent<T> {
     pub fn public_did(&self) -> &str {
         self.did_exchange.public_did()
     }
+
+    /// Enables hosting of tenant wallets as separate profiles of the store backing `manager`.
+    pub fn with_tenants(mut self, manager: AskarTenantManager) -> Self {
+        self.tenants = Some(Arc::new(ServiceTenants::new(manager)));
+        self
+    }
+
+    pub fn tenants(&self) -> AgentResult<Arc<ServiceTenants>> {
+        self.tenants
+            .clone()
+            .ok_or_else(|| AgentError::from_kind(AgentErrorKind::MultiTenancyServiceUnavailable))
+    }
 }
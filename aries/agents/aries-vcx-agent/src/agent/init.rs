@@ -22,6 +22,7 @@ use aries_vcx_wallet::wallet::{
     base_wallet::{issuer_config::IssuerConfig, BaseWallet, ManageWallet},
 };
 use did_peer::resolver::PeerDidResolver;
+use did_resolver_key::resolver::DidKeyResolver;
 use did_resolver_registry::ResolverRegistry;
 use did_resolver_sov::resolution::DidSovResolver;
 use display_as_json::Display;
@@ -130,11 +131,13 @@ impl<W: BaseWallet> Agent<W> {
         let ledger_write = Arc::new(ledger_write);
 
         let did_peer_resolver = PeerDidResolver::new();
+        let did_key_resolver = DidKeyResolver::new();
         let did_sov_resolver: DidSovResolver<Arc<DefaultIndyLedgerRead>, DefaultIndyLedgerRead> =
             DidSovResolver::new(ledger_read.clone());
         let did_resolver_registry = Arc::new(
             ResolverRegistry::new()
                 .register_resolver("peer".into(), did_peer_resolver)
+                .register_resolver("key".into(), did_key_resolver)
                 .register_resolver("sov".into(), did_sov_resolver),
         );
 
@@ -209,6 +212,7 @@ impl<W: BaseWallet> Agent<W> {
             holder,
             verifier,
             prover,
+            tenants: None,
             issuer_did: issuer_did.to_string(),
         })
     }
@@ -7,4 +7,5 @@ pub(crate) mod out_of_band;
 pub(crate) mod prover;
 pub(crate) mod revocation_registry;
 pub(crate) mod schema;
+pub(crate) mod tenants;
 pub(crate) mod verifier;
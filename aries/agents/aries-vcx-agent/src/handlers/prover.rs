@@ -2,6 +2,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use anoncreds_types::data_types::messages::cred_selection::SelectedCredentials;
 use aries_vcx::{
+    common::proofs::prover::credential_selection::CredentialSelector,
     handlers::{proof_presentation::prover::Prover, util::PresentationProposalData},
     messages::{
         msg_fields::protocols::present_proof::v1::{
@@ -77,21 +78,14 @@ impl<T: BaseWallet> ServiceProver<T> {
         prover: &Prover,
         tails_dir: Option<&str>,
     ) -> AgentResult<SelectedCredentials> {
-        let credentials = prover
-            .retrieve_credentials(self.wallet.as_ref(), &self.anoncreds)
-            .await?;
-
-        let mut res_credentials = SelectedCredentials::default();
-
-        for (referent, cred_array) in credentials.credentials_by_referent.into_iter() {
-            if !cred_array.is_empty() {
-                let first_cred = cred_array[0].clone();
-                let tails_dir = tails_dir.map(|x| x.to_owned());
-                res_credentials
-                    .select_credential_for_referent_from_retrieved(referent, first_cred, tails_dir);
-            }
+        let mut selector = CredentialSelector::new();
+        if let Some(tails_dir) = tails_dir {
+            selector = selector.with_tails_dir(tails_dir);
         }
-        Ok(res_credentials)
+        let selection = prover
+            .select_credentials(self.wallet.as_ref(), &self.anoncreds, &selector)
+            .await?;
+        Ok(selection.into_selected_credentials()?)
     }
 
     pub fn create_from_request(
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use aries_vcx_wallet::wallet::{
+    askar::{askar_tenant_manager::AskarTenantManager, AskarWallet},
+    base_wallet::did_data::DidData,
+    structs_io::UnpackMessageOutput,
+};
+
+use crate::AgentResult;
+
+pub struct ServiceTenants {
+    manager: AskarTenantManager,
+}
+
+impl ServiceTenants {
+    pub fn new(manager: AskarTenantManager) -> Self {
+        Self { manager }
+    }
+
+    pub async fn create_tenant(&self, tenant_id: &str) -> AgentResult<Arc<AskarWallet>> {
+        Ok(Arc::new(self.manager.create_tenant(tenant_id).await?))
+    }
+
+    pub async fn tenant_wallet(&self, tenant_id: &str) -> AgentResult<Arc<AskarWallet>> {
+        Ok(Arc::new(self.manager.open_tenant(tenant_id).await?))
+    }
+
+    pub async fn list_tenants(&self) -> AgentResult<Vec<String>> {
+        Ok(self.manager.list_tenants().await?)
+    }
+
+    pub async fn remove_tenant(&self, tenant_id: &str) -> AgentResult<()> {
+        Ok(self.manager.remove_tenant(tenant_id).await?)
+    }
+
+    pub async fn create_tenant_did(&self, tenant_id: &str) -> AgentResult<DidData> {
+        Ok(self
+            .manager
+            .create_and_store_tenant_did(tenant_id, None)
+            .await?)
+    }
+
+    pub async fn sync_route_keys(&self, tenant_id: &str) -> AgentResult<usize> {
+        Ok(self.manager.sync_route_keys(tenant_id).await?)
+    }
+
+    pub async fn route_inbound(&self, msg: &[u8]) -> AgentResult<(String, Arc<AskarWallet>)> {
+        let (tenant_id, wallet) = self.manager.route_message(msg).await?;
+        Ok((tenant_id, Arc::new(wallet)))
+    }
+
+    pub async fn unpack_inbound(&self, msg: &[u8]) -> AgentResult<(String, UnpackMessageOutput)> {
+        Ok(self.manager.unpack_tenant_message(msg).await?)
+    }
+}
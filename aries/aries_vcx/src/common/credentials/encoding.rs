@@ -1,109 +1,539 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use anoncreds_types::data_types::messages::{
+    pres_request::{PredicateInfo, PredicateTypes, PresentationRequest},
+    presentation::Presentation,
+};
+use num_bigint::{BigInt, Sign};
+use serde_json::Value;
+use time::{Date, Month, OffsetDateTime};
 
 use crate::{
     errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
-    utils::openssl::encode,
+    utils::openssl::{encode, hash},
 };
 
-pub fn encode_attributes(attributes: &str) -> VcxResult<String> {
-    let mut dictionary = HashMap::new();
-    match serde_json::from_str::<HashMap<String, serde_json::Value>>(attributes) {
-        Ok(mut attributes) => {
-            for (attr, attr_data) in attributes.iter_mut() {
-                let first_attr = match attr_data {
-                    // new style input such as {"address2":"101 Wilson Lane"}
-                    serde_json::Value::String(str_type) => str_type,
-
-                    // old style input such as {"address2":["101 Wilson Lane"]}
-                    serde_json::Value::Array(array_type) => {
-                        let attrib_value: &str =
-                            match array_type.first().and_then(serde_json::Value::as_str) {
-                                Some(x) => x,
-                                None => {
-                                    return Err(AriesVcxError::from_msg(
-                                        AriesVcxErrorKind::InvalidAttributesStructure,
-                                        "Attribute value not found",
-                                    ));
-                                }
-                            };
-
-                        warn!(
-                            "Old attribute format detected. See vcx_issuer_create_credential api \
-                             for additional information."
-                        );
-                        attrib_value
-                    }
-                    _ => {
-                        return Err(AriesVcxError::from_msg(
-                            AriesVcxErrorKind::InvalidJson,
-                            "Invalid Json for Attribute data",
-                        ));
-                    }
-                };
+/// Type of a credential attribute, determining how its raw values are encoded. An empty raw
+/// value stands for null and is hashed regardless of the attribute type.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeType {
+    /// Integers up to `u32::MAX` are encoded as themselves, any other value is hashed, following
+    /// the indy conventions
+    String,
+    /// Non-negative integers below 2^256 are encoded as themselves, negative and larger integers
+    /// are hashed. Only values within `i32` can be used in predicates.
+    Integer,
+    /// `true` / `false` (or `1` / `0`), encoded as `1` / `0`
+    Boolean,
+    /// Calendar date as `YYYY-MM-DD` or `YYYYMMDD`, encoded as the `YYYYMMDD` integer so that
+    /// dates can be compared in predicates
+    Date,
+    /// Floating point number, hashed from its shortest representation so that `1.50` and `1.5`
+    /// encode alike. Floats cannot be used in predicates.
+    Float,
+}
+
+impl AttributeType {
+    pub fn encode(&self, raw: &str) -> VcxResult<String> {
+        if raw.is_empty() {
+            return Ok(hash(raw));
+        }
+        match self {
+            Self::String => encode(raw),
+            Self::Integer => encode_integer(raw),
+            Self::Boolean => encode_boolean(raw),
+            Self::Date => encode_date(raw).map(|date| date.to_string()),
+            Self::Float => encode_float(raw),
+        }
+    }
+}
 
-                let encoded = encode(first_attr)?;
-                let attrib_values = json!({
-                    "raw": first_attr,
+fn encoding_error(raw: &str, attr_type: &str, reason: impl fmt::Display) -> AriesVcxError {
+    AriesVcxError::from_msg(
+        AriesVcxErrorKind::InvalidInput,
+        format!("Cannot encode \"{raw}\" as {attr_type}: {reason}"),
+    )
+}
+
+fn encode_integer(raw: &str) -> VcxResult<String> {
+    let value: BigInt = raw
+        .parse()
+        .map_err(|err| encoding_error(raw, "integer", err))?;
+    if value.sign() != Sign::Minus && value.bits() <= 256 {
+        Ok(value.to_string())
+    } else {
+        Ok(hash(&value.to_string()))
+    }
+}
+
+fn encode_boolean(raw: &str) -> VcxResult<String> {
+    match raw.to_lowercase().as_str() {
+        "true" | "1" => Ok("1".to_string()),
+        "false" | "0" => Ok("0".to_string()),
+        _ => Err(encoding_error(raw, "boolean", "expected true or false")),
+    }
+}
+
+fn encode_float(raw: &str) -> VcxResult<String> {
+    let value: f64 = raw
+        .parse()
+        .map_err(|err| encoding_error(raw, "float", err))?;
+    if !value.is_finite() {
+        return Err(encoding_error(raw, "float", "value is not finite"));
+    }
+    Ok(hash(&value.to_string()))
+}
+
+/// Encodes a `YYYY-MM-DD` or `YYYYMMDD` date as the `YYYYMMDD` integer.
+pub fn encode_date(raw: &str) -> VcxResult<i32> {
+    let bytes = raw.as_bytes();
+    let digits = match bytes.len() {
+        10 if bytes[4] == b'-' && bytes[7] == b'-' => raw.replacen('-', "", 2),
+        8 => raw.to_string(),
+        _ => {
+            return Err(encoding_error(
+                raw,
+                "date",
+                "expected YYYY-MM-DD or YYYYMMDD",
+            ))
+        }
+    };
+    if digits.len() != 8 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(encoding_error(
+            raw,
+            "date",
+            "expected YYYY-MM-DD or YYYYMMDD",
+        ));
+    }
+    // all ascii digits, the parsing cannot fail
+    let year: i32 = digits[..4].parse().unwrap_or_default();
+    let month: u8 = digits[4..6].parse().unwrap_or_default();
+    let day: u8 = digits[6..].parse().unwrap_or_default();
+    let month = Month::try_from(month).map_err(|err| encoding_error(raw, "date", err))?;
+    let date = Date::from_calendar_date(year, month, day)
+        .map_err(|err| encoding_error(raw, "date", err))?;
+    Ok(date_to_int(date))
+}
+
+fn date_to_int(date: Date) -> i32 {
+    date.year() * 10000 + u8::from(date.month()) as i32 * 100 + date.day() as i32
+}
+
+/// Latest birth date, as the encoded `YYYYMMDD` integer, of persons at least `min_age` years old
+/// on `on`. People born on February 29th come of age on March 1st in common years.
+pub fn birth_date_bound(min_age: u32, on: Date) -> VcxResult<i32> {
+    let year = i32::try_from(min_age)
+        .ok()
+        .and_then(|min_age| on.year().checked_sub(min_age))
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                format!("Minimum age {min_age} is out of range"),
+            )
+        })?;
+    let bound = Date::from_calendar_date(year, on.month(), on.day())
+        .or_else(|_| Date::from_calendar_date(year, on.month(), on.day() - 1))
+        .map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                format!("Minimum age {min_age} is out of range: {err}"),
+            )
+        })?;
+    Ok(date_to_int(bound))
+}
+
+/// Predicate proving that the holder is at least `min_age` years old today, `attribute` being a
+/// birth date attribute of type [`AttributeType::Date`].
+pub fn min_age_predicate(attribute: &str, min_age: u32) -> VcxResult<PredicateInfo> {
+    let today = OffsetDateTime::now_utc().date();
+    Ok(PredicateInfo {
+        name: attribute.to_string(),
+        p_type: PredicateTypes::LE,
+        p_value: birth_date_bound(min_age, today)?,
+        restrictions: None,
+        non_revoked: None,
+    })
+}
+
+/// Encoder of the raw values of a single attribute.
+pub type CustomEncoder = Arc<dyn Fn(&str) -> VcxResult<String> + Send + Sync>;
+
+#[derive(Clone)]
+enum Encoding {
+    Typed(AttributeType),
+    Custom(CustomEncoder),
+}
+
+impl Encoding {
+    fn encode(&self, raw: &str) -> VcxResult<String> {
+        match self {
+            Self::Typed(attr_type) => attr_type.encode(raw),
+            Self::Custom(encoder) => encoder(raw),
+        }
+    }
+}
+
+impl fmt::Debug for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Typed(attr_type) => write!(f, "{attr_type:?}"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+impl PartialEq for Encoding {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Typed(this), Self::Typed(other)) => this == other,
+            (Self::Custom(this), Self::Custom(other)) => Arc::ptr_eq(this, other),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Encoding {}
+
+/// Raw value of a credential attribute whose encoded value differs from the encoding of the raw
+/// value.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct EncodingMismatch {
+    /// Attribute name, or for presentations the referent the value was revealed for
+    pub attribute: String,
+    pub raw: String,
+    pub encoded: String,
+    /// Encoding of the raw value, `None` if the raw value is invalid for the attribute's encoding
+    pub expected: Option<String>,
+}
+
+impl fmt::Display for EncodingMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.expected {
+            Some(expected) => write!(
+                f,
+                "{}: raw value \"{}\" encodes to {}, not {}",
+                self.attribute, self.raw, expected, self.encoded
+            ),
+            None => write!(
+                f,
+                "{}: raw value \"{}\" cannot be encoded",
+                self.attribute, self.raw
+            ),
+        }
+    }
+}
+
+/// Encodes credential attribute values using the types and custom encoders registered for the
+/// attributes of each schema. Attributes without a registered encoding are encoded as
+/// [`AttributeType::String`], the indy conventions, unless given as JSON booleans or numbers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AttributeEncoder {
+    // schema id -> attribute name in its normalized form -> encoding
+    schemas: HashMap<String, HashMap<String, Encoding>>,
+}
+
+// attribute names are matched ignoring case and spaces, as anoncreds does
+fn normalize_attr_name(name: &str) -> String {
+    name.replace(' ', "").to_lowercase()
+}
+
+impl AttributeEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_attribute_type(
+        mut self,
+        schema_id: impl Into<String>,
+        attribute: &str,
+        attr_type: AttributeType,
+    ) -> Self {
+        self.schemas
+            .entry(schema_id.into())
+            .or_default()
+            .insert(normalize_attr_name(attribute), Encoding::Typed(attr_type));
+        self
+    }
+
+    pub fn with_custom_encoder<F>(
+        mut self,
+        schema_id: impl Into<String>,
+        attribute: &str,
+        encoder: F,
+    ) -> Self
+    where
+        F: Fn(&str) -> VcxResult<String> + Send + Sync + 'static,
+    {
+        self.schemas.entry(schema_id.into()).or_default().insert(
+            normalize_attr_name(attribute),
+            Encoding::Custom(Arc::new(encoder)),
+        );
+        self
+    }
+
+    fn encoding(&self, schema_id: Option<&str>, attribute: &str) -> Option<&Encoding> {
+        self.schemas
+            .get(schema_id?)?
+            .get(&normalize_attr_name(attribute))
+    }
+
+    /// Encodes the raw value of an attribute of the schema, `None` standing for an unknown
+    /// schema.
+    pub fn encode(&self, schema_id: Option<&str>, attribute: &str, raw: &str) -> VcxResult<String> {
+        match self.encoding(schema_id, attribute) {
+            Some(encoding) => encoding.encode(raw),
+            None => AttributeType::String.encode(raw),
+        }
+    }
+
+    /// Checks that `encoded` is the encoding of `raw`.
+    pub fn check(
+        &self,
+        schema_id: Option<&str>,
+        attribute: &str,
+        raw: &str,
+        encoded: &str,
+    ) -> Option<EncodingMismatch> {
+        let expected = self.encode(schema_id, attribute, raw).ok();
+        if expected.as_deref() == Some(encoded) {
+            return None;
+        }
+        Some(EncodingMismatch {
+            attribute: attribute.to_string(),
+            raw: raw.to_string(),
+            encoded: encoded.to_string(),
+            expected,
+        })
+    }
+
+    /// Encodes credential values given in any of the formats accepted by
+    /// [`encode_attributes`]. Values can also be given as JSON booleans, numbers or null, and
+    /// already encoded as `{"raw": .., "encoded": ..}`, in which case the encoded value is
+    /// checked against the raw value. The raw value of a JSON boolean or number is its JSON
+    /// text, encoded like a string unless the attribute has a registered type, as verifiers only
+    /// see the raw string.
+    pub fn encode_attributes(
+        &self,
+        schema_id: Option<&str>,
+        attributes: &str,
+    ) -> VcxResult<String> {
+        let mut dictionary = HashMap::new();
+        let mut mismatches = vec![];
+        for (name, value) in parse_credential_values(attributes)? {
+            let (raw, encoded) = match value {
+                CredentialValue::Raw(value) => {
+                    let raw = match &value {
+                        Value::String(raw) => raw.clone(),
+                        Value::Null => String::new(),
+                        value => value.to_string(),
+                    };
+                    let encoded = self.encode(schema_id, &name, &raw)?;
+                    (raw, encoded)
+                }
+                CredentialValue::Encoded { raw, encoded } => {
+                    if let Some(mismatch) = self.check(schema_id, &name, &raw, &encoded) {
+                        mismatches.push(mismatch);
+                    }
+                    (raw, encoded)
+                }
+            };
+            dictionary.insert(
+                name,
+                json!({
+                    "raw": raw,
                     "encoded": encoded
-                });
+                }),
+            );
+        }
+        if !mismatches.is_empty() {
+            mismatches.sort_by(|a, b| a.attribute.cmp(&b.attribute));
+            let mismatches: Vec<String> = mismatches.iter().map(ToString::to_string).collect();
+            warn!("Encoded credential values do not match their raw values: {mismatches:?}");
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidAttributesStructure,
+                format!(
+                    "Encoded credential values do not match their raw values: {}",
+                    mismatches.join("; ")
+                ),
+            ));
+        }
+        serde_json::to_string_pretty(&dictionary).map_err(|err| {
+            warn!("Invalid Json for Attribute data");
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Invalid Json for Attribute data: {err}"),
+            )
+        })
+    }
 
-                dictionary.insert(attr.to_string(), attrib_values);
-            }
-            serde_json::to_string_pretty(&dictionary).map_err(|err| {
-                warn!("Invalid Json for Attribute data");
-                AriesVcxError::from_msg(
-                    AriesVcxErrorKind::InvalidJson,
-                    format!("Invalid Json for Attribute data: {err}"),
+    /// Checks the values revealed for `referent` in the presentation, reporting mismatches by
+    /// referent, or `referent.name` for attribute groups.
+    pub fn check_revealed(
+        &self,
+        presentation_request: &PresentationRequest,
+        presentation: &Presentation,
+        referent: &str,
+    ) -> Vec<EncodingMismatch> {
+        let requested_proof = &presentation.requested_proof;
+        let schema_id = |sub_proof_index: u32| {
+            presentation
+                .identifiers
+                .get(sub_proof_index as usize)
+                .map(|identifier| identifier.schema_id.0.as_str())
+        };
+        let mut mismatches = vec![];
+        if let Some(attr) = requested_proof.revealed_attrs.get(referent) {
+            let name = presentation_request
+                .value()
+                .requested_attributes
+                .get(referent)
+                .and_then(|info| info.name.as_deref())
+                .unwrap_or(referent);
+            mismatches.extend(
+                self.check(
+                    schema_id(attr.sub_proof_index),
+                    name,
+                    &attr.raw,
+                    &attr.encoded,
                 )
-            })
+                .map(|mismatch| EncodingMismatch {
+                    attribute: referent.to_string(),
+                    ..mismatch
+                }),
+            );
         }
-        Err(_err) => {
-            // TODO: Check error type
-            match serde_json::from_str::<Vec<serde_json::Value>>(attributes) {
-                Ok(mut attributes) => {
-                    for cred_value in attributes.iter_mut() {
-                        let name = cred_value.get("name").ok_or(AriesVcxError::from_msg(
-                            AriesVcxErrorKind::InvalidAttributesStructure,
-                            format!("No 'name' field in cred_value: {cred_value:?}"),
-                        ))?;
-                        let value = cred_value.get("value").ok_or(AriesVcxError::from_msg(
-                            AriesVcxErrorKind::InvalidAttributesStructure,
-                            format!("No 'value' field in cred_value: {cred_value:?}"),
-                        ))?;
-                        let encoded = encode(value.as_str().ok_or(AriesVcxError::from_msg(
-                            AriesVcxErrorKind::InvalidAttributesStructure,
-                            format!("Failed to convert value {value:?} to string"),
-                        ))?)?;
-                        let attrib_values = json!({
-                            "raw": value,
-                            "encoded": encoded
-                        });
-                        let name = name
-                            .as_str()
-                            .ok_or(AriesVcxError::from_msg(
-                                AriesVcxErrorKind::InvalidAttributesStructure,
-                                format!(
-                                    "Failed to convert attribute name {cred_value:?} to string"
-                                ),
-                            ))?
-                            .to_string();
-                        dictionary.insert(name, attrib_values);
-                    }
-                    serde_json::to_string_pretty(&dictionary).map_err(|err| {
-                        warn!("Invalid Json for Attribute data");
-                        AriesVcxError::from_msg(
-                            AriesVcxErrorKind::InvalidJson,
-                            format!("Invalid Json for Attribute data: {err}"),
-                        )
+        if let Some(group) = requested_proof.revealed_attr_groups.get(referent) {
+            let schema_id = schema_id(group.sub_proof_index);
+            mismatches.extend(group.values.iter().filter_map(|(name, value)| {
+                self.check(schema_id, name, &value.raw, &value.encoded)
+                    .map(|mismatch| EncodingMismatch {
+                        attribute: format!("{referent}.{name}"),
+                        ..mismatch
                     })
+            }));
+        }
+        // sorted so that the reported mismatches are stable across checks
+        mismatches.sort_by(|a, b| a.attribute.cmp(&b.attribute));
+        mismatches
+    }
+
+    /// Checks all values revealed in the presentation.
+    pub fn check_presentation(
+        &self,
+        presentation_request: &PresentationRequest,
+        presentation: &Presentation,
+    ) -> Vec<EncodingMismatch> {
+        let requested_proof = &presentation.requested_proof;
+        let mut referents: Vec<&String> = requested_proof
+            .revealed_attrs
+            .keys()
+            .chain(requested_proof.revealed_attr_groups.keys())
+            .collect();
+        referents.sort();
+        referents.dedup();
+        referents
+            .into_iter()
+            .flat_map(|referent| self.check_revealed(presentation_request, presentation, referent))
+            .collect()
+    }
+}
+
+enum CredentialValue {
+    Raw(Value),
+    Encoded { raw: String, encoded: String },
+}
+
+fn parse_credential_value(value: &Value) -> VcxResult<CredentialValue> {
+    match value {
+        // new style input such as {"address2":"101 Wilson Lane"}
+        Value::String(_) | Value::Bool(_) | Value::Number(_) | Value::Null => {
+            Ok(CredentialValue::Raw(value.clone()))
+        }
+
+        // old style input such as {"address2":["101 Wilson Lane"]}
+        Value::Array(array_type) => {
+            let attrib_value = match array_type.first() {
+                Some(Value::String(x)) => x.clone(),
+                _ => {
+                    return Err(AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidAttributesStructure,
+                        "Attribute value not found",
+                    ));
                 }
-                Err(err) => Err(AriesVcxError::from_msg(
-                    AriesVcxErrorKind::InvalidAttributesStructure,
-                    format!("Attribute value not found: {err:?}"),
-                )),
-            }
+            };
+
+            warn!(
+                "Old attribute format detected. See vcx_issuer_create_credential api for \
+                 additional information."
+            );
+            Ok(CredentialValue::Raw(Value::String(attrib_value)))
         }
+
+        // pre-encoded input such as {"dob": {"raw": "1990-01-15", "encoded": "19900115"}}
+        Value::Object(object) => match (
+            object.get("raw").and_then(Value::as_str),
+            object.get("encoded").and_then(Value::as_str),
+        ) {
+            (Some(raw), Some(encoded)) => Ok(CredentialValue::Encoded {
+                raw: raw.to_string(),
+                encoded: encoded.to_string(),
+            }),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                "Invalid Json for Attribute data",
+            )),
+        },
+    }
+}
+
+fn parse_credential_values(attributes: &str) -> VcxResult<Vec<(String, CredentialValue)>> {
+    if let Ok(attributes) = serde_json::from_str::<HashMap<String, Value>>(attributes) {
+        return attributes
+            .into_iter()
+            .map(|(attr, attr_data)| Ok((attr, parse_credential_value(&attr_data)?)))
+            .collect();
     }
+    // aries style input such as [{"name": "address2", "value": "101 Wilson Lane"}]
+    let attributes = serde_json::from_str::<Vec<Value>>(attributes).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidAttributesStructure,
+            format!("Attribute value not found: {err:?}"),
+        )
+    })?;
+    attributes
+        .iter()
+        .map(|cred_value| {
+            let name = cred_value.get("name").ok_or(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidAttributesStructure,
+                format!("No 'name' field in cred_value: {cred_value:?}"),
+            ))?;
+            let value = cred_value.get("value").ok_or(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidAttributesStructure,
+                format!("No 'value' field in cred_value: {cred_value:?}"),
+            ))?;
+            if value.is_array() {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidAttributesStructure,
+                    format!("Failed to convert value {value:?} to string"),
+                ));
+            }
+            let name = name
+                .as_str()
+                .ok_or(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidAttributesStructure,
+                    format!("Failed to convert attribute name {cred_value:?} to string"),
+                ))?
+                .to_string();
+            Ok((name, parse_credential_value(value)?))
+        })
+        .collect()
+}
+
+/// Encodes credential values following the indy conventions, see [`AttributeEncoder`] for
+/// encoding typed attributes.
+pub fn encode_attributes(attributes: &str) -> VcxResult<String> {
+    AttributeEncoder::default().encode_attributes(None, attributes)
 }
 
 #[cfg(test)]
@@ -111,7 +541,16 @@ pub mod unit_tests {
     use serde_json::Value;
     use test_utils::devsetup::*;
 
-    use crate::common::credentials::encoding::encode_attributes;
+    use crate::{
+        common::credentials::encoding::{
+            birth_date_bound, encode_attributes, encode_date, AttributeEncoder, AttributeType,
+            EncodingMismatch,
+        },
+        errors::error::{AriesVcxError, AriesVcxErrorKind},
+        utils::openssl::hash,
+    };
+
+    const SCHEMA_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:2:person:1.0";
 
     #[test]
     fn test_encode_with_several_attributes_success() {
@@ -357,4 +796,168 @@ pub mod unit_tests {
         let results: Value = serde_json::from_str(&results_json).unwrap();
         assert_eq!(expected, results);
     }
+
+    #[test]
+    fn test_encode_attribute_types() {
+        assert_eq!(AttributeType::String.encode("87121").unwrap(), "87121");
+        assert_eq!(AttributeType::String.encode("SLC").unwrap(), hash("SLC"));
+
+        assert_eq!(AttributeType::Integer.encode("0042").unwrap(), "42");
+        assert_eq!(
+            AttributeType::Integer
+                .encode("18446744073709551616")
+                .unwrap(),
+            "18446744073709551616"
+        );
+        assert_eq!(AttributeType::Integer.encode("-5").unwrap(), hash("-5"));
+        assert!(AttributeType::Integer.encode("4.2").is_err());
+
+        assert_eq!(AttributeType::Boolean.encode("TRUE").unwrap(), "1");
+        assert_eq!(AttributeType::Boolean.encode("0").unwrap(), "0");
+        assert!(AttributeType::Boolean.encode("yes").is_err());
+
+        assert_eq!(
+            AttributeType::Date.encode("1990-01-15").unwrap(),
+            "19900115"
+        );
+        assert_eq!(AttributeType::Date.encode("19900115").unwrap(), "19900115");
+
+        assert_eq!(
+            AttributeType::Float.encode("1.50").unwrap(),
+            AttributeType::Float.encode("1.5").unwrap()
+        );
+        assert!(AttributeType::Float.encode("NaN").is_err());
+
+        // null values are hashed whatever the type
+        assert_eq!(AttributeType::Date.encode("").unwrap(), hash(""));
+    }
+
+    #[test]
+    fn test_encode_date_invalid() {
+        assert!(encode_date("1990-02-30").is_err());
+        assert!(encode_date("1990-13-01").is_err());
+        assert!(encode_date("1990/01/15").is_err());
+        assert!(encode_date("199001-15").is_err());
+    }
+
+    #[test]
+    fn test_birth_date_bound() {
+        let on = time::Date::from_calendar_date(2026, time::Month::October, 19).unwrap();
+        assert_eq!(birth_date_bound(18, on).unwrap(), 20081019);
+
+        let leap_day = time::Date::from_calendar_date(2028, time::Month::February, 29).unwrap();
+        assert_eq!(birth_date_bound(18, leap_day).unwrap(), 20100228);
+        assert_eq!(birth_date_bound(20, leap_day).unwrap(), 20080229);
+    }
+
+    #[test]
+    fn test_encoder_typed_attributes() {
+        let encoder = AttributeEncoder::new()
+            .with_attribute_type(SCHEMA_ID, "Birth Date", AttributeType::Date)
+            .with_custom_encoder(SCHEMA_ID, "height", |raw: &str| {
+                raw.parse::<f64>()
+                    .map(|height| ((height * 100.0).round() as u32).to_string())
+                    .map_err(|err| {
+                        AriesVcxError::from_msg(AriesVcxErrorKind::InvalidInput, err.to_string())
+                    })
+            });
+
+        let results_json = encoder
+            .encode_attributes(
+                Some(SCHEMA_ID),
+                r#"{"birthdate": "1990-01-15", "height": "1.82", "name": "Alice", "over18": true, "nickname": null}"#,
+            )
+            .unwrap();
+        let results: Value = serde_json::from_str(&results_json).unwrap();
+        assert_eq!(
+            results,
+            json!({
+                "birthdate": {"raw": "1990-01-15", "encoded": "19900115"},
+                "height": {"raw": "1.82", "encoded": "182"},
+                "name": {"raw": "Alice", "encoded": hash("Alice")},
+                "over18": {"raw": "true", "encoded": hash("true")},
+                "nickname": {"raw": "", "encoded": hash("")},
+            })
+        );
+
+        // attribute types are registered per schema
+        assert_eq!(
+            encoder.encode(None, "birthdate", "1990-01-15").unwrap(),
+            hash("1990-01-15")
+        );
+        assert!(encoder
+            .encode_attributes(Some(SCHEMA_ID), r#"{"birthdate": "15/01/1990"}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn test_encode_json_scalars_as_strings() {
+        let results_json =
+            encode_attributes(r#"{"over18": true, "count": 7, "big": 4294967296, "ratio": 1.5}"#)
+                .unwrap();
+        let results: Value = serde_json::from_str(&results_json).unwrap();
+        assert_eq!(
+            results,
+            json!({
+                "over18": {"raw": "true", "encoded": hash("true")},
+                "count": {"raw": "7", "encoded": "7"},
+                "big": {"raw": "4294967296", "encoded": hash("4294967296")},
+                "ratio": {"raw": "1.5", "encoded": hash("1.5")},
+            })
+        );
+
+        // verifiers check the raw strings with the same encoder
+        let encoder = AttributeEncoder::default();
+        for (name, value) in results.as_object().unwrap() {
+            let raw = value["raw"].as_str().unwrap();
+            let encoded = value["encoded"].as_str().unwrap();
+            assert_eq!(encoder.check(None, name, raw, encoded), None);
+        }
+    }
+
+    #[test]
+    fn test_encoder_reports_mismatches() {
+        let encoder = AttributeEncoder::new().with_attribute_type(
+            SCHEMA_ID,
+            "birthdate",
+            AttributeType::Date,
+        );
+
+        assert_eq!(
+            encoder.check(Some(SCHEMA_ID), "birthdate", "1990-01-15", "19900115"),
+            None
+        );
+        assert_eq!(
+            encoder.check(Some(SCHEMA_ID), "birthdate", "1990-01-15", "19900116"),
+            Some(EncodingMismatch {
+                attribute: "birthdate".to_string(),
+                raw: "1990-01-15".to_string(),
+                encoded: "19900116".to_string(),
+                expected: Some("19900115".to_string()),
+            })
+        );
+
+        let results_json = encoder
+            .encode_attributes(
+                Some(SCHEMA_ID),
+                r#"{"birthdate": {"raw": "1990-01-15", "encoded": "19900115"}}"#,
+            )
+            .unwrap();
+        let results: Value = serde_json::from_str(&results_json).unwrap();
+        assert_eq!(
+            results,
+            json!({"birthdate": {"raw": "1990-01-15", "encoded": "19900115"}})
+        );
+
+        let err = encoder
+            .encode_attributes(
+                Some(SCHEMA_ID),
+                r#"[{"name": "birthdate", "value": {"raw": "1990-01-15", "encoded": "1"}}]"#,
+            )
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidAttributesStructure);
+        assert!(err
+            .msg()
+            .contains("birthdate: raw value \"1990-01-15\" encodes to 19900115, not 1"));
+    }
 }
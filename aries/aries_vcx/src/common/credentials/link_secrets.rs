@@ -0,0 +1,752 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::{
+    BaseAnonCreds, CredentialId, LinkSecretId, LINK_SECRET_ID_TAG,
+};
+use aries_vcx_wallet::wallet::{
+    base_wallet::{record::Record, record_category::RecordCategory, BaseWallet},
+    record_tags::{RecordTag, RecordTags},
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    global::settings::DEFAULT_LINK_SECRET_ALIAS,
+};
+
+const ACTIVE_TAG: &str = "active";
+const CREATED_AT_TAG: &str = "created_at";
+const RETIRED_AT_TAG: &str = "retired_at";
+
+/// Link secret stored in the wallet, without its value. Link secrets, their state and the
+/// bindings of credentials to them are wallet records, so wallet backups
+/// (`BaseWallet::export_wallet`) and migrations (`migrate_records`) carry them along.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LinkSecretInfo {
+    pub id: LinkSecretId,
+    /// New credentials are requested with the active link secret
+    pub active: bool,
+    /// Unix timestamp, unknown for link secrets not created through this module
+    pub created_at: Option<u64>,
+    /// Unix timestamp the link secret was rotated out at
+    pub retired_at: Option<u64>,
+}
+
+impl LinkSecretInfo {
+    fn new(id: &str, active: bool) -> Self {
+        Self {
+            id: id.to_string(),
+            active,
+            created_at: Some(now()),
+            retired_at: None,
+        }
+    }
+
+    fn from_record(record: &Record) -> Self {
+        let tag = |key: &str| {
+            record
+                .tags()
+                .clone()
+                .into_iter()
+                .find(|tag| tag.key() == key)
+                .map(|tag| tag.value().to_string())
+        };
+        Self {
+            id: record.name().to_string(),
+            active: tag(ACTIVE_TAG).as_deref() == Some("true"),
+            created_at: tag(CREATED_AT_TAG).and_then(|value| value.parse().ok()),
+            retired_at: tag(RETIRED_AT_TAG).and_then(|value| value.parse().ok()),
+        }
+    }
+
+    fn tags(&self) -> RecordTags {
+        let mut tags = vec![RecordTag::new(ACTIVE_TAG, &self.active.to_string())];
+        if let Some(created_at) = self.created_at {
+            tags.push(RecordTag::new(CREATED_AT_TAG, &created_at.to_string()));
+        }
+        if let Some(retired_at) = self.retired_at {
+            tags.push(RecordTag::new(RETIRED_AT_TAG, &retired_at.to_string()));
+        }
+        RecordTags::new(tags)
+    }
+}
+
+fn now() -> u64 {
+    OffsetDateTime::now_utc().unix_timestamp() as u64
+}
+
+async fn find_record(
+    wallet: &impl BaseWallet,
+    category: RecordCategory,
+    name: &str,
+) -> VcxResult<Option<Record>> {
+    match wallet.get_record(category, name).await {
+        Ok(record) => Ok(Some(record)),
+        Err(err) => {
+            let err = AriesVcxError::from(err);
+            if err.kind() == AriesVcxErrorKind::WalletRecordNotFound {
+                Ok(None)
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+async fn store_info(wallet: &impl BaseWallet, info: &LinkSecretInfo) -> VcxResult<()> {
+    Ok(wallet
+        .update_record_tags(RecordCategory::LinkSecret, &info.id, info.tags())
+        .await?)
+}
+
+/// Link secrets of the wallet, oldest first.
+pub async fn list_link_secrets(wallet: &impl BaseWallet) -> VcxResult<Vec<LinkSecretInfo>> {
+    let mut link_secrets: Vec<LinkSecretInfo> = wallet
+        .search_record(RecordCategory::LinkSecret, None)
+        .await?
+        .iter()
+        .map(LinkSecretInfo::from_record)
+        .collect();
+    link_secrets.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    Ok(link_secrets)
+}
+
+pub async fn get_link_secret_info(
+    wallet: &impl BaseWallet,
+    link_secret_id: &str,
+) -> VcxResult<LinkSecretInfo> {
+    find_record(wallet, RecordCategory::LinkSecret, link_secret_id)
+        .await?
+        .map(|record| LinkSecretInfo::from_record(&record))
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::WalletRecordNotFound,
+                format!("Link secret {link_secret_id} not found in wallet"),
+            )
+        })
+}
+
+pub(crate) async fn find_active_link_secret(
+    wallet: &impl BaseWallet,
+) -> VcxResult<Option<LinkSecretId>> {
+    let link_secrets = list_link_secrets(wallet).await?;
+    if let Some(active) = link_secrets.iter().find(|info| info.active) {
+        return Ok(Some(active.id.clone()));
+    }
+    // wallets which never selected a link secret use the default one, or their only one
+    if link_secrets
+        .iter()
+        .any(|info| info.id == DEFAULT_LINK_SECRET_ALIAS)
+    {
+        return Ok(Some(DEFAULT_LINK_SECRET_ALIAS.to_string()));
+    }
+    match link_secrets.as_slice() {
+        [only] => Ok(Some(only.id.clone())),
+        _ => Ok(None),
+    }
+}
+
+/// Link secret new credentials are requested with.
+pub async fn active_link_secret(wallet: &impl BaseWallet) -> VcxResult<LinkSecretId> {
+    find_active_link_secret(wallet).await?.ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidState,
+            "No active link secret in wallet, create or select one",
+        )
+    })
+}
+
+/// Creates a link secret, which becomes the active one if the wallet has none.
+pub async fn create_link_secret(
+    wallet: &impl BaseWallet,
+    anoncreds: &impl BaseAnonCreds,
+    link_secret_id: &str,
+) -> VcxResult<LinkSecretInfo> {
+    let has_active = find_active_link_secret(wallet).await?.is_some();
+    anoncreds
+        .prover_create_link_secret(wallet, &link_secret_id.to_string())
+        .await?;
+    let info = LinkSecretInfo::new(link_secret_id, !has_active);
+    store_info(wallet, &info).await?;
+    Ok(info)
+}
+
+/// Makes the link secret the active one. Credentials bound to the previously active link
+/// secret remain usable.
+pub async fn select_link_secret(
+    wallet: &impl BaseWallet,
+    link_secret_id: &str,
+) -> VcxResult<LinkSecretInfo> {
+    let mut selected = get_link_secret_info(wallet, link_secret_id).await?;
+    for mut info in list_link_secrets(wallet).await? {
+        if info.active && info.id != link_secret_id {
+            info.active = false;
+            store_info(wallet, &info).await?;
+        }
+    }
+    selected.active = true;
+    selected.retired_at = None;
+    store_info(wallet, &selected).await?;
+    Ok(selected)
+}
+
+/// Creates a new link secret and makes it the active one, retiring the previously active link
+/// secret. A random id is used unless `new_link_secret_id` is given.
+pub async fn rotate_link_secret(
+    wallet: &impl BaseWallet,
+    anoncreds: &impl BaseAnonCreds,
+    new_link_secret_id: Option<&str>,
+) -> VcxResult<LinkSecretInfo> {
+    let previous = find_active_link_secret(wallet).await?;
+    let link_secret_id = new_link_secret_id
+        .map(ToString::to_string)
+        .unwrap_or_else(|| format!("link-secret-{}", Uuid::new_v4()));
+    anoncreds
+        .prover_create_link_secret(wallet, &link_secret_id)
+        .await?;
+    let info = LinkSecretInfo::new(&link_secret_id, true);
+    store_info(wallet, &info).await?;
+
+    if let Some(previous) = previous {
+        let mut previous = get_link_secret_info(wallet, &previous).await?;
+        previous.active = false;
+        previous.retired_at = Some(now());
+        store_info(wallet, &previous).await?;
+        info!("Rotated link secret {} to {link_secret_id}", previous.id);
+    }
+    Ok(info)
+}
+
+/// Deletes a link secret no stored credential is bound to. The active link secret cannot be
+/// deleted.
+pub async fn delete_link_secret(wallet: &impl BaseWallet, link_secret_id: &str) -> VcxResult<()> {
+    if find_active_link_secret(wallet).await?.as_deref() == Some(link_secret_id) {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidState,
+            format!("Link secret {link_secret_id} is active, select another one first"),
+        ));
+    }
+    let bound = credentials_bound_to(wallet, link_secret_id).await?;
+    if !bound.is_empty() {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidState,
+            format!(
+                "Link secret {link_secret_id} is bound to {} stored credentials",
+                bound.len()
+            ),
+        ));
+    }
+    Ok(wallet
+        .delete_record(RecordCategory::LinkSecret, link_secret_id)
+        .await?)
+}
+
+// credentials stored before link secrets were recorded are bound to the default link secret
+pub(crate) fn bound_link_secret(record: &Record) -> LinkSecretId {
+    record
+        .tags()
+        .clone()
+        .into_iter()
+        .find(|tag| tag.key() == LINK_SECRET_ID_TAG)
+        .map(|tag| tag.value().to_string())
+        .unwrap_or_else(|| DEFAULT_LINK_SECRET_ALIAS.to_string())
+}
+
+/// Link secret the stored credential is bound to.
+pub async fn credential_link_secret(
+    wallet: &impl BaseWallet,
+    cred_id: &str,
+) -> VcxResult<LinkSecretId> {
+    let record = wallet.get_record(RecordCategory::Cred, cred_id).await?;
+    Ok(bound_link_secret(&record))
+}
+
+/// Records the link secret a stored credential is bound to, for credentials imported from other
+/// wallets or stored before the binding was recorded.
+pub async fn bind_credential(
+    wallet: &impl BaseWallet,
+    cred_id: &str,
+    link_secret_id: &str,
+) -> VcxResult<()> {
+    get_link_secret_info(wallet, link_secret_id).await?;
+    let record = wallet.get_record(RecordCategory::Cred, cred_id).await?;
+    let tags: RecordTags = record
+        .tags()
+        .clone()
+        .into_iter()
+        .filter(|tag| tag.key() != LINK_SECRET_ID_TAG)
+        .chain([RecordTag::new(LINK_SECRET_ID_TAG, link_secret_id)])
+        .collect();
+    Ok(wallet
+        .update_record_tags(RecordCategory::Cred, cred_id, tags)
+        .await?)
+}
+
+/// Stored credentials bound to the link secret.
+pub async fn credentials_bound_to(
+    wallet: &impl BaseWallet,
+    link_secret_id: &str,
+) -> VcxResult<Vec<CredentialId>> {
+    let query = json!({ LINK_SECRET_ID_TAG: link_secret_id }).to_string();
+    let mut records = wallet
+        .search_record(RecordCategory::Cred, Some(query))
+        .await?;
+    if link_secret_id == DEFAULT_LINK_SECRET_ALIAS {
+        let query = json!({ "$not": { "$exist": [LINK_SECRET_ID_TAG] } }).to_string();
+        records.extend(
+            wallet
+                .search_record(RecordCategory::Cred, Some(query))
+                .await?,
+        );
+    }
+    Ok(records
+        .iter()
+        .map(|record| record.name().to_string())
+        .collect())
+}
+
+/// Link secret to create a presentation of the credentials with, the active link secret if no
+/// credential is presented. Credentials bound to different link secrets cannot be presented
+/// together.
+pub async fn link_secret_for_credentials<'a>(
+    wallet: &impl BaseWallet,
+    cred_ids: impl IntoIterator<Item = &'a str>,
+) -> VcxResult<LinkSecretId> {
+    let cred_ids: BTreeSet<&str> = cred_ids.into_iter().collect();
+    let mut bound: BTreeMap<LinkSecretId, Vec<&str>> = BTreeMap::new();
+    for cred_id in cred_ids {
+        let link_secret_id = credential_link_secret(wallet, cred_id).await?;
+        bound.entry(link_secret_id).or_default().push(cred_id);
+    }
+    let mut link_secrets = bound.keys();
+    match (link_secrets.next(), link_secrets.next()) {
+        (None, _) => active_link_secret(wallet).await,
+        (Some(link_secret_id), None) => Ok(link_secret_id.clone()),
+        _ => {
+            let bindings: Vec<String> = bound
+                .iter()
+                .map(|(link_secret_id, cred_ids)| {
+                    format!("{link_secret_id}: {}", cred_ids.join(", "))
+                })
+                .collect();
+            Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                format!(
+                    "Credentials bound to different link secrets cannot be presented together: {}",
+                    bindings.join("; ")
+                ),
+            ))
+        }
+    }
+}
+
+/// Parses a link secret as stored by aries-vcx, a decimal integer, or by indy-sdk and
+/// indy-credx based wallets, `{"value": {"ms": "<decimal integer>"}}`.
+fn parse_link_secret_value(value: &str) -> VcxResult<String> {
+    let is_decimal = |value: &str| !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+    let value = value.trim();
+    if is_decimal(value) {
+        return Ok(value.to_string());
+    }
+    let json: serde_json::Value = serde_json::from_str(value).map_err(|_| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidInput,
+            "Link secret is neither a decimal integer nor a JSON link secret",
+        )
+    })?;
+    match json["value"]["ms"].as_str().or(json["ms"].as_str()) {
+        Some(ms) if is_decimal(ms) => Ok(ms.to_string()),
+        _ => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidInput,
+            "JSON link secret has no decimal \"ms\" value",
+        )),
+    }
+}
+
+/// Imports a link secret created by another wallet, for instance when migrating a holder from
+/// another stack. The link secret becomes the active one if the wallet has none.
+pub async fn import_link_secret(
+    wallet: &impl BaseWallet,
+    link_secret_id: &str,
+    value: &str,
+) -> VcxResult<LinkSecretInfo> {
+    let value = parse_link_secret_value(value)?;
+    if find_record(wallet, RecordCategory::LinkSecret, link_secret_id)
+        .await?
+        .is_some()
+    {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::DuplicationMasterSecret,
+            format!("Link secret {link_secret_id} already exists in wallet"),
+        ));
+    }
+    let info = LinkSecretInfo::new(
+        link_secret_id,
+        find_active_link_secret(wallet).await?.is_none(),
+    );
+    add_link_secret(wallet, &info, value).await?;
+    Ok(info)
+}
+
+async fn add_link_secret(
+    wallet: &impl BaseWallet,
+    info: &LinkSecretInfo,
+    value: String,
+) -> VcxResult<()> {
+    let record = Record::builder()
+        .name(info.id.clone())
+        .category(RecordCategory::LinkSecret)
+        .value(value)
+        .tags(info.tags())
+        .build();
+    Ok(wallet.add_record(record).await?)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_link_secret_value() {
+        assert_eq!(parse_link_secret_value("12345").unwrap(), "12345");
+        assert_eq!(
+            parse_link_secret_value(r#"{"value": {"ms": "12345"}}"#).unwrap(),
+            "12345"
+        );
+        assert_eq!(
+            parse_link_secret_value(r#"{"ms": "12345"}"#).unwrap(),
+            "12345"
+        );
+        assert_eq!(
+            parse_link_secret_value("12a45").unwrap_err().kind(),
+            AriesVcxErrorKind::InvalidInput
+        );
+        assert!(parse_link_secret_value(r#"{"value": {"ms": ""}}"#).is_err());
+    }
+
+    #[test]
+    fn test_link_secret_info_record_roundtrip() {
+        let info = LinkSecretInfo {
+            id: "holder-secret".to_string(),
+            active: true,
+            created_at: Some(1_700_000_000),
+            retired_at: None,
+        };
+        let record = Record::builder()
+            .name(info.id.clone())
+            .category(RecordCategory::LinkSecret)
+            .value("12345".to_string())
+            .tags(info.tags())
+            .build();
+        assert_eq!(LinkSecretInfo::from_record(&record), info);
+
+        let legacy = Record::builder()
+            .name(DEFAULT_LINK_SECRET_ALIAS.to_string())
+            .category(RecordCategory::LinkSecret)
+            .value("12345".to_string())
+            .build();
+        assert_eq!(
+            LinkSecretInfo::from_record(&legacy),
+            LinkSecretInfo {
+                id: DEFAULT_LINK_SECRET_ALIAS.to_string(),
+                active: false,
+                created_at: None,
+                retired_at: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_bound_link_secret() {
+        let record = Record::builder()
+            .name("cred".to_string())
+            .category(RecordCategory::Cred)
+            .value("{}".to_string())
+            .tags(RecordTags::new(vec![RecordTag::new(
+                LINK_SECRET_ID_TAG,
+                "holder-secret",
+            )]))
+            .build();
+        assert_eq!(bound_link_secret(&record), "holder-secret");
+
+        let legacy = Record::builder()
+            .name("cred".to_string())
+            .category(RecordCategory::Cred)
+            .value("{}".to_string())
+            .build();
+        assert_eq!(bound_link_secret(&legacy), DEFAULT_LINK_SECRET_ALIAS);
+    }
+}
+
+#[cfg(test)]
+mod pool_tests {
+    use std::error::Error;
+
+    use aries_vcx_wallet::wallet::base_wallet::migrate::migrate_records;
+    use test_utils::{
+        constants::TRUSTEE_SEED,
+        devsetup::{build_setup_profile, dev_build_featured_wallet},
+    };
+
+    use super::*;
+
+    async fn add_credential(
+        wallet: &impl BaseWallet,
+        cred_id: &str,
+        link_secret_id: Option<&str>,
+    ) -> VcxResult<()> {
+        let tags = link_secret_id
+            .map(|id| vec![RecordTag::new(LINK_SECRET_ID_TAG, id)])
+            .unwrap_or_default();
+        let record = Record::builder()
+            .name(cred_id.to_string())
+            .category(RecordCategory::Cred)
+            .value("{}".to_string())
+            .tags(RecordTags::new(tags))
+            .build();
+        Ok(wallet.add_record(record).await?)
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_pool_create_select_rotate_link_secrets() -> Result<(), Box<dyn Error>> {
+        let setup = build_setup_profile().await;
+        let wallet = &setup.wallet;
+
+        // the link secret created by the setup predates this module
+        assert_eq!(active_link_secret(wallet).await?, DEFAULT_LINK_SECRET_ALIAS);
+
+        let created = create_link_secret(wallet, &setup.anoncreds, "holder-secret").await?;
+        assert!(!created.active);
+        assert!(created.created_at.is_some());
+        assert_eq!(active_link_secret(wallet).await?, DEFAULT_LINK_SECRET_ALIAS);
+
+        let selected = select_link_secret(wallet, "holder-secret").await?;
+        assert!(selected.active);
+        assert_eq!(active_link_secret(wallet).await?, "holder-secret");
+
+        let rotated = rotate_link_secret(wallet, &setup.anoncreds, Some("rotated-secret")).await?;
+        assert!(rotated.active);
+        assert_eq!(active_link_secret(wallet).await?, "rotated-secret");
+        let retired = get_link_secret_info(wallet, "holder-secret").await?;
+        assert!(!retired.active);
+        assert!(retired.retired_at.is_some());
+
+        let ids: Vec<LinkSecretId> = list_link_secrets(wallet)
+            .await?
+            .into_iter()
+            .map(|info| info.id)
+            .collect();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.contains(&DEFAULT_LINK_SECRET_ALIAS.to_string()));
+        assert!(ids.contains(&"holder-secret".to_string()));
+        assert!(ids.contains(&"rotated-secret".to_string()));
+        assert_eq!(
+            list_link_secrets(wallet)
+                .await?
+                .iter()
+                .filter(|info| info.active)
+                .count(),
+            1
+        );
+
+        // selecting a retired link secret reactivates it
+        let reselected = select_link_secret(wallet, "holder-secret").await?;
+        assert!(reselected.active);
+        assert_eq!(reselected.retired_at, None);
+        assert!(!get_link_secret_info(wallet, "rotated-secret").await?.active);
+
+        assert_eq!(
+            select_link_secret(wallet, "unknown-secret")
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::WalletRecordNotFound
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_pool_delete_link_secret() -> Result<(), Box<dyn Error>> {
+        let setup = build_setup_profile().await;
+        let wallet = &setup.wallet;
+
+        create_link_secret(wallet, &setup.anoncreds, "bound-secret").await?;
+        create_link_secret(wallet, &setup.anoncreds, "unused-secret").await?;
+        add_credential(wallet, "cred-1", Some("bound-secret")).await?;
+
+        assert_eq!(
+            delete_link_secret(wallet, DEFAULT_LINK_SECRET_ALIAS)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::InvalidState
+        );
+        assert_eq!(
+            delete_link_secret(wallet, "bound-secret")
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::InvalidState
+        );
+
+        delete_link_secret(wallet, "unused-secret").await?;
+        assert_eq!(
+            get_link_secret_info(wallet, "unused-secret")
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::WalletRecordNotFound
+        );
+        assert!(get_link_secret_info(wallet, "bound-secret").await.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_pool_credentials_bound_to() -> Result<(), Box<dyn Error>> {
+        let setup = build_setup_profile().await;
+        let wallet = &setup.wallet;
+
+        create_link_secret(wallet, &setup.anoncreds, "holder-secret").await?;
+        add_credential(wallet, "legacy-cred", None).await?;
+        add_credential(wallet, "default-cred", Some(DEFAULT_LINK_SECRET_ALIAS)).await?;
+        add_credential(wallet, "holder-cred", Some("holder-secret")).await?;
+
+        let mut bound = credentials_bound_to(wallet, DEFAULT_LINK_SECRET_ALIAS).await?;
+        bound.sort();
+        assert_eq!(bound, vec!["default-cred", "legacy-cred"]);
+        assert_eq!(
+            credentials_bound_to(wallet, "holder-secret").await?,
+            vec!["holder-cred"]
+        );
+
+        bind_credential(wallet, "legacy-cred", "holder-secret").await?;
+        assert_eq!(
+            credential_link_secret(wallet, "legacy-cred").await?,
+            "holder-secret"
+        );
+        assert_eq!(
+            credentials_bound_to(wallet, DEFAULT_LINK_SECRET_ALIAS).await?,
+            vec!["default-cred"]
+        );
+        assert_eq!(
+            bind_credential(wallet, "holder-cred", "unknown-secret")
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::WalletRecordNotFound
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_pool_link_secret_for_credentials() -> Result<(), Box<dyn Error>> {
+        let setup = build_setup_profile().await;
+        let wallet = &setup.wallet;
+
+        create_link_secret(wallet, &setup.anoncreds, "holder-secret").await?;
+        add_credential(wallet, "legacy-cred", None).await?;
+        add_credential(wallet, "holder-cred-1", Some("holder-secret")).await?;
+        add_credential(wallet, "holder-cred-2", Some("holder-secret")).await?;
+
+        assert_eq!(
+            link_secret_for_credentials(wallet, Vec::<&str>::new()).await?,
+            DEFAULT_LINK_SECRET_ALIAS
+        );
+        assert_eq!(
+            link_secret_for_credentials(wallet, ["legacy-cred"]).await?,
+            DEFAULT_LINK_SECRET_ALIAS
+        );
+        assert_eq!(
+            link_secret_for_credentials(wallet, ["holder-cred-1", "holder-cred-2"]).await?,
+            "holder-secret"
+        );
+
+        let err = link_secret_for_credentials(wallet, ["legacy-cred", "holder-cred-1"])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidInput);
+        assert!(err.to_string().contains("holder-cred-1"));
+        assert!(err.to_string().contains("legacy-cred"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_pool_import_link_secret() -> Result<(), Box<dyn Error>> {
+        let setup = build_setup_profile().await;
+        let wallet = &setup.wallet;
+
+        let imported =
+            import_link_secret(wallet, "indy-secret", r#"{"value": {"ms": "12345"}}"#).await?;
+        assert!(!imported.active);
+        assert_eq!(
+            wallet
+                .get_record(RecordCategory::LinkSecret, "indy-secret")
+                .await?
+                .value(),
+            "12345"
+        );
+        assert_eq!(
+            import_link_secret(wallet, "indy-secret", "67890")
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::DuplicationMasterSecret
+        );
+        assert_eq!(
+            import_link_secret(wallet, "invalid-secret", "not a link secret")
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::InvalidInput
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_pool_link_secrets_migrate_with_wallet() -> Result<(), Box<dyn Error>> {
+        let setup = build_setup_profile().await;
+        let wallet = &setup.wallet;
+
+        create_link_secret(wallet, &setup.anoncreds, "holder-secret").await?;
+        rotate_link_secret(wallet, &setup.anoncreds, Some("rotated-secret")).await?;
+        add_credential(wallet, "legacy-cred", None).await?;
+        add_credential(wallet, "holder-cred", Some("holder-secret")).await?;
+
+        let (_, new_wallet) = dev_build_featured_wallet(TRUSTEE_SEED).await;
+        migrate_records(wallet, &new_wallet, |record| Ok::<_, String>(Some(record))).await?;
+
+        assert_eq!(
+            list_link_secrets(&new_wallet).await?,
+            list_link_secrets(wallet).await?
+        );
+        assert_eq!(active_link_secret(&new_wallet).await?, "rotated-secret");
+        for id in [DEFAULT_LINK_SECRET_ALIAS, "holder-secret", "rotated-secret"] {
+            assert_eq!(
+                new_wallet
+                    .get_record(RecordCategory::LinkSecret, id)
+                    .await?
+                    .value(),
+                wallet
+                    .get_record(RecordCategory::LinkSecret, id)
+                    .await?
+                    .value()
+            );
+        }
+        assert_eq!(
+            credentials_bound_to(&new_wallet, "holder-secret").await?,
+            vec!["holder-cred"]
+        );
+        assert_eq!(
+            credentials_bound_to(&new_wallet, DEFAULT_LINK_SECRET_ALIAS).await?,
+            vec!["legacy-cred"]
+        );
+        Ok(())
+    }
+}
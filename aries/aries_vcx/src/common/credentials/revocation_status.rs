@@ -0,0 +1,420 @@
+use std::{collections::HashMap, fmt};
+
+use anoncreds_types::data_types::{
+    identifiers::rev_reg_def_id::RevocationRegistryDefinitionId,
+    ledger::rev_status_list::RevocationStatusList,
+    messages::cred_selection::RetrievedCredentialInfo,
+};
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::{BaseAnonCreds, CredentialId};
+use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
+use aries_vcx_wallet::wallet::{
+    base_wallet::{record_category::RecordCategory, BaseWallet},
+    record_tags::RecordTag,
+};
+use time::OffsetDateTime;
+
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+/// Credential record tag holding the last checked [`CredentialRevocationStatus`], so that stored
+/// credentials can be searched by revocation status, e.g. `{"revocation_status": "revoked"}`.
+pub const REVOCATION_STATUS_TAG: &str = "revocation_status";
+/// Credential record tag holding the unix timestamp of the last revocation status check.
+pub const REVOCATION_CHECKED_AT_TAG: &str = "revocation_checked_at";
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialRevocationStatus {
+    Active,
+    Revoked,
+    /// The credential was issued without a revocation registry
+    NonRevocable,
+    /// The status could not be determined, see [`CredentialRevocationCheck::error`]
+    Unknown,
+}
+
+impl CredentialRevocationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Revoked => "revoked",
+            Self::NonRevocable => "non_revocable",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// Status of the credential with the given revocation id in the status list.
+    pub fn from_status_list(
+        status_list: &RevocationStatusList,
+        cred_rev_id: u32,
+    ) -> VcxResult<Self> {
+        match status_list.state().get(cred_rev_id as usize) {
+            Some(is_revoked) if *is_revoked => Ok(Self::Revoked),
+            Some(_) => Ok(Self::Active),
+            None => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidRevocationDetails,
+                format!(
+                    "Credential revocation id {cred_rev_id} is out of range of the revocation \
+                     status list"
+                ),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CredentialRevocationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CredentialRevocationCheck {
+    pub cred_id: CredentialId,
+    pub rev_reg_id: Option<String>,
+    pub cred_rev_id: Option<u32>,
+    pub status: CredentialRevocationStatus,
+    /// Timestamp of the revocation status list on the ledger the status was read from
+    pub status_list_timestamp: Option<u64>,
+    /// Unix timestamp of the check
+    pub checked_at: u64,
+    /// Why the status could not be determined
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Checks the revocation status of credentials stored in the holder's wallet against the
+/// current revocation status lists on the ledger.
+#[derive(Clone, Debug, Default)]
+pub struct HolderRevocationChecker {
+    update_wallet_tags: bool,
+}
+
+impl HolderRevocationChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the result of each check in the [`REVOCATION_STATUS_TAG`] and
+    /// [`REVOCATION_CHECKED_AT_TAG`] tags of the credential record.
+    pub fn with_wallet_tags(mut self) -> Self {
+        self.update_wallet_tags = true;
+        self
+    }
+
+    /// Checks a single credential. Unlike [`Self::check_credentials`], fails if the status can't
+    /// be determined.
+    pub async fn check(
+        &self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger: &impl AnoncredsLedgerRead,
+        cred_id: &CredentialId,
+    ) -> VcxResult<CredentialRevocationCheck> {
+        let credential = anoncreds.prover_get_credential(wallet, cred_id).await?;
+        let checked_at = OffsetDateTime::now_utc().unix_timestamp() as u64;
+        let check = self
+            .check_credential(ledger, &mut HashMap::new(), &credential, checked_at)
+            .await?;
+        if self.update_wallet_tags {
+            update_revocation_tags(wallet, &check).await?;
+        }
+        Ok(check)
+    }
+
+    /// Checks every credential of the wallet matching the WQL filter, as accepted by
+    /// [`BaseAnonCreds::prover_get_credentials`].
+    pub async fn check_all(
+        &self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger: &impl AnoncredsLedgerRead,
+        filter_json: Option<&str>,
+    ) -> VcxResult<Vec<CredentialRevocationCheck>> {
+        let credentials = anoncreds
+            .prover_get_credentials(wallet, filter_json)
+            .await?;
+        self.check_credentials(wallet, ledger, &credentials).await
+    }
+
+    /// Checks the credentials, fetching the status list of each revocation registry only once.
+    ///
+    /// A credential whose status can't be determined, e.g. because its revocation registry is
+    /// unreachable, is reported with the [`CredentialRevocationStatus::Unknown`] status without
+    /// affecting the others. Its wallet tags keep the last status determined.
+    pub async fn check_credentials(
+        &self,
+        wallet: &impl BaseWallet,
+        ledger: &impl AnoncredsLedgerRead,
+        credentials: &[RetrievedCredentialInfo],
+    ) -> VcxResult<Vec<CredentialRevocationCheck>> {
+        let checked_at = OffsetDateTime::now_utc().unix_timestamp() as u64;
+        let mut status_lists = HashMap::new();
+        let mut checks = Vec::with_capacity(credentials.len());
+        for credential in credentials {
+            match self
+                .check_credential(ledger, &mut status_lists, credential, checked_at)
+                .await
+            {
+                Ok(check) => {
+                    if self.update_wallet_tags {
+                        update_revocation_tags(wallet, &check).await?;
+                    }
+                    checks.push(check);
+                }
+                Err(err) => {
+                    warn!(
+                        "HolderRevocationChecker::check_credentials >>> failed to check \
+                         revocation status of credential {}: {err}",
+                        credential.referent
+                    );
+                    checks.push(CredentialRevocationCheck {
+                        cred_id: credential.referent.clone(),
+                        rev_reg_id: credential.rev_reg_id.clone(),
+                        cred_rev_id: credential.cred_rev_id,
+                        status: CredentialRevocationStatus::Unknown,
+                        status_list_timestamp: None,
+                        checked_at,
+                        error: Some(err.to_string()),
+                    });
+                }
+            }
+        }
+        Ok(checks)
+    }
+
+    /// Checks the credential against the status list of its registry, fetched once into
+    /// `status_lists` along with the outcome of fetching it.
+    async fn check_credential<'a>(
+        &self,
+        ledger: &impl AnoncredsLedgerRead,
+        status_lists: &mut HashMap<&'a str, VcxResult<(RevocationStatusList, u64)>>,
+        credential: &'a RetrievedCredentialInfo,
+        checked_at: u64,
+    ) -> VcxResult<CredentialRevocationCheck> {
+        let (Some(rev_reg_id), Some(cred_rev_id)) =
+            (&credential.rev_reg_id, credential.cred_rev_id)
+        else {
+            return Ok(CredentialRevocationCheck {
+                cred_id: credential.referent.clone(),
+                rev_reg_id: None,
+                cred_rev_id: None,
+                status: CredentialRevocationStatus::NonRevocable,
+                status_list_timestamp: None,
+                checked_at,
+                error: None,
+            });
+        };
+        if !status_lists.contains_key(rev_reg_id.as_str()) {
+            let status_list = match RevocationRegistryDefinitionId::try_from(rev_reg_id.as_str()) {
+                Ok(rev_reg_id) => ledger
+                    .get_rev_status_list(&rev_reg_id, checked_at, None)
+                    .await
+                    .map_err(AriesVcxError::from),
+                Err(err) => Err(err.into()),
+            };
+            status_lists.insert(rev_reg_id.as_str(), status_list);
+        }
+        let (status_list, timestamp) = match &status_lists[rev_reg_id.as_str()] {
+            Ok(status_list) => status_list,
+            Err(err) => {
+                return Err(AriesVcxError::from_msg(
+                    err.kind(),
+                    format!("Failed to get revocation status list of registry {rev_reg_id}: {err}"),
+                ))
+            }
+        };
+        Ok(CredentialRevocationCheck {
+            cred_id: credential.referent.clone(),
+            rev_reg_id: Some(rev_reg_id.clone()),
+            cred_rev_id: Some(cred_rev_id),
+            status: CredentialRevocationStatus::from_status_list(status_list, cred_rev_id)?,
+            status_list_timestamp: Some(*timestamp),
+            checked_at,
+            error: None,
+        })
+    }
+}
+
+async fn update_revocation_tags(
+    wallet: &impl BaseWallet,
+    check: &CredentialRevocationCheck,
+) -> VcxResult<()> {
+    let record = wallet
+        .get_record(RecordCategory::Cred, &check.cred_id)
+        .await?;
+    let mut tags = record.tags().clone();
+    let status_tag = RecordTag::new(REVOCATION_STATUS_TAG, check.status.as_str());
+    let checked_at_tag = RecordTag::new(REVOCATION_CHECKED_AT_TAG, &check.checked_at.to_string());
+    tags.remove(status_tag.clone());
+    tags.remove(checked_at_tag.clone());
+    tags.add(status_tag);
+    tags.add(checked_at_tag);
+    wallet
+        .update_record_tags(RecordCategory::Cred, &check.cred_id, tags)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod pool_tests {
+    use std::error::Error;
+
+    use aries_vcx_wallet::wallet::{base_wallet::record::Record, record_tags::RecordTags};
+    use test_utils::{constants::schema_id, devsetup::build_setup_profile};
+
+    use super::{unit_tests::credential, *};
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_pool_check_credentials_updates_revocation_tags() -> Result<(), Box<dyn Error>> {
+        let setup = build_setup_profile().await;
+        let record = Record::builder()
+            .category(RecordCategory::Cred)
+            .name("cred_1".into())
+            .tags(RecordTags::new(vec![RecordTag::new(
+                "schema_id",
+                &schema_id().to_string(),
+            )]))
+            .value("{}".into())
+            .build();
+        setup.wallet.add_record(record).await?;
+        let checker = HolderRevocationChecker::new().with_wallet_tags();
+
+        let checks = checker
+            .check_credentials(
+                &setup.wallet,
+                &setup.ledger_read,
+                &[credential("cred_1", None, None)],
+            )
+            .await?;
+        assert_eq!(checks[0].status, CredentialRevocationStatus::NonRevocable);
+        let tags = setup
+            .wallet
+            .get_record(RecordCategory::Cred, "cred_1")
+            .await?
+            .tags()
+            .clone();
+        let tags: HashMap<String, String> = tags
+            .into_iter()
+            .map(|tag| (tag.key().to_string(), tag.value().to_string()))
+            .collect();
+        assert_eq!(tags[REVOCATION_STATUS_TAG], "non_revocable");
+        assert_eq!(
+            tags[REVOCATION_CHECKED_AT_TAG],
+            checks[0].checked_at.to_string()
+        );
+        // unrelated tags are kept
+        assert_eq!(tags["schema_id"], schema_id().to_string());
+
+        // a failed check keeps the last status determined
+        let checks = checker
+            .check_credentials(
+                &setup.wallet,
+                &setup.ledger_read,
+                &[credential("cred_1", Some("not a rev reg id"), Some(1))],
+            )
+            .await?;
+        assert_eq!(checks[0].status, CredentialRevocationStatus::Unknown);
+        let record = setup
+            .wallet
+            .get_record(RecordCategory::Cred, "cred_1")
+            .await?;
+        assert!(record
+            .tags()
+            .clone()
+            .into_iter()
+            .any(|tag| tag.key() == REVOCATION_STATUS_TAG && tag.value() == "non_revocable"));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use test_utils::{
+        constants::{cred_def_id, schema_id, REV_REG_ID},
+        mock_wallet::MockWallet,
+        mockdata::mock_ledger::MockLedger,
+    };
+
+    use super::*;
+
+    pub(super) fn credential(
+        cred_id: &str,
+        rev_reg_id: Option<&str>,
+        cred_rev_id: Option<u32>,
+    ) -> RetrievedCredentialInfo {
+        RetrievedCredentialInfo {
+            referent: cred_id.to_string(),
+            attributes: HashMap::new(),
+            schema_id: schema_id(),
+            cred_def_id: cred_def_id(),
+            rev_reg_id: rev_reg_id.map(ToString::to_string),
+            cred_rev_id,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_credentials() {
+        // the status list of the mock ledger is [0, 1, 1, 0]
+        let credentials = [
+            credential("active", Some(REV_REG_ID), Some(0)),
+            credential("revoked", Some(REV_REG_ID), Some(1)),
+            credential("out_of_range", Some(REV_REG_ID), Some(9)),
+            credential("unreachable", Some("not a rev reg id"), Some(0)),
+            credential("non_revocable", None, None),
+        ];
+        let checks = HolderRevocationChecker::new()
+            .check_credentials(&MockWallet, &MockLedger, &credentials)
+            .await
+            .unwrap();
+
+        let statuses: Vec<_> = checks
+            .iter()
+            .map(|check| (check.cred_id.as_str(), check.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("active", CredentialRevocationStatus::Active),
+                ("revoked", CredentialRevocationStatus::Revoked),
+                ("out_of_range", CredentialRevocationStatus::Unknown),
+                ("unreachable", CredentialRevocationStatus::Unknown),
+                ("non_revocable", CredentialRevocationStatus::NonRevocable),
+            ]
+        );
+        assert_eq!(checks[0].status_list_timestamp, Some(1));
+        assert!(checks[0].error.is_none());
+        assert!(checks[2].error.is_some());
+        assert_eq!(checks[3].rev_reg_id.as_deref(), Some("not a rev reg id"));
+        assert!(checks[3].error.is_some());
+        assert!(checks[3].status_list_timestamp.is_none());
+    }
+
+    #[test]
+    fn test_status_from_status_list() {
+        let status_list: RevocationStatusList = serde_json::from_value(serde_json::json!({
+            "issuerId": "V4SGRU86Z58d6TV7PBUe6f",
+            "revocationList": [0, 1, 0],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            CredentialRevocationStatus::from_status_list(&status_list, 0).unwrap(),
+            CredentialRevocationStatus::Active
+        );
+        assert_eq!(
+            CredentialRevocationStatus::from_status_list(&status_list, 1).unwrap(),
+            CredentialRevocationStatus::Revoked
+        );
+        let err = CredentialRevocationStatus::from_status_list(&status_list, 3).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidRevocationDetails);
+    }
+
+    #[test]
+    fn test_status_serialization() {
+        assert_eq!(
+            serde_json::to_value(CredentialRevocationStatus::NonRevocable).unwrap(),
+            serde_json::json!(CredentialRevocationStatus::NonRevocable.as_str())
+        );
+    }
+}
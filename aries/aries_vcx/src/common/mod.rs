@@ -4,3 +4,4 @@ pub mod ledger;
 pub mod primitives;
 pub mod proofs;
 pub mod signing;
+pub mod tails;
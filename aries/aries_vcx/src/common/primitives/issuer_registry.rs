@@ -0,0 +1,916 @@
+use std::{cmp::Ordering, collections::HashSet, fmt};
+
+use anoncreds_types::data_types::identifiers::{
+    cred_def_id::CredentialDefinitionId, rev_reg_def_id::RevocationRegistryDefinitionId,
+    schema_id::SchemaId,
+};
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::{
+    errors::error::{VcxLedgerError, VcxLedgerResult},
+    ledger::{
+        base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite},
+        indy::pool_manager::IndyPoolManager,
+        indy_vdr_ledger::{DefaultIndyLedgerRead, DefaultIndyLedgerWrite},
+    },
+};
+use aries_vcx_wallet::wallet::{
+    base_wallet::{record::Record, record_category::RecordCategory, BaseWallet},
+    record_tags::{RecordTag, RecordTags},
+};
+use did_parser_nom::Did;
+use time::OffsetDateTime;
+
+use super::{
+    credential_definition::{CredentialDef, PublicEntityStateType},
+    credential_schema::Schema,
+    revocation_registry::RevocationRegistry,
+};
+use crate::{
+    common::tails::TailsUploader,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum IssuerObjectKind {
+    Schema,
+    CredDef,
+    RevReg,
+}
+
+impl IssuerObjectKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Schema => "schema",
+            Self::CredDef => "cred_def",
+            Self::RevReg => "rev_reg",
+        }
+    }
+}
+
+impl fmt::Display for IssuerObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum IssuerObjectState {
+    /// Created in the wallet, not written to the ledger
+    Local,
+    /// Handed over to an endorser, which is expected to write it to the ledger
+    EndorsementPending,
+    Published,
+}
+
+impl IssuerObjectState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::EndorsementPending => "endorsement_pending",
+            Self::Published => "published",
+        }
+    }
+}
+
+impl fmt::Display for IssuerObjectState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Schema, credential definition or revocation registry created by the issuer, as tracked by the
+/// [`IssuerRegistry`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct IssuerObject {
+    pub id: String,
+    pub kind: IssuerObjectKind,
+    /// Name of the ledger the object belongs to
+    pub ledger: String,
+    pub issuer_did: String,
+    pub state: IssuerObjectState,
+    /// Schema id of credential definitions, credential definition id of revocation registries
+    pub parent_id: Option<String>,
+    /// Schema name, or tag of credential definitions and revocation registries
+    pub name: String,
+    /// Schema version
+    pub version: Option<String>,
+    /// Unix timestamp the object was first tracked at
+    pub created_at: u64,
+    pub published_at: Option<u64>,
+    /// Serialized [`Schema`], [`CredentialDef`] or [`RevocationRegistry`] the object can be
+    /// published from. Objects imported from the ledger have no local data.
+    pub data: Option<String>,
+}
+
+impl IssuerObject {
+    fn record_name(ledger: &str, id: &str) -> String {
+        format!("{ledger}/{id}")
+    }
+
+    fn tags(&self) -> RecordTags {
+        let mut tags = vec![
+            RecordTag::new(KIND_TAG, self.kind.as_str()),
+            RecordTag::new(LEDGER_TAG, &self.ledger),
+            RecordTag::new(STATE_TAG, self.state.as_str()),
+            RecordTag::new(ISSUER_DID_TAG, &self.issuer_did),
+            RecordTag::new(NAME_TAG, &self.name),
+        ];
+        if let Some(parent_id) = &self.parent_id {
+            tags.push(RecordTag::new(PARENT_ID_TAG, parent_id));
+        }
+        RecordTags::new(tags)
+    }
+
+    fn into_record(self) -> VcxResult<Record> {
+        Ok(Record::builder()
+            .name(Self::record_name(&self.ledger, &self.id))
+            .category(RecordCategory::IssuerObject)
+            .tags(self.tags())
+            .value(serde_json::to_string(&self)?)
+            .build())
+    }
+
+    fn from_record(record: &Record) -> VcxResult<Self> {
+        serde_json::from_str(record.value()).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize issuer object {}: {err}", record.name()),
+            )
+        })
+    }
+
+    fn local_data(&self) -> VcxResult<&str> {
+        self.data.as_deref().ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!(
+                    "{} {} is not on the ledger and has no local data to publish it from",
+                    self.kind, self.id
+                ),
+            )
+        })
+    }
+}
+
+const KIND_TAG: &str = "kind";
+const LEDGER_TAG: &str = "ledger";
+const STATE_TAG: &str = "state";
+const ISSUER_DID_TAG: &str = "issuer_did";
+const PARENT_ID_TAG: &str = "parent_id";
+const NAME_TAG: &str = "name";
+
+/// Criteria of [`IssuerRegistry::list`], unset criteria match every object.
+#[derive(Clone, Debug, Default)]
+pub struct IssuerObjectFilter {
+    pub kind: Option<IssuerObjectKind>,
+    pub state: Option<IssuerObjectState>,
+    pub issuer_did: Option<String>,
+    pub parent_id: Option<String>,
+    pub name: Option<String>,
+}
+
+impl IssuerObjectFilter {
+    fn to_wql(&self, ledger: Option<&str>) -> String {
+        let mut query = serde_json::Map::new();
+        let criteria = [
+            (KIND_TAG, self.kind.map(|kind| kind.as_str())),
+            (LEDGER_TAG, ledger),
+            (STATE_TAG, self.state.map(|state| state.as_str())),
+            (ISSUER_DID_TAG, self.issuer_did.as_deref()),
+            (PARENT_ID_TAG, self.parent_id.as_deref()),
+            (NAME_TAG, self.name.as_deref()),
+        ];
+        for (tag, value) in criteria {
+            if let Some(value) = value {
+                query.insert(tag.to_string(), value.into());
+            }
+        }
+        serde_json::Value::Object(query).to_string()
+    }
+}
+
+/// Index of the schemas, credential definitions and revocation registries an issuer created on
+/// a ledger, stored in the wallet. The registry is bound to the clients of its ledger, which
+/// every object it tracks, publishes or imports is labeled with. Several registries of different
+/// ledgers can share a wallet to track objects across ledgers.
+#[derive(Debug)]
+pub struct IssuerRegistry<'a, R, W> {
+    ledger: String,
+    ledger_read: &'a R,
+    ledger_write: &'a W,
+}
+
+impl<'a> IssuerRegistry<'a, DefaultIndyLedgerRead, DefaultIndyLedgerWrite> {
+    /// Registry of the indy network `namespace` connected by the pool manager, labeled with the
+    /// did:indy namespace of the network.
+    pub fn for_indy_network(pool_manager: &'a IndyPoolManager, namespace: &str) -> VcxResult<Self> {
+        match (
+            pool_manager.ledger_read(namespace),
+            pool_manager.ledger_write(namespace),
+        ) {
+            (Some(ledger_read), Some(ledger_write)) => {
+                Ok(Self::new(namespace, ledger_read, ledger_write))
+            }
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidConfiguration,
+                format!("No indy network {namespace} is connected by the pool manager"),
+            )),
+        }
+    }
+}
+
+impl<'a, R, W> IssuerRegistry<'a, R, W>
+where
+    R: AnoncredsLedgerRead,
+    W: AnoncredsLedgerWrite,
+{
+    /// Registry of the ledger served by `ledger_read` and `ledger_write`, labeled `ledger`. The
+    /// label of an indy network should be its did:indy namespace, see
+    /// [`IssuerRegistry::for_indy_network`].
+    pub fn new(ledger: impl Into<String>, ledger_read: &'a R, ledger_write: &'a W) -> Self {
+        Self {
+            ledger: ledger.into(),
+            ledger_read,
+            ledger_write,
+        }
+    }
+
+    pub fn ledger(&self) -> &str {
+        &self.ledger
+    }
+
+    pub async fn record_schema(
+        &self,
+        wallet: &impl BaseWallet,
+        schema: &Schema,
+    ) -> VcxResult<IssuerObject> {
+        let object = IssuerObject {
+            id: schema.schema_id.to_string(),
+            kind: IssuerObjectKind::Schema,
+            ledger: self.ledger.clone(),
+            issuer_did: schema.schema_json.issuer_id.to_string(),
+            state: IssuerObjectState::Local,
+            parent_id: None,
+            name: schema.name.clone(),
+            version: Some(schema.version.clone()),
+            created_at: now(),
+            published_at: None,
+            data: Some(schema.to_string_versioned()?),
+        };
+        self.track(
+            wallet,
+            object,
+            schema.state == PublicEntityStateType::Published,
+        )
+        .await
+    }
+
+    pub async fn record_cred_def(
+        &self,
+        wallet: &impl BaseWallet,
+        cred_def: &CredentialDef,
+    ) -> VcxResult<IssuerObject> {
+        let cred_def_json = cred_def.get_cred_def_json();
+        let object = IssuerObject {
+            id: cred_def.get_cred_def_id().to_string(),
+            kind: IssuerObjectKind::CredDef,
+            ledger: self.ledger.clone(),
+            issuer_did: cred_def_json.issuer_id.to_string(),
+            state: IssuerObjectState::Local,
+            parent_id: Some(cred_def.get_schema_id().to_string()),
+            name: cred_def_json.tag,
+            version: None,
+            created_at: now(),
+            published_at: None,
+            data: Some(cred_def.to_string()?),
+        };
+        self.track(wallet, object, cred_def.was_published()).await
+    }
+
+    pub async fn record_rev_reg(
+        &self,
+        wallet: &impl BaseWallet,
+        rev_reg: &RevocationRegistry,
+    ) -> VcxResult<IssuerObject> {
+        let rev_reg_def = rev_reg.get_rev_reg_def();
+        let object = IssuerObject {
+            id: rev_reg.get_rev_reg_id(),
+            kind: IssuerObjectKind::RevReg,
+            ledger: self.ledger.clone(),
+            issuer_did: rev_reg_def.issuer_id.to_string(),
+            state: IssuerObjectState::Local,
+            parent_id: Some(rev_reg.get_cred_def_id()),
+            name: rev_reg_def.tag,
+            version: None,
+            created_at: now(),
+            published_at: None,
+            data: Some(rev_reg.to_string()?),
+        };
+        let published =
+            rev_reg.was_rev_reg_def_published() && rev_reg.was_rev_reg_delta_published();
+        self.track(wallet, object, published).await
+    }
+
+    /// Stores the object, keeping the creation time and state of an already tracked object.
+    async fn track(
+        &self,
+        wallet: &impl BaseWallet,
+        mut object: IssuerObject,
+        published: bool,
+    ) -> VcxResult<IssuerObject> {
+        if let Some(existing) = self.find(wallet, &object.id).await? {
+            object.created_at = existing.created_at;
+            object.state = existing.state;
+            object.published_at = existing.published_at;
+            object.data = object.data.or(existing.data);
+        }
+        if published && object.state != IssuerObjectState::Published {
+            object.state = IssuerObjectState::Published;
+            object.published_at = Some(now());
+        }
+        self.store(wallet, object).await
+    }
+
+    async fn store(
+        &self,
+        wallet: &impl BaseWallet,
+        object: IssuerObject,
+    ) -> VcxResult<IssuerObject> {
+        let record = object.clone().into_record()?;
+        if self.find(wallet, &object.id).await?.is_some() {
+            wallet
+                .update_record_value(RecordCategory::IssuerObject, record.name(), record.value())
+                .await?;
+            wallet
+                .update_record_tags(
+                    RecordCategory::IssuerObject,
+                    record.name(),
+                    record.tags().clone(),
+                )
+                .await?;
+        } else {
+            wallet.add_record(record).await?;
+        }
+        Ok(object)
+    }
+
+    pub async fn get(&self, wallet: &impl BaseWallet, id: &str) -> VcxResult<IssuerObject> {
+        self.find(wallet, id).await?.ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::WalletRecordNotFound,
+                format!("No issuer object {id} tracked for ledger {}", self.ledger),
+            )
+        })
+    }
+
+    async fn find(&self, wallet: &impl BaseWallet, id: &str) -> VcxResult<Option<IssuerObject>> {
+        let name = IssuerObject::record_name(&self.ledger, id);
+        match wallet.get_record(RecordCategory::IssuerObject, &name).await {
+            Ok(record) => Ok(Some(IssuerObject::from_record(&record)?)),
+            Err(err) => {
+                let err = AriesVcxError::from(err);
+                if err.kind() == AriesVcxErrorKind::WalletRecordNotFound {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    /// Objects of this registry's ledger matching the filter, oldest first.
+    pub async fn list(
+        &self,
+        wallet: &impl BaseWallet,
+        filter: &IssuerObjectFilter,
+    ) -> VcxResult<Vec<IssuerObject>> {
+        Self::search(wallet, filter.to_wql(Some(&self.ledger))).await
+    }
+
+    /// Objects of all ledgers tracked in the wallet matching the filter, oldest first.
+    pub async fn list_all_ledgers(
+        &self,
+        wallet: &impl BaseWallet,
+        filter: &IssuerObjectFilter,
+    ) -> VcxResult<Vec<IssuerObject>> {
+        Self::search(wallet, filter.to_wql(None)).await
+    }
+
+    async fn search(wallet: &impl BaseWallet, query: String) -> VcxResult<Vec<IssuerObject>> {
+        let mut objects = wallet
+            .search_record(RecordCategory::IssuerObject, Some(query))
+            .await?
+            .iter()
+            .map(IssuerObject::from_record)
+            .collect::<VcxResult<Vec<_>>>()?;
+        objects.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(objects)
+    }
+
+    /// Versions of the schema `name` of the issuer, lowest version first.
+    pub async fn schema_versions(
+        &self,
+        wallet: &impl BaseWallet,
+        issuer_did: &str,
+        name: &str,
+    ) -> VcxResult<Vec<IssuerObject>> {
+        let filter = IssuerObjectFilter {
+            kind: Some(IssuerObjectKind::Schema),
+            issuer_did: Some(issuer_did.to_string()),
+            name: Some(name.to_string()),
+            ..Default::default()
+        };
+        let mut schemas = self.list(wallet, &filter).await?;
+        schemas.sort_by(|a, b| {
+            compare_versions(
+                a.version.as_deref().unwrap_or_default(),
+                b.version.as_deref().unwrap_or_default(),
+            )
+        });
+        Ok(schemas)
+    }
+
+    pub async fn latest_schema_version(
+        &self,
+        wallet: &impl BaseWallet,
+        issuer_did: &str,
+        name: &str,
+    ) -> VcxResult<Option<IssuerObject>> {
+        Ok(self.schema_versions(wallet, issuer_did, name).await?.pop())
+    }
+
+    /// Creates and tracks the next version of the schema `name`, `1.0` if the issuer has no
+    /// schema of that name yet. The minor version is bumped unless `major` is set.
+    pub async fn create_schema_version(
+        &self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        submitter_did: &Did,
+        name: &str,
+        attributes: Vec<String>,
+        major: bool,
+    ) -> VcxResult<(Schema, IssuerObject)> {
+        let latest = self
+            .latest_schema_version(wallet, &submitter_did.to_string(), name)
+            .await?;
+        let version = match latest.as_ref().and_then(|schema| schema.version.as_deref()) {
+            Some(version) => next_version(version, major)?,
+            None => "1.0".to_string(),
+        };
+        let source_id = format!("{name}:{version}");
+        let schema = Schema::create(
+            anoncreds,
+            &source_id,
+            submitter_did,
+            name,
+            &version,
+            attributes,
+        )
+        .await?;
+        let object = self.record_schema(wallet, &schema).await?;
+        Ok((schema, object))
+    }
+
+    /// Marks the object as handed over to an endorser. [`Self::ensure_published`] does not
+    /// submit objects pending endorsement, but marks them as published once on the ledger.
+    pub async fn mark_endorsement_pending(
+        &self,
+        wallet: &impl BaseWallet,
+        id: &str,
+    ) -> VcxResult<IssuerObject> {
+        let mut object = self.get(wallet, id).await?;
+        match object.state {
+            IssuerObjectState::Published => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("{} {id} is already published", object.kind),
+            )),
+            _ => {
+                object.state = IssuerObjectState::EndorsementPending;
+                self.store(wallet, object).await
+            }
+        }
+    }
+
+    /// Publishes the object unless it is already on the ledger. Safe to retry: the ledger is
+    /// checked before every write, and objects are marked as published as soon as they are
+    /// found on the ledger. The definition and the initial entry of a revocation registry are
+    /// checked separately, and its tails file is uploaded before the definition is published.
+    pub async fn ensure_published(
+        &self,
+        wallet: &impl BaseWallet,
+        uploader: &impl TailsUploader,
+        id: &str,
+    ) -> VcxResult<IssuerObject> {
+        trace!(
+            "IssuerRegistry::ensure_published >>> ledger: {}, id: {id}",
+            self.ledger
+        );
+        let mut object = self.get(wallet, id).await?;
+        let on_ledger = is_on_ledger(self.ledger_read, object.kind, id).await?;
+        // the definition of a revocation registry may be on the ledger without its initial entry
+        let entry_on_ledger = match object.kind {
+            IssuerObjectKind::RevReg if on_ledger => {
+                is_rev_reg_entry_on_ledger(self.ledger_read, id).await?
+            }
+            _ => on_ledger,
+        };
+        if entry_on_ledger && object.state == IssuerObjectState::Published {
+            return Ok(object);
+        }
+
+        match object.kind {
+            IssuerObjectKind::Schema if !on_ledger => {
+                if object.state == IssuerObjectState::EndorsementPending {
+                    return Ok(object);
+                }
+                let schema = Schema::from_string_versioned(object.local_data()?)?;
+                let schema = schema.publish(wallet, self.ledger_write).await?;
+                object.data = Some(schema.to_string_versioned()?);
+            }
+            IssuerObjectKind::CredDef if !on_ledger => {
+                if object.state == IssuerObjectState::EndorsementPending {
+                    return Ok(object);
+                }
+                let cred_def = CredentialDef::from_string(object.local_data()?)?;
+                let cred_def = cred_def
+                    .publish_cred_def(wallet, self.ledger_read, self.ledger_write)
+                    .await?;
+                object.data = Some(cred_def.to_string()?);
+            }
+            IssuerObjectKind::RevReg => {
+                if !on_ledger && object.state == IssuerObjectState::EndorsementPending {
+                    return Ok(object);
+                }
+                if entry_on_ledger {
+                    // published by an earlier attempt whose result was not persisted
+                    if let Some(data) = object.data.as_deref() {
+                        let mut rev_reg = RevocationRegistry::from_string(data)?;
+                        rev_reg.mark_rev_reg_def_published();
+                        rev_reg.mark_rev_reg_delta_published();
+                        object.data = Some(rev_reg.to_string()?);
+                    }
+                } else {
+                    let mut rev_reg = RevocationRegistry::from_string(object.local_data()?)?;
+                    if on_ledger {
+                        rev_reg.mark_rev_reg_def_published();
+                    }
+                    rev_reg
+                        .upload_and_publish_revocation_primitives(
+                            wallet,
+                            self.ledger_write,
+                            uploader,
+                        )
+                        .await?;
+                    object.data = Some(rev_reg.to_string()?);
+                }
+            }
+            _ => {}
+        }
+
+        if object.state != IssuerObjectState::Published {
+            info!(
+                "IssuerRegistry::ensure_published >>> {} {id} is published on ledger {}",
+                object.kind, self.ledger
+            );
+            object.state = IssuerObjectState::Published;
+            object.published_at = Some(now());
+        }
+        self.store(wallet, object).await
+    }
+
+    /// Tracks objects published on the ledger without local data, e.g. after the index was
+    /// lost. Objects already tracked keep their local data and are marked as published.
+    pub async fn import_from_ledger(
+        &self,
+        wallet: &impl BaseWallet,
+        kind: IssuerObjectKind,
+        id: &str,
+    ) -> VcxResult<IssuerObject> {
+        let object = match kind {
+            IssuerObjectKind::Schema => {
+                let schema = self
+                    .ledger_read
+                    .get_schema(&SchemaId::try_from(id.to_string())?, None)
+                    .await?;
+                IssuerObject {
+                    id: id.to_string(),
+                    kind,
+                    ledger: self.ledger.clone(),
+                    issuer_did: schema.issuer_id.to_string(),
+                    state: IssuerObjectState::Published,
+                    parent_id: None,
+                    name: schema.name,
+                    version: Some(schema.version),
+                    created_at: now(),
+                    published_at: Some(now()),
+                    data: None,
+                }
+            }
+            IssuerObjectKind::CredDef => {
+                let cred_def = self
+                    .ledger_read
+                    .get_cred_def(&CredentialDefinitionId::try_from(id.to_string())?, None)
+                    .await?;
+                IssuerObject {
+                    id: id.to_string(),
+                    kind,
+                    ledger: self.ledger.clone(),
+                    issuer_did: cred_def.issuer_id.to_string(),
+                    state: IssuerObjectState::Published,
+                    parent_id: Some(cred_def.schema_id.to_string()),
+                    name: cred_def.tag,
+                    version: None,
+                    created_at: now(),
+                    published_at: Some(now()),
+                    data: None,
+                }
+            }
+            IssuerObjectKind::RevReg => {
+                let (rev_reg_def, _) = self
+                    .ledger_read
+                    .get_rev_reg_def_json(&RevocationRegistryDefinitionId::try_from(
+                        id.to_string(),
+                    )?)
+                    .await?;
+                IssuerObject {
+                    id: id.to_string(),
+                    kind,
+                    ledger: self.ledger.clone(),
+                    issuer_did: rev_reg_def.issuer_id.to_string(),
+                    state: IssuerObjectState::Published,
+                    parent_id: Some(rev_reg_def.cred_def_id.to_string()),
+                    name: rev_reg_def.tag,
+                    version: None,
+                    created_at: now(),
+                    published_at: Some(now()),
+                    data: None,
+                }
+            }
+        };
+        self.track(wallet, object, true).await
+    }
+
+    /// Rebuilds the index from the ledger. As the ledger cannot be searched by issuer, the
+    /// objects are discovered from the index itself and from the schemas, credential definitions
+    /// and revocation registry definitions kept in the wallet by anoncreds. The state of every
+    /// object is refreshed from the ledger, objects found on the ledger only by their anoncreds
+    /// records are imported. Revocation registries count as published once their initial entry
+    /// is on the ledger.
+    pub async fn rebuild_index(&self, wallet: &impl BaseWallet) -> VcxResult<Vec<IssuerObject>> {
+        let mut candidates: Vec<(IssuerObjectKind, String)> = vec![];
+        for (category, kind) in [
+            (RecordCategory::CredSchema, IssuerObjectKind::Schema),
+            (RecordCategory::CredDef, IssuerObjectKind::CredDef),
+            (RecordCategory::RevRegDef, IssuerObjectKind::RevReg),
+        ] {
+            for record in wallet.search_record(category, None).await? {
+                candidates.push((kind, record.name().to_string()));
+            }
+        }
+        for object in self.list(wallet, &IssuerObjectFilter::default()).await? {
+            candidates.push((object.kind, object.id));
+        }
+
+        let mut seen = HashSet::new();
+        let mut objects = vec![];
+        for (kind, id) in candidates {
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            let tracked = self.find(wallet, &id).await?;
+            let on_ledger = match is_published(self.ledger_read, kind, &id).await {
+                Ok(on_ledger) => on_ledger,
+                Err(err) => {
+                    warn!(
+                        "IssuerRegistry::rebuild_index >>> failed to look up {kind} {id} on \
+                         ledger {}: {err}",
+                        self.ledger
+                    );
+                    if let Some(object) = tracked {
+                        objects.push(object);
+                    }
+                    continue;
+                }
+            };
+            match tracked {
+                Some(mut object) => {
+                    if on_ledger && object.state != IssuerObjectState::Published {
+                        object.state = IssuerObjectState::Published;
+                        object.published_at = Some(now());
+                        object = self.store(wallet, object).await?;
+                    } else if !on_ledger && object.state == IssuerObjectState::Published {
+                        warn!(
+                            "IssuerRegistry::rebuild_index >>> {kind} {id} is marked as published, \
+                             but was not found on ledger {}",
+                            self.ledger
+                        );
+                        object.state = IssuerObjectState::Local;
+                        object.published_at = None;
+                        object = self.store(wallet, object).await?;
+                    }
+                    objects.push(object);
+                }
+                None if on_ledger => {
+                    objects.push(self.import_from_ledger(wallet, kind, &id).await?);
+                }
+                // local anoncreds material never published, and not created through the registry
+                None => {}
+            }
+        }
+        Ok(objects)
+    }
+}
+
+async fn is_on_ledger(
+    ledger_read: &impl AnoncredsLedgerRead,
+    kind: IssuerObjectKind,
+    id: &str,
+) -> VcxResult<bool> {
+    let result = match kind {
+        IssuerObjectKind::Schema => ledger_read
+            .get_schema(&SchemaId::try_from(id.to_string())?, None)
+            .await
+            .map(|_| ()),
+        IssuerObjectKind::CredDef => ledger_read
+            .get_cred_def(&CredentialDefinitionId::try_from(id.to_string())?, None)
+            .await
+            .map(|_| ()),
+        IssuerObjectKind::RevReg => ledger_read
+            .get_rev_reg_def_json(&RevocationRegistryDefinitionId::try_from(id.to_string())?)
+            .await
+            .map(|_| ()),
+    };
+    presence(result, kind.as_str(), id)
+}
+
+/// Whether the initial entry of the revocation registry, or any later one, is on the ledger.
+async fn is_rev_reg_entry_on_ledger(
+    ledger_read: &impl AnoncredsLedgerRead,
+    id: &str,
+) -> VcxResult<bool> {
+    let result = ledger_read
+        .get_rev_reg(
+            &RevocationRegistryDefinitionId::try_from(id.to_string())?,
+            now(),
+        )
+        .await
+        .map(|_| ());
+    presence(result, "rev_reg entry of", id)
+}
+
+/// Whether the object is on the ledger, including the initial entry of revocation registries.
+async fn is_published(
+    ledger_read: &impl AnoncredsLedgerRead,
+    kind: IssuerObjectKind,
+    id: &str,
+) -> VcxResult<bool> {
+    let on_ledger = is_on_ledger(ledger_read, kind, id).await?;
+    match kind {
+        IssuerObjectKind::RevReg if on_ledger => is_rev_reg_entry_on_ledger(ledger_read, id).await,
+        _ => Ok(on_ledger),
+    }
+}
+
+fn presence(result: VcxLedgerResult<()>, what: &str, id: &str) -> VcxResult<bool> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(VcxLedgerError::LedgerItemNotFound) => Ok(false),
+        Err(err) => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidLedgerResponse,
+            format!("Failed to check presence of {what} {id} on the ledger\nError: {err}"),
+        )),
+    }
+}
+
+fn now() -> u64 {
+    OffsetDateTime::now_utc().unix_timestamp() as u64
+}
+
+fn parse_version(version: &str) -> VcxResult<Vec<u64>> {
+    version
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                format!("Invalid schema version {version}, expected numbers separated by dots"),
+            )
+        })
+}
+
+/// Orders dot separated numeric versions numerically, e.g. `1.10` after `1.9`. Versions which
+/// are not numeric are ordered lexicographically, before numeric ones.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (parse_version(a), parse_version(b)) {
+        (Ok(a), Ok(b)) => {
+            let len = a.len().max(b.len());
+            let part = |version: &[u64], i: usize| version.get(i).copied().unwrap_or(0);
+            (0..len)
+                .map(|i| part(&a, i).cmp(&part(&b, i)))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        }
+        (Ok(_), Err(_)) => Ordering::Greater,
+        (Err(_), Ok(_)) => Ordering::Less,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+fn next_version(version: &str, major: bool) -> VcxResult<String> {
+    let mut parts = parse_version(version)?;
+    if major {
+        Ok(format!("{}.0", parts[0] + 1))
+    } else {
+        if parts.len() < 2 {
+            parts.push(0);
+        }
+        let last = parts.len() - 1;
+        parts[last] += 1;
+        Ok(parts
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join("."))
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.9", "1.10"), Ordering::Less);
+        assert_eq!(compare_versions("2.0", "1.10"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("beta", "1.0"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_next_version() {
+        assert_eq!(next_version("1.0", false).unwrap(), "1.1");
+        assert_eq!(next_version("1.9", false).unwrap(), "1.10");
+        assert_eq!(next_version("1.2.3", false).unwrap(), "1.2.4");
+        assert_eq!(next_version("2", false).unwrap(), "2.1");
+        assert_eq!(next_version("1.9", true).unwrap(), "2.0");
+        assert_eq!(
+            next_version("1.x", false).unwrap_err().kind(),
+            AriesVcxErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn test_filter_to_wql() {
+        let filter = IssuerObjectFilter {
+            kind: Some(IssuerObjectKind::CredDef),
+            state: Some(IssuerObjectState::EndorsementPending),
+            ..Default::default()
+        };
+        let query: serde_json::Value =
+            serde_json::from_str(&filter.to_wql(Some("sovrin:staging"))).unwrap();
+        assert_eq!(
+            query,
+            json!({
+                "kind": "cred_def",
+                "ledger": "sovrin:staging",
+                "state": "endorsement_pending",
+            })
+        );
+        assert_eq!(IssuerObjectFilter::default().to_wql(None), "{}");
+    }
+
+    #[test]
+    fn test_object_record() {
+        let object = IssuerObject {
+            id: "V4SGRU86Z58d6TV7PBUe6f:3:CL:16:tag".to_string(),
+            kind: IssuerObjectKind::CredDef,
+            ledger: "sovrin".to_string(),
+            issuer_did: "V4SGRU86Z58d6TV7PBUe6f".to_string(),
+            state: IssuerObjectState::Local,
+            parent_id: Some("V4SGRU86Z58d6TV7PBUe6f:2:schema:1.0".to_string()),
+            name: "tag".to_string(),
+            version: None,
+            created_at: 1_700_000_000,
+            published_at: None,
+            data: None,
+        };
+        let record = object.clone().into_record().unwrap();
+        assert_eq!(record.name(), "sovrin/V4SGRU86Z58d6TV7PBUe6f:3:CL:16:tag");
+        assert_eq!(record.category(), &RecordCategory::IssuerObject);
+        let tags: Vec<(String, String)> = record
+            .tags()
+            .clone()
+            .into_iter()
+            .map(RecordTag::into_pair)
+            .collect();
+        assert!(tags.contains(&(STATE_TAG.to_string(), "local".to_string())));
+        assert!(tags.contains(&(
+            PARENT_ID_TAG.to_string(),
+            "V4SGRU86Z58d6TV7PBUe6f:2:schema:1.0".to_string()
+        )));
+        assert_eq!(IssuerObject::from_record(&record).unwrap(), object);
+    }
+}
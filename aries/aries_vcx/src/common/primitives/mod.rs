@@ -1,3 +1,6 @@
 pub mod credential_definition;
 pub mod credential_schema;
+pub mod issuer_registry;
+pub mod revocation_batch;
 pub mod revocation_registry;
+pub mod revocation_registry_pool;
@@ -0,0 +1,454 @@
+use std::collections::BTreeMap;
+
+use anoncreds_types::data_types::ledger::rev_status_list::RevocationStatusList;
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use did_parser_nom::Did;
+use messages::decorators::please_ack::AckOn;
+
+use super::revocation_registry::publish_local_revocations;
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::{
+        issuance::issuer::Issuer, revocation_notification::sender::RevocationNotificationSender,
+    },
+    protocols::{revocation_notification::sender::state_machine::SenderConfigBuilder, SendClosure},
+};
+
+/// Credential queued for revocation.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct QueuedRevocation {
+    pub rev_reg_id: String,
+    pub cred_rev_id: u32,
+    /// Holder to send a `revocation_notification` to once the revocation is published, e.g. the
+    /// id of the connection the credential was issued over
+    pub holder: Option<String>,
+}
+
+/// Changes a batch makes to the revocation status list of a registry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RevocationStatusListDiff {
+    pub newly_revoked: Vec<u32>,
+    pub reinstated: Vec<u32>,
+}
+
+impl RevocationStatusListDiff {
+    pub fn between(current: &RevocationStatusList, updated: &RevocationStatusList) -> Self {
+        let current = current.state();
+        let updated = updated.state();
+        let mut diff = Self::default();
+        for idx in 0..current.len().max(updated.len()) {
+            let was_revoked = current.get(idx).is_some_and(|bit| *bit);
+            let is_revoked = updated.get(idx).is_some_and(|bit| *bit);
+            match (was_revoked, is_revoked) {
+                (false, true) => diff.newly_revoked.push(idx as u32),
+                (true, false) => diff.reinstated.push(idx as u32),
+                _ => {}
+            }
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.newly_revoked.is_empty() && self.reinstated.is_empty()
+    }
+}
+
+/// Preview of a batch committed for one revocation registry.
+#[derive(Clone, Debug)]
+pub struct RevocationBatchPreview {
+    pub rev_reg_id: String,
+    /// Status list the registry would have on the ledger once the batch is committed
+    pub status_list: RevocationStatusList,
+    pub diff: RevocationStatusListDiff,
+    /// Queued credentials which are revoked already, on the ledger or locally
+    pub already_revoked: Vec<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublishedRevocations {
+    pub rev_reg_id: String,
+    pub cred_rev_ids: Vec<u32>,
+}
+
+#[derive(Debug)]
+pub struct FailedRevocations {
+    pub rev_reg_id: String,
+    pub cred_rev_ids: Vec<u32>,
+    pub error: AriesVcxError,
+}
+
+#[derive(Debug)]
+pub struct RevocationNotificationResult {
+    pub revocation: QueuedRevocation,
+    pub result: VcxResult<RevocationNotificationSender>,
+}
+
+#[derive(Debug, Default)]
+pub struct RevocationBatchReport {
+    pub published: Vec<PublishedRevocations>,
+    /// Registries whose revocations are left in the batch, to be retried by the next commit
+    pub failed: Vec<FailedRevocations>,
+    pub notifications: Vec<RevocationNotificationResult>,
+}
+
+impl RevocationBatchReport {
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+            && self
+                .notifications
+                .iter()
+                .all(|notification| notification.result.is_ok())
+    }
+}
+
+/// Queue of credentials to revoke, committed with a single ledger write per revocation registry
+/// instead of one per credential. Holders of the revoked credentials are sent a
+/// `revocation_notification` once the revocation is published.
+///
+/// Revocations are staged in the local revocation registry delta of the wallet, so credentials
+/// revoked through [`BaseAnonCreds::revoke_credential_local`] but not published yet are published
+/// along with the batch.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RevocationBatch {
+    /// Holders by `cred_rev_id`, by revocation registry id
+    revocations: BTreeMap<String, BTreeMap<u32, Option<String>>>,
+    comment: Option<String>,
+    ack_on: Vec<AckOn>,
+}
+
+impl RevocationBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Comment sent along with the revocation notifications.
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    pub fn with_ack_on(mut self, ack_on: Vec<AckOn>) -> Self {
+        self.ack_on = ack_on;
+        self
+    }
+
+    /// Queues a credential for revocation. Queueing it again only updates the holder to notify.
+    pub fn queue(&mut self, rev_reg_id: &str, cred_rev_id: u32, holder: Option<String>) {
+        self.revocations
+            .entry(rev_reg_id.to_string())
+            .or_default()
+            .insert(cred_rev_id, holder);
+    }
+
+    /// Queues the credential issued by the issuer for revocation.
+    pub fn queue_issued(&mut self, issuer: &Issuer, holder: Option<String>) -> VcxResult<()> {
+        if !issuer.is_revokable() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!(
+                    "Can't revoke credential in state {:?}, credential is not revokable",
+                    issuer.get_state()
+                ),
+            ));
+        }
+        self.queue(&issuer.get_rev_reg_id()?, issuer.get_rev_id()?, holder);
+        Ok(())
+    }
+
+    pub fn dequeue(&mut self, rev_reg_id: &str, cred_rev_id: u32) -> bool {
+        let Some(queued) = self.revocations.get_mut(rev_reg_id) else {
+            return false;
+        };
+        let removed = queued.remove(&cred_rev_id).is_some();
+        if queued.is_empty() {
+            self.revocations.remove(rev_reg_id);
+        }
+        removed
+    }
+
+    pub fn len(&self) -> usize {
+        self.revocations.values().map(BTreeMap::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.revocations.is_empty()
+    }
+
+    pub fn rev_reg_ids(&self) -> impl Iterator<Item = &str> {
+        self.revocations.keys().map(String::as_str)
+    }
+
+    pub fn queued(&self) -> impl Iterator<Item = QueuedRevocation> + '_ {
+        self.revocations.iter().flat_map(|(rev_reg_id, queued)| {
+            queued.iter().map(|(cred_rev_id, holder)| QueuedRevocation {
+                rev_reg_id: rev_reg_id.clone(),
+                cred_rev_id: *cred_rev_id,
+                holder: holder.clone(),
+            })
+        })
+    }
+
+    /// Computes the revocation status list of each registry of the batch as it would be after
+    /// committing, without changing any state.
+    pub async fn preview(
+        &self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger_read: &impl AnoncredsLedgerRead,
+    ) -> VcxResult<Vec<RevocationBatchPreview>> {
+        let mut previews = Vec::with_capacity(self.revocations.len());
+        for (rev_reg_id, queued) in &self.revocations {
+            let cred_rev_ids: Vec<u32> = queued.keys().copied().collect();
+            #[allow(deprecated)] // TODO - https://github.com/openwallet-foundation/vcx/issues/1309
+            let rev_reg_delta_json = ledger_read
+                .get_rev_reg_delta_json(&rev_reg_id.to_owned().try_into()?, None, None)
+                .await?
+                .0;
+            let (current, updated) = anoncreds
+                .preview_revoke_credentials_local(
+                    wallet,
+                    &rev_reg_id.to_owned().try_into()?,
+                    &cred_rev_ids,
+                    rev_reg_delta_json,
+                )
+                .await?;
+            let already_revoked = cred_rev_ids
+                .into_iter()
+                .filter(|cred_rev_id| {
+                    current
+                        .state()
+                        .get(*cred_rev_id as usize)
+                        .is_some_and(|bit| *bit)
+                })
+                .collect();
+            previews.push(RevocationBatchPreview {
+                rev_reg_id: rev_reg_id.clone(),
+                diff: RevocationStatusListDiff::between(&current, &updated),
+                status_list: updated,
+                already_revoked,
+            });
+        }
+        Ok(previews)
+    }
+
+    /// Revokes the queued credentials and publishes each revocation registry with a single
+    /// ledger write, then notifies the holders of the credentials newly revoked by the batch
+    /// through the closure returned by `send_message` for their holder. Holders of credentials
+    /// which were revoked on the ledger already are not notified again.
+    ///
+    /// Registries failing to publish do not prevent the others from being committed; their
+    /// revocations stay queued and are retried by the next commit.
+    pub async fn commit<'a>(
+        &mut self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger_read: &impl AnoncredsLedgerRead,
+        ledger_write: &impl AnoncredsLedgerWrite,
+        submitter_did: &Did,
+        send_message: impl Fn(&str) -> VcxResult<SendClosure<'a>>,
+    ) -> VcxResult<RevocationBatchReport> {
+        trace!(
+            "RevocationBatch::commit >>> {} revocations in {} registries",
+            self.len(),
+            self.revocations.len()
+        );
+        let mut report = RevocationBatchReport::default();
+        let rev_reg_ids: Vec<String> = self.revocations.keys().cloned().collect();
+        for rev_reg_id in rev_reg_ids {
+            let cred_rev_ids: Vec<u32> = self.revocations[&rev_reg_id].keys().copied().collect();
+            let newly_revoked = match Self::commit_registry(
+                wallet,
+                anoncreds,
+                ledger_read,
+                ledger_write,
+                submitter_did,
+                &rev_reg_id,
+                &cred_rev_ids,
+            )
+            .await
+            {
+                Ok(newly_revoked) => newly_revoked,
+                Err(error) => {
+                    warn!(
+                        "RevocationBatch::commit >>> failed to publish revocations of registry \
+                         {rev_reg_id}: {error}"
+                    );
+                    report.failed.push(FailedRevocations {
+                        rev_reg_id,
+                        cred_rev_ids,
+                        error,
+                    });
+                    continue;
+                }
+            };
+            info!(
+                "RevocationBatch::commit >>> published {} revocations of registry {rev_reg_id}",
+                cred_rev_ids.len()
+            );
+
+            let queued = self.revocations.remove(&rev_reg_id).unwrap_or_default();
+            for (cred_rev_id, holder) in queued {
+                // credentials revoked on the ledger before the batch were notified already
+                let Some(holder) = holder.filter(|_| newly_revoked.contains(&cred_rev_id)) else {
+                    continue;
+                };
+                let revocation = QueuedRevocation {
+                    rev_reg_id: rev_reg_id.clone(),
+                    cred_rev_id,
+                    holder: Some(holder.clone()),
+                };
+                let result = self
+                    .notify(&rev_reg_id, cred_rev_id, send_message(&holder))
+                    .await;
+                if let Err(err) = &result {
+                    warn!(
+                        "RevocationBatch::commit >>> failed to notify holder {holder} of \
+                         revocation of credential {cred_rev_id} of registry {rev_reg_id}: {err}"
+                    );
+                }
+                report
+                    .notifications
+                    .push(RevocationNotificationResult { revocation, result });
+            }
+            report.published.push(PublishedRevocations {
+                rev_reg_id,
+                cred_rev_ids,
+            });
+        }
+        Ok(report)
+    }
+
+    async fn commit_registry(
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger_read: &impl AnoncredsLedgerRead,
+        ledger_write: &impl AnoncredsLedgerWrite,
+        submitter_did: &Did,
+        rev_reg_id: &str,
+        cred_rev_ids: &[u32],
+    ) -> VcxResult<Vec<u32>> {
+        #[allow(deprecated)] // TODO - https://github.com/openwallet-foundation/vcx/issues/1309
+        let rev_reg_delta_json = ledger_read
+            .get_rev_reg_delta_json(&rev_reg_id.to_owned().try_into()?, None, None)
+            .await?
+            .0;
+        // credentials revoked locally but not published yet are newly revoked as well
+        let newly_revoked = cred_rev_ids
+            .iter()
+            .copied()
+            .filter(|cred_rev_id| !rev_reg_delta_json.value.revoked.contains(cred_rev_id))
+            .collect();
+        anoncreds
+            .revoke_credentials_local(
+                wallet,
+                &rev_reg_id.to_owned().try_into()?,
+                cred_rev_ids,
+                rev_reg_delta_json,
+            )
+            .await?;
+        match publish_local_revocations(wallet, anoncreds, ledger_write, rev_reg_id, submitter_did)
+            .await
+        {
+            // every credential was revoked on the ledger already
+            Err(err) if err.kind() == AriesVcxErrorKind::RevDeltaNotFound => {}
+            result => result?,
+        }
+        Ok(newly_revoked)
+    }
+
+    async fn notify(
+        &self,
+        rev_reg_id: &str,
+        cred_rev_id: u32,
+        send_message: VcxResult<SendClosure<'_>>,
+    ) -> VcxResult<RevocationNotificationSender> {
+        let config = SenderConfigBuilder::default()
+            .rev_reg_id(rev_reg_id.to_string())
+            .cred_rev_id(cred_rev_id)
+            .comment(self.comment.clone())
+            .ack_on(self.ack_on.clone())
+            .build()?;
+        RevocationNotificationSender::build()
+            .send_revocation_notification(config, send_message?)
+            .await
+    }
+
+    pub fn to_string(&self) -> VcxResult<String> {
+        serde_json::to_string(&self).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::SerializationError,
+                format!("Cannot serialize revocation batch: {err:?}"),
+            )
+        })
+    }
+
+    pub fn from_string(batch_data: &str) -> VcxResult<Self> {
+        serde_json::from_str(batch_data).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize revocation batch: {err:?}"),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn status_list(revoked: &[usize]) -> RevocationStatusList {
+        serde_json::from_value(serde_json::json!({
+            "issuerId": "V4SGRU86Z58d6TV7PBUe6f",
+            "revocationList": (0..5)
+                .map(|idx| u8::from(revoked.contains(&idx)))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_status_list_diff() {
+        let diff = RevocationStatusListDiff::between(&status_list(&[1]), &status_list(&[1, 2, 4]));
+        assert_eq!(diff.newly_revoked, vec![2, 4]);
+        assert!(diff.reinstated.is_empty());
+
+        let diff = RevocationStatusListDiff::between(&status_list(&[1, 3]), &status_list(&[3]));
+        assert_eq!(diff.reinstated, vec![1]);
+
+        assert!(
+            RevocationStatusListDiff::between(&status_list(&[0]), &status_list(&[0])).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_queue_and_dequeue() {
+        let mut batch = RevocationBatch::new();
+        batch.queue("rev_reg_1", 1, None);
+        batch.queue("rev_reg_1", 2, Some("connection_1".into()));
+        batch.queue("rev_reg_2", 1, None);
+        // queueing again only updates the holder
+        batch.queue("rev_reg_1", 1, Some("connection_2".into()));
+        assert_eq!(batch.len(), 3);
+        assert_eq!(
+            batch.rev_reg_ids().collect::<Vec<_>>(),
+            ["rev_reg_1", "rev_reg_2"]
+        );
+        assert_eq!(
+            batch.queued().next().unwrap(),
+            QueuedRevocation {
+                rev_reg_id: "rev_reg_1".into(),
+                cred_rev_id: 1,
+                holder: Some("connection_2".into()),
+            }
+        );
+
+        assert!(batch.dequeue("rev_reg_2", 1));
+        assert!(!batch.dequeue("rev_reg_2", 1));
+        assert_eq!(batch.rev_reg_ids().collect::<Vec<_>>(), ["rev_reg_1"]);
+
+        let batch = RevocationBatch::from_string(&batch.to_string().unwrap()).unwrap();
+        assert_eq!(batch.len(), 2);
+    }
+}
@@ -10,7 +10,10 @@ use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 use did_parser_nom::Did;
 
 use super::credential_definition::PublicEntityStateType;
-use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+use crate::{
+    common::tails::TailsUploader,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+};
 
 #[derive(Clone, Deserialize, Debug, Serialize)]
 pub struct RevocationRegistry {
@@ -90,10 +93,22 @@ impl RevocationRegistry {
         self.rev_reg_def_state == PublicEntityStateType::Published
     }
 
+    /// Records that the definition is on the ledger already, e.g. published by an earlier attempt
+    /// whose result was not persisted.
+    pub(in crate::common) fn mark_rev_reg_def_published(&mut self) {
+        self.rev_reg_def_state = PublicEntityStateType::Published;
+    }
+
     pub fn was_rev_reg_delta_published(&self) -> bool {
         self.rev_reg_delta_state == PublicEntityStateType::Published
     }
 
+    /// Records that the initial entry is on the ledger already, see
+    /// [`Self::mark_rev_reg_def_published`].
+    pub(in crate::common) fn mark_rev_reg_delta_published(&mut self) {
+        self.rev_reg_delta_state = PublicEntityStateType::Published;
+    }
+
     pub async fn publish_rev_reg_def(
         &mut self,
         wallet: &impl BaseWallet,
@@ -167,6 +182,29 @@ impl RevocationRegistry {
         self.publish_built_rev_reg_delta(wallet, ledger_write).await
     }
 
+    /// Uploads the tails file of the registry, returning its public location.
+    pub async fn upload_tails_file(&self, uploader: &impl TailsUploader) -> VcxResult<String> {
+        let tails_file = Path::new(&self.tails_dir).join(&self.rev_reg_def.value.tails_hash);
+        uploader.upload(&self.rev_reg_id, &tails_file).await
+    }
+
+    /// Like [`Self::publish_revocation_primitives`], but uploads the tails file first and publishes
+    /// the location returned by the uploader.
+    pub async fn upload_and_publish_revocation_primitives(
+        &mut self,
+        wallet: &impl BaseWallet,
+        ledger_write: &impl AnoncredsLedgerWrite,
+        uploader: &impl TailsUploader,
+    ) -> VcxResult<()> {
+        let tails_url = if self.was_rev_reg_def_published() {
+            self.rev_reg_def.value.tails_location.clone()
+        } else {
+            self.upload_tails_file(uploader).await?
+        };
+        self.publish_revocation_primitives(wallet, ledger_write, &tails_url)
+            .await
+    }
+
     async fn publish_built_rev_reg_delta(
         &mut self,
         wallet: &impl BaseWallet,
@@ -246,54 +284,73 @@ impl RevocationRegistry {
         ledger_write: &impl AnoncredsLedgerWrite,
         submitter_did: &Did,
     ) -> VcxResult<()> {
-        if let Some(delta) = anoncreds
-            .get_rev_reg_delta(wallet, &self.rev_reg_id.to_owned().try_into()?)
-            .await?
-        {
-            ledger_write
-                .publish_rev_reg_delta(
-                    wallet,
-                    &self.rev_reg_id.to_string().try_into()?,
-                    delta,
-                    submitter_did,
-                )
-                .await?;
+        publish_local_revocations(
+            wallet,
+            anoncreds,
+            ledger_write,
+            &self.rev_reg_id,
+            submitter_did,
+        )
+        .await
+    }
+}
+
+/// Publishes the local revocation registry delta of the registry in a single ledger write, then
+/// clears it.
+pub async fn publish_local_revocations(
+    wallet: &impl BaseWallet,
+    anoncreds: &impl BaseAnonCreds,
+    ledger_write: &impl AnoncredsLedgerWrite,
+    rev_reg_id: &str,
+    submitter_did: &Did,
+) -> VcxResult<()> {
+    if let Some(delta) = anoncreds
+        .get_rev_reg_delta(wallet, &rev_reg_id.to_owned().try_into()?)
+        .await?
+    {
+        ledger_write
+            .publish_rev_reg_delta(
+                wallet,
+                &rev_reg_id.to_string().try_into()?,
+                delta,
+                submitter_did,
+            )
+            .await?;
 
-            info!(
-                "publish_local_revocations >>> rev_reg_delta published for rev_reg_id {}",
-                self.rev_reg_id
-            );
+        info!(
+            "publish_local_revocations >>> rev_reg_delta published for rev_reg_id {}",
+            rev_reg_id
+        );
 
-            match anoncreds
-                .clear_rev_reg_delta(wallet, &self.rev_reg_id.to_owned().try_into()?)
-                .await
-            {
-                Ok(_) => {
-                    info!(
-                        "publish_local_revocations >>> rev_reg_delta storage cleared for \
-                         rev_reg_id {}",
-                        self.rev_reg_id
-                    );
-                    Ok(())
-                }
-                Err(err) => Err(AriesVcxError::from_msg(
-                    AriesVcxErrorKind::RevDeltaFailedToClear,
-                    format!(
-                        "Failed to clear revocation delta storage for rev_reg_id: {}, error: {err}",
-                        self.rev_reg_id
-                    ),
-                )),
+        match anoncreds
+            .clear_rev_reg_delta(wallet, &rev_reg_id.to_owned().try_into()?)
+            .await
+        {
+            Ok(_) => {
+                info!(
+                    "publish_local_revocations >>> rev_reg_delta storage cleared for \
+                     rev_reg_id {}",
+                    rev_reg_id
+                );
+                Ok(())
             }
-        } else {
-            Err(AriesVcxError::from_msg(
-                AriesVcxErrorKind::RevDeltaNotFound,
+            Err(err) => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::RevDeltaFailedToClear,
                 format!(
-                    "Failed to publish revocation delta for revocation registry {}, no delta \
-                     found. Possibly already published?",
-                    self.rev_reg_id
+                    "Failed to clear revocation delta storage for rev_reg_id: {}, error: {err}",
+                    rev_reg_id
                 ),
-            ))
+            )),
         }
+    } else {
+        Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::RevDeltaNotFound,
+            format!(
+                "Failed to publish revocation delta for revocation registry {}, no delta \
+                 found. Possibly already published?",
+                rev_reg_id
+            ),
+        ))
     }
 }
 
@@ -0,0 +1,418 @@
+use anoncreds_types::data_types::identifiers::cred_def_id::CredentialDefinitionId;
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerWrite;
+use aries_vcx_wallet::wallet::{
+    base_wallet::{record::Record, record_category::RecordCategory, BaseWallet},
+    record_tags::{RecordTag, RecordTags},
+};
+use did_parser_nom::Did;
+use serde_json::json;
+
+use super::revocation_registry::RevocationRegistry;
+use crate::{
+    common::tails::TailsUploader,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RevocationRegistryPoolConfig {
+    pub issuer_did: Did,
+    pub cred_def_id: CredentialDefinitionId,
+    pub tails_dir: String,
+    /// Capacity of each registry of the pool
+    pub max_creds: u32,
+}
+
+/// Revocation registry a credential was issued into.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct IssuedCredentialRegistry {
+    pub rev_reg_id: String,
+    pub cred_rev_id: u32,
+}
+
+impl IssuedCredentialRegistry {
+    fn into_record(self, thread_id: &str, cred_def_id: &str) -> VcxResult<Record> {
+        let tags = RecordTags::new(vec![
+            RecordTag::new(CRED_DEF_ID_TAG, cred_def_id),
+            RecordTag::new(REV_REG_ID_TAG, &self.rev_reg_id),
+        ]);
+        Ok(Record::builder()
+            .category(RecordCategory::RevRegIssuance)
+            .name(thread_id.to_string())
+            .tags(tags)
+            .value(serde_json::to_string(&self)?)
+            .build())
+    }
+}
+
+const CRED_DEF_ID_TAG: &str = "cred_def_id";
+const REV_REG_ID_TAG: &str = "rev_reg_id";
+
+/// Part of a revocation registry definition stored by [`BaseAnonCreds`], used to find the
+/// registries of a credential definition
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredRevRegDef {
+    cred_def_id: String,
+    tag: String,
+}
+
+/// Revocation registries of a credential definition, rotated as they fill up. Credentials are
+/// issued into the active registry, while a standby registry is already published on the ledger
+/// to take over as soon as the active one is full, so that issuance never waits for the ledger.
+///
+/// Only the active and standby registries are part of the pool's serialized state. Retired
+/// registries and the registry each credential was issued into are stored in the wallet
+/// ([`RecordCategory::RetiredRevReg`], [`RecordCategory::RevRegIssuance`]).
+///
+/// The pool only depends on [`AnoncredsLedgerWrite`], so it works with every ledger which
+/// supports publishing revocation registries.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RevocationRegistryPool {
+    config: RevocationRegistryPoolConfig,
+    active: RevocationRegistry,
+    standby: Option<RevocationRegistry>,
+    /// Highest `cred_rev_id` issued from the active registry
+    active_issued: u32,
+}
+
+impl RevocationRegistryPool {
+    /// Creates and publishes the active and standby registries of a new pool.
+    pub async fn create(
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger_write: &impl AnoncredsLedgerWrite,
+        uploader: &impl TailsUploader,
+        config: RevocationRegistryPoolConfig,
+    ) -> VcxResult<Self> {
+        trace!("RevocationRegistryPool::create >>> config: {config:?}");
+        if config.max_creds == 0 {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidOption,
+                "Revocation registry capacity must be greater than 0",
+            ));
+        }
+        let active =
+            Self::create_registry(wallet, anoncreds, ledger_write, uploader, &config).await?;
+        let mut pool = Self {
+            config,
+            active,
+            standby: None,
+            active_issued: 0,
+        };
+        pool.prepare_standby(wallet, anoncreds, ledger_write, uploader)
+            .await?;
+        Ok(pool)
+    }
+
+    async fn create_registry(
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger_write: &impl AnoncredsLedgerWrite,
+        uploader: &impl TailsUploader,
+        config: &RevocationRegistryPoolConfig,
+    ) -> VcxResult<RevocationRegistry> {
+        let tag = next_tag(wallet, &config.cred_def_id).await?;
+        let mut registry = RevocationRegistry::create(
+            wallet,
+            anoncreds,
+            &config.issuer_did,
+            &config.cred_def_id,
+            &config.tails_dir,
+            config.max_creds,
+            tag,
+        )
+        .await?;
+        registry
+            .upload_and_publish_revocation_primitives(wallet, ledger_write, uploader)
+            .await?;
+        Ok(registry)
+    }
+
+    pub fn config(&self) -> &RevocationRegistryPoolConfig {
+        &self.config
+    }
+
+    pub fn active_registry(&self) -> &RevocationRegistry {
+        &self.active
+    }
+
+    pub fn standby_registry(&self) -> Option<&RevocationRegistry> {
+        self.standby.as_ref()
+    }
+
+    /// Registries the pool retired as they filled up.
+    pub async fn retired_registries(
+        &self,
+        wallet: &impl BaseWallet,
+    ) -> VcxResult<Vec<RevocationRegistry>> {
+        let query = json!({ CRED_DEF_ID_TAG: self.config.cred_def_id.to_string() }).to_string();
+        let mut registries = wallet
+            .search_record(RecordCategory::RetiredRevReg, Some(query))
+            .await?
+            .iter()
+            .map(|record| RevocationRegistry::from_string(record.value()))
+            .collect::<VcxResult<Vec<_>>>()?;
+        registries.sort_by_key(|registry| registry.tag);
+        Ok(registries)
+    }
+
+    /// Registry of the pool with the given id, whether active, standby or retired.
+    pub async fn registry(
+        &self,
+        wallet: &impl BaseWallet,
+        rev_reg_id: &str,
+    ) -> VcxResult<Option<RevocationRegistry>> {
+        if let Some(registry) = std::iter::once(&self.active)
+            .chain(self.standby.iter())
+            .find(|registry| registry.rev_reg_id == rev_reg_id)
+        {
+            return Ok(Some(registry.clone()));
+        }
+        match find_record(wallet, RecordCategory::RetiredRevReg, rev_reg_id).await? {
+            Some(record) => Ok(Some(RevocationRegistry::from_string(record.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn is_active_full(&self) -> bool {
+        self.active_issued >= self.active.max_creds
+    }
+
+    /// Registry the credential issued in the given thread went into.
+    pub async fn issued_credential(
+        &self,
+        wallet: &impl BaseWallet,
+        thread_id: &str,
+    ) -> VcxResult<Option<IssuedCredentialRegistry>> {
+        match find_record(wallet, RecordCategory::RevRegIssuance, thread_id).await? {
+            Some(record) => Ok(Some(serde_json::from_str(record.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Records a credential issued from the pool.
+    pub async fn record_issuance(
+        &mut self,
+        wallet: &impl BaseWallet,
+        thread_id: &str,
+        rev_reg_id: &str,
+        cred_rev_id: u32,
+    ) -> VcxResult<()> {
+        if self.registry(wallet, rev_reg_id).await?.is_none() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidRevocationDetails,
+                format!("Revocation registry {rev_reg_id} is not part of the pool"),
+            ));
+        }
+        let record = IssuedCredentialRegistry {
+            rev_reg_id: rev_reg_id.to_string(),
+            cred_rev_id,
+        }
+        .into_record(thread_id, &self.config.cred_def_id.to_string())?;
+        wallet.add_record(record).await?;
+        if rev_reg_id == self.active.rev_reg_id {
+            self.active_issued = self.active_issued.max(cred_rev_id);
+        }
+        Ok(())
+    }
+
+    /// Switches over to the standby registry if the active one is full, then publishes a new
+    /// standby registry. Returns whether the pool was rotated.
+    pub async fn rotate_if_full(
+        &mut self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger_write: &impl AnoncredsLedgerWrite,
+        uploader: &impl TailsUploader,
+    ) -> VcxResult<bool> {
+        if !self.is_active_full() {
+            // a previous rotation may have failed to publish the standby, which must not prevent
+            // issuance while the active registry has capacity left
+            if self.standby.is_none() {
+                if let Err(err) = self
+                    .prepare_standby(wallet, anoncreds, ledger_write, uploader)
+                    .await
+                {
+                    warn!(
+                        "RevocationRegistryPool::rotate_if_full >>> failed to prepare standby \
+                         registry: {err}"
+                    );
+                }
+            }
+            return Ok(false);
+        }
+        self.rotate(wallet, anoncreds, ledger_write, uploader)
+            .await?;
+        Ok(true)
+    }
+
+    /// Retires the active registry, regardless of its remaining capacity.
+    pub async fn rotate(
+        &mut self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger_write: &impl AnoncredsLedgerWrite,
+        uploader: &impl TailsUploader,
+    ) -> VcxResult<()> {
+        if self.standby.is_none() {
+            self.prepare_standby(wallet, anoncreds, ledger_write, uploader)
+                .await?;
+        }
+        let retired = Record::builder()
+            .category(RecordCategory::RetiredRevReg)
+            .name(self.active.rev_reg_id.clone())
+            .tags(RecordTags::new(vec![RecordTag::new(
+                CRED_DEF_ID_TAG,
+                &self.config.cred_def_id.to_string(),
+            )]))
+            .value(self.active.to_string()?)
+            .build();
+        match wallet
+            .add_record(retired)
+            .await
+            .map_err(AriesVcxError::from)
+        {
+            // retired by an earlier rotation whose outcome was not persisted
+            Err(err) if err.kind() == AriesVcxErrorKind::DuplicationWalletRecord => {}
+            result => result?,
+        }
+        let Some(standby) = self.standby.take() else {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "No standby revocation registry available",
+            ));
+        };
+        let retired = std::mem::replace(&mut self.active, standby);
+        info!(
+            "RevocationRegistryPool::rotate >>> retired revocation registry {}, active revocation \
+             registry is now {}",
+            retired.rev_reg_id, self.active.rev_reg_id
+        );
+        self.active_issued = 0;
+
+        // the new active registry is usable already, failing to publish the next standby is
+        // retried on the next rotation
+        if let Err(err) = self
+            .prepare_standby(wallet, anoncreds, ledger_write, uploader)
+            .await
+        {
+            warn!("RevocationRegistryPool::rotate >>> failed to prepare standby registry: {err}");
+        }
+        Ok(())
+    }
+
+    async fn prepare_standby(
+        &mut self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger_write: &impl AnoncredsLedgerWrite,
+        uploader: &impl TailsUploader,
+    ) -> VcxResult<()> {
+        let standby =
+            Self::create_registry(wallet, anoncreds, ledger_write, uploader, &self.config).await?;
+        self.standby = Some(standby);
+        Ok(())
+    }
+
+    pub fn to_string(&self) -> VcxResult<String> {
+        serde_json::to_string(&self).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::SerializationError,
+                format!("Cannot serialize revocation registry pool: {err:?}"),
+            )
+        })
+    }
+
+    pub fn from_string(pool_data: &str) -> VcxResult<Self> {
+        serde_json::from_str(pool_data).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize revocation registry pool: {err:?}"),
+            )
+        })
+    }
+}
+
+async fn find_record(
+    wallet: &impl BaseWallet,
+    category: RecordCategory,
+    name: &str,
+) -> VcxResult<Option<Record>> {
+    match wallet.get_record(category, name).await {
+        Ok(record) => Ok(Some(record)),
+        Err(err) => {
+            let err = AriesVcxError::from(err);
+            if err.kind() == AriesVcxErrorKind::WalletRecordNotFound {
+                Ok(None)
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Tag following the highest `tag{n}` of the revocation registries stored for the credential
+/// definition, so that registries created by an earlier pool or outside of a pool are not
+/// overwritten.
+async fn next_tag(
+    wallet: &impl BaseWallet,
+    cred_def_id: &CredentialDefinitionId,
+) -> VcxResult<u32> {
+    let cred_def_id = cred_def_id.to_string();
+    let highest = wallet
+        .search_record(RecordCategory::RevRegDef, None)
+        .await?
+        .iter()
+        .filter_map(|record| serde_json::from_str::<StoredRevRegDef>(record.value()).ok())
+        .filter(|rev_reg_def| rev_reg_def.cred_def_id == cred_def_id)
+        .filter_map(|rev_reg_def| parse_tag(&rev_reg_def.tag))
+        .max()
+        .unwrap_or(0);
+    Ok(highest + 1)
+}
+
+/// Number of a `tag{n}` registry tag, as created by [`RevocationRegistry::create`]
+fn parse_tag(tag: &str) -> Option<u32> {
+    tag.strip_prefix("tag")?.parse().ok()
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tag() {
+        assert_eq!(parse_tag("tag1"), Some(1));
+        assert_eq!(parse_tag("tag42"), Some(42));
+        assert_eq!(parse_tag("tag"), None);
+        assert_eq!(parse_tag("custom"), None);
+        assert_eq!(parse_tag("1"), None);
+    }
+
+    #[test]
+    fn test_issued_credential_record() {
+        let issued = IssuedCredentialRegistry {
+            rev_reg_id: "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:16:tag:CL_ACCUM:tag1"
+                .to_string(),
+            cred_rev_id: 3,
+        };
+        let record = issued
+            .clone()
+            .into_record("thread-1", "V4SGRU86Z58d6TV7PBUe6f:3:CL:16:tag")
+            .unwrap();
+        assert_eq!(record.name(), "thread-1");
+        assert_eq!(record.category(), &RecordCategory::RevRegIssuance);
+        let tags: Vec<(String, String)> = record
+            .tags()
+            .clone()
+            .into_iter()
+            .map(RecordTag::into_pair)
+            .collect();
+        assert!(tags.contains(&(REV_REG_ID_TAG.to_string(), issued.rev_reg_id.clone())));
+        assert_eq!(
+            serde_json::from_str::<IssuedCredentialRegistry>(record.value()).unwrap(),
+            issued
+        );
+    }
+}
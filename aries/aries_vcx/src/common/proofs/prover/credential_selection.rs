@@ -0,0 +1,871 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+};
+
+use anoncreds_types::data_types::messages::{
+    cred_selection::{RetrievedCredentialForReferent, RetrievedCredentials, SelectedCredentials},
+    pres_request::{
+        NonRevokedInterval, PredicateTypes, PresentationRequest, PresentationRequestPayload,
+    },
+};
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::LinkSecretId;
+use aries_vcx_wallet::wallet::base_wallet::{record_category::RecordCategory, BaseWallet};
+
+use crate::{
+    common::credentials::{
+        link_secrets::{bound_link_secret, find_active_link_secret, list_link_secrets},
+        revocation_status::{
+            CredentialRevocationCheck, CredentialRevocationStatus, REVOCATION_STATUS_TAG,
+        },
+    },
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+};
+
+/// Credential record tag holding the unix timestamp the credential was stored at.
+pub const CREDENTIAL_STORED_AT_TAG: &str = "stored_at";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferentKind {
+    Attribute,
+    Predicate,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RejectionReason {
+    MissingAttribute(String),
+    PredicateNotMet {
+        attribute: String,
+        value: String,
+        p_type: PredicateTypes,
+        p_value: i32,
+    },
+    /// A non-revocation proof is requested, but the credential is revoked
+    Revoked,
+    /// A non-revocation proof is requested, but no tails directory is configured
+    MissingTailsDir,
+    /// The credential is bound to another link secret than the credentials selected for the
+    /// other referents
+    LinkSecretMismatch {
+        bound: LinkSecretId,
+        presented: LinkSecretId,
+    },
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAttribute(attribute) => write!(f, "attribute {attribute} is missing"),
+            Self::PredicateNotMet {
+                attribute,
+                value,
+                p_type,
+                p_value,
+            } => write!(
+                f,
+                "attribute {attribute} with value {value} does not satisfy predicate {p_type} \
+                 {p_value}"
+            ),
+            Self::Revoked => write!(f, "credential is revoked"),
+            Self::MissingTailsDir => write!(
+                f,
+                "non-revocation proof is requested, but no tails directory is configured"
+            ),
+            Self::LinkSecretMismatch { bound, presented } => write!(
+                f,
+                "credential is bound to link secret {bound}, the presentation to link secret \
+                 {presented}"
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RejectedCandidate {
+    pub cred_id: String,
+    pub reason: RejectionReason,
+}
+
+/// Referent of the proof request no candidate credential satisfies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsatisfiedReferent {
+    pub referent: String,
+    pub kind: ReferentKind,
+    /// The referent may be satisfied by a self attested attribute instead
+    pub self_attest_allowed: bool,
+    /// Candidates retrieved for the referent, along with the reason they were rejected
+    pub rejected: Vec<RejectedCandidate>,
+}
+
+impl fmt::Display for UnsatisfiedReferent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "referent {}: ", self.referent)?;
+        if self.rejected.is_empty() {
+            return write!(f, "no candidate credentials");
+        }
+        for (idx, rejected) in self.rejected.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "credential {} rejected, {}",
+                rejected.cred_id, rejected.reason
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CredentialSelection {
+    pub selected: SelectedCredentials,
+    pub unsatisfied: Vec<UnsatisfiedReferent>,
+}
+
+impl CredentialSelection {
+    pub fn is_complete(&self) -> bool {
+        self.unsatisfied.is_empty()
+    }
+
+    /// Returns the selected credentials, failing with an explanation of every unsatisfied
+    /// referent unless it can be self attested.
+    pub fn into_selected_credentials(self) -> VcxResult<SelectedCredentials> {
+        let unsatisfied: Vec<String> = self
+            .unsatisfied
+            .iter()
+            .filter(|referent| !referent.self_attest_allowed)
+            .map(ToString::to_string)
+            .collect();
+        if !unsatisfied.is_empty() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::UnsatisfiableProofRequest,
+                format!("Cannot satisfy proof request; {}", unsatisfied.join("; ")),
+            ));
+        }
+        Ok(self.selected)
+    }
+}
+
+/// Selects a credential for each referent of a proof request among the candidates retrieved by
+/// [`BaseAnonCreds::prover_get_credentials_for_proof_req`].
+///
+/// The candidates match the `restrictions` and attribute names of their referent already, as
+/// they are filtered by the wallet query of the retrieval. They are checked against the
+/// predicates and `non_revoked` intervals of the request, then ranked: credentials known to be
+/// active first, revoked credentials last, and the most recently stored credential first among
+/// equally ranked ones. Revocation statuses are read from the tags set by the
+/// [`HolderRevocationChecker`](crate::common::credentials::revocation_status::HolderRevocationChecker),
+/// unless passed through [`Self::with_revocation_checks`].
+///
+/// All credentials of a presentation must be bound to the same link secret. The selection uses
+/// the link secret with candidates for the most referents, preferring the active link secret and
+/// then the most recently created one, and only selects candidates bound to it.
+///
+/// [`BaseAnonCreds::prover_get_credentials_for_proof_req`]: aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds::prover_get_credentials_for_proof_req
+#[derive(Clone, Debug, Default)]
+pub struct CredentialSelector {
+    tails_dir: Option<String>,
+    revocation_statuses: HashMap<String, CredentialRevocationStatus>,
+}
+
+struct Candidate<'a> {
+    retrieved: &'a RetrievedCredentialForReferent,
+    revocation_status: Option<CredentialRevocationStatus>,
+    stored_at: Option<u64>,
+    link_secret_id: LinkSecretId,
+}
+
+struct ReferentCandidates<'a> {
+    referent: &'a String,
+    kind: ReferentKind,
+    self_attest_allowed: bool,
+    non_revoked: Option<&'a NonRevokedInterval>,
+    candidates: Vec<Candidate<'a>>,
+    rejected: Vec<RejectedCandidate>,
+}
+
+impl CredentialSelector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directory holding the tails files of the revocation registries, such as the cache
+    /// directory of a [`TailsFetcher`](crate::common::tails::TailsFetcher). Revocable credentials
+    /// can only be selected for referents requesting a non-revocation proof if it is set.
+    pub fn with_tails_dir(mut self, tails_dir: impl Into<String>) -> Self {
+        self.tails_dir = Some(tails_dir.into());
+        self
+    }
+
+    pub fn with_revocation_checks(
+        mut self,
+        checks: impl IntoIterator<Item = CredentialRevocationCheck>,
+    ) -> Self {
+        self.revocation_statuses.extend(
+            checks
+                .into_iter()
+                .map(|check| (check.cred_id, check.status)),
+        );
+        self
+    }
+
+    pub async fn select(
+        &self,
+        wallet: &impl BaseWallet,
+        presentation_request: &PresentationRequest,
+        retrieved: &RetrievedCredentials,
+    ) -> VcxResult<CredentialSelection> {
+        let request = presentation_request.value();
+        let mut record_tags: HashMap<&str, (HashMap<String, String>, LinkSecretId)> =
+            HashMap::new();
+        for candidates in retrieved.credentials_by_referent.values() {
+            for candidate in candidates {
+                let cred_id = candidate.cred_info.referent.as_str();
+                if !record_tags.contains_key(cred_id) {
+                    let record = wallet.get_record(RecordCategory::Cred, cred_id).await?;
+                    let link_secret_id = bound_link_secret(&record);
+                    let tags = record.tags().clone().into_iter().map(|tag| tag.into_pair());
+                    record_tags.insert(cred_id, (tags.collect(), link_secret_id));
+                }
+            }
+        }
+
+        let referents = request
+            .requested_attributes
+            .keys()
+            .map(|referent| (referent, ReferentKind::Attribute))
+            .chain(
+                request
+                    .requested_predicates
+                    .keys()
+                    .map(|referent| (referent, ReferentKind::Predicate)),
+            );
+        let mut referent_candidates = vec![];
+        for (referent, kind) in referents {
+            let (non_revoked, self_attest_allowed) = match kind {
+                ReferentKind::Attribute => {
+                    let info = &request.requested_attributes[referent];
+                    (
+                        info.non_revoked.as_ref(),
+                        info.self_attest_allowed.unwrap_or(false),
+                    )
+                }
+                ReferentKind::Predicate => {
+                    let info = &request.requested_predicates[referent];
+                    (info.non_revoked.as_ref(), false)
+                }
+            };
+            let non_revoked = non_revoked.or(request.non_revoked.as_ref());
+
+            let mut candidates = vec![];
+            let mut rejected = vec![];
+            for retrieved in retrieved
+                .credentials_by_referent
+                .get(referent)
+                .into_iter()
+                .flatten()
+            {
+                let cred_id = &retrieved.cred_info.referent;
+                let (tags, link_secret_id) = &record_tags[cred_id.as_str()];
+                let candidate = Candidate {
+                    revocation_status: self
+                        .revocation_statuses
+                        .get(cred_id)
+                        .copied()
+                        .or_else(|| revocation_status_from_tags(tags)),
+                    stored_at: tags
+                        .get(CREDENTIAL_STORED_AT_TAG)
+                        .and_then(|stored_at| stored_at.parse().ok()),
+                    link_secret_id: link_secret_id.clone(),
+                    retrieved,
+                };
+                match self.check_candidate(&candidate, request, referent, kind, non_revoked) {
+                    Ok(()) => candidates.push(candidate),
+                    Err(reason) => rejected.push(RejectedCandidate {
+                        cred_id: cred_id.clone(),
+                        reason,
+                    }),
+                }
+            }
+            referent_candidates.push(ReferentCandidates {
+                referent,
+                kind,
+                self_attest_allowed,
+                non_revoked,
+                candidates,
+                rejected,
+            });
+        }
+
+        let link_secret_id = presentation_link_secret(wallet, &referent_candidates).await?;
+        let mut selection = CredentialSelection::default();
+        for ReferentCandidates {
+            referent,
+            kind,
+            self_attest_allowed,
+            non_revoked,
+            candidates,
+            mut rejected,
+        } in referent_candidates
+        {
+            let (mut candidates, other_link_secrets): (Vec<_>, Vec<_>) = candidates
+                .into_iter()
+                .partition(|candidate| Some(&candidate.link_secret_id) == link_secret_id.as_ref());
+            rejected.extend(
+                other_link_secrets
+                    .into_iter()
+                    .map(|candidate| RejectedCandidate {
+                        cred_id: candidate.retrieved.cred_info.referent.clone(),
+                        reason: RejectionReason::LinkSecretMismatch {
+                            bound: candidate.link_secret_id,
+                            presented: link_secret_id.clone().unwrap_or_default(),
+                        },
+                    }),
+            );
+
+            // stable sort, so that equally ranked candidates keep the wallet order
+            candidates.sort_by_key(|candidate| {
+                (
+                    revocation_rank(candidate.revocation_status),
+                    Reverse(candidate.stored_at),
+                )
+            });
+            match candidates.first() {
+                Some(best) => {
+                    let revocable = best.retrieved.cred_info.rev_reg_id.is_some();
+                    let tails_dir = if non_revoked.is_some() && revocable {
+                        self.tails_dir.clone()
+                    } else {
+                        None
+                    };
+                    selection
+                        .selected
+                        .select_credential_for_referent_from_retrieved(
+                            referent.clone(),
+                            best.retrieved.clone(),
+                            tails_dir,
+                        );
+                }
+                None => selection.unsatisfied.push(UnsatisfiedReferent {
+                    referent: referent.clone(),
+                    kind,
+                    self_attest_allowed,
+                    rejected,
+                }),
+            }
+        }
+        selection
+            .unsatisfied
+            .sort_by(|a, b| a.referent.cmp(&b.referent));
+        Ok(selection)
+    }
+
+    fn check_candidate(
+        &self,
+        candidate: &Candidate,
+        request: &PresentationRequestPayload,
+        referent: &str,
+        kind: ReferentKind,
+        non_revoked: Option<&NonRevokedInterval>,
+    ) -> Result<(), RejectionReason> {
+        if kind == ReferentKind::Predicate {
+            let info = &request.requested_predicates[referent];
+            let attributes = &candidate.retrieved.cred_info.attributes;
+            let Some(value) = find_attribute(attributes, &info.name) else {
+                return Err(RejectionReason::MissingAttribute(info.name.clone()));
+            };
+            if !satisfies_predicate(value, &info.p_type, info.p_value) {
+                return Err(RejectionReason::PredicateNotMet {
+                    attribute: info.name.clone(),
+                    value: value.to_string(),
+                    p_type: info.p_type.clone(),
+                    p_value: info.p_value,
+                });
+            }
+        }
+
+        if non_revoked.is_some() && candidate.retrieved.cred_info.rev_reg_id.is_some() {
+            if candidate.revocation_status == Some(CredentialRevocationStatus::Revoked) {
+                return Err(RejectionReason::Revoked);
+            }
+            if self.tails_dir.is_none() {
+                return Err(RejectionReason::MissingTailsDir);
+            }
+        }
+        Ok(())
+    }
+}
+
+// link secret with candidates for the most referents, the active link secret and then the most
+// recently created one if several link secrets have candidates for as many referents
+async fn presentation_link_secret(
+    wallet: &impl BaseWallet,
+    referents: &[ReferentCandidates<'_>],
+) -> VcxResult<Option<LinkSecretId>> {
+    let mut satisfied_referents: BTreeMap<&str, usize> = BTreeMap::new();
+    for referent in referents {
+        let link_secrets: BTreeSet<&str> = referent
+            .candidates
+            .iter()
+            .map(|candidate| candidate.link_secret_id.as_str())
+            .collect();
+        for link_secret_id in link_secrets {
+            *satisfied_referents.entry(link_secret_id).or_default() += 1;
+        }
+    }
+    let Some(most) = satisfied_referents.values().max().copied() else {
+        return Ok(None);
+    };
+    let best: Vec<&str> = satisfied_referents
+        .into_iter()
+        .filter(|(_, satisfied)| *satisfied == most)
+        .map(|(link_secret_id, _)| link_secret_id)
+        .collect();
+    if let [only] = best.as_slice() {
+        return Ok(Some(only.to_string()));
+    }
+
+    if let Some(active) = find_active_link_secret(wallet).await? {
+        if best.contains(&active.as_str()) {
+            return Ok(Some(active));
+        }
+    }
+    // link secrets are listed oldest first
+    Ok(list_link_secrets(wallet)
+        .await?
+        .into_iter()
+        .rev()
+        .map(|info| info.id)
+        .find(|link_secret_id| best.contains(&link_secret_id.as_str()))
+        .or_else(|| best.first().map(ToString::to_string)))
+}
+
+fn revocation_status_from_tags(
+    tags: &HashMap<String, String>,
+) -> Option<CredentialRevocationStatus> {
+    match tags.get(REVOCATION_STATUS_TAG)?.as_str() {
+        "active" => Some(CredentialRevocationStatus::Active),
+        "revoked" => Some(CredentialRevocationStatus::Revoked),
+        "non_revocable" => Some(CredentialRevocationStatus::NonRevocable),
+        _ => None,
+    }
+}
+
+fn revocation_rank(status: Option<CredentialRevocationStatus>) -> u8 {
+    match status {
+        Some(CredentialRevocationStatus::Active) => 0,
+        Some(CredentialRevocationStatus::NonRevocable) => 1,
+        None | Some(CredentialRevocationStatus::Unknown) => 2,
+        Some(CredentialRevocationStatus::Revoked) => 3,
+    }
+}
+
+// attribute names are case insensitive and ignore spaces
+fn normalize_attr_name(name: &str) -> String {
+    name.replace(' ', "").to_lowercase()
+}
+
+fn find_attribute<'a>(attributes: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    let name = normalize_attr_name(name);
+    attributes
+        .iter()
+        .find(|(attribute, _)| normalize_attr_name(attribute) == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn satisfies_predicate(value: &str, p_type: &PredicateTypes, p_value: i32) -> bool {
+    let Ok(value) = value.parse::<i64>() else {
+        return false;
+    };
+    let p_value = i64::from(p_value);
+    match p_type {
+        PredicateTypes::GE => value >= p_value,
+        PredicateTypes::GT => value > p_value,
+        PredicateTypes::LE => value <= p_value,
+        PredicateTypes::LT => value < p_value,
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use anoncreds_types::data_types::messages::cred_selection::RetrievedCredentialInfo;
+    use test_utils::{
+        constants::{cred_def_id, schema_id, REV_REG_ID},
+        mock_wallet::MockWallet,
+    };
+
+    use super::*;
+
+    pub(super) fn retrieved(
+        cred_id: &str,
+        attributes: &[(&str, &str)],
+        revocable: bool,
+    ) -> RetrievedCredentialForReferent {
+        RetrievedCredentialForReferent {
+            cred_info: RetrievedCredentialInfo {
+                referent: cred_id.to_string(),
+                attributes: attributes
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                schema_id: schema_id(),
+                cred_def_id: cred_def_id(),
+                rev_reg_id: revocable.then(|| REV_REG_ID.to_string()),
+                cred_rev_id: revocable.then_some(1),
+            },
+            interval: None,
+        }
+    }
+
+    fn revocation_check(
+        cred_id: &str,
+        status: CredentialRevocationStatus,
+    ) -> CredentialRevocationCheck {
+        CredentialRevocationCheck {
+            cred_id: cred_id.to_string(),
+            rev_reg_id: Some(REV_REG_ID.to_string()),
+            cred_rev_id: Some(1),
+            status,
+            status_list_timestamp: Some(1),
+            checked_at: 1,
+            error: None,
+        }
+    }
+
+    fn selected_cred_id<'a>(selection: &'a CredentialSelection, referent: &str) -> &'a str {
+        &selection.selected.credential_for_referent[referent]
+            .credential
+            .cred_info
+            .referent
+    }
+
+    #[tokio::test]
+    async fn test_select() {
+        let request = PresentationRequest::PresentationRequestV1(
+            serde_json::from_value(serde_json::json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "1.0",
+                "requested_attributes": {
+                    "name_1": {"name": "name"},
+                    "nickname_1": {"name": "nickname", "self_attest_allowed": true},
+                },
+                "requested_predicates": {
+                    "age_1": {"name": "age", "p_type": ">=", "p_value": 18},
+                },
+                "non_revoked": {"to": 100},
+            }))
+            .unwrap(),
+        );
+        let mut retrieved_credentials = RetrievedCredentials::default();
+        retrieved_credentials.credentials_by_referent.insert(
+            "name_1".into(),
+            vec![
+                retrieved("revoked", &[("name", "alice")], true),
+                retrieved("non_revocable", &[("name", "alice")], false),
+                retrieved("active", &[("name", "alice")], true),
+            ],
+        );
+        retrieved_credentials.credentials_by_referent.insert(
+            "age_1".into(),
+            vec![
+                retrieved("minor", &[("Age", "17")], false),
+                retrieved("adult", &[("Age", "18")], false),
+            ],
+        );
+        let checks = [
+            revocation_check("revoked", CredentialRevocationStatus::Revoked),
+            revocation_check("active", CredentialRevocationStatus::Active),
+        ];
+
+        // active credentials rank first, predicates are checked case insensitively
+        let selector = CredentialSelector::new()
+            .with_tails_dir("/tmp/tails")
+            .with_revocation_checks(checks.clone());
+        let selection = selector
+            .select(&MockWallet, &request, &retrieved_credentials)
+            .await
+            .unwrap();
+        assert_eq!(selected_cred_id(&selection, "name_1"), "active");
+        assert_eq!(
+            selection.selected.credential_for_referent["name_1"].tails_dir,
+            Some("/tmp/tails".to_string())
+        );
+        assert_eq!(selected_cred_id(&selection, "age_1"), "adult");
+        assert!(selection.selected.credential_for_referent["age_1"]
+            .tails_dir
+            .is_none());
+        // the self attestable referent does not prevent the presentation
+        assert_eq!(selection.unsatisfied.len(), 1);
+        assert_eq!(selection.unsatisfied[0].referent, "nickname_1");
+        assert!(selection.unsatisfied[0].self_attest_allowed);
+        assert!(selection.clone().into_selected_credentials().is_ok());
+
+        // revocable credentials require a tails directory for a non-revocation proof
+        let selection = CredentialSelector::new()
+            .with_revocation_checks(checks)
+            .select(&MockWallet, &request, &retrieved_credentials)
+            .await
+            .unwrap();
+        assert_eq!(selected_cred_id(&selection, "name_1"), "non_revocable");
+
+        // rejected candidates are reported for unsatisfied referents
+        retrieved_credentials.credentials_by_referent.insert(
+            "age_1".into(),
+            vec![retrieved("minor", &[("Age", "17")], false)],
+        );
+        retrieved_credentials.credentials_by_referent.insert(
+            "name_1".into(),
+            vec![retrieved("revoked", &[("name", "alice")], true)],
+        );
+        let selection = selector
+            .select(&MockWallet, &request, &retrieved_credentials)
+            .await
+            .unwrap();
+        assert_eq!(
+            selection.unsatisfied[0],
+            UnsatisfiedReferent {
+                referent: "age_1".into(),
+                kind: ReferentKind::Predicate,
+                self_attest_allowed: false,
+                rejected: vec![RejectedCandidate {
+                    cred_id: "minor".into(),
+                    reason: RejectionReason::PredicateNotMet {
+                        attribute: "age".into(),
+                        value: "17".into(),
+                        p_type: PredicateTypes::GE,
+                        p_value: 18,
+                    },
+                }],
+            }
+        );
+        assert_eq!(
+            selection.unsatisfied[1].rejected,
+            vec![RejectedCandidate {
+                cred_id: "revoked".into(),
+                reason: RejectionReason::Revoked,
+            }]
+        );
+        let err = selection.into_selected_credentials().unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::UnsatisfiableProofRequest);
+    }
+
+    #[test]
+    fn test_satisfies_predicate() {
+        assert!(satisfies_predicate("18", &PredicateTypes::GE, 18));
+        assert!(!satisfies_predicate("18", &PredicateTypes::GT, 18));
+        assert!(satisfies_predicate("-5", &PredicateTypes::LT, 0));
+        assert!(!satisfies_predicate("eighteen", &PredicateTypes::GE, 18));
+    }
+
+    #[test]
+    fn test_revocation_rank() {
+        let mut statuses = vec![
+            Some(CredentialRevocationStatus::Revoked),
+            None,
+            Some(CredentialRevocationStatus::Active),
+            Some(CredentialRevocationStatus::NonRevocable),
+        ];
+        statuses.sort_by_key(|status| revocation_rank(*status));
+        assert_eq!(
+            statuses,
+            vec![
+                Some(CredentialRevocationStatus::Active),
+                Some(CredentialRevocationStatus::NonRevocable),
+                None,
+                Some(CredentialRevocationStatus::Revoked),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsatisfied_referent_explanation() {
+        let selection = CredentialSelection {
+            selected: SelectedCredentials::default(),
+            unsatisfied: vec![UnsatisfiedReferent {
+                referent: "age".into(),
+                kind: ReferentKind::Predicate,
+                self_attest_allowed: false,
+                rejected: vec![RejectedCandidate {
+                    cred_id: "cred_1".into(),
+                    reason: RejectionReason::PredicateNotMet {
+                        attribute: "age".into(),
+                        value: "17".into(),
+                        p_type: PredicateTypes::GE,
+                        p_value: 18,
+                    },
+                }],
+            }],
+        };
+        let err = selection.into_selected_credentials().unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::UnsatisfiableProofRequest);
+        assert!(err
+            .msg()
+            .contains("credential cred_1 rejected, attribute age with value 17"));
+    }
+}
+
+#[cfg(test)]
+mod pool_tests {
+    use std::error::Error;
+
+    use aries_vcx_anoncreds::anoncreds::base_anoncreds::LINK_SECRET_ID_TAG;
+    use aries_vcx_wallet::wallet::{
+        base_wallet::record::Record,
+        record_tags::{RecordTag, RecordTags},
+    };
+    use test_utils::devsetup::build_setup_profile;
+
+    use super::{unit_tests::retrieved, *};
+    use crate::{
+        common::credentials::link_secrets::{link_secret_for_credentials, rotate_link_secret},
+        global::settings::DEFAULT_LINK_SECRET_ALIAS,
+    };
+
+    async fn store_credential(
+        wallet: &impl BaseWallet,
+        cred_id: &str,
+        link_secret_id: &str,
+        stored_at: u64,
+    ) -> VcxResult<()> {
+        let record = Record::builder()
+            .name(cred_id.to_string())
+            .category(RecordCategory::Cred)
+            .value("{}".to_string())
+            .tags(RecordTags::new(vec![
+                RecordTag::new(LINK_SECRET_ID_TAG, link_secret_id),
+                RecordTag::new(CREDENTIAL_STORED_AT_TAG, &stored_at.to_string()),
+            ]))
+            .build();
+        Ok(wallet.add_record(record).await?)
+    }
+
+    fn retrieved_credentials(
+        name_cred_ids: &[&str],
+        age_cred_ids: &[&str],
+    ) -> RetrievedCredentials {
+        let mut retrieved_credentials = RetrievedCredentials::default();
+        retrieved_credentials.credentials_by_referent.insert(
+            "name_1".into(),
+            name_cred_ids
+                .iter()
+                .map(|cred_id| retrieved(cred_id, &[("name", "alice")], false))
+                .collect(),
+        );
+        retrieved_credentials.credentials_by_referent.insert(
+            "age_1".into(),
+            age_cred_ids
+                .iter()
+                .map(|cred_id| retrieved(cred_id, &[("age", "25")], false))
+                .collect(),
+        );
+        retrieved_credentials
+    }
+
+    fn selected_cred_ids(selection: &CredentialSelection) -> Vec<&str> {
+        ["name_1", "age_1"]
+            .into_iter()
+            .map(|referent| {
+                selection.selected.credential_for_referent[referent]
+                    .credential
+                    .cred_info
+                    .referent
+                    .as_str()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_pool_select_credentials_of_one_link_secret() -> Result<(), Box<dyn Error>> {
+        let setup = build_setup_profile().await;
+        let wallet = &setup.wallet;
+        rotate_link_secret(wallet, &setup.anoncreds, Some("rotated-secret")).await?;
+
+        store_credential(wallet, "name_default", DEFAULT_LINK_SECRET_ALIAS, 100).await?;
+        store_credential(wallet, "name_rotated", "rotated-secret", 200).await?;
+        store_credential(wallet, "age_default", DEFAULT_LINK_SECRET_ALIAS, 300).await?;
+        store_credential(wallet, "age_rotated", "rotated-secret", 50).await?;
+
+        let request = PresentationRequest::PresentationRequestV1(serde_json::from_value(
+            serde_json::json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "1.0",
+                "requested_attributes": {"name_1": {"name": "name"}},
+                "requested_predicates": {
+                    "age_1": {"name": "age", "p_type": ">=", "p_value": 18},
+                },
+            }),
+        )?);
+        let selector = CredentialSelector::new();
+
+        // only the link secret rotated out of has a credential for every referent, the newer
+        // credential bound to the active link secret is passed over
+        let selection = selector
+            .select(
+                wallet,
+                &request,
+                &retrieved_credentials(&["name_default", "name_rotated"], &["age_default"]),
+            )
+            .await?;
+        assert!(selection.is_complete());
+        let cred_ids = selected_cred_ids(&selection);
+        assert_eq!(cred_ids, vec!["name_default", "age_default"]);
+        assert_eq!(
+            link_secret_for_credentials(wallet, cred_ids).await?,
+            DEFAULT_LINK_SECRET_ALIAS
+        );
+
+        // both link secrets have a credential for every referent, the active one is preferred
+        // over the more recently stored credentials
+        let selection = selector
+            .select(
+                wallet,
+                &request,
+                &retrieved_credentials(
+                    &["name_default", "name_rotated"],
+                    &["age_default", "age_rotated"],
+                ),
+            )
+            .await?;
+        assert!(selection.is_complete());
+        let cred_ids = selected_cred_ids(&selection);
+        assert_eq!(cred_ids, vec!["name_rotated", "age_rotated"]);
+        assert_eq!(
+            link_secret_for_credentials(wallet, cred_ids).await?,
+            "rotated-secret"
+        );
+
+        // without a consistent set, the referents of the other link secret are unsatisfied
+        let selection = selector
+            .select(
+                wallet,
+                &request,
+                &retrieved_credentials(&["name_rotated"], &["age_default"]),
+            )
+            .await?;
+        assert_eq!(
+            selection.unsatisfied,
+            vec![UnsatisfiedReferent {
+                referent: "age_1".into(),
+                kind: ReferentKind::Predicate,
+                self_attest_allowed: false,
+                rejected: vec![RejectedCandidate {
+                    cred_id: "age_default".into(),
+                    reason: RejectionReason::LinkSecretMismatch {
+                        bound: DEFAULT_LINK_SECRET_ALIAS.into(),
+                        presented: "rotated-secret".into(),
+                    },
+                }],
+            }]
+        );
+        assert_eq!(
+            selection.into_selected_credentials().unwrap_err().kind(),
+            AriesVcxErrorKind::UnsatisfiableProofRequest
+        );
+        Ok(())
+    }
+}
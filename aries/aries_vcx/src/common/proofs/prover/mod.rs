@@ -1,3 +1,4 @@
+pub mod credential_selection;
 mod prover_internal;
 
 use std::collections::HashMap;
@@ -11,12 +12,14 @@ use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 
 use crate::{
-    common::proofs::prover::prover_internal::{
-        build_cred_defs_json_prover, build_requested_credentials_json, build_rev_states_json,
-        build_schemas_json_prover, credential_def_identifiers,
+    common::{
+        credentials::link_secrets::link_secret_for_credentials,
+        proofs::prover::prover_internal::{
+            build_cred_defs_json_prover, build_requested_credentials_json, build_rev_states_json,
+            build_schemas_json_prover, credential_def_identifiers,
+        },
     },
     errors::error::prelude::*,
-    global::settings,
 };
 
 pub async fn generate_indy_proof(
@@ -48,12 +51,20 @@ pub async fn generate_indy_proof(
     let credential_defs_json =
         build_cred_defs_json_prover(ledger, &credentials_identifiers).await?;
 
+    let link_secret_id = link_secret_for_credentials(
+        wallet,
+        credentials_identifiers
+            .iter()
+            .map(|cred_info| cred_info.credential_referent.as_str()),
+    )
+    .await?;
+
     anoncreds
         .prover_create_proof(
             wallet,
             proof_req_data_json,
             requested_credentials,
-            &settings::DEFAULT_LINK_SECRET_ALIAS.to_string(),
+            &link_secret_id,
             schemas_json,
             credential_defs_json,
             Some(revoc_states_json),
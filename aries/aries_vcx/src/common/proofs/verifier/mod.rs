@@ -1,12 +1,16 @@
+pub mod policy;
 mod verifier_internal;
 
 use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
 use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 
 use crate::{
-    common::proofs::verifier::verifier_internal::{
-        build_cred_defs_json_verifier, build_rev_reg_defs_json, build_rev_reg_json,
-        build_schemas_json_verifier, get_credential_info, validate_proof_revealed_attributes,
+    common::{
+        credentials::encoding::AttributeEncoder,
+        proofs::verifier::verifier_internal::{
+            build_cred_defs_json_verifier, build_rev_reg_defs_json, build_rev_reg_json,
+            build_schemas_json_verifier, get_credential_info, validate_proof_revealed_attributes,
+        },
     },
     errors::error::prelude::*,
 };
@@ -17,7 +21,25 @@ pub async fn validate_indy_proof(
     proof_json: &str,
     proof_req_json: &str,
 ) -> VcxResult<bool> {
-    validate_proof_revealed_attributes(proof_json)?;
+    validate_indy_proof_with_encoder(
+        ledger,
+        anoncreds,
+        proof_json,
+        proof_req_json,
+        &AttributeEncoder::default(),
+    )
+    .await
+}
+
+/// Validates the proof, checking its revealed values against the encodings of `encoder`.
+pub async fn validate_indy_proof_with_encoder(
+    ledger: &impl AnoncredsLedgerRead,
+    anoncreds: &impl BaseAnonCreds,
+    proof_json: &str,
+    proof_req_json: &str,
+    encoder: &AttributeEncoder,
+) -> VcxResult<bool> {
+    validate_proof_revealed_attributes(proof_json, proof_req_json, encoder)?;
 
     let credential_data = get_credential_info(proof_json)?;
     debug!("validate_indy_proof >> credential_data: {credential_data:?}");
@@ -0,0 +1,996 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    time::Duration,
+};
+
+use anoncreds_types::data_types::{
+    identifiers::cred_def_id::CredentialDefinitionId,
+    messages::{
+        pres_request::PresentationRequest,
+        presentation::{Identifier, Presentation},
+    },
+};
+use time::OffsetDateTime;
+
+use crate::common::credentials::encoding::AttributeEncoder;
+
+/// Issuers and credential definitions accepted for a referent of a presentation request. A
+/// `None` list accepts any issuer or credential definition.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ReferentPolicy {
+    /// Issuer DIDs, either unqualified or as `did:sov` / `did:indy` DIDs. Unqualified and
+    /// `did:sov` DIDs only match `did:indy` DIDs of the namespace of the verifier.
+    pub trusted_issuers: Option<Vec<String>>,
+    /// Credential definition ids. Legacy ids only match did:indy ids of the namespace of the
+    /// verifier.
+    pub allowed_cred_def_ids: Option<Vec<String>>,
+}
+
+impl ReferentPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_trusted_issuers<I, S>(mut self, issuers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.trusted_issuers = Some(issuers.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_allowed_cred_def_ids<I, S>(mut self, cred_def_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_cred_def_ids = Some(cred_def_ids.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+/// Rules a cryptographically valid presentation must additionally satisfy to be accepted by the
+/// verifier. Evaluating the policy produces a [`PolicyReport`] explaining, for every referent of
+/// the presentation request, why the presentation was accepted or rejected.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct VerifierPolicy {
+    default_policy: ReferentPolicy,
+    /// Policies of individual referents, replacing the default policy for these referents
+    referent_policies: HashMap<String, ReferentPolicy>,
+    max_revocation_age: Option<Duration>,
+    max_proof_age: Option<Duration>,
+    /// did:indy namespace of the ledger unqualified DIDs and legacy ids are resolved on
+    indy_namespace: Option<String>,
+    /// Encodings of typed attributes, not serialized along with the policy
+    #[serde(skip)]
+    attribute_encoder: AttributeEncoder,
+}
+
+impl VerifierPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_trusted_issuers<I, S>(mut self, issuers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.default_policy = self.default_policy.with_trusted_issuers(issuers);
+        self
+    }
+
+    pub fn with_allowed_cred_def_ids<I, S>(mut self, cred_def_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.default_policy = self.default_policy.with_allowed_cred_def_ids(cred_def_ids);
+        self
+    }
+
+    pub fn with_referent_policy(
+        mut self,
+        referent: impl Into<String>,
+        policy: ReferentPolicy,
+    ) -> Self {
+        self.referent_policies.insert(referent.into(), policy);
+        self
+    }
+
+    /// Maximum age of the revocation status list the non-revocation of each credential was
+    /// proven against. Revocable credentials without a non-revocation proof are rejected.
+    pub fn with_max_revocation_age(mut self, max_age: Duration) -> Self {
+        self.max_revocation_age = Some(max_age);
+        self
+    }
+
+    /// Maximum time between requesting and evaluating the presentation. The request time is
+    /// the time the presentation request was sent or, if unknown, the `non_revoked.to` of the
+    /// request. Presentations answering a request of unknown time are rejected.
+    pub fn with_max_proof_age(mut self, max_age: Duration) -> Self {
+        self.max_proof_age = Some(max_age);
+        self
+    }
+
+    /// did:indy namespace of the ledger the verifier resolves unqualified and `did:sov` DIDs and
+    /// legacy credential definition ids on, e.g. `sovrin`. Without a namespace these only match
+    /// themselves, never a did:indy DID or id.
+    pub fn with_indy_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.indy_namespace = Some(namespace.into());
+        self
+    }
+
+    /// Encoder the revealed values are checked against, both when verifying the presentation
+    /// and when evaluating the policy.
+    pub fn with_attribute_encoder(mut self, encoder: AttributeEncoder) -> Self {
+        self.attribute_encoder = encoder;
+        self
+    }
+
+    pub fn attribute_encoder(&self) -> &AttributeEncoder {
+        &self.attribute_encoder
+    }
+
+    fn referent_policy(&self, referent: &str) -> &ReferentPolicy {
+        self.referent_policies
+            .get(referent)
+            .unwrap_or(&self.default_policy)
+    }
+
+    /// Evaluates the policy against the presentation, `requested_at` being the unix timestamp
+    /// the verifier sent the presentation request at, if known.
+    pub fn evaluate(
+        &self,
+        presentation_request: &PresentationRequest,
+        presentation: &Presentation,
+        requested_at: Option<u64>,
+    ) -> PolicyReport {
+        let now = OffsetDateTime::now_utc().unix_timestamp() as u64;
+        self.evaluate_at(presentation_request, presentation, requested_at, now)
+    }
+
+    pub fn evaluate_at(
+        &self,
+        presentation_request: &PresentationRequest,
+        presentation: &Presentation,
+        requested_at: Option<u64>,
+        now: u64,
+    ) -> PolicyReport {
+        let request = presentation_request.value();
+        let referents = request
+            .requested_attributes
+            .keys()
+            .chain(request.requested_predicates.keys())
+            .map(|referent| {
+                let report =
+                    self.evaluate_referent(presentation_request, referent, presentation, now);
+                (referent.clone(), report)
+            })
+            .collect();
+
+        let mut checks = vec![];
+        if let Some(max_age) = self.max_proof_age {
+            let requested_at = requested_at.or_else(|| {
+                request
+                    .non_revoked
+                    .as_ref()
+                    .and_then(|interval| interval.to)
+            });
+            checks.push(check_proof_age(requested_at, max_age, now));
+        }
+
+        PolicyReport {
+            referents,
+            checks,
+            evaluated_at: now,
+        }
+    }
+
+    fn evaluate_referent(
+        &self,
+        presentation_request: &PresentationRequest,
+        referent: &str,
+        presentation: &Presentation,
+        now: u64,
+    ) -> ReferentReport {
+        let requested_proof = &presentation.requested_proof;
+        let mut checks = vec![];
+        let sub_proof_index = requested_proof
+            .revealed_attrs
+            .get(referent)
+            .map(|attr| attr.sub_proof_index)
+            .or_else(|| {
+                requested_proof
+                    .revealed_attr_groups
+                    .get(referent)
+                    .map(|group| group.sub_proof_index)
+            })
+            .or_else(|| {
+                requested_proof
+                    .unrevealed_attrs
+                    .get(referent)
+                    .map(|attr| attr.sub_proof_index)
+            })
+            .or_else(|| {
+                requested_proof
+                    .predicates
+                    .get(referent)
+                    .map(|predicate| predicate.sub_proof_index)
+            });
+
+        let Some(sub_proof_index) = sub_proof_index else {
+            let policy = self.referent_policy(referent);
+            let restricted =
+                policy.trusted_issuers.is_some() || policy.allowed_cred_def_ids.is_some();
+            let check = match requested_proof.self_attested_attrs.get(referent) {
+                Some(_) if restricted => PolicyCheck::failed(
+                    PolicyRule::Presented,
+                    "attribute is self attested, but the policy requires a credential",
+                ),
+                Some(_) => PolicyCheck::passed(PolicyRule::Presented),
+                None => PolicyCheck::failed(
+                    PolicyRule::Presented,
+                    "referent is missing from the presentation",
+                ),
+            };
+            checks.push(check);
+            return ReferentReport {
+                cred_def_id: None,
+                issuer_did: None,
+                timestamp: None,
+                checks,
+            };
+        };
+
+        let Some(identifier) = presentation.identifiers.get(sub_proof_index as usize) else {
+            checks.push(PolicyCheck::failed(
+                PolicyRule::Presented,
+                format!("presentation has no identifier for sub proof {sub_proof_index}"),
+            ));
+            return ReferentReport {
+                cred_def_id: None,
+                issuer_did: None,
+                timestamp: None,
+                checks,
+            };
+        };
+        checks.push(PolicyCheck::passed(PolicyRule::Presented));
+
+        let issuer_did = issuer_did(&identifier.cred_def_id);
+        let policy = self.referent_policy(referent);
+        if let Some(trusted_issuers) = &policy.trusted_issuers {
+            checks.push(check_trusted_issuer(
+                &issuer_did,
+                trusted_issuers,
+                self.indy_namespace.as_deref(),
+            ));
+        }
+        if let Some(allowed_cred_def_ids) = &policy.allowed_cred_def_ids {
+            checks.push(check_allowed_cred_def(
+                &identifier.cred_def_id,
+                allowed_cred_def_ids,
+                self.indy_namespace.as_deref(),
+            ));
+        }
+        checks.push(check_encoding(
+            &self.attribute_encoder,
+            presentation_request,
+            presentation,
+            referent,
+        ));
+        if let Some(max_age) = self.max_revocation_age {
+            checks.push(check_revocation_freshness(identifier, max_age, now));
+        }
+
+        ReferentReport {
+            cred_def_id: Some(identifier.cred_def_id.to_string()),
+            issuer_did: Some(issuer_did),
+            timestamp: identifier.timestamp,
+            checks,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyRule {
+    /// The referent was answered by a credential of the presentation
+    Presented,
+    TrustedIssuer,
+    AllowedCredDef,
+    /// Revealed raw values match their encoded values
+    AttributeEncoding,
+    RevocationFreshness,
+    ProofAge,
+}
+
+impl PolicyRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Presented => "presented",
+            Self::TrustedIssuer => "trusted_issuer",
+            Self::AllowedCredDef => "allowed_cred_def",
+            Self::AttributeEncoding => "attribute_encoding",
+            Self::RevocationFreshness => "revocation_freshness",
+            Self::ProofAge => "proof_age",
+        }
+    }
+}
+
+impl fmt::Display for PolicyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyOutcome {
+    Passed,
+    Failed,
+    /// The rule could not be applied, which does not reject the presentation
+    Skipped,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PolicyCheck {
+    pub rule: PolicyRule,
+    pub outcome: PolicyOutcome,
+    /// Why the check failed or was skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl PolicyCheck {
+    fn passed(rule: PolicyRule) -> Self {
+        Self {
+            rule,
+            outcome: PolicyOutcome::Passed,
+            reason: None,
+        }
+    }
+
+    fn failed(rule: PolicyRule, reason: impl Into<String>) -> Self {
+        Self {
+            rule,
+            outcome: PolicyOutcome::Failed,
+            reason: Some(reason.into()),
+        }
+    }
+
+    fn skipped(rule: PolicyRule, reason: impl Into<String>) -> Self {
+        Self {
+            rule,
+            outcome: PolicyOutcome::Skipped,
+            reason: Some(reason.into()),
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.outcome == PolicyOutcome::Failed
+    }
+}
+
+impl fmt::Display for PolicyCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = match self.outcome {
+            PolicyOutcome::Passed => "passed",
+            PolicyOutcome::Failed => "failed",
+            PolicyOutcome::Skipped => "skipped",
+        };
+        write!(f, "{}: {outcome}", self.rule)?;
+        if let Some(reason) = &self.reason {
+            write!(f, " ({reason})")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ReferentReport {
+    pub cred_def_id: Option<String>,
+    pub issuer_did: Option<String>,
+    /// Timestamp of the revocation status list non-revocation was proven against
+    pub timestamp: Option<u64>,
+    pub checks: Vec<PolicyCheck>,
+}
+
+impl ReferentReport {
+    pub fn is_accepted(&self) -> bool {
+        !self.checks.iter().any(PolicyCheck::is_failed)
+    }
+}
+
+/// Outcome of every rule of a [`VerifierPolicy`] applied to a presentation, per referent of the
+/// presentation request.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PolicyReport {
+    pub referents: BTreeMap<String, ReferentReport>,
+    /// Checks of the presentation as a whole
+    pub checks: Vec<PolicyCheck>,
+    /// Unix timestamp of the evaluation
+    pub evaluated_at: u64,
+}
+
+impl PolicyReport {
+    pub fn is_accepted(&self) -> bool {
+        !self.checks.iter().any(PolicyCheck::is_failed)
+            && self.referents.values().all(ReferentReport::is_accepted)
+    }
+
+    /// Reasons of all failed checks, prefixed by their referent.
+    pub fn rejection_reasons(&self) -> Vec<String> {
+        let proof_reasons = self
+            .checks
+            .iter()
+            .filter(|check| check.is_failed())
+            .map(|check| format!("presentation: {check}"));
+        let referent_reasons = self.referents.iter().flat_map(|(referent, report)| {
+            report
+                .checks
+                .iter()
+                .filter(|check| check.is_failed())
+                .map(move |check| format!("{referent}: {check}"))
+        });
+        proof_reasons.chain(referent_reasons).collect()
+    }
+}
+
+impl fmt::Display for PolicyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.is_accepted() {
+            "accepted"
+        } else {
+            "rejected"
+        };
+        write!(f, "presentation {verdict} at {}", self.evaluated_at)?;
+        for check in &self.checks {
+            write!(f, "; presentation: {check}")?;
+        }
+        for (referent, report) in &self.referents {
+            write!(
+                f,
+                "; {referent} (cred_def_id: {}):",
+                report.cred_def_id.as_deref().unwrap_or("none")
+            )?;
+            for (i, check) in report.checks.iter().enumerate() {
+                let separator = if i == 0 { " " } else { ", " };
+                write!(f, "{separator}{check}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// DID of the issuer of the credential definition, e.g. `did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9`
+/// for did:indy identifiers and `5nDyJVP1NrcPAttP3xwMB9` for legacy identifiers.
+fn issuer_did(cred_def_id: &CredentialDefinitionId) -> String {
+    let id = cred_def_id.0.as_str();
+    let issuer = if cred_def_id.is_did_indy() {
+        id.split_once("/anoncreds/").map(|(did, _)| did)
+    } else if cred_def_id.is_legacy_cred_def_identifier() {
+        id.split_once(':').map(|(did, _)| did)
+    } else {
+        id.split_once('/').map(|(did, _)| did)
+    };
+    issuer.unwrap_or(id).to_string()
+}
+
+/// Form of the DID issuers are compared by. Unqualified DIDs are equivalent to `did:sov` DIDs,
+/// which resolve to the did:indy DID of `indy_namespace` if the verifier configured one.
+/// `did:indy` DIDs are compared as they are.
+fn qualified_did(did: &str, indy_namespace: Option<&str>) -> String {
+    let id = match did.strip_prefix("did:sov:") {
+        Some(id) => id,
+        None if !did.starts_with("did:") => did,
+        None => return did.to_string(),
+    };
+    match indy_namespace {
+        Some(namespace) => format!("did:indy:{namespace}:{id}"),
+        None => format!("did:sov:{id}"),
+    }
+}
+
+/// Form of the credential definition id allowed ids are compared by. Legacy ids resolve to the
+/// did:indy id of `indy_namespace` if the verifier configured one.
+fn qualified_cred_def_id(
+    cred_def_id: &CredentialDefinitionId,
+    indy_namespace: Option<&str>,
+) -> String {
+    indy_namespace
+        .and_then(|namespace| cred_def_id.to_did_indy(namespace))
+        .unwrap_or_else(|| cred_def_id.clone())
+        .0
+}
+
+fn check_trusted_issuer(
+    issuer_did: &str,
+    trusted_issuers: &[String],
+    indy_namespace: Option<&str>,
+) -> PolicyCheck {
+    let issuer = qualified_did(issuer_did, indy_namespace);
+    if trusted_issuers
+        .iter()
+        .any(|trusted| qualified_did(trusted, indy_namespace) == issuer)
+    {
+        PolicyCheck::passed(PolicyRule::TrustedIssuer)
+    } else {
+        PolicyCheck::failed(
+            PolicyRule::TrustedIssuer,
+            format!("issuer {issuer_did} is not trusted"),
+        )
+    }
+}
+
+fn check_allowed_cred_def(
+    cred_def_id: &CredentialDefinitionId,
+    allowed_cred_def_ids: &[String],
+    indy_namespace: Option<&str>,
+) -> PolicyCheck {
+    let id = qualified_cred_def_id(cred_def_id, indy_namespace);
+    let allowed = allowed_cred_def_ids.iter().any(|allowed| {
+        let allowed = CredentialDefinitionId::new_unchecked(allowed.as_str());
+        qualified_cred_def_id(&allowed, indy_namespace) == id
+    });
+    if allowed {
+        PolicyCheck::passed(PolicyRule::AllowedCredDef)
+    } else {
+        PolicyCheck::failed(
+            PolicyRule::AllowedCredDef,
+            format!("credential definition {cred_def_id} is not allowed"),
+        )
+    }
+}
+
+fn check_encoding(
+    encoder: &AttributeEncoder,
+    presentation_request: &PresentationRequest,
+    presentation: &Presentation,
+    referent: &str,
+) -> PolicyCheck {
+    let requested_proof = &presentation.requested_proof;
+    if !requested_proof.revealed_attrs.contains_key(referent)
+        && !requested_proof.revealed_attr_groups.contains_key(referent)
+    {
+        return PolicyCheck::skipped(PolicyRule::AttributeEncoding, "no revealed values");
+    }
+    let mismatches: Vec<String> = encoder
+        .check_revealed(presentation_request, presentation, referent)
+        .into_iter()
+        .map(|mismatch| mismatch.attribute)
+        .collect();
+    if mismatches.is_empty() {
+        PolicyCheck::passed(PolicyRule::AttributeEncoding)
+    } else {
+        PolicyCheck::failed(
+            PolicyRule::AttributeEncoding,
+            format!(
+                "raw values of {} do not match their encoded values",
+                mismatches.join(", ")
+            ),
+        )
+    }
+}
+
+fn check_revocation_freshness(identifier: &Identifier, max_age: Duration, now: u64) -> PolicyCheck {
+    match (&identifier.rev_reg_id, identifier.timestamp) {
+        (None, _) => PolicyCheck::skipped(
+            PolicyRule::RevocationFreshness,
+            "credential is not revocable",
+        ),
+        (Some(_), None) => PolicyCheck::failed(
+            PolicyRule::RevocationFreshness,
+            "credential is revocable, but its non-revocation was not proven",
+        ),
+        (Some(_), Some(timestamp)) => {
+            let age = now.saturating_sub(timestamp);
+            if age > max_age.as_secs() {
+                PolicyCheck::failed(
+                    PolicyRule::RevocationFreshness,
+                    format!(
+                        "revocation status from {timestamp} is {age}s old, at most {}s allowed",
+                        max_age.as_secs()
+                    ),
+                )
+            } else {
+                PolicyCheck::passed(PolicyRule::RevocationFreshness)
+            }
+        }
+    }
+}
+
+fn check_proof_age(requested_at: Option<u64>, max_age: Duration, now: u64) -> PolicyCheck {
+    let Some(requested_at) = requested_at else {
+        return PolicyCheck::failed(
+            PolicyRule::ProofAge,
+            "time of the presentation request is unknown",
+        );
+    };
+    let age = now.saturating_sub(requested_at);
+    if age > max_age.as_secs() {
+        PolicyCheck::failed(
+            PolicyRule::ProofAge,
+            format!(
+                "presentation requested at {requested_at} is {age}s old, at most {}s allowed",
+                max_age.as_secs()
+            ),
+        )
+    } else {
+        PolicyCheck::passed(PolicyRule::ProofAge)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use serde_json::json;
+    use test_utils::constants::PROOF_JSON;
+
+    use super::*;
+    use crate::{common::credentials::encoding::AttributeType, utils::openssl::encode};
+
+    const ISSUER_DID: &str = "V4SGRU86Z58d6TV7PBUe6f";
+    const CRED_DEF_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:3:CL:16:tag";
+    const REV_REG_ID: &str =
+        "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:16:tag:CL_ACCUM:1";
+    const NOW: u64 = 1_700_000_000;
+
+    fn presentation_request() -> PresentationRequest {
+        serde_json::from_value(json!({
+            "nonce": "123432421212",
+            "name": "proof_req",
+            "version": "1.0",
+            "requested_attributes": {
+                "name_ref": {"name": "name"},
+                "address_ref": {"names": ["street", "city"]},
+            },
+            "requested_predicates": {
+                "age_ref": {"name": "age", "p_type": ">=", "p_value": 18},
+            },
+        }))
+        .unwrap()
+    }
+
+    fn presentation(name_encoded: &str, timestamp: Option<u64>) -> Presentation {
+        let mut presentation: Presentation = serde_json::from_str(PROOF_JSON).unwrap();
+        presentation.requested_proof = serde_json::from_value(json!({
+            "revealed_attrs": {
+                "name_ref": {"sub_proof_index": 0, "raw": "Alice", "encoded": name_encoded},
+            },
+            "revealed_attr_groups": {
+                "address_ref": {
+                    "sub_proof_index": 0,
+                    "values": {
+                        "street": {"raw": "Main Street", "encoded": encode("Main Street").unwrap()},
+                        "city": {"raw": "42", "encoded": "42"},
+                    },
+                },
+            },
+            "predicates": {"age_ref": {"sub_proof_index": 0}},
+        }))
+        .unwrap();
+        presentation.identifiers = serde_json::from_value(json!([{
+            "schema_id": "V4SGRU86Z58d6TV7PBUe6f:2:schema:1.0",
+            "cred_def_id": CRED_DEF_ID,
+            "rev_reg_id": REV_REG_ID,
+            "timestamp": timestamp,
+        }]))
+        .unwrap();
+        presentation
+    }
+
+    fn valid_presentation() -> Presentation {
+        presentation(&encode("Alice").unwrap(), Some(NOW - 60))
+    }
+
+    fn did_indy_presentation(namespace: &str) -> Presentation {
+        let mut presentation = valid_presentation();
+        presentation.identifiers[0].cred_def_id = CredentialDefinitionId::new_unchecked(format!(
+            "did:indy:{namespace}:{ISSUER_DID}/anoncreds/v0/CLAIM_DEF/16/tag"
+        ));
+        presentation
+    }
+
+    #[test]
+    fn test_issuer_did() {
+        assert_eq!(
+            issuer_did(&CredentialDefinitionId::new_unchecked(CRED_DEF_ID)),
+            ISSUER_DID
+        );
+        assert_eq!(
+            issuer_did(&CredentialDefinitionId::new_unchecked(
+                "did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9/anoncreds/v0/CLAIM_DEF/56495/npdb"
+            )),
+            "did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9"
+        );
+    }
+
+    #[test]
+    fn test_empty_policy_accepts_valid_presentation() {
+        let report = VerifierPolicy::new().evaluate_at(
+            &presentation_request(),
+            &valid_presentation(),
+            None,
+            NOW,
+        );
+        assert!(report.is_accepted(), "{report}");
+        assert_eq!(report.referents.len(), 3);
+        assert_eq!(
+            report.referents["name_ref"].issuer_did.as_deref(),
+            Some(ISSUER_DID)
+        );
+    }
+
+    #[test]
+    fn test_trusted_issuers() {
+        let report = VerifierPolicy::new()
+            .with_trusted_issuers([format!("did:sov:{ISSUER_DID}")])
+            .evaluate_at(&presentation_request(), &valid_presentation(), None, NOW);
+        assert!(report.is_accepted(), "{report}");
+
+        let report = VerifierPolicy::new()
+            .with_trusted_issuers(["2MHGDD2XpRJohQzsXu4FAV"])
+            .with_referent_policy(
+                "age_ref",
+                ReferentPolicy::new().with_trusted_issuers([ISSUER_DID]),
+            )
+            .evaluate_at(&presentation_request(), &valid_presentation(), None, NOW);
+        assert!(!report.is_accepted());
+        assert!(report.referents["age_ref"].is_accepted());
+        assert!(!report.referents["name_ref"].is_accepted());
+        assert!(report.rejection_reasons().contains(&format!(
+            "name_ref: trusted_issuer: failed (issuer {ISSUER_DID} is not trusted)"
+        )));
+    }
+
+    #[test]
+    fn test_trusted_issuers_across_networks() {
+        let sovrin_did = format!("did:indy:sovrin:{ISSUER_DID}");
+
+        // an unqualified issuer is only the did:indy issuer of the namespace of the verifier
+        let policy = VerifierPolicy::new().with_trusted_issuers([sovrin_did.as_str()]);
+        let report = policy.evaluate_at(&presentation_request(), &valid_presentation(), None, NOW);
+        assert!(!report.is_accepted());
+        let report = policy.clone().with_indy_namespace("sovrin").evaluate_at(
+            &presentation_request(),
+            &valid_presentation(),
+            None,
+            NOW,
+        );
+        assert!(report.is_accepted(), "{report}");
+
+        // the same nym on another network is a different issuer
+        let report = policy.with_indy_namespace("sovrin").evaluate_at(
+            &presentation_request(),
+            &did_indy_presentation("bcovrin:test"),
+            None,
+            NOW,
+        );
+        assert!(!report.is_accepted());
+        assert!(report.rejection_reasons().contains(&format!(
+            "name_ref: trusted_issuer: failed (issuer did:indy:bcovrin:test:{ISSUER_DID} is not \
+             trusted)"
+        )));
+
+        let policy = VerifierPolicy::new().with_trusted_issuers([ISSUER_DID]);
+        let report = policy.evaluate_at(
+            &presentation_request(),
+            &did_indy_presentation("sovrin"),
+            None,
+            NOW,
+        );
+        assert!(!report.is_accepted());
+        let report = policy.with_indy_namespace("sovrin").evaluate_at(
+            &presentation_request(),
+            &did_indy_presentation("sovrin"),
+            None,
+            NOW,
+        );
+        assert!(report.is_accepted(), "{report}");
+    }
+
+    #[test]
+    fn test_allowed_cred_def_ids() {
+        let report = VerifierPolicy::new()
+            .with_allowed_cred_def_ids([CRED_DEF_ID])
+            .evaluate_at(&presentation_request(), &valid_presentation(), None, NOW);
+        assert!(report.is_accepted(), "{report}");
+
+        // legacy ids only match the did:indy ids of the namespace of the verifier
+        let did_indy_id = "did:indy:sovrin:V4SGRU86Z58d6TV7PBUe6f/anoncreds/v0/CLAIM_DEF/16/tag";
+        let policy = VerifierPolicy::new().with_allowed_cred_def_ids([did_indy_id]);
+        let report = policy.evaluate_at(&presentation_request(), &valid_presentation(), None, NOW);
+        assert!(!report.is_accepted());
+        let report = policy.clone().with_indy_namespace("sovrin").evaluate_at(
+            &presentation_request(),
+            &valid_presentation(),
+            None,
+            NOW,
+        );
+        assert!(report.is_accepted(), "{report}");
+        let report = policy.with_indy_namespace("sovrin").evaluate_at(
+            &presentation_request(),
+            &did_indy_presentation("bcovrin:test"),
+            None,
+            NOW,
+        );
+        assert!(!report.is_accepted());
+        let report = VerifierPolicy::new()
+            .with_allowed_cred_def_ids([CRED_DEF_ID])
+            .with_indy_namespace("bcovrin:test")
+            .evaluate_at(
+                &presentation_request(),
+                &did_indy_presentation("sovrin"),
+                None,
+                NOW,
+            );
+        assert!(!report.is_accepted());
+
+        let report = VerifierPolicy::new()
+            .with_allowed_cred_def_ids(["V4SGRU86Z58d6TV7PBUe6f:3:CL:16:other"])
+            .evaluate_at(&presentation_request(), &valid_presentation(), None, NOW);
+        assert!(!report.is_accepted());
+    }
+
+    #[test]
+    fn test_encoding_mismatch() {
+        let report = VerifierPolicy::new().evaluate_at(
+            &presentation_request(),
+            &presentation("12345", Some(NOW)),
+            None,
+            NOW,
+        );
+        assert!(!report.is_accepted());
+        assert_eq!(
+            report.referents["name_ref"].checks,
+            vec![
+                PolicyCheck::passed(PolicyRule::Presented),
+                PolicyCheck::failed(
+                    PolicyRule::AttributeEncoding,
+                    "raw values of name_ref do not match their encoded values"
+                ),
+            ]
+        );
+        assert!(report.referents["address_ref"].is_accepted());
+    }
+
+    #[test]
+    fn test_encoding_with_attribute_encoder() {
+        // "42" as a hashed float rather than the integer encoding
+        let policy = VerifierPolicy::new().with_attribute_encoder(
+            AttributeEncoder::new().with_attribute_type(
+                "V4SGRU86Z58d6TV7PBUe6f:2:schema:1.0",
+                "City",
+                AttributeType::Float,
+            ),
+        );
+        let report = policy.evaluate_at(&presentation_request(), &valid_presentation(), None, NOW);
+        assert!(!report.is_accepted());
+        assert_eq!(
+            report.referents["address_ref"].checks,
+            vec![
+                PolicyCheck::passed(PolicyRule::Presented),
+                PolicyCheck::failed(
+                    PolicyRule::AttributeEncoding,
+                    "raw values of address_ref.city do not match their encoded values"
+                ),
+            ]
+        );
+        assert!(report.referents["name_ref"].is_accepted());
+    }
+
+    #[test]
+    fn test_revocation_freshness() {
+        let policy = VerifierPolicy::new().with_max_revocation_age(Duration::from_secs(3600));
+        let encoded = encode("Alice").unwrap();
+
+        let report = policy.evaluate_at(
+            &presentation_request(),
+            &presentation(&encoded, Some(NOW - 60)),
+            None,
+            NOW,
+        );
+        assert!(report.is_accepted(), "{report}");
+
+        let report = policy.evaluate_at(
+            &presentation_request(),
+            &presentation(&encoded, Some(NOW - 7200)),
+            None,
+            NOW,
+        );
+        assert!(!report.is_accepted());
+
+        let report = policy.evaluate_at(
+            &presentation_request(),
+            &presentation(&encoded, None),
+            None,
+            NOW,
+        );
+        assert!(!report.is_accepted());
+    }
+
+    #[test]
+    fn test_proof_age() {
+        let policy = VerifierPolicy::new().with_max_proof_age(Duration::from_secs(300));
+
+        // without a request time the age of the presentation is unknown
+        let report = policy.evaluate_at(&presentation_request(), &valid_presentation(), None, NOW);
+        assert!(!report.is_accepted());
+        assert_eq!(
+            report.checks,
+            vec![PolicyCheck::failed(
+                PolicyRule::ProofAge,
+                "time of the presentation request is unknown"
+            )]
+        );
+
+        // falls back to the end of the non-revocation interval the verifier requested
+        let mut request = serde_json::to_value(presentation_request()).unwrap();
+        request["non_revoked"] = json!({"to": NOW - 100});
+        let request: PresentationRequest = serde_json::from_value(request).unwrap();
+        let report = policy.evaluate_at(&request, &valid_presentation(), None, NOW);
+        assert!(report.is_accepted(), "{report}");
+        let report = policy.evaluate_at(&request, &valid_presentation(), Some(NOW - 1000), NOW);
+        assert!(!report.is_accepted());
+
+        let report = policy.evaluate_at(
+            &presentation_request(),
+            &valid_presentation(),
+            Some(NOW - 100),
+            NOW,
+        );
+        assert!(report.is_accepted());
+
+        let report = policy.evaluate_at(
+            &presentation_request(),
+            &valid_presentation(),
+            Some(NOW - 1000),
+            NOW,
+        );
+        assert!(!report.is_accepted());
+        assert_eq!(report.rejection_reasons().len(), 1);
+    }
+
+    #[test]
+    fn test_missing_and_self_attested_referents() {
+        let mut presentation = valid_presentation();
+        presentation.requested_proof.predicates.clear();
+        presentation.requested_proof.revealed_attrs.clear();
+        presentation
+            .requested_proof
+            .self_attested_attrs
+            .insert("name_ref".to_string(), "Alice".to_string());
+
+        let report =
+            VerifierPolicy::new().evaluate_at(&presentation_request(), &presentation, None, NOW);
+        assert!(report.referents["name_ref"].is_accepted());
+        assert!(!report.referents["age_ref"].is_accepted());
+
+        let report = VerifierPolicy::new()
+            .with_trusted_issuers([ISSUER_DID])
+            .evaluate_at(&presentation_request(), &presentation, None, NOW);
+        assert!(!report.referents["name_ref"].is_accepted());
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let check = PolicyCheck::failed(PolicyRule::ProofAge, "too old");
+        assert_eq!(
+            serde_json::to_value(&check).unwrap(),
+            json!({"rule": "proof_age", "outcome": "failed", "reason": "too old"})
+        );
+        let report = VerifierPolicy::new().evaluate_at(
+            &presentation_request(),
+            &valid_presentation(),
+            None,
+            NOW,
+        );
+        let serialized = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PolicyReport>(&serialized).unwrap(),
+            report
+        );
+    }
+}
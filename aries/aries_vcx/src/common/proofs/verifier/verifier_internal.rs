@@ -2,7 +2,10 @@ use anoncreds_types::data_types::identifiers::schema_id::SchemaId;
 use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 use serde_json::{self, Value};
 
-use crate::{errors::error::prelude::*, utils::openssl::encode};
+use crate::{
+    common::credentials::encoding::{AttributeEncoder, EncodingMismatch},
+    errors::error::prelude::*,
+};
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct CredInfoVerifier {
@@ -49,19 +52,30 @@ pub fn get_credential_info(proof: &str) -> VcxResult<Vec<CredInfoVerifier>> {
     Ok(rtn)
 }
 
-pub fn validate_proof_revealed_attributes(proof_json: &str) -> VcxResult<()> {
+pub fn validate_proof_revealed_attributes(
+    proof_json: &str,
+    proof_req_json: &str,
+    encoder: &AttributeEncoder,
+) -> VcxResult<()> {
     let proof: Value = serde_json::from_str(proof_json).map_err(|err| {
         AriesVcxError::from_msg(
             AriesVcxErrorKind::InvalidJson,
             format!("Cannot deserialize libndy proof: {err}"),
         )
     })?;
+    let proof_req: Value = serde_json::from_str(proof_req_json).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot deserialize proof request: {err}"),
+        )
+    })?;
 
     let revealed_attrs = match proof["requested_proof"]["revealed_attrs"].as_object() {
         Some(revealed_attrs) => revealed_attrs,
         None => return Ok(()),
     };
 
+    let mut mismatches = vec![];
     for (attr1_referent, info) in revealed_attrs.iter() {
         let raw = info["raw"].as_str().ok_or(AriesVcxError::from_msg(
             AriesVcxErrorKind::InvalidProof,
@@ -71,19 +85,29 @@ pub fn validate_proof_revealed_attributes(proof_json: &str) -> VcxResult<()> {
             AriesVcxErrorKind::InvalidProof,
             format!("Cannot get encoded value for \"{attr1_referent}\" attribute"),
         ))?;
-
-        let expected_encoded = encode(raw)?;
-
-        if expected_encoded != *encoded_ {
-            return Err(AriesVcxError::from_msg(
-                AriesVcxErrorKind::InvalidProof,
-                format!(
-                    "Encoded values are different. Expected: {expected_encoded}. From Proof: {encoded_}"
-                ),
-            ));
+        let schema_id = info["sub_proof_index"]
+            .as_u64()
+            .and_then(|index| proof["identifiers"][index as usize]["schema_id"].as_str());
+        let name = proof_req["requested_attributes"][attr1_referent]["name"]
+            .as_str()
+            .unwrap_or(attr1_referent);
+
+        if let Some(mismatch) = encoder.check(schema_id, name, raw, encoded_) {
+            mismatches.push(EncodingMismatch {
+                attribute: attr1_referent.to_string(),
+                ..mismatch
+            });
         }
     }
 
+    if !mismatches.is_empty() {
+        let mismatches: Vec<String> = mismatches.iter().map(ToString::to_string).collect();
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidProof,
+            format!("Encoded values are different: {}", mismatches.join("; ")),
+        ));
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,198 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anoncreds_types::data_types::ledger::rev_reg_def::RevocationRegistryDefinition;
+use sha2::{Digest, Sha256};
+use shared::{errors::http_error::HttpError, http_client::get_chunked};
+use url::Url;
+use uuid::Uuid;
+
+use super::{check_tails_hash, validate_tails_hash_format};
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+/// Downloads tails files for holders, storing them in a cache directory under their tails hash.
+/// As that is the layout anoncreds expects, the cache directory can be passed directly as the
+/// tails directory when creating proofs.
+#[derive(Clone, Debug)]
+pub struct TailsFetcher {
+    cache_dir: PathBuf,
+}
+
+impl TailsFetcher {
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+        }
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Path of the cached tails file with the given hash, if it was already fetched.
+    pub fn cached_tails_file(&self, tails_hash: &str) -> VcxResult<Option<PathBuf>> {
+        validate_tails_hash_format(tails_hash)?;
+        let path = self.cache_dir.join(tails_hash);
+        Ok(path.is_file().then_some(path))
+    }
+
+    pub async fn fetch_for_rev_reg_def(
+        &self,
+        rev_reg_def: &RevocationRegistryDefinition,
+    ) -> VcxResult<PathBuf> {
+        self.fetch(
+            &rev_reg_def.value.tails_location,
+            &rev_reg_def.value.tails_hash,
+        )
+        .await
+    }
+
+    /// Returns the path of the tails file with the given hash, downloading it from
+    /// `tails_location` unless it is cached. Downloaded files are only cached if their hash
+    /// matches `tails_hash`.
+    pub async fn fetch(&self, tails_location: &str, tails_hash: &str) -> VcxResult<PathBuf> {
+        trace!(
+            "TailsFetcher::fetch >>> tails_location: {tails_location}, tails_hash: {tails_hash}"
+        );
+        if let Some(path) = self.cached_tails_file(tails_hash)? {
+            trace!("TailsFetcher::fetch <<< using cached tails file {path:?}");
+            return Ok(path);
+        }
+
+        let url = Url::parse(tails_location).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidUrl,
+                format!("Invalid tails location {tails_location}: {err}"),
+            )
+        })?;
+
+        // download to a temporary file first, so that concurrent readers never observe a
+        // partially written or unverified tails file
+        std::fs::create_dir_all(&self.cache_dir)?;
+        let path = self.cache_dir.join(tails_hash);
+        let tmp_path = self
+            .cache_dir
+            .join(format!("{tails_hash}.{}.tmp", Uuid::new_v4()));
+        let result = download_verified(&url, &tmp_path, tails_hash)
+            .await
+            .and_then(|_| std::fs::rename(&tmp_path, &path).map_err(Into::into));
+        if let Err(err) = result {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(err);
+        }
+        debug!("TailsFetcher::fetch <<< downloaded tails file {path:?} from {tails_location}");
+        Ok(path)
+    }
+}
+
+/// Streams the file at `url` to `path`, hashing it on the way
+async fn download_verified(url: &Url, path: &Path, tails_hash: &str) -> VcxResult<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    let mut hasher = Sha256::new();
+    get_chunked(url, |chunk| {
+        hasher.update(chunk);
+        file.write_all(chunk).map_err(|err| {
+            HttpError::from_msg(format!("Failed to write tails file {path:?}: {err}"))
+        })
+    })
+    .await?;
+    file.flush()?;
+    check_tails_hash(&bs58::encode(hasher.finalize()).into_string(), tails_hash)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::common::tails::tails_hash;
+
+    fn temp_cache_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("tails_fetcher_{}", Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_fetch_returns_cached_tails_file() {
+        let cache_dir = temp_cache_dir();
+        let content = b"tails file content";
+        let hash = tails_hash(content);
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        std::fs::write(cache_dir.join(&hash), content).unwrap();
+
+        let fetcher = TailsFetcher::new(&cache_dir);
+        // the location is never contacted for cached files
+        let path = fetcher.fetch("not a url", &hash).await.unwrap();
+        assert_eq!(path, cache_dir.join(&hash));
+
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    /// Serves `content` to a single GET request
+    async fn serve_once(content: &'static [u8]) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/tails", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).await.unwrap();
+            let header = format!(
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                content.len()
+            );
+            stream.write_all(header.as_bytes()).await.unwrap();
+            stream.write_all(content).await.unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_fetch_downloads_and_verifies_tails_file() {
+        let cache_dir = temp_cache_dir();
+        let content = b"tails file content";
+        let hash = tails_hash(content);
+        let fetcher = TailsFetcher::new(&cache_dir);
+
+        let url = serve_once(content).await;
+        let path = fetcher.fetch(&url, &hash).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), content);
+        assert_eq!(fetcher.cached_tails_file(&hash).unwrap(), Some(path));
+
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_discards_tampered_tails_file() {
+        let cache_dir = temp_cache_dir();
+        let hash = tails_hash(b"tails file content");
+        let fetcher = TailsFetcher::new(&cache_dir);
+
+        let url = serve_once(b"tampered content").await;
+        let err = fetcher.fetch(&url, &hash).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidTailsFile);
+        // neither the tails file nor the temporary download is left behind
+        assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 0);
+
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_invalid_inputs() {
+        let fetcher = TailsFetcher::new(temp_cache_dir());
+
+        let err = fetcher
+            .fetch("https://tails.example.com/abc", "../abc")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidTailsFile);
+
+        let err = fetcher
+            .fetch("not a url", &tails_hash(b"content"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidUrl);
+        assert!(!fetcher.cache_dir().exists());
+    }
+}
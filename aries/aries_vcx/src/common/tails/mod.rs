@@ -0,0 +1,67 @@
+//! Distribution of revocation tails files: issuers publish them through a [`TailsUploader`],
+//! holders download them through a [`TailsFetcher`].
+
+mod fetcher;
+mod uploader;
+
+pub use fetcher::TailsFetcher;
+use sha2::{Digest, Sha256};
+pub use uploader::{IndyTailsServerUploader, TailsUploader};
+
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+/// Tails hash as found in `RevocationRegistryDefinition.value.tails_hash`: base58 encoded SHA-256
+/// digest of the tails file.
+pub fn tails_hash(content: &[u8]) -> String {
+    bs58::encode(Sha256::digest(content)).into_string()
+}
+
+pub fn verify_tails_hash(content: &[u8], expected_tails_hash: &str) -> VcxResult<()> {
+    check_tails_hash(&tails_hash(content), expected_tails_hash)
+}
+
+fn check_tails_hash(actual_tails_hash: &str, expected_tails_hash: &str) -> VcxResult<()> {
+    if actual_tails_hash != expected_tails_hash {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidTailsFile,
+            format!(
+                "Tails file hash mismatch, expected: {expected_tails_hash}, actual: \
+                 {actual_tails_hash}"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Tails files are stored under their hash, make sure it cannot be used to escape a directory.
+fn validate_tails_hash_format(tails_hash: &str) -> VcxResult<()> {
+    match bs58::decode(tails_hash).into_vec() {
+        Ok(digest) if digest.len() == 32 => Ok(()),
+        _ => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidTailsFile,
+            format!("Invalid tails hash: {tails_hash}"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_tails_hash() {
+        let content = b"tails file content";
+        let hash = tails_hash(content);
+
+        validate_tails_hash_format(&hash).unwrap();
+        verify_tails_hash(content, &hash).unwrap();
+        let err = verify_tails_hash(b"tampered content", &hash).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidTailsFile);
+    }
+
+    #[test]
+    fn test_validate_tails_hash_format_rejects_paths() {
+        assert!(validate_tails_hash_format("../etc/passwd").is_err());
+        assert!(validate_tails_hash_format("3yqkTkLtZQx").is_err());
+    }
+}
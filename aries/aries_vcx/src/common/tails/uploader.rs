@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use shared::http_client::{put_multipart, MultipartFile};
+use url::Url;
+
+use super::tails_hash;
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+/// Publishes tails files of revocation registries so that holders can download them.
+#[async_trait]
+pub trait TailsUploader: Send + Sync {
+    /// Uploads the tails file of the revocation registry, returning the public location of the
+    /// file to be recorded in the revocation registry definition (`tails_location`).
+    async fn upload(&self, rev_reg_def_id: &str, tails_file: &Path) -> VcxResult<String>;
+}
+
+/// Uploader for tails servers implementing the
+/// [indy-tails-server](https://github.com/bcgov/indy-tails-server) API: tails files are uploaded
+/// by `PUT {base_url}/{rev_reg_def_id}` as the `tails` part of a multipart form and served at the
+/// same location. The bundled `tails_server` crate implements the same API.
+#[derive(Clone, Debug)]
+pub struct IndyTailsServerUploader {
+    base_url: Url,
+    genesis_file: Option<PathBuf>,
+    auth_token: Option<String>,
+}
+
+impl IndyTailsServerUploader {
+    pub fn new(base_url: Url) -> Self {
+        Self {
+            base_url,
+            genesis_file: None,
+            auth_token: None,
+        }
+    }
+
+    /// indy-tails-server verifies uploads against the revocation registry definition on the
+    /// ledger, which it connects to using the pool genesis transactions sent along with the file.
+    pub fn with_genesis_file(mut self, genesis_file: impl Into<PathBuf>) -> Self {
+        self.genesis_file = Some(genesis_file.into());
+        self
+    }
+
+    /// Bearer token sent with uploads, required by the bundled `tails_server`.
+    pub fn with_auth_token(mut self, auth_token: impl Into<String>) -> Self {
+        self.auth_token = Some(auth_token.into());
+        self
+    }
+
+    pub fn tails_location(&self, rev_reg_def_id: &str) -> VcxResult<Url> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .map_err(|_| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidUrl,
+                    format!("Invalid tails server url: {}", self.base_url),
+                )
+            })?
+            .pop_if_empty()
+            .push(rev_reg_def_id);
+        Ok(url)
+    }
+}
+
+#[async_trait]
+impl TailsUploader for IndyTailsServerUploader {
+    async fn upload(&self, rev_reg_def_id: &str, tails_file: &Path) -> VcxResult<String> {
+        trace!(
+            "IndyTailsServerUploader::upload >>> rev_reg_def_id: {rev_reg_def_id}, tails_file: \
+             {tails_file:?}"
+        );
+        let content = std::fs::read(tails_file)?;
+        let expected_hash = tails_hash(&content);
+
+        let mut files = vec![];
+        if let Some(genesis_file) = &self.genesis_file {
+            files.push(MultipartFile {
+                field_name: "genesis".into(),
+                file_name: "genesis".into(),
+                content: std::fs::read(genesis_file)?,
+            });
+        }
+        files.push(MultipartFile {
+            field_name: "tails".into(),
+            file_name: expected_hash.clone(),
+            content,
+        });
+
+        let location = self.tails_location(rev_reg_def_id)?;
+        let response = put_multipart(&location, files, self.auth_token.as_deref()).await?;
+        let uploaded_hash = String::from_utf8(response)?;
+        let uploaded_hash = uploaded_hash.trim();
+        if !uploaded_hash.is_empty() && uploaded_hash != expected_hash {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidTailsFile,
+                format!(
+                    "Tails server stored tails file with hash {uploaded_hash}, expected \
+                     {expected_hash}"
+                ),
+            ));
+        }
+        Ok(location.to_string())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_tails_location() {
+        let rev_reg_def_id =
+            "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:16:tag:CL_ACCUM:1";
+        let uploader = IndyTailsServerUploader::new("https://tails.example.com/".parse().unwrap());
+        assert_eq!(
+            uploader.tails_location(rev_reg_def_id).unwrap().as_str(),
+            format!("https://tails.example.com/{rev_reg_def_id}")
+        );
+
+        let uploader = IndyTailsServerUploader::new("https://example.com/tails".parse().unwrap());
+        assert_eq!(
+            uploader.tails_location(rev_reg_def_id).unwrap().as_str(),
+            format!("https://example.com/tails/{rev_reg_def_id}")
+        );
+    }
+}
@@ -60,6 +60,8 @@ pub enum AriesVcxErrorKind {
     InvalidRevocationTimestamp,
     #[error("No revocation definition found")]
     RevRegDefNotFound,
+    #[error("Tails file does not match the revocation registry definition")]
+    InvalidTailsFile,
 
     // Issuer Credential
     #[error("Attributes provided to Credential Offer are not correct, possibly malformed")]
@@ -76,6 +78,8 @@ pub enum AriesVcxErrorKind {
     InvalidProofRequest,
     #[error("The proof was rejected")]
     ProofRejected,
+    #[error("The proof request cannot be satisfied by the available credentials")]
+    UnsatisfiableProofRequest,
 
     // Schema
     #[error("No Schema for that schema sequence number")]
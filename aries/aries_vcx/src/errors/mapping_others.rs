@@ -1,4 +1,4 @@
-use std::{num::ParseIntError, string::FromUtf8Error, sync::PoisonError};
+use std::{io, num::ParseIntError, string::FromUtf8Error, sync::PoisonError};
 
 use base64::DecodeError;
 use did_doc::schema::{types::uri::UriWrapperError, utils::error::DidDocumentLookupError};
@@ -112,3 +112,9 @@ impl From<ParseError> for AriesVcxError {
         AriesVcxError::from_msg(AriesVcxErrorKind::InvalidInput, err.to_string())
     }
 }
+
+impl From<io::Error> for AriesVcxError {
+    fn from(err: io::Error) -> Self {
+        AriesVcxError::from_msg(AriesVcxErrorKind::IOError, err.to_string())
+    }
+}
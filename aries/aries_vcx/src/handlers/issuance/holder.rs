@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
 use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
@@ -26,7 +28,7 @@ use messages::{
 use uuid::Uuid;
 
 use crate::{
-    common::credentials::get_cred_rev_id,
+    common::{credentials::get_cred_rev_id, tails::TailsFetcher},
     errors::error::prelude::*,
     handlers::revocation_notification::receiver::RevocationNotificationReceiver,
     protocols::issuance::holder::state_machine::{HolderFullState, HolderSM, HolderState},
@@ -103,6 +105,34 @@ impl Holder {
             .clone()
             .prepare_credential_request(wallet, ledger, anoncreds, my_pw_did)
             .await?;
+        self.credential_request_message()
+    }
+
+    /// Prepares the credential request, binding the credential to the given link secret rather
+    /// than to the active one.
+    pub async fn prepare_credential_request_with_link_secret(
+        &mut self,
+        wallet: &impl BaseWallet,
+        ledger: &impl AnoncredsLedgerRead,
+        anoncreds: &impl BaseAnonCreds,
+        my_pw_did: Did,
+        link_secret_id: &str,
+    ) -> VcxResult<AriesMessage> {
+        self.holder_sm = self
+            .holder_sm
+            .clone()
+            .prepare_credential_request_with_link_secret(
+                wallet,
+                ledger,
+                anoncreds,
+                my_pw_did,
+                link_secret_id,
+            )
+            .await?;
+        self.credential_request_message()
+    }
+
+    fn credential_request_message(&self) -> VcxResult<AriesMessage> {
         match self.get_state() {
             HolderState::Failed => Ok(self.get_problem_report()?.into()),
             HolderState::RequestSet => Ok(self.get_msg_credential_request()?.into()),
@@ -188,6 +218,14 @@ impl Holder {
         self.holder_sm.get_tails_hash()
     }
 
+    /// Downloads the tails file of the credential's revocation registry, verifying it against the
+    /// tails hash of the registry definition. Returns the path of the cached file.
+    pub async fn fetch_tails_file(&self, fetcher: &TailsFetcher) -> VcxResult<PathBuf> {
+        fetcher
+            .fetch(&self.get_tails_location()?, &self.get_tails_hash()?)
+            .await
+    }
+
     pub fn get_rev_reg_id(&self) -> VcxResult<String> {
         self.holder_sm.get_rev_reg_id()
     }
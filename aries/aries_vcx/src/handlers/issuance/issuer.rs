@@ -1,5 +1,5 @@
 use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
-use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
+use aries_vcx_ledger::ledger::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite};
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 use messages::{
     misc::MimeType,
@@ -20,6 +20,10 @@ use messages::{
 };
 
 use crate::{
+    common::{
+        credentials::encoding::AttributeEncoder,
+        primitives::revocation_registry_pool::RevocationRegistryPool, tails::TailsUploader,
+    },
     errors::error::prelude::*,
     handlers::util::OfferInfo,
     protocols::issuance::issuer::state_machine::{IssuerSM, IssuerState, RevocationInfoV1},
@@ -189,6 +193,82 @@ impl Issuer {
         Ok(())
     }
 
+    /// Builds the credential, encoding its values with the attribute types and custom encoders
+    /// of `encoder`.
+    pub async fn build_credential_with_encoder(
+        &mut self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        encoder: &AttributeEncoder,
+    ) -> VcxResult<()> {
+        self.issuer_sm = self
+            .issuer_sm
+            .clone()
+            .build_credential_with_encoder(wallet, anoncreds, encoder)
+            .await?;
+        Ok(())
+    }
+
+    /// Builds the credential into the active registry of `pool`, rotating the pool's registries
+    /// when they fill up. If the active registry turns out to be full, e.g. because the pool was
+    /// restored from an outdated state, the pool is rotated and the credential built into the new
+    /// active registry.
+    pub async fn build_credential_from_pool(
+        &mut self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        ledger_write: &impl AnoncredsLedgerWrite,
+        uploader: &impl TailsUploader,
+        pool: &mut RevocationRegistryPool,
+    ) -> VcxResult<()> {
+        pool.rotate_if_full(wallet, anoncreds, ledger_write, uploader)
+            .await?;
+        let mut rotated = false;
+        let (issuer_sm, rev_reg_id) = loop {
+            let registry = pool.active_registry();
+            let rev_reg_id = registry.get_rev_reg_id();
+            let issuer_sm = self
+                .issuer_sm
+                .clone()
+                .set_revocation_registry(rev_reg_id.clone(), registry.get_tails_dir())?;
+            match issuer_sm
+                .try_build_credential_with_encoder(wallet, anoncreds, &AttributeEncoder::default())
+                .await
+            {
+                Ok(issuer_sm) => break (issuer_sm, rev_reg_id),
+                // anoncreds refuses to issue into a full registry
+                Err(err) if err.kind() == AriesVcxErrorKind::ActionNotSupported && !rotated => {
+                    warn!(
+                        "Issuer::build_credential_from_pool >>> revocation registry {rev_reg_id} \
+                         is full, rotating: {err}"
+                    );
+                    pool.rotate(wallet, anoncreds, ledger_write, uploader)
+                        .await?;
+                    rotated = true;
+                }
+                // a failure to build the credential is reported through the problem report
+                Err(err) => {
+                    self.issuer_sm = issuer_sm.fail_credential_creation(&err)?;
+                    return Ok(());
+                }
+            }
+        };
+        self.issuer_sm = issuer_sm;
+
+        if let Ok(cred_rev_id) = self.get_revocation_id() {
+            pool.record_issuance(wallet, &self.get_thread_id()?, &rev_reg_id, cred_rev_id)
+                .await?;
+            // the credential is issued already, rotation is retried on the next issuance
+            if let Err(err) = pool
+                .rotate_if_full(wallet, anoncreds, ledger_write, uploader)
+                .await
+            {
+                warn!("Issuer::build_credential_from_pool >>> failed to rotate registries: {err}");
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_msg_issue_credential(&mut self) -> VcxResult<IssueCredentialV1> {
         self.issuer_sm.clone().get_msg_issue_credential()
     }
@@ -30,6 +30,7 @@ use messages::{
 use uuid::Uuid;
 
 use crate::{
+    common::proofs::prover::credential_selection::{CredentialSelection, CredentialSelector},
     errors::error::prelude::*,
     handlers::util::{get_attach_as_string, PresentationProposalData},
     protocols::{
@@ -91,6 +92,22 @@ impl Prover {
         Ok(json_retrieved_credentials)
     }
 
+    /// Retrieves the candidate credentials for the presentation request and selects the best one
+    /// for each referent.
+    pub async fn select_credentials(
+        &self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        selector: &CredentialSelector,
+    ) -> VcxResult<CredentialSelection> {
+        trace!("Prover::select_credentials >>>");
+        let presentation_request = serde_json::from_str(&self.presentation_request_data()?)?;
+        let retrieved_credentials = self.retrieve_credentials(wallet, anoncreds).await?;
+        selector
+            .select(wallet, &presentation_request, &retrieved_credentials)
+            .await
+    }
+
     pub async fn generate_presentation(
         &mut self,
         wallet: &impl BaseWallet,
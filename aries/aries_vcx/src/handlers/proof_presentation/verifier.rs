@@ -17,6 +17,7 @@ use messages::{
 };
 
 use crate::{
+    common::proofs::verifier::policy::{PolicyReport, VerifierPolicy},
     errors::error::prelude::*,
     handlers::util::get_attach_as_string,
     protocols::{
@@ -103,6 +104,25 @@ impl Verifier {
         self.verifier_sm.get_final_message()
     }
 
+    /// Verifies the presentation like [`Verifier::verify_presentation`], additionally rejecting
+    /// presentations which do not satisfy the policy. The outcome of each policy rule is available
+    /// through [`Verifier::get_policy_report`].
+    pub async fn verify_presentation_with_policy(
+        &mut self,
+        ledger: &impl AnoncredsLedgerRead,
+        anoncreds: &impl BaseAnonCreds,
+        presentation: PresentationV1,
+        policy: &VerifierPolicy,
+    ) -> VcxResult<AriesMessage> {
+        trace!("Verifier::verify_presentation_with_policy >>> policy: {policy:?}");
+        self.verifier_sm = self
+            .verifier_sm
+            .clone()
+            .verify_presentation_with_policy(ledger, anoncreds, presentation, policy)
+            .await?;
+        self.verifier_sm.get_final_message()
+    }
+
     pub fn set_presentation_request(
         &mut self,
         presentation_request_data: PresentationRequest,
@@ -138,6 +158,10 @@ impl Verifier {
         self.verifier_sm.get_verification_status()
     }
 
+    pub fn get_policy_report(&self) -> Option<&PolicyReport> {
+        self.verifier_sm.get_policy_report()
+    }
+
     pub fn get_presentation_attachment(&self) -> VcxResult<String> {
         let presentation = &self.verifier_sm.get_presentation_msg()?;
         Ok(get_attach_as_string!(
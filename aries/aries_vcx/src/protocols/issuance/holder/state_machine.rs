@@ -30,9 +30,8 @@ use messages::{
 use uuid::Uuid;
 
 use crate::{
-    common::credentials::{get_cred_rev_id, is_cred_revoked},
+    common::credentials::{get_cred_rev_id, is_cred_revoked, link_secrets::active_link_secret},
     errors::error::prelude::*,
-    global::settings,
     handlers::util::{
         get_attach_as_string, make_attach_from_str, verify_thread_id, AttachmentId, Status,
     },
@@ -213,6 +212,38 @@ impl HolderSM {
         ledger: &'a impl AnoncredsLedgerRead,
         anoncreds: &'a impl BaseAnonCreds,
         my_pw_did: Did,
+    ) -> VcxResult<Self> {
+        self.prepare_credential_request_internal(wallet, ledger, anoncreds, my_pw_did, None)
+            .await
+    }
+
+    /// Prepares the credential request, binding the credential to the given link secret rather
+    /// than to the active one.
+    pub async fn prepare_credential_request_with_link_secret<'a>(
+        self,
+        wallet: &impl BaseWallet,
+        ledger: &'a impl AnoncredsLedgerRead,
+        anoncreds: &'a impl BaseAnonCreds,
+        my_pw_did: Did,
+        link_secret_id: &'a str,
+    ) -> VcxResult<Self> {
+        self.prepare_credential_request_internal(
+            wallet,
+            ledger,
+            anoncreds,
+            my_pw_did,
+            Some(link_secret_id),
+        )
+        .await
+    }
+
+    async fn prepare_credential_request_internal<'a>(
+        self,
+        wallet: &impl BaseWallet,
+        ledger: &'a impl AnoncredsLedgerRead,
+        anoncreds: &'a impl BaseAnonCreds,
+        my_pw_did: Did,
+        link_secret_id: Option<&'a str>,
     ) -> VcxResult<Self> {
         trace!("HolderSM::prepare_credential_request >>");
         let state = match self.state {
@@ -224,6 +255,7 @@ impl HolderSM {
                     self.thread_id.clone(),
                     my_pw_did,
                     &state_data.offer,
+                    link_secret_id,
                 )
                 .await
                 {
@@ -591,6 +623,7 @@ pub async fn create_anoncreds_credential_request(
     anoncreds: &impl BaseAnonCreds,
     prover_did: &Did,
     cred_offer: &str,
+    link_secret_id: Option<&str>,
 ) -> VcxResult<(String, String, String, String, SchemaId)> {
     let offer: CredentialOffer = serde_json::from_str(cred_offer)?;
 
@@ -599,14 +632,17 @@ pub async fn create_anoncreds_credential_request(
 
     let cred_def_json = ledger.get_cred_def(&cred_def_id, None).await?;
 
-    let master_secret_id = settings::DEFAULT_LINK_SECRET_ALIAS;
+    let link_secret_id = match link_secret_id {
+        Some(link_secret_id) => link_secret_id.to_string(),
+        None => active_link_secret(wallet).await?,
+    };
     anoncreds
         .prover_create_credential_req(
             wallet,
             prover_did,
             offer,
             cred_def_json.try_clone()?,
-            &master_secret_id.to_string(),
+            &link_secret_id,
         )
         .await
         .map_err(|err| {
@@ -635,6 +671,7 @@ async fn build_credential_request_msg(
     thread_id: String,
     my_pw_did: Did,
     offer: &OfferCredentialV1,
+    link_secret_id: Option<&str>,
 ) -> VcxResult<(RequestCredentialV1, String, String, SchemaId)> {
     trace!("Holder::_make_credential_request >>> my_pw_did: {my_pw_did:?}, offer: {offer:?}");
 
@@ -642,8 +679,15 @@ async fn build_credential_request_msg(
 
     trace!("Parsed cred offer attachment: {cred_offer}");
     let (req, req_meta, _cred_def_id, cred_def_json, schema_id) =
-        create_anoncreds_credential_request(wallet, ledger, anoncreds, &my_pw_did, &cred_offer)
-            .await?;
+        create_anoncreds_credential_request(
+            wallet,
+            ledger,
+            anoncreds,
+            &my_pw_did,
+            &cred_offer,
+            link_secret_id,
+        )
+        .await?;
     trace!("Created cred def json: {cred_def_json}");
     let credential_request_msg = _build_credential_request_msg(req, &thread_id);
     Ok((credential_request_msg, req_meta, cred_def_json, schema_id))
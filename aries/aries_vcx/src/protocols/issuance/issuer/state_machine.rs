@@ -1,6 +1,6 @@
 use std::{fmt::Display, path::Path};
 
-use anoncreds_types::data_types::messages::credential::Credential;
+use anoncreds_types::data_types::messages::{cred_offer::CredentialOffer, credential::Credential};
 use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
 use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
@@ -30,7 +30,7 @@ use messages::{
 use uuid::Uuid;
 
 use crate::{
-    common::credentials::{encoding::encode_attributes, is_cred_revoked},
+    common::credentials::{encoding::AttributeEncoder, is_cred_revoked},
     errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
     handlers::util::{
         get_attach_as_string, make_attach_from_str, matches_opt_thread_id, verify_thread_id,
@@ -421,52 +421,120 @@ impl IssuerSM {
         Ok(Self { state, ..self })
     }
 
+    /// Replaces the revocation registry the credential will be issued into. Only possible until
+    /// the credential is built.
+    pub fn set_revocation_registry(
+        self,
+        rev_reg_id: String,
+        tails_file: String,
+    ) -> VcxResult<Self> {
+        let state = match self.state {
+            IssuerFullState::OfferSet(state) => IssuerFullState::OfferSet(OfferSetState {
+                rev_reg_id: Some(rev_reg_id),
+                tails_file: Some(tails_file),
+                ..state
+            }),
+            IssuerFullState::RequestReceived(state) => {
+                IssuerFullState::RequestReceived(RequestReceivedState {
+                    rev_reg_id: Some(rev_reg_id),
+                    tails_file: Some(tails_file),
+                    ..state
+                })
+            }
+            s => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    format!("Can not set revocation registry in current state {s}."),
+                ));
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
     pub async fn build_credential(
         self,
         wallet: &impl BaseWallet,
         anoncreds: &impl BaseAnonCreds,
     ) -> VcxResult<Self> {
+        self.build_credential_with_encoder(wallet, anoncreds, &AttributeEncoder::default())
+            .await
+    }
+
+    /// Builds the credential, encoding its values with `encoder`. Values given already encoded
+    /// which do not match their raw values fail the issuance.
+    pub async fn build_credential_with_encoder(
+        self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        encoder: &AttributeEncoder,
+    ) -> VcxResult<Self> {
+        match self
+            .try_build_credential_with_encoder(wallet, anoncreds, encoder)
+            .await
+        {
+            Ok(issuer_sm) => Ok(issuer_sm),
+            Err(err) => self.fail_credential_creation(&err),
+        }
+    }
+
+    /// Like [`Self::build_credential_with_encoder`], but a failure to create the credential is
+    /// returned instead of finishing the issuance with a problem report, so that the caller can
+    /// retry, e.g. with another revocation registry.
+    pub async fn try_build_credential_with_encoder(
+        &self,
+        wallet: &impl BaseWallet,
+        anoncreds: &impl BaseAnonCreds,
+        encoder: &AttributeEncoder,
+    ) -> VcxResult<Self> {
+        let IssuerFullState::RequestReceived(state_data) = &self.state else {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "Invalid action",
+            ));
+        };
+        let (msg_issue_credential, cred_rev_id) = create_credential(
+            wallet,
+            anoncreds,
+            &state_data.request,
+            &state_data.rev_reg_id,
+            &state_data.tails_file,
+            &state_data.offer,
+            &state_data.cred_data,
+            self.thread_id.clone(),
+            encoder,
+        )
+        .await?;
+        // todo: have constructor for this
+        let state = IssuerFullState::CredentialSet(CredentialSetState {
+            msg_issue_credential,
+            revocation_info_v1: Some(RevocationInfoV1 {
+                cred_rev_id: cred_rev_id.as_ref().map(ToString::to_string),
+                rev_reg_id: state_data.rev_reg_id.clone(),
+                tails_file: state_data.tails_file.clone(),
+            }),
+        });
+        Ok(Self {
+            source_id: self.source_id.clone(),
+            thread_id: self.thread_id.clone(),
+            state,
+        })
+    }
+
+    /// Finishes the issuance with a problem report describing the failure to create the
+    /// credential.
+    pub fn fail_credential_creation(self, err: &AriesVcxError) -> VcxResult<Self> {
         let state = match self.state {
+            // todo: 1. Don't transition, throw error, add to_failed transition() api which
+            // SM consumer can call       2. Also create separate
+            // "Failed" state
             IssuerFullState::RequestReceived(state_data) => {
-                match create_credential(
-                    wallet,
-                    anoncreds,
-                    &state_data.request,
-                    &state_data.rev_reg_id,
-                    &state_data.tails_file,
-                    &state_data.offer,
-                    &state_data.cred_data,
-                    self.thread_id.clone(),
-                )
-                .await
-                {
-                    Ok((msg_issue_credential, cred_rev_id)) => {
-                        // todo: have constructor for this
-                        IssuerFullState::CredentialSet(CredentialSetState {
-                            msg_issue_credential,
-                            revocation_info_v1: Some(RevocationInfoV1 {
-                                cred_rev_id: cred_rev_id.as_ref().map(ToString::to_string),
-                                rev_reg_id: state_data.rev_reg_id,
-                                tails_file: state_data.tails_file,
-                            }),
-                        })
-                    }
-                    // todo: 1. Don't transition, throw error, add to_failed transition() api which
-                    // SM consumer can call       2. Also create separate
-                    // "Failed" state
-                    Err(err) => {
-                        let problem_report =
-                            build_problem_report_msg(Some(err.to_string()), &self.thread_id);
-                        error!(
-                            "Failed to create credential, generated problem report \
-                             {problem_report:?}",
-                        );
-                        IssuerFullState::Finished(FinishedState::from_request_and_error(
-                            state_data,
-                            problem_report,
-                        ))
-                    }
-                }
+                let problem_report =
+                    build_problem_report_msg(Some(err.to_string()), &self.thread_id);
+                error!("Failed to create credential, generated problem report {problem_report:?}");
+                IssuerFullState::Finished(FinishedState::from_request_and_error(
+                    state_data,
+                    problem_report,
+                ))
             }
             _ => {
                 return Err(AriesVcxError::from_msg(
@@ -579,6 +647,7 @@ async fn create_credential(
     offer: &OfferCredentialV1,
     cred_data: &str,
     thread_id: String,
+    encoder: &AttributeEncoder,
 ) -> VcxResult<(IssueCredentialV1, Option<u32>)> {
     let offer = get_attach_as_string!(&offer.content.offers_attach);
 
@@ -595,11 +664,12 @@ async fn create_credential(
 
     let request = get_attach_as_string!(&request.content.requests_attach);
 
-    let cred_data = encode_attributes(cred_data)?;
+    let offer: CredentialOffer = serde_json::from_str(&offer)?;
+    let cred_data = encoder.encode_attributes(Some(&offer.schema_id.0), cred_data)?;
     let (libindy_credential, cred_rev_id) = anoncreds
         .issuer_create_credential(
             wallet,
-            serde_json::from_str(&offer)?,
+            offer,
             serde_json::from_str(&request)?,
             serde_json::from_str(&cred_data)?,
             rev_reg_id
@@ -25,6 +25,10 @@ use messages::{
 use uuid::Uuid;
 
 use crate::{
+    common::{
+        credentials::encoding::AttributeEncoder,
+        proofs::verifier::policy::{PolicyReport, VerifierPolicy},
+    },
     errors::error::prelude::*,
     handlers::util::{make_attach_from_str, verify_thread_id, AttachmentId, Status},
     protocols::{
@@ -251,18 +255,70 @@ impl VerifierSM {
         ledger: &'a impl AnoncredsLedgerRead,
         anoncreds: &'a impl BaseAnonCreds,
         presentation: PresentationV1,
+    ) -> VcxResult<Self> {
+        self.verify_presentation_internal(ledger, anoncreds, presentation, None)
+            .await
+    }
+
+    /// Verifies the presentation, then evaluates the verifier policy against it. A
+    /// cryptographically valid presentation rejected by the policy is marked as invalid.
+    pub async fn verify_presentation_with_policy<'a>(
+        self,
+        ledger: &'a impl AnoncredsLedgerRead,
+        anoncreds: &'a impl BaseAnonCreds,
+        presentation: PresentationV1,
+        policy: &'a VerifierPolicy,
+    ) -> VcxResult<Self> {
+        self.verify_presentation_internal(ledger, anoncreds, presentation, Some(policy))
+            .await
+    }
+
+    async fn verify_presentation_internal<'a>(
+        self,
+        ledger: &'a impl AnoncredsLedgerRead,
+        anoncreds: &'a impl BaseAnonCreds,
+        presentation: PresentationV1,
+        policy: Option<&'a VerifierPolicy>,
     ) -> VcxResult<Self> {
         verify_thread_id(&self.thread_id, &presentation.clone().into())?;
+        let default_encoder = AttributeEncoder::default();
+        let encoder = policy.map_or(&default_encoder, VerifierPolicy::attribute_encoder);
         let state = match self.state {
             VerifierFullState::PresentationRequestSent(state) => {
-                let verification_result = state
-                    .verify_presentation(ledger, anoncreds, &presentation, &self.thread_id)
-                    .await;
+                let verification_result = match state
+                    .verify_presentation(ledger, anoncreds, &presentation, &self.thread_id, encoder)
+                    .await
+                {
+                    Ok(()) => policy
+                        .map(|policy| state.evaluate_policy(&presentation, policy))
+                        .transpose(),
+                    Err(err) => Err(err),
+                };
 
                 match verification_result {
-                    Ok(()) => VerifierFullState::Finished(
-                        (state, presentation, PresentationVerificationStatus::Valid).into(),
-                    ),
+                    Ok(policy_report) => {
+                        let verification_status = match &policy_report {
+                            Some(report) if report.is_accepted() => {
+                                info!(
+                                    "Verifier policy accepted presentation of thread {}: {report}",
+                                    self.thread_id
+                                );
+                                PresentationVerificationStatus::Valid
+                            }
+                            Some(report) => {
+                                warn!(
+                                    "Verifier policy rejected presentation of thread {}: {report}",
+                                    self.thread_id
+                                );
+                                PresentationVerificationStatus::Invalid
+                            }
+                            None => PresentationVerificationStatus::Valid,
+                        };
+                        let mut finished_state: FinishedState =
+                            (state, presentation, verification_status).into();
+                        finished_state.policy_report = policy_report;
+                        VerifierFullState::Finished(finished_state)
+                    }
                     Err(err) => {
                         let problem_report =
                             build_problem_report_msg(Some(err.to_string()), &self.thread_id);
@@ -417,6 +473,13 @@ impl VerifierSM {
         }
     }
 
+    pub fn get_policy_report(&self) -> Option<&PolicyReport> {
+        match self.state {
+            VerifierFullState::Finished(ref state) => state.policy_report.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn presentation_request_msg(&self) -> VcxResult<RequestPresentationV1> {
         match self.state {
             VerifierFullState::Initial(_) => Err(AriesVcxError::from_msg(
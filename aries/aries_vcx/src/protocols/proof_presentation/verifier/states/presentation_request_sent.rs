@@ -1,3 +1,6 @@
+use anoncreds_types::data_types::messages::{
+    pres_request::PresentationRequest, presentation::Presentation,
+};
 use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
 use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 use messages::msg_fields::protocols::{
@@ -6,7 +9,13 @@ use messages::msg_fields::protocols::{
 };
 
 use crate::{
-    common::proofs::verifier::validate_indy_proof,
+    common::{
+        credentials::encoding::AttributeEncoder,
+        proofs::verifier::{
+            policy::{PolicyReport, VerifierPolicy},
+            validate_indy_proof_with_encoder,
+        },
+    },
     errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
     handlers::util::{get_attach_as_string, matches_thread_id, Status},
     protocols::proof_presentation::verifier::{
@@ -17,6 +26,9 @@ use crate::{
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PresentationRequestSentState {
     pub presentation_request: RequestPresentationV1,
+    /// Unix timestamp the presentation request was marked as sent at
+    #[serde(default)]
+    pub sent_at: Option<u64>,
 }
 
 impl PresentationRequestSentState {
@@ -26,6 +38,7 @@ impl PresentationRequestSentState {
         anoncreds: &impl BaseAnonCreds,
         presentation: &PresentationV1,
         thread_id: &str,
+        encoder: &AttributeEncoder,
     ) -> VcxResult<()> {
         if !matches_thread_id!(presentation, thread_id) {
             return Err(AriesVcxError::from_msg(
@@ -45,7 +58,14 @@ impl PresentationRequestSentState {
                 .request_presentations_attach
         );
 
-        let valid = validate_indy_proof(ledger, anoncreds, &proof_json, &proof_req_json).await?;
+        let valid = validate_indy_proof_with_encoder(
+            ledger,
+            anoncreds,
+            &proof_json,
+            &proof_req_json,
+            encoder,
+        )
+        .await?;
 
         if !valid {
             return Err(AriesVcxError::from_msg(
@@ -56,6 +76,24 @@ impl PresentationRequestSentState {
 
         Ok(())
     }
+
+    pub fn evaluate_policy(
+        &self,
+        presentation: &PresentationV1,
+        policy: &VerifierPolicy,
+    ) -> VcxResult<PolicyReport> {
+        let proof_json = get_attach_as_string!(&presentation.content.presentations_attach);
+        let proof_req_json = get_attach_as_string!(
+            &self
+                .presentation_request
+                .content
+                .request_presentations_attach
+        );
+        let proof: Presentation = serde_json::from_str(&proof_json)?;
+        let proof_req: PresentationRequest = serde_json::from_str(&proof_req_json)?;
+
+        Ok(policy.evaluate(&proof_req, &proof, self.sent_at))
+    }
 }
 
 impl
@@ -78,6 +116,7 @@ impl
             presentation: Some(presentation),
             status: Status::Success,
             verification_status,
+            policy_report: None,
         }
     }
 }
@@ -93,6 +132,7 @@ impl From<(PresentationRequestSentState, ProblemReport)> for FinishedState {
             presentation: None,
             status: Status::Failed(problem_report),
             verification_status: PresentationVerificationStatus::Unavailable,
+            policy_report: None,
         }
     }
 }
@@ -1,6 +1,7 @@
 use messages::msg_fields::protocols::present_proof::v1::request::{
     RequestPresentationV1, RequestPresentationV1Content,
 };
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::protocols::proof_presentation::verifier::states::presentation_request_sent::PresentationRequestSentState;
@@ -39,6 +40,7 @@ impl From<PresentationRequestSetState> for PresentationRequestSentState {
         trace!("transit state from PresentationRequestSetState to PresentationRequestSentState");
         PresentationRequestSentState {
             presentation_request: state.presentation_request,
+            sent_at: Some(OffsetDateTime::now_utc().unix_timestamp() as u64),
         }
     }
 }
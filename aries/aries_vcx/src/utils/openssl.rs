@@ -6,17 +6,19 @@ use crate::errors::error::prelude::*;
 pub fn encode(s: &str) -> VcxResult<String> {
     match s.parse::<u32>() {
         Ok(val) => Ok(val.to_string()),
-        Err(_) => {
-            let mut hasher = Sha256::new();
-            hasher.update(s.as_bytes());
-            let hash = hasher.finalize();
-            let bignum = BigUint::from_bytes_be(hash.as_slice());
-            let encoded = bignum.to_str_radix(10);
-            Ok(encoded)
-        }
+        Err(_) => Ok(hash(s)),
     }
 }
 
+/// SHA-256 digest of `s` as a decimal integer, the encoding of values which are not integers.
+pub fn hash(s: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(s.as_bytes());
+    let hash = hasher.finalize();
+    let bignum = BigUint::from_bytes_be(hash.as_slice());
+    bignum.to_str_radix(10)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
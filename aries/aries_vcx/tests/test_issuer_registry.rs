@@ -0,0 +1,384 @@
+use std::{error::Error, path::Path, sync::Mutex, time::Duration};
+
+use anoncreds_types::data_types::{
+    identifiers::rev_reg_def_id::RevocationRegistryDefinitionId,
+    ledger::{
+        cred_def::CredentialDefinition, rev_reg_def::RevocationRegistryDefinition,
+        rev_reg_delta::RevocationRegistryDelta, schema::Schema as LedgerSchema,
+    },
+};
+use aries_vcx::{
+    common::{
+        primitives::{
+            credential_definition::{CredentialDef, PublicEntityStateType},
+            credential_schema::Schema,
+            issuer_registry::{
+                IssuerObjectFilter, IssuerObjectKind, IssuerObjectState, IssuerRegistry,
+            },
+            revocation_registry::RevocationRegistry,
+        },
+        tails::TailsUploader,
+    },
+    errors::error::VcxResult,
+};
+use aries_vcx_ledger::{
+    errors::error::VcxLedgerResult,
+    ledger::{base_ledger::AnoncredsLedgerWrite, indy::pool::test_utils::get_temp_dir_path},
+};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use async_trait::async_trait;
+use did_parser_nom::Did;
+use test_utils::{
+    constants::{DEFAULT_SCHEMA_ATTRS, TEST_TAILS_URL},
+    devsetup::build_setup_profile,
+    random::{generate_random_schema_name, generate_random_schema_version},
+};
+
+use crate::utils::{
+    create_and_publish_test_rev_reg, create_and_write_test_cred_def, create_and_write_test_schema,
+};
+
+pub mod utils;
+
+struct StaticTailsUploader;
+
+#[async_trait]
+impl TailsUploader for StaticTailsUploader {
+    async fn upload(&self, _rev_reg_def_id: &str, _tails_file: &Path) -> VcxResult<String> {
+        Ok(TEST_TAILS_URL.to_string())
+    }
+}
+
+/// Forwards writes to the ledger, recording which kinds of objects were written.
+#[derive(Debug)]
+struct RecordingLedgerWrite<'a, W> {
+    inner: &'a W,
+    writes: Mutex<Vec<&'static str>>,
+}
+
+impl<'a, W> RecordingLedgerWrite<'a, W> {
+    fn new(inner: &'a W) -> Self {
+        Self {
+            inner,
+            writes: Mutex::new(vec![]),
+        }
+    }
+
+    fn writes(&self) -> Vec<&'static str> {
+        self.writes.lock().unwrap().clone()
+    }
+
+    fn record(&self, write: &'static str) {
+        self.writes.lock().unwrap().push(write);
+    }
+}
+
+#[async_trait]
+impl<W: AnoncredsLedgerWrite> AnoncredsLedgerWrite for RecordingLedgerWrite<'_, W> {
+    async fn publish_schema(
+        &self,
+        wallet: &impl BaseWallet,
+        schema_json: LedgerSchema,
+        submitter_did: &Did,
+        endorser_did: Option<&Did>,
+    ) -> VcxLedgerResult<()> {
+        self.record("schema");
+        self.inner
+            .publish_schema(wallet, schema_json, submitter_did, endorser_did)
+            .await
+    }
+
+    async fn publish_cred_def(
+        &self,
+        wallet: &impl BaseWallet,
+        cred_def_json: CredentialDefinition,
+        submitter_did: &Did,
+    ) -> VcxLedgerResult<()> {
+        self.record("cred_def");
+        self.inner
+            .publish_cred_def(wallet, cred_def_json, submitter_did)
+            .await
+    }
+
+    async fn publish_rev_reg_def(
+        &self,
+        wallet: &impl BaseWallet,
+        rev_reg_def: RevocationRegistryDefinition,
+        submitter_did: &Did,
+    ) -> VcxLedgerResult<()> {
+        self.record("rev_reg_def");
+        self.inner
+            .publish_rev_reg_def(wallet, rev_reg_def, submitter_did)
+            .await
+    }
+
+    async fn publish_rev_reg_delta(
+        &self,
+        wallet: &impl BaseWallet,
+        rev_reg_id: &RevocationRegistryDefinitionId,
+        rev_reg_entry_json: RevocationRegistryDelta,
+        submitter_did: &Did,
+    ) -> VcxLedgerResult<()> {
+        self.record("rev_reg_entry");
+        self.inner
+            .publish_rev_reg_delta(wallet, rev_reg_id, rev_reg_entry_json, submitter_did)
+            .await
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_issuer_registry_ensure_published() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let ledger_write = RecordingLedgerWrite::new(&setup.ledger_write);
+    let registry = IssuerRegistry::new("test", &setup.ledger_read, &ledger_write);
+
+    let schema = Schema::create(
+        &setup.anoncreds,
+        "source_id",
+        &setup.institution_did,
+        &generate_random_schema_name(),
+        &generate_random_schema_version(),
+        serde_json::from_str(DEFAULT_SCHEMA_ATTRS)?,
+    )
+    .await?;
+    let schema_id = schema.schema_id.to_string();
+    let object = registry.record_schema(&setup.wallet, &schema).await?;
+    assert_eq!(object.state, IssuerObjectState::Local);
+    let object = registry
+        .ensure_published(&setup.wallet, &StaticTailsUploader, &schema_id)
+        .await?;
+    assert_eq!(object.state, IssuerObjectState::Published);
+    assert!(object.published_at.is_some());
+    // published objects are not written again
+    registry
+        .ensure_published(&setup.wallet, &StaticTailsUploader, &schema_id)
+        .await?;
+    assert_eq!(ledger_write.writes(), vec!["schema"]);
+    std::thread::sleep(Duration::from_millis(500));
+
+    let cred_def = CredentialDef::create(
+        &setup.wallet,
+        &setup.ledger_read,
+        &setup.anoncreds,
+        "1".to_string(),
+        setup.institution_did.clone(),
+        schema.schema_id.clone(),
+        "1".to_string(),
+        true,
+    )
+    .await?;
+    let cred_def_id = cred_def.get_cred_def_id().clone();
+    registry.record_cred_def(&setup.wallet, &cred_def).await?;
+    registry
+        .ensure_published(
+            &setup.wallet,
+            &StaticTailsUploader,
+            &cred_def_id.to_string(),
+        )
+        .await?;
+    assert_eq!(ledger_write.writes(), vec!["schema", "cred_def"]);
+
+    // an earlier attempt published the definition, but its result was not persisted
+    let tails_dir = get_temp_dir_path().to_str().unwrap().to_string();
+    let rev_reg = RevocationRegistry::create(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.institution_did,
+        &cred_def_id,
+        &tails_dir,
+        10,
+        1,
+    )
+    .await?;
+    rev_reg
+        .clone()
+        .publish_rev_reg_def(
+            &setup.wallet,
+            &setup.ledger_write,
+            &setup.institution_did,
+            TEST_TAILS_URL,
+        )
+        .await?;
+    let object = registry.record_rev_reg(&setup.wallet, &rev_reg).await?;
+    assert_eq!(object.state, IssuerObjectState::Local);
+    let object = registry
+        .ensure_published(&setup.wallet, &StaticTailsUploader, &object.id)
+        .await?;
+    assert_eq!(object.state, IssuerObjectState::Published);
+    assert_eq!(
+        ledger_write.writes(),
+        vec!["schema", "cred_def", "rev_reg_entry"]
+    );
+
+    // an earlier attempt published the definition and the initial entry
+    let rev_reg = RevocationRegistry::create(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.institution_did,
+        &cred_def_id,
+        &tails_dir,
+        10,
+        2,
+    )
+    .await?;
+    rev_reg
+        .clone()
+        .publish_revocation_primitives(&setup.wallet, &setup.ledger_write, TEST_TAILS_URL)
+        .await?;
+    let object = registry.record_rev_reg(&setup.wallet, &rev_reg).await?;
+    let object = registry
+        .ensure_published(&setup.wallet, &StaticTailsUploader, &object.id)
+        .await?;
+    assert_eq!(object.state, IssuerObjectState::Published);
+    let rev_reg = RevocationRegistry::from_string(object.data.as_deref().unwrap())?;
+    assert!(rev_reg.was_rev_reg_def_published());
+    assert!(rev_reg.was_rev_reg_delta_published());
+    assert_eq!(
+        ledger_write.writes(),
+        vec!["schema", "cred_def", "rev_reg_entry"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_issuer_registry_rebuild_index() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let registry = IssuerRegistry::new("test", &setup.ledger_read, &setup.ledger_write);
+
+    // published without the registry, known to the wallet by the anoncreds records only
+    let schema = create_and_write_test_schema(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        DEFAULT_SCHEMA_ATTRS,
+    )
+    .await;
+    let cred_def = create_and_write_test_cred_def(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_read,
+        &setup.ledger_write,
+        &setup.institution_did,
+        &schema.schema_id,
+        true,
+    )
+    .await;
+    let rev_reg = create_and_publish_test_rev_reg(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        cred_def.get_cred_def_id(),
+    )
+    .await;
+
+    // tracked as published, but never written to the ledger
+    let mut unpublished = Schema::create(
+        &setup.anoncreds,
+        "source_id",
+        &setup.institution_did,
+        &generate_random_schema_name(),
+        &generate_random_schema_version(),
+        serde_json::from_str(DEFAULT_SCHEMA_ATTRS)?,
+    )
+    .await?;
+    unpublished.state = PublicEntityStateType::Published;
+    let object = registry.record_schema(&setup.wallet, &unpublished).await?;
+    assert_eq!(object.state, IssuerObjectState::Published);
+
+    let objects = registry.rebuild_index(&setup.wallet).await?;
+    assert_eq!(objects.len(), 4);
+    let imported = registry
+        .get(&setup.wallet, &rev_reg.get_rev_reg_id())
+        .await?;
+    assert_eq!(imported.kind, IssuerObjectKind::RevReg);
+    assert_eq!(imported.state, IssuerObjectState::Published);
+    assert_eq!(imported.ledger, "test");
+    assert_eq!(
+        imported.parent_id.as_deref(),
+        Some(cred_def.get_cred_def_id().to_string().as_str())
+    );
+    assert!(imported.data.is_none());
+    let demoted = registry
+        .get(&setup.wallet, &unpublished.schema_id.to_string())
+        .await?;
+    assert_eq!(demoted.state, IssuerObjectState::Local);
+    assert!(demoted.published_at.is_none());
+    assert!(demoted.data.is_some());
+
+    // objects are tracked per ledger
+    let other = IssuerRegistry::new("other", &setup.ledger_read, &setup.ledger_write);
+    let filter = IssuerObjectFilter::default();
+    assert!(other.list(&setup.wallet, &filter).await?.is_empty());
+    assert_eq!(
+        other.list_all_ledgers(&setup.wallet, &filter).await?.len(),
+        4
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_issuer_registry_import_from_ledger() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let registry = IssuerRegistry::new("test", &setup.ledger_read, &setup.ledger_write);
+    let schema = create_and_write_test_schema(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        DEFAULT_SCHEMA_ATTRS,
+    )
+    .await;
+    let schema_id = schema.schema_id.to_string();
+
+    let imported = registry
+        .import_from_ledger(&setup.wallet, IssuerObjectKind::Schema, &schema_id)
+        .await?;
+    assert_eq!(imported.state, IssuerObjectState::Published);
+    assert_eq!(imported.name, schema.name);
+    assert_eq!(imported.version.as_deref(), Some(schema.version.as_str()));
+    assert!(imported.data.is_none());
+
+    // tracking the local schema afterwards keeps the import time and adds the local data
+    let tracked = registry.record_schema(&setup.wallet, &schema).await?;
+    assert_eq!(tracked.created_at, imported.created_at);
+    assert_eq!(tracked.published_at, imported.published_at);
+    assert!(tracked.data.is_some());
+
+    // importing again keeps the local data
+    let reimported = registry
+        .import_from_ledger(&setup.wallet, IssuerObjectKind::Schema, &schema_id)
+        .await?;
+    assert_eq!(reimported.data, tracked.data);
+    assert_eq!(
+        registry
+            .list(&setup.wallet, &IssuerObjectFilter::default())
+            .await?
+            .len(),
+        1
+    );
+
+    // objects missing from the ledger cannot be imported
+    let unpublished = Schema::create(
+        &setup.anoncreds,
+        "source_id",
+        &setup.institution_did,
+        &generate_random_schema_name(),
+        &generate_random_schema_version(),
+        serde_json::from_str(DEFAULT_SCHEMA_ATTRS)?,
+    )
+    .await?;
+    assert!(registry
+        .import_from_ledger(
+            &setup.wallet,
+            IssuerObjectKind::Schema,
+            &unpublished.schema_id.to_string()
+        )
+        .await
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,288 @@
+use std::{
+    error::Error,
+    sync::{Arc, Mutex},
+};
+
+use anoncreds_types::data_types::identifiers::rev_reg_def_id::RevocationRegistryDefinitionId;
+use aries_vcx::{
+    common::primitives::revocation_batch::RevocationBatch, errors::error::VcxResult,
+    protocols::SendClosure,
+};
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
+use test_utils::{constants::DEFAULT_SCHEMA_ATTRS, devsetup::build_setup_profile};
+
+use crate::utils::{
+    create_and_publish_test_rev_reg, create_and_write_test_cred_def, create_and_write_test_schema,
+};
+
+pub mod utils;
+
+fn recording_sender(
+    sent: Arc<Mutex<Vec<String>>>,
+) -> impl Fn(&str) -> VcxResult<SendClosure<'static>> {
+    move |holder: &str| {
+        let sent = sent.clone();
+        let holder = holder.to_string();
+        Ok(Box::new(move |_message| {
+            Box::pin(async move {
+                sent.lock().unwrap().push(holder);
+                Ok(())
+            })
+        }))
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_revocation_batch_preview() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let schema = create_and_write_test_schema(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        DEFAULT_SCHEMA_ATTRS,
+    )
+    .await;
+    let cred_def = create_and_write_test_cred_def(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_read,
+        &setup.ledger_write,
+        &setup.institution_did,
+        &schema.schema_id,
+        true,
+    )
+    .await;
+    let rev_reg = create_and_publish_test_rev_reg(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        cred_def.get_cred_def_id(),
+    )
+    .await;
+    let rev_reg_id = rev_reg.get_rev_reg_id();
+
+    let mut batch = RevocationBatch::new();
+    batch.queue(&rev_reg_id, 1, Some("holder_1".into()));
+    batch.queue(&rev_reg_id, 2, None);
+
+    let previews = batch
+        .preview(&setup.wallet, &setup.anoncreds, &setup.ledger_read)
+        .await?;
+    assert_eq!(previews.len(), 1);
+    assert_eq!(previews[0].rev_reg_id, rev_reg_id);
+    assert_eq!(previews[0].diff.newly_revoked, vec![1, 2]);
+    assert!(previews[0].diff.reinstated.is_empty());
+    assert!(previews[0].already_revoked.is_empty());
+    assert!(previews[0].status_list.state()[1]);
+    assert!(!previews[0].status_list.state()[3]);
+
+    // previewing stages nothing
+    let rev_reg_def_id: RevocationRegistryDefinitionId = rev_reg_id.clone().try_into()?;
+    assert!(setup
+        .anoncreds
+        .get_rev_reg_delta(&setup.wallet, &rev_reg_def_id)
+        .await?
+        .is_none());
+    assert_eq!(batch.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_revocation_batch_commit() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let schema = create_and_write_test_schema(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        DEFAULT_SCHEMA_ATTRS,
+    )
+    .await;
+    let cred_def = create_and_write_test_cred_def(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_read,
+        &setup.ledger_write,
+        &setup.institution_did,
+        &schema.schema_id,
+        true,
+    )
+    .await;
+    let rev_reg = create_and_publish_test_rev_reg(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        cred_def.get_cred_def_id(),
+    )
+    .await;
+    let rev_reg_id = rev_reg.get_rev_reg_id();
+    let rev_reg_def_id: RevocationRegistryDefinitionId = rev_reg_id.clone().try_into()?;
+
+    // credential 3 is revoked on the ledger, credential 2 only locally
+    #[allow(deprecated)] // TODO - https://github.com/openwallet-foundation/vcx/issues/1309
+    let ledger_delta = setup
+        .ledger_read
+        .get_rev_reg_delta_json(&rev_reg_def_id, None, None)
+        .await?
+        .0;
+    setup
+        .anoncreds
+        .revoke_credential_local(&setup.wallet, &rev_reg_def_id, 3, ledger_delta)
+        .await?;
+    rev_reg
+        .publish_local_revocations(
+            &setup.wallet,
+            &setup.anoncreds,
+            &setup.ledger_write,
+            &setup.institution_did,
+        )
+        .await?;
+    #[allow(deprecated)] // TODO - https://github.com/openwallet-foundation/vcx/issues/1309
+    let ledger_delta = setup
+        .ledger_read
+        .get_rev_reg_delta_json(&rev_reg_def_id, None, None)
+        .await?
+        .0;
+    // revoking a single credential which is revoked already is still an error
+    assert!(setup
+        .anoncreds
+        .revoke_credential_local(&setup.wallet, &rev_reg_def_id, 3, ledger_delta.clone())
+        .await
+        .is_err());
+    setup
+        .anoncreds
+        .revoke_credential_local(&setup.wallet, &rev_reg_def_id, 2, ledger_delta)
+        .await?;
+
+    let mut batch = RevocationBatch::new().with_comment("revoked in a batch");
+    batch.queue(&rev_reg_id, 1, Some("holder_1".into()));
+    batch.queue(&rev_reg_id, 2, Some("holder_2".into()));
+    batch.queue(&rev_reg_id, 3, Some("holder_3".into()));
+
+    let previews = batch
+        .preview(&setup.wallet, &setup.anoncreds, &setup.ledger_read)
+        .await?;
+    assert_eq!(previews[0].already_revoked, vec![2, 3]);
+    assert_eq!(previews[0].diff.newly_revoked, vec![1]);
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let report = batch
+        .commit(
+            &setup.wallet,
+            &setup.anoncreds,
+            &setup.ledger_read,
+            &setup.ledger_write,
+            &setup.institution_did,
+            recording_sender(sent.clone()),
+        )
+        .await?;
+    assert!(report.is_success());
+    assert_eq!(report.published.len(), 1);
+    assert_eq!(report.published[0].cred_rev_ids, vec![1, 2, 3]);
+    assert!(batch.is_empty());
+    // the holder of the credential revoked on the ledger before the batch is not notified again
+    let notified: Vec<u32> = report
+        .notifications
+        .iter()
+        .map(|notification| notification.revocation.cred_rev_id)
+        .collect();
+    assert_eq!(notified, vec![1, 2]);
+    assert_eq!(*sent.lock().unwrap(), vec!["holder_1", "holder_2"]);
+
+    // the local delta is published and cleared
+    assert!(setup
+        .anoncreds
+        .get_rev_reg_delta(&setup.wallet, &rev_reg_def_id)
+        .await?
+        .is_none());
+    #[allow(deprecated)] // TODO - https://github.com/openwallet-foundation/vcx/issues/1309
+    let ledger_delta = setup
+        .ledger_read
+        .get_rev_reg_delta_json(&rev_reg_def_id, None, None)
+        .await?
+        .0;
+    for cred_rev_id in [1, 2, 3] {
+        assert!(ledger_delta.value.revoked.contains(&cred_rev_id));
+    }
+
+    // a batch of credentials revoked on the ledger already publishes nothing and notifies no one
+    batch.queue(&rev_reg_id, 1, Some("holder_1".into()));
+    let report = batch
+        .commit(
+            &setup.wallet,
+            &setup.anoncreds,
+            &setup.ledger_read,
+            &setup.ledger_write,
+            &setup.institution_did,
+            recording_sender(sent.clone()),
+        )
+        .await?;
+    assert!(report.is_success());
+    assert_eq!(report.published.len(), 1);
+    assert!(report.notifications.is_empty());
+    assert_eq!(sent.lock().unwrap().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_revocation_batch_keeps_failed_registries_queued() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let schema = create_and_write_test_schema(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        DEFAULT_SCHEMA_ATTRS,
+    )
+    .await;
+    let cred_def = create_and_write_test_cred_def(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_read,
+        &setup.ledger_write,
+        &setup.institution_did,
+        &schema.schema_id,
+        true,
+    )
+    .await;
+    let rev_reg = create_and_publish_test_rev_reg(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        cred_def.get_cred_def_id(),
+    )
+    .await;
+    let rev_reg_id = rev_reg.get_rev_reg_id();
+
+    let mut batch = RevocationBatch::new();
+    batch.queue(&rev_reg_id, 1, None);
+    // out of range of the registry, which has a capacity of 10
+    batch.queue(&rev_reg_id, 50, Some("holder_50".into()));
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let report = batch
+        .commit(
+            &setup.wallet,
+            &setup.anoncreds,
+            &setup.ledger_read,
+            &setup.ledger_write,
+            &setup.institution_did,
+            recording_sender(sent.clone()),
+        )
+        .await?;
+    assert!(!report.is_success());
+    assert!(report.published.is_empty());
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].cred_rev_ids, vec![1, 50]);
+    assert_eq!(batch.len(), 2);
+    assert!(sent.lock().unwrap().is_empty());
+    Ok(())
+}
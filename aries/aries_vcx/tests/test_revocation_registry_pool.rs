@@ -0,0 +1,200 @@
+use std::{error::Error, path::Path};
+
+use aries_vcx::{
+    common::{
+        primitives::revocation_registry_pool::{
+            IssuedCredentialRegistry, RevocationRegistryPool, RevocationRegistryPoolConfig,
+        },
+        tails::TailsUploader,
+    },
+    errors::error::{AriesVcxErrorKind, VcxResult},
+};
+use aries_vcx_ledger::ledger::indy::pool::test_utils::get_temp_dir_path;
+use async_trait::async_trait;
+use test_utils::{
+    constants::{DEFAULT_SCHEMA_ATTRS, TEST_TAILS_URL},
+    devsetup::build_setup_profile,
+};
+
+use crate::utils::{create_and_write_test_cred_def, create_and_write_test_schema};
+
+pub mod utils;
+
+struct StaticTailsUploader;
+
+#[async_trait]
+impl TailsUploader for StaticTailsUploader {
+    async fn upload(&self, _rev_reg_def_id: &str, _tails_file: &Path) -> VcxResult<String> {
+        Ok(TEST_TAILS_URL.to_string())
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_revocation_registry_pool_rotation() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let schema = create_and_write_test_schema(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        DEFAULT_SCHEMA_ATTRS,
+    )
+    .await;
+    let cred_def = create_and_write_test_cred_def(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_read,
+        &setup.ledger_write,
+        &setup.institution_did,
+        &schema.schema_id,
+        true,
+    )
+    .await;
+    let config = RevocationRegistryPoolConfig {
+        issuer_did: setup.institution_did.clone(),
+        cred_def_id: cred_def.get_cred_def_id().clone(),
+        tails_dir: get_temp_dir_path().to_str().unwrap().to_string(),
+        max_creds: 2,
+    };
+
+    let mut pool = RevocationRegistryPool::create(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &StaticTailsUploader,
+        config.clone(),
+    )
+    .await?;
+    let first = pool.active_registry().get_rev_reg_id();
+    let standby = pool.standby_registry().unwrap().get_rev_reg_id();
+    assert!(first.ends_with(":tag1"));
+    assert!(standby.ends_with(":tag2"));
+    assert!(pool.active_registry().was_rev_reg_def_published());
+
+    // issuing into the active registry fills it up
+    pool.record_issuance(&setup.wallet, "thread-1", &first, 1)
+        .await?;
+    assert!(!pool.is_active_full());
+    assert!(
+        !pool
+            .rotate_if_full(
+                &setup.wallet,
+                &setup.anoncreds,
+                &setup.ledger_write,
+                &StaticTailsUploader
+            )
+            .await?
+    );
+    pool.record_issuance(&setup.wallet, "thread-2", &first, 2)
+        .await?;
+    assert!(pool.is_active_full());
+    assert!(
+        pool.rotate_if_full(
+            &setup.wallet,
+            &setup.anoncreds,
+            &setup.ledger_write,
+            &StaticTailsUploader
+        )
+        .await?
+    );
+    assert_eq!(pool.active_registry().get_rev_reg_id(), standby);
+    assert!(pool
+        .standby_registry()
+        .unwrap()
+        .get_rev_reg_id()
+        .ends_with(":tag3"));
+    assert!(!pool.is_active_full());
+
+    // bindings and retired registries live in the wallet, not in the serialized pool
+    let pool = RevocationRegistryPool::from_string(&pool.to_string()?)?;
+    assert!(!pool.to_string()?.contains("thread-1"));
+    assert_eq!(
+        pool.issued_credential(&setup.wallet, "thread-2").await?,
+        Some(IssuedCredentialRegistry {
+            rev_reg_id: first.clone(),
+            cred_rev_id: 2,
+        })
+    );
+    assert_eq!(
+        pool.issued_credential(&setup.wallet, "unknown-thread")
+            .await?,
+        None
+    );
+    let retired = pool.retired_registries(&setup.wallet).await?;
+    assert_eq!(retired.len(), 1);
+    assert_eq!(retired[0].get_rev_reg_id(), first);
+    assert!(pool.registry(&setup.wallet, &first).await?.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_revocation_registry_pool_continues_tags() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let schema = create_and_write_test_schema(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        DEFAULT_SCHEMA_ATTRS,
+    )
+    .await;
+    let cred_def = create_and_write_test_cred_def(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_read,
+        &setup.ledger_write,
+        &setup.institution_did,
+        &schema.schema_id,
+        true,
+    )
+    .await;
+    let config = RevocationRegistryPoolConfig {
+        issuer_did: setup.institution_did.clone(),
+        cred_def_id: cred_def.get_cred_def_id().clone(),
+        tails_dir: get_temp_dir_path().to_str().unwrap().to_string(),
+        max_creds: 2,
+    };
+    let mut pool = RevocationRegistryPool::create(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &StaticTailsUploader,
+        config.clone(),
+    )
+    .await?;
+
+    // an explicit rotation retires the active registry regardless of its capacity
+    pool.rotate(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &StaticTailsUploader,
+    )
+    .await?;
+    assert!(pool.active_registry().get_rev_reg_id().ends_with(":tag2"));
+
+    // a new pool for the same credential definition does not reuse the tags of the first one
+    let mut pool = RevocationRegistryPool::create(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &StaticTailsUploader,
+        config,
+    )
+    .await?;
+    assert!(pool.active_registry().get_rev_reg_id().ends_with(":tag4"));
+    assert!(pool
+        .standby_registry()
+        .unwrap()
+        .get_rev_reg_id()
+        .ends_with(":tag5"));
+
+    let err = pool
+        .record_issuance(&setup.wallet, "thread-1", "unknown_rev_reg", 1)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), AriesVcxErrorKind::InvalidRevocationDetails);
+    Ok(())
+}
@@ -29,7 +29,8 @@ use serde_json::json;
 use test_utils::{constants::DEFAULT_SCHEMA_ATTRS, devsetup::build_setup_profile};
 
 use crate::utils::{
-    create_and_write_credential, create_and_write_test_cred_def, create_and_write_test_schema,
+    create_and_write_credential, create_and_write_credential_with_data,
+    create_and_write_test_cred_def, create_and_write_test_schema,
 };
 
 pub mod utils;
@@ -723,3 +724,106 @@ async fn test_pool_prover_verify_proof_with_predicate_fail_case() -> Result<(),
         .unwrap_err();
     Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_proof_validate_json_scalar_values() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let schema = create_and_write_test_schema(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        r#"["over18", "count", "big", "ratio"]"#,
+    )
+    .await;
+    let cred_def = create_and_write_test_cred_def(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_read,
+        &setup.ledger_write,
+        &setup.institution_did,
+        &schema.schema_id,
+        false,
+    )
+    .await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    // issued with the default encoder, which encodes JSON scalars like their raw strings
+    let cred_id = create_and_write_credential_with_data(
+        &setup.wallet,
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.anoncreds,
+        &setup.institution_did,
+        &schema,
+        &cred_def,
+        None,
+        r#"{"over18": true, "count": 7, "big": 4294967296, "ratio": 1.5}"#,
+    )
+    .await;
+
+    let names = ["over18", "count", "big", "ratio"];
+    let proof_req = PresentationRequestPayload::builder()
+        .requested_attributes(
+            names
+                .iter()
+                .map(|name| {
+                    (
+                        format!("{name}_ref"),
+                        AttributeInfo::builder().name(name.to_string()).build(),
+                    )
+                })
+                .collect(),
+        )
+        .name("json_scalars".to_owned())
+        .nonce(Nonce::new()?)
+        .build();
+    let proof_req_json = serde_json::to_string(&proof_req)?;
+    let cred_def_json = serde_json::to_value(cred_def.get_cred_def_json())?;
+    let proof = setup
+        .anoncreds
+        .prover_create_proof(
+            &setup.wallet,
+            proof_req.into_v1(),
+            RequestedCredentials {
+                self_attested_attributes: Default::default(),
+                requested_attributes: names
+                    .iter()
+                    .map(|name| {
+                        (
+                            format!("{name}_ref"),
+                            RequestedAttribute {
+                                cred_id: cred_id.clone(),
+                                timestamp: None,
+                                revealed: true,
+                            },
+                        )
+                    })
+                    .collect(),
+                requested_predicates: Default::default(),
+            },
+            &"main".to_string(),
+            serde_json::from_str(&json!({ schema.schema_id: schema.schema_json }).to_string())?,
+            serde_json::from_str(
+                &json!({ cred_def.get_cred_def_id().to_string(): cred_def_json }).to_string(),
+            )?,
+            None,
+        )
+        .await?;
+    let proof_json = serde_json::to_value(&proof)?;
+    assert_eq!(
+        proof_json["requested_proof"]["revealed_attrs"]["over18_ref"]["raw"],
+        "true"
+    );
+
+    assert!(
+        validate_indy_proof(
+            &setup.ledger_read,
+            &setup.anoncreds,
+            &proof_json.to_string(),
+            &proof_req_json,
+        )
+        .await?
+    );
+    Ok(())
+}
@@ -112,8 +112,34 @@ pub async fn create_and_write_credential(
     cred_def: &CredentialDef,
     rev_reg: Option<&RevocationRegistry>,
 ) -> String {
-    // TODO: Inject credential_data from caller
     let credential_data = r#"{"address1": ["123 Main St"], "address2": ["Suite 3"], "city": ["Draper"], "state": ["UT"], "zip": ["84000"]}"#;
+    create_and_write_credential_with_data(
+        wallet_issuer,
+        wallet_holder,
+        anoncreds_issuer,
+        anoncreds_holder,
+        institution_did,
+        schema,
+        cred_def,
+        rev_reg,
+        credential_data,
+    )
+    .await
+}
+
+/// Issues a credential of `credential_data`, encoded with the default encoder.
+#[allow(clippy::too_many_arguments)] // test util code
+pub async fn create_and_write_credential_with_data(
+    wallet_issuer: &impl BaseWallet,
+    wallet_holder: &impl BaseWallet,
+    anoncreds_issuer: &impl BaseAnonCreds,
+    anoncreds_holder: &impl BaseAnonCreds,
+    institution_did: &Did,
+    schema: &Schema,
+    cred_def: &CredentialDef,
+    rev_reg: Option<&RevocationRegistry>,
+    credential_data: &str,
+) -> String {
     let encoded_attributes = encode_attributes(credential_data).unwrap();
 
     let offer = anoncreds_issuer
@@ -77,7 +77,7 @@ use uuid::Uuid;
 
 use super::base_anoncreds::{
     BaseAnonCreds, CredentialDefinitionsMap, CredentialId, LinkSecretId, RevocationRegistriesMap,
-    RevocationRegistryDefinitionsMap, RevocationStatesMap, SchemasMap,
+    RevocationRegistryDefinitionsMap, RevocationStatesMap, SchemasMap, LINK_SECRET_ID_TAG,
 };
 use crate::{
     anoncreds::anoncreds::type_conversion::Convert,
@@ -239,6 +239,139 @@ impl Anoncreds {
 
         Self::_get_credentials(wallet, &wql_query).await
     }
+    /// Revocation status list built from the last known delta of the registry, along with the
+    /// status list once the given credentials are revoked as well. Unless `skip_revoked` is set,
+    /// revoking a credential which is revoked already is an error. The updated status list is
+    /// `None` if all of the credentials are skipped.
+    async fn revoke_in_rev_status_list(
+        &self,
+        wallet: &impl BaseWallet,
+        rev_reg_id: &RevocationRegistryDefinitionId,
+        last_rev_reg_delta: &RevocationRegistryDelta,
+        cred_rev_ids: &[u32],
+        skip_revoked: bool,
+    ) -> VcxAnoncredsResult<(RevocationStatusList, Option<RevocationStatusList>)> {
+        let rev_reg_def: RevocationRegistryDefinition = self
+            .get_wallet_record_value(wallet, RecordCategory::RevRegDef, &rev_reg_id.to_string())
+            .await?;
+
+        let current_time = OffsetDateTime::now_utc().unix_timestamp() as u64;
+        let rev_status_list = from_revocation_registry_delta_to_revocation_status_list(
+            &last_rev_reg_delta.value,
+            current_time,
+            &rev_reg_def.id,
+            rev_reg_def.value.max_cred_num as usize,
+            rev_reg_def.issuer_id.clone(),
+        )?;
+
+        // without skipping, anoncreds rejects credentials which are revoked already
+        let mut revoked = Vec::new();
+        for &cred_rev_id in cred_rev_ids {
+            if !skip_revoked {
+                revoked.push(cred_rev_id);
+                continue;
+            }
+            match rev_status_list.state().get(cred_rev_id as usize) {
+                Some(is_revoked) if *is_revoked => {}
+                Some(_) if !revoked.contains(&cred_rev_id) => revoked.push(cred_rev_id),
+                Some(_) => {}
+                None => {
+                    return Err(VcxAnoncredsError::InvalidInput(format!(
+                        "Credential revocation id {cred_rev_id} is out of range of revocation \
+                         registry {rev_reg_id}"
+                    )))
+                }
+            }
+        }
+        if revoked.is_empty() {
+            return Ok((rev_status_list, None));
+        }
+
+        let cred_def = self
+            .get_wallet_record_value(
+                wallet,
+                RecordCategory::CredDef,
+                &rev_reg_def.cred_def_id.to_string(),
+            )
+            .await?;
+
+        let rev_reg_def_priv = self
+            .get_wallet_record_value(
+                wallet,
+                RecordCategory::RevRegDefPriv,
+                &rev_reg_id.to_string(),
+            )
+            .await?;
+
+        let updated_rev_status_list = anoncreds::issuer::update_revocation_status_list(
+            &cred_def,
+            &rev_reg_def.convert(())?,
+            &rev_reg_def_priv,
+            &rev_status_list.clone().convert(())?,
+            None,
+            Some(revoked.into_iter().collect()),
+            None,
+        )?;
+
+        Ok((rev_status_list, Some(updated_rev_status_list.convert(())?)))
+    }
+
+    /// Revokes the credentials into the local revocation registry delta.
+    async fn revoke_local(
+        &self,
+        wallet: &impl BaseWallet,
+        rev_reg_id: &RevocationRegistryDefinitionId,
+        cred_rev_ids: &[u32],
+        ledger_rev_reg_delta_json: RevocationRegistryDelta,
+        skip_revoked: bool,
+    ) -> VcxAnoncredsResult<()> {
+        let last_rev_reg_delta_stored = self.get_rev_reg_delta(wallet, rev_reg_id).await?;
+        let last_rev_reg_delta = last_rev_reg_delta_stored
+            .clone()
+            .unwrap_or(ledger_rev_reg_delta_json.clone());
+
+        let (_, Some(updated_rev_status_list)) = self
+            .revoke_in_rev_status_list(
+                wallet,
+                rev_reg_id,
+                &last_rev_reg_delta,
+                cred_rev_ids,
+                skip_revoked,
+            )
+            .await?
+        else {
+            // all of the credentials are revoked already
+            return Ok(());
+        };
+
+        let prev_accum = ledger_rev_reg_delta_json.value.accum;
+        let updated_revocation_registry_delta =
+            from_revocation_status_list_to_revocation_registry_delta(
+                &updated_rev_status_list,
+                Some(prev_accum),
+            )?;
+        let updated_revocation_registry_delta_str =
+            serde_json::to_string(&updated_revocation_registry_delta)?;
+
+        if last_rev_reg_delta_stored.is_some() {
+            wallet
+                .update_record_value(
+                    RecordCategory::RevRegDelta,
+                    &rev_reg_id.to_string(),
+                    &updated_revocation_registry_delta_str,
+                )
+                .await?;
+        } else {
+            let record = Record::builder()
+                .name(rev_reg_id.to_string())
+                .category(RecordCategory::RevRegDelta)
+                .value(updated_revocation_registry_delta_str)
+                .build();
+            wallet.add_record(record).await?;
+        };
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -1009,6 +1142,11 @@ impl BaseAnonCreds for Anoncreds {
             RecordTag::new("schema_version", &schema_version),
             RecordTag::new("issuer_did", &issuer_did.0),
             RecordTag::new("cred_def_id", &cred_def_id.0),
+            RecordTag::new(LINK_SECRET_ID_TAG, link_secret_id),
+            RecordTag::new(
+                "stored_at",
+                &OffsetDateTime::now_utc().unix_timestamp().to_string(),
+            ),
         ]);
 
         if let Some(rev_reg_id) = &credential.rev_reg_id {
@@ -1106,78 +1244,51 @@ impl BaseAnonCreds for Anoncreds {
         cred_rev_id: u32,
         ledger_rev_reg_delta_json: RevocationRegistryDelta,
     ) -> VcxAnoncredsResult<()> {
-        let rev_reg_def: RevocationRegistryDefinition = self
-            .get_wallet_record_value(wallet, RecordCategory::RevRegDef, &rev_reg_id.to_string())
-            .await?;
-
-        let last_rev_reg_delta_stored = self.get_rev_reg_delta(wallet, rev_reg_id).await?;
-        let last_rev_reg_delta = last_rev_reg_delta_stored
-            .clone()
-            .unwrap_or(ledger_rev_reg_delta_json.clone());
-
-        let prev_accum = ledger_rev_reg_delta_json.value.accum;
-
-        let current_time = OffsetDateTime::now_utc().unix_timestamp() as u64;
-        let rev_status_list = from_revocation_registry_delta_to_revocation_status_list(
-            &last_rev_reg_delta.value,
-            current_time,
-            &rev_reg_def.id,
-            rev_reg_def.value.max_cred_num as usize,
-            rev_reg_def.issuer_id.clone(),
-        )?;
+        self.revoke_local(
+            wallet,
+            rev_reg_id,
+            &[cred_rev_id],
+            ledger_rev_reg_delta_json,
+            false,
+        )
+        .await
+    }
 
-        let cred_def = self
-            .get_wallet_record_value(
-                wallet,
-                RecordCategory::CredDef,
-                &rev_reg_def.cred_def_id.to_string(),
-            )
-            .await?;
+    async fn revoke_credentials_local(
+        &self,
+        wallet: &impl BaseWallet,
+        rev_reg_id: &RevocationRegistryDefinitionId,
+        cred_rev_ids: &[u32],
+        ledger_rev_reg_delta_json: RevocationRegistryDelta,
+    ) -> VcxAnoncredsResult<()> {
+        self.revoke_local(
+            wallet,
+            rev_reg_id,
+            cred_rev_ids,
+            ledger_rev_reg_delta_json,
+            true,
+        )
+        .await
+    }
 
-        let rev_reg_def_priv = self
-            .get_wallet_record_value(
-                wallet,
-                RecordCategory::RevRegDefPriv,
-                &rev_reg_id.to_string(),
-            )
+    async fn preview_revoke_credentials_local(
+        &self,
+        wallet: &impl BaseWallet,
+        rev_reg_id: &RevocationRegistryDefinitionId,
+        cred_rev_ids: &[u32],
+        ledger_rev_reg_delta_json: RevocationRegistryDelta,
+    ) -> VcxAnoncredsResult<(RevocationStatusList, RevocationStatusList)> {
+        let last_rev_reg_delta = self
+            .get_rev_reg_delta(wallet, rev_reg_id)
+            .await?
+            .unwrap_or(ledger_rev_reg_delta_json);
+
+        let (current_rev_status_list, updated_rev_status_list) = self
+            .revoke_in_rev_status_list(wallet, rev_reg_id, &last_rev_reg_delta, cred_rev_ids, true)
             .await?;
-
-        let updated_rev_status_list = anoncreds::issuer::update_revocation_status_list(
-            &cred_def,
-            &rev_reg_def.convert(())?,
-            &rev_reg_def_priv,
-            &rev_status_list.convert(())?,
-            None,
-            Some(vec![cred_rev_id].into_iter().collect()),
-            None,
-        )?;
-
-        let updated_revocation_registry_delta =
-            from_revocation_status_list_to_revocation_registry_delta(
-                &updated_rev_status_list.convert(())?,
-                Some(prev_accum),
-            )?;
-        let updated_revocation_registry_delta_str =
-            serde_json::to_string(&updated_revocation_registry_delta)?;
-
-        if last_rev_reg_delta_stored.is_some() {
-            wallet
-                .update_record_value(
-                    RecordCategory::RevRegDelta,
-                    &rev_reg_id.to_string(),
-                    &updated_revocation_registry_delta_str,
-                )
-                .await?;
-        } else {
-            let record = Record::builder()
-                .name(rev_reg_id.to_string())
-                .category(RecordCategory::RevRegDelta)
-                .value(updated_revocation_registry_delta_str)
-                .build();
-            wallet.add_record(record).await?;
-        };
-
-        Ok(())
+        let updated_rev_status_list =
+            updated_rev_status_list.unwrap_or_else(|| current_rev_status_list.clone());
+        Ok((current_rev_status_list, updated_rev_status_list))
     }
 
     async fn get_rev_reg_delta(
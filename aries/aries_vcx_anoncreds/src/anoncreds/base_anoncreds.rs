@@ -29,10 +29,12 @@ use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 use async_trait::async_trait;
 use did_parser_nom::Did;
 
-use crate::errors::error::VcxAnoncredsResult;
+use crate::errors::error::{VcxAnoncredsError, VcxAnoncredsResult};
 
 pub type CredentialId = String;
 pub type LinkSecretId = String;
+/// Tag of stored credentials naming the link secret the credential is bound to
+pub const LINK_SECRET_ID_TAG: &str = "link_secret_id";
 pub type SchemasMap = HashMap<SchemaId, Schema>;
 pub type CredentialDefinitionsMap = HashMap<CredentialDefinitionId, CredentialDefinition>;
 pub type RevocationStatesMap = HashMap<String, HashMap<u64, CredentialRevocationState>>;
@@ -185,6 +187,35 @@ pub trait BaseAnonCreds: std::fmt::Debug + Send + Sync {
         rev_reg_delta_json: RevocationRegistryDelta,
     ) -> VcxAnoncredsResult<()>;
 
+    /// Revokes the credentials into the local revocation registry delta with a single
+    /// accumulator update. Credentials which are revoked already are skipped.
+    async fn revoke_credentials_local(
+        &self,
+        _wallet: &impl BaseWallet,
+        _rev_reg_id: &RevocationRegistryDefinitionId,
+        _cred_rev_ids: &[u32],
+        _rev_reg_delta_json: RevocationRegistryDelta,
+    ) -> VcxAnoncredsResult<()> {
+        Err(VcxAnoncredsError::UnimplementedFeature(
+            "revoke_credentials_local is not supported by this implementation".into(),
+        ))
+    }
+
+    /// Returns the current revocation status list of the registry, including unpublished local
+    /// revocations, and the status list once the credentials are revoked as well, without
+    /// changing the local revocation registry delta.
+    async fn preview_revoke_credentials_local(
+        &self,
+        _wallet: &impl BaseWallet,
+        _rev_reg_id: &RevocationRegistryDefinitionId,
+        _cred_rev_ids: &[u32],
+        _rev_reg_delta_json: RevocationRegistryDelta,
+    ) -> VcxAnoncredsResult<(RevocationStatusList, RevocationStatusList)> {
+        Err(VcxAnoncredsError::UnimplementedFeature(
+            "preview_revoke_credentials_local is not supported by this implementation".into(),
+        ))
+    }
+
     async fn get_rev_reg_delta(
         &self,
         wallet: &impl BaseWallet,
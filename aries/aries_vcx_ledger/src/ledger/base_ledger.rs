@@ -18,12 +18,24 @@ use indy_vdr::ledger::constants::UpdateRole;
 use public_key::Key;
 use serde::Serialize;
 
-use crate::errors::error::VcxLedgerResult;
+use crate::errors::error::{VcxLedgerError, VcxLedgerResult};
 
 #[async_trait]
 pub trait IndyLedgerRead: Debug + Send + Sync {
     async fn get_attr(&self, target_did: &Did, attr_name: &str) -> VcxLedgerResult<String>;
     async fn get_nym(&self, did: &Did) -> VcxLedgerResult<String>;
+    /// Reads the NYM of `did` as it was after the transaction with sequence number `seq_no`,
+    /// or as it was at `timestamp` (requires node protocol >= 1.4).
+    async fn get_nym_version(
+        &self,
+        _did: &Did,
+        _seq_no: Option<i32>,
+        _timestamp: Option<u64>,
+    ) -> VcxLedgerResult<String> {
+        Err(VcxLedgerError::UnimplementedFeature(
+            "get_nym_version is not supported by this implementation".into(),
+        ))
+    }
     async fn get_txn_author_agreement(&self) -> VcxLedgerResult<Option<String>>;
     async fn get_ledger_txn(
         &self,
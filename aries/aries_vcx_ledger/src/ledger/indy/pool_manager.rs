@@ -0,0 +1,1004 @@
+//! Management of several indy networks, with reads routed to the network an identifier belongs
+//! to.
+use std::{
+    collections::HashMap,
+    fs,
+    future::Future,
+    pin::Pin,
+    sync::{PoisonError, RwLock},
+    time::SystemTime,
+};
+
+use anoncreds_types::data_types::{
+    identifiers::{
+        cred_def_id::CredentialDefinitionId, issuer_id::IssuerId,
+        rev_reg_def_id::RevocationRegistryDefinitionId, schema_id::SchemaId,
+    },
+    ledger::{
+        cred_def::CredentialDefinition, rev_reg::RevocationRegistry,
+        rev_reg_def::RevocationRegistryDefinition, rev_reg_delta::RevocationRegistryDelta,
+        rev_status_list::RevocationStatusList, schema::Schema,
+    },
+};
+use async_trait::async_trait;
+use did_parser_nom::Did;
+use indy_vdr::config::PoolConfig;
+use log::{info, warn};
+
+use crate::{
+    errors::error::{VcxLedgerError, VcxLedgerResult},
+    ledger::{
+        base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerSupport, IndyLedgerRead},
+        indy_vdr_ledger::{
+            indyvdr_build_ledger_read, indyvdr_build_ledger_write, DefaultIndyLedgerRead,
+            DefaultIndyLedgerWrite,
+        },
+        request_submitter::vdr_ledger::{IndyVdrLedgerPool, IndyVdrSubmitter},
+        response_cacher::in_memory::InMemoryResponseCacherConfig,
+    },
+};
+
+/// did:indy namespaces of well known indy networks
+pub mod namespaces {
+    pub const SOVRIN_MAINNET: &str = "sovrin";
+    pub const SOVRIN_STAGING: &str = "sovrin:staging";
+    pub const SOVRIN_BUILDER: &str = "sovrin:builder";
+    pub const BCOVRIN_TEST: &str = "bcovrin:test";
+    pub const IDUNION: &str = "idunion";
+    pub const IDUNION_TEST: &str = "idunion:test";
+    pub const INDICIO: &str = "indicio";
+    pub const INDICIO_TEST: &str = "indicio:test";
+}
+
+#[derive(Clone)]
+pub struct IndyNetworkConfig {
+    /// did:indy namespace of the network, e.g. `sovrin:staging`
+    pub namespace: String,
+    /// Refreshed pool transactions are written back to this file
+    pub genesis_file_path: String,
+    pub indy_vdr_config: Option<PoolConfig>,
+    pub response_cache_config: Option<InMemoryResponseCacherConfig>,
+    /// Whether legacy (unqualified and did:sov) identifiers may be looked up on this network.
+    /// Such networks are tried in the order they were added.
+    pub resolves_legacy_identifiers: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkStatus {
+    /// The network has not been checked yet
+    Unknown,
+    Healthy,
+    Unreachable(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkHealth {
+    pub namespace: String,
+    pub status: NetworkStatus,
+    /// Number of pool transactions (i.e. validator node entries) in use
+    pub pool_transactions: Option<usize>,
+    pub last_checked: Option<SystemTime>,
+    /// Last time the refresh found new pool transactions
+    pub last_updated: Option<SystemTime>,
+}
+
+impl NetworkHealth {
+    fn new(namespace: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            status: NetworkStatus::Unknown,
+            pool_transactions: None,
+            last_checked: None,
+            last_updated: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PoolConnection {
+    pool: IndyVdrLedgerPool,
+    ledger_write: DefaultIndyLedgerWrite,
+    genesis_file_path: String,
+}
+
+#[derive(Debug)]
+struct IndyNetwork<R> {
+    namespace: String,
+    resolves_legacy_identifiers: bool,
+    ledger_read: R,
+    connection: Option<PoolConnection>,
+    health: RwLock<NetworkHealth>,
+}
+
+/// Holds several named indy networks. Reads of did:indy DIDs and identifiers are routed to the
+/// network of their namespace, legacy identifiers are looked up on the networks which resolve
+/// legacy identifiers until one of them knows the object. The network an issuer DID was found
+/// on is remembered and tried first afterwards.
+///
+/// did:indy identifiers are sent to the ledger in their legacy form, so the objects returned
+/// carry legacy identifiers.
+#[derive(Debug)]
+pub struct IndyPoolManager<R = DefaultIndyLedgerRead> {
+    networks: Vec<IndyNetwork<R>>,
+    legacy_issuer_networks: RwLock<HashMap<String, String>>,
+}
+
+impl<R> Default for IndyPoolManager<R> {
+    fn default() -> Self {
+        Self {
+            networks: Vec::new(),
+            legacy_issuer_networks: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl IndyPoolManager {
+    /// Connects to every configured network
+    pub fn connect(configs: Vec<IndyNetworkConfig>) -> VcxLedgerResult<Self> {
+        let mut manager = Self::default();
+        for config in configs {
+            manager = manager.connect_network(config)?;
+        }
+        Ok(manager)
+    }
+
+    pub fn connect_network(mut self, config: IndyNetworkConfig) -> VcxLedgerResult<Self> {
+        info!(
+            "IndyPoolManager::connect_network >> namespace: {}, genesis_file_path: {}",
+            config.namespace, config.genesis_file_path
+        );
+        self.ensure_unique(&config.namespace)?;
+        let cache_config = match config.response_cache_config {
+            None => InMemoryResponseCacherConfig::builder()
+                .ttl(std::time::Duration::from_secs(60))
+                .capacity(1000)?
+                .build(),
+            Some(cfg) => cfg,
+        };
+        let pool = IndyVdrLedgerPool::new(
+            config.genesis_file_path.clone(),
+            config.indy_vdr_config.unwrap_or_default(),
+            vec![],
+        )?;
+        let request_submitter = IndyVdrSubmitter::new(pool.clone());
+        let ledger_read = indyvdr_build_ledger_read(request_submitter.clone(), cache_config)?;
+        let ledger_write = indyvdr_build_ledger_write(request_submitter, None);
+
+        self.networks.push(IndyNetwork {
+            health: RwLock::new(NetworkHealth::new(&config.namespace)),
+            namespace: config.namespace,
+            resolves_legacy_identifiers: config.resolves_legacy_identifiers,
+            ledger_read,
+            connection: Some(PoolConnection {
+                pool,
+                ledger_write,
+                genesis_file_path: config.genesis_file_path,
+            }),
+        });
+        Ok(self)
+    }
+
+    pub fn ledger_write(&self, namespace: &str) -> Option<&DefaultIndyLedgerWrite> {
+        self.network(namespace)?
+            .connection
+            .as_ref()
+            .map(|connection| &connection.ledger_write)
+    }
+}
+
+impl<R> IndyPoolManager<R> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a network served by `ledger_read` without a pool managed by this manager, e.g. a
+    /// proxied ledger. Such networks are not refreshed.
+    pub fn register_network(
+        mut self,
+        namespace: impl Into<String>,
+        ledger_read: R,
+        resolves_legacy_identifiers: bool,
+    ) -> VcxLedgerResult<Self> {
+        let namespace = namespace.into();
+        self.ensure_unique(&namespace)?;
+        self.networks.push(IndyNetwork {
+            health: RwLock::new(NetworkHealth::new(&namespace)),
+            namespace,
+            resolves_legacy_identifiers,
+            ledger_read,
+            connection: None,
+        });
+        Ok(self)
+    }
+
+    pub fn namespaces(&self) -> impl Iterator<Item = &str> {
+        self.networks
+            .iter()
+            .map(|network| network.namespace.as_str())
+    }
+
+    pub fn ledger_read(&self, namespace: &str) -> Option<&R> {
+        self.network(namespace).map(|network| &network.ledger_read)
+    }
+
+    pub fn health(&self) -> Vec<NetworkHealth> {
+        self.networks
+            .iter()
+            .map(|network| {
+                network
+                    .health
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone()
+            })
+            .collect()
+    }
+
+    pub fn network_health(&self, namespace: &str) -> Option<NetworkHealth> {
+        let network = self.network(namespace)?;
+        let health = network
+            .health
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        Some(health.clone())
+    }
+
+    /// Fetches the latest pool transactions of the network and persists them to its genesis file
+    /// if they changed. The outcome is reflected in the health of the network. Returns whether
+    /// the pool transactions changed.
+    pub async fn refresh(&self, namespace: &str) -> VcxLedgerResult<bool> {
+        let network = self.namespace_network(namespace)?;
+        let Some(connection) = &network.connection else {
+            return Ok(false);
+        };
+
+        let result = refresh_connection(connection).await;
+        network.record_refresh(&result)?;
+        result.map(|(updated, _)| updated)
+    }
+
+    /// Refreshes every network, see [IndyPoolManager::refresh]
+    pub async fn refresh_all(&self) -> Vec<NetworkHealth> {
+        for network in &self.networks {
+            // failures are recorded in the network health
+            self.refresh(&network.namespace).await.ok();
+        }
+        self.health()
+    }
+
+    fn ensure_unique(&self, namespace: &str) -> VcxLedgerResult<()> {
+        if self.network(namespace).is_some() {
+            return Err(VcxLedgerError::InvalidInput(format!(
+                "Indy network {namespace} is already registered"
+            )));
+        }
+        Ok(())
+    }
+
+    fn network(&self, namespace: &str) -> Option<&IndyNetwork<R>> {
+        self.networks
+            .iter()
+            .find(|network| network.namespace == namespace)
+    }
+
+    fn did_indy_network(&self, namespace: &str, id: &str) -> VcxLedgerResult<&IndyNetwork<R>> {
+        self.network(namespace)
+            .ok_or_else(|| VcxLedgerError::UnsupportedLedgerIdentifier(id.to_string()))
+    }
+
+    /// Networks resolving legacy identifiers, starting with the one `legacy_did` was found on
+    fn legacy_networks(&self, legacy_did: Option<&str>) -> Vec<&IndyNetwork<R>> {
+        let known_namespace = legacy_did.and_then(|did| {
+            self.legacy_issuer_networks
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(did)
+                .cloned()
+        });
+        let mut networks: Vec<_> = self
+            .networks
+            .iter()
+            .filter(|network| network.resolves_legacy_identifiers)
+            .collect();
+        if let Some(known_namespace) = known_namespace {
+            networks.sort_by_key(|network| network.namespace != known_namespace);
+        }
+        networks
+    }
+
+    fn remember_legacy_issuer(&self, legacy_did: &str, namespace: &str) {
+        self.legacy_issuer_networks
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(legacy_did.to_string(), namespace.to_string());
+    }
+
+    /// Tries `networks` in order until one of them finds the object, remembering the network
+    /// the issuer `legacy_did` was found on
+    async fn read_first_found<'a, T, F>(
+        &'a self,
+        id: &str,
+        networks: Vec<&'a IndyNetwork<R>>,
+        legacy_did: Option<&str>,
+        read: F,
+    ) -> VcxLedgerResult<T>
+    where
+        R: Sync,
+        T: Send,
+        F: Fn(&'a R) -> Pin<Box<dyn Future<Output = VcxLedgerResult<T>> + Send + 'a>> + Send,
+    {
+        if networks.is_empty() {
+            return Err(VcxLedgerError::UnsupportedLedgerIdentifier(id.to_string()));
+        }
+        for network in networks {
+            match read(&network.ledger_read).await {
+                Err(VcxLedgerError::LedgerItemNotFound) => continue,
+                Ok(value) => {
+                    if let Some(legacy_did) = legacy_did {
+                        self.remember_legacy_issuer(legacy_did, &network.namespace);
+                    }
+                    return Ok(value);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Err(VcxLedgerError::LedgerItemNotFound)
+    }
+}
+
+impl<R> IndyNetwork<R> {
+    /// Reflects the outcome of a refresh, i.e. whether the pool transactions changed and how many
+    /// there are, in the health of the network
+    fn record_refresh(&self, result: &VcxLedgerResult<(bool, usize)>) -> VcxLedgerResult<()> {
+        let mut health = self.health.write()?;
+        health.last_checked = Some(SystemTime::now());
+        match result {
+            Ok((updated, pool_transactions)) => {
+                health.status = NetworkStatus::Healthy;
+                health.pool_transactions = Some(*pool_transactions);
+                if *updated {
+                    health.last_updated = health.last_checked;
+                }
+            }
+            Err(err) => {
+                warn!("Failed to refresh indy network {}: {err}", self.namespace);
+                health.status = NetworkStatus::Unreachable(err.to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn refresh_connection(connection: &PoolConnection) -> VcxLedgerResult<(bool, usize)> {
+    let updated = connection.pool.refresh().await?;
+    let txns = connection.pool.transactions().await?;
+    if updated {
+        persist_genesis(&connection.genesis_file_path, &txns)?;
+    }
+    Ok((updated, txns.len()))
+}
+
+/// Replaces the genesis file through a rename, so that it is never left half written
+fn persist_genesis(genesis_file_path: &str, txns: &[String]) -> VcxLedgerResult<()> {
+    info!("persist_genesis >> genesis_file_path: {genesis_file_path}");
+    let tmp_path = format!("{genesis_file_path}.tmp");
+    fs::write(&tmp_path, txns.join("\n"))
+        .and_then(|_| fs::rename(&tmp_path, genesis_file_path))
+        .map_err(|err| {
+            VcxLedgerError::UnknownError(format!(
+                "Failed to persist pool transactions to {genesis_file_path}: {err}"
+            ))
+        })
+}
+
+/// The issuer DID a legacy object identifier starts with
+fn legacy_issuer(id: &str) -> Option<&str> {
+    let did = id.split(':').next()?;
+    IssuerId::new_unchecked(did).is_legacy().then_some(did)
+}
+
+#[async_trait]
+impl<R> AnoncredsLedgerRead for IndyPoolManager<R>
+where
+    R: AnoncredsLedgerRead + AnoncredsLedgerSupport,
+{
+    type RevocationRegistryDefinitionAdditionalMetadata =
+        R::RevocationRegistryDefinitionAdditionalMetadata;
+
+    async fn get_schema(
+        &self,
+        schema_id: &SchemaId,
+        submitter_did: Option<&Did>,
+    ) -> VcxLedgerResult<Schema> {
+        if let Some(namespace) = schema_id.did_indy_namespace() {
+            let network = self.did_indy_network(namespace, &schema_id.0)?;
+            let legacy_id = schema_id
+                .to_legacy()
+                .ok_or_else(|| VcxLedgerError::UnsupportedLedgerIdentifier(schema_id.0.clone()))?;
+            return network
+                .ledger_read
+                .get_schema(&legacy_id, submitter_did)
+                .await;
+        }
+        let legacy_did = legacy_issuer(&schema_id.0);
+        let networks = self
+            .legacy_networks(legacy_did)
+            .into_iter()
+            .filter(|network| network.ledger_read.supports_schema(schema_id))
+            .collect();
+        self.read_first_found(&schema_id.0, networks, legacy_did, |reader| {
+            Box::pin(reader.get_schema(schema_id, submitter_did))
+        })
+        .await
+    }
+
+    async fn get_cred_def(
+        &self,
+        cred_def_id: &CredentialDefinitionId,
+        submitter_did: Option<&Did>,
+    ) -> VcxLedgerResult<CredentialDefinition> {
+        if let Some(namespace) = cred_def_id.did_indy_namespace() {
+            let network = self.did_indy_network(namespace, &cred_def_id.0)?;
+            let legacy_id = cred_def_id.to_legacy().ok_or_else(|| {
+                VcxLedgerError::UnsupportedLedgerIdentifier(cred_def_id.0.clone())
+            })?;
+            return network
+                .ledger_read
+                .get_cred_def(&legacy_id, submitter_did)
+                .await;
+        }
+        let legacy_did = legacy_issuer(&cred_def_id.0);
+        let networks = self
+            .legacy_networks(legacy_did)
+            .into_iter()
+            .filter(|network| {
+                network
+                    .ledger_read
+                    .supports_credential_definition(cred_def_id)
+            })
+            .collect();
+        self.read_first_found(&cred_def_id.0, networks, legacy_did, |reader| {
+            Box::pin(reader.get_cred_def(cred_def_id, submitter_did))
+        })
+        .await
+    }
+
+    async fn get_rev_reg_def_json(
+        &self,
+        rev_reg_id: &RevocationRegistryDefinitionId,
+    ) -> VcxLedgerResult<(
+        RevocationRegistryDefinition,
+        Self::RevocationRegistryDefinitionAdditionalMetadata,
+    )> {
+        let (networks, rev_reg_id, legacy_did) = self.rev_reg_networks(rev_reg_id)?;
+        self.read_first_found(&rev_reg_id.0, networks, legacy_did, |reader| {
+            Box::pin(reader.get_rev_reg_def_json(&rev_reg_id))
+        })
+        .await
+    }
+
+    async fn get_rev_reg_delta_json(
+        &self,
+        rev_reg_id: &RevocationRegistryDefinitionId,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> VcxLedgerResult<(RevocationRegistryDelta, u64)> {
+        let (networks, rev_reg_id, legacy_did) = self.rev_reg_networks(rev_reg_id)?;
+        self.read_first_found(&rev_reg_id.0, networks, legacy_did, |reader| {
+            #[allow(deprecated)] // TODO - https://github.com/openwallet-foundation/vcx/issues/1309
+            Box::pin(reader.get_rev_reg_delta_json(&rev_reg_id, from, to))
+        })
+        .await
+    }
+
+    async fn get_rev_status_list(
+        &self,
+        rev_reg_id: &RevocationRegistryDefinitionId,
+        timestamp: u64,
+        rev_reg_def_meta: Option<&Self::RevocationRegistryDefinitionAdditionalMetadata>,
+    ) -> VcxLedgerResult<(RevocationStatusList, u64)> {
+        let (networks, rev_reg_id, legacy_did) = self.rev_reg_networks(rev_reg_id)?;
+        self.read_first_found(&rev_reg_id.0, networks, legacy_did, |reader| {
+            Box::pin(reader.get_rev_status_list(&rev_reg_id, timestamp, rev_reg_def_meta))
+        })
+        .await
+    }
+
+    async fn get_rev_reg(
+        &self,
+        rev_reg_id: &RevocationRegistryDefinitionId,
+        timestamp: u64,
+    ) -> VcxLedgerResult<(RevocationRegistry, u64)> {
+        let (networks, rev_reg_id, legacy_did) = self.rev_reg_networks(rev_reg_id)?;
+        self.read_first_found(&rev_reg_id.0, networks, legacy_did, |reader| {
+            Box::pin(reader.get_rev_reg(&rev_reg_id, timestamp))
+        })
+        .await
+    }
+}
+
+impl<R> IndyPoolManager<R>
+where
+    R: AnoncredsLedgerSupport,
+{
+    /// Networks to look revocation registry `rev_reg_id` up on, with the identifier to use there
+    #[allow(clippy::type_complexity)]
+    fn rev_reg_networks<'a>(
+        &self,
+        rev_reg_id: &'a RevocationRegistryDefinitionId,
+    ) -> VcxLedgerResult<(
+        Vec<&IndyNetwork<R>>,
+        RevocationRegistryDefinitionId,
+        Option<&'a str>,
+    )> {
+        if let Some(namespace) = rev_reg_id.did_indy_namespace() {
+            let network = self.did_indy_network(namespace, &rev_reg_id.0)?;
+            let legacy_id = rev_reg_id
+                .to_legacy()
+                .ok_or_else(|| VcxLedgerError::UnsupportedLedgerIdentifier(rev_reg_id.0.clone()))?;
+            return Ok((vec![network], legacy_id, None));
+        }
+        let legacy_did = legacy_issuer(&rev_reg_id.0);
+        let networks = self
+            .legacy_networks(legacy_did)
+            .into_iter()
+            .filter(|network| network.ledger_read.supports_revocation_registry(rev_reg_id))
+            .collect();
+        Ok((networks, rev_reg_id.clone(), legacy_did))
+    }
+}
+
+impl<R> AnoncredsLedgerSupport for IndyPoolManager<R>
+where
+    R: AnoncredsLedgerSupport,
+{
+    fn supports_schema(&self, id: &SchemaId) -> bool {
+        match id.did_indy_namespace() {
+            Some(namespace) => self.network(namespace).is_some(),
+            None => self
+                .legacy_networks(None)
+                .iter()
+                .any(|network| network.ledger_read.supports_schema(id)),
+        }
+    }
+
+    fn supports_credential_definition(&self, id: &CredentialDefinitionId) -> bool {
+        match id.did_indy_namespace() {
+            Some(namespace) => self.network(namespace).is_some(),
+            None => self
+                .legacy_networks(None)
+                .iter()
+                .any(|network| network.ledger_read.supports_credential_definition(id)),
+        }
+    }
+
+    fn supports_revocation_registry(&self, id: &RevocationRegistryDefinitionId) -> bool {
+        match id.did_indy_namespace() {
+            Some(namespace) => self.network(namespace).is_some(),
+            None => self
+                .legacy_networks(None)
+                .iter()
+                .any(|network| network.ledger_read.supports_revocation_registry(id)),
+        }
+    }
+}
+
+impl<R> IndyPoolManager<R> {
+    /// Network to read `did` from, with the DID as the ledger knows it. Legacy DIDs are read from
+    /// the network they were last found on, or the first network resolving legacy identifiers.
+    fn did_network(&self, did: &Did) -> VcxLedgerResult<(&IndyNetwork<R>, Did)> {
+        if did.method() == Some("indy") {
+            let namespace = did.namespace().unwrap_or_default();
+            let network = self.did_indy_network(namespace, did.did())?;
+            return Ok((network, ledger_did(did)?));
+        }
+        let network = self
+            .legacy_networks(Some(did.id()))
+            .into_iter()
+            .next()
+            .ok_or_else(|| VcxLedgerError::UnsupportedLedgerIdentifier(did.to_string()))?;
+        Ok((network, did.clone()))
+    }
+
+    fn namespace_network(&self, namespace: &str) -> VcxLedgerResult<&IndyNetwork<R>> {
+        self.network(namespace).ok_or_else(|| {
+            VcxLedgerError::InvalidInput(format!("Unknown indy network: {namespace}"))
+        })
+    }
+
+    fn default_network(&self) -> VcxLedgerResult<&IndyNetwork<R>> {
+        self.legacy_networks(None)
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                VcxLedgerError::InvalidState(
+                    "No indy network resolving legacy identifiers is registered".to_string(),
+                )
+            })
+    }
+}
+
+impl<R> IndyPoolManager<R>
+where
+    R: IndyLedgerRead,
+{
+    /// Reads the transaction author agreement of the network of `namespace`
+    pub async fn get_txn_author_agreement_in(
+        &self,
+        namespace: &str,
+    ) -> VcxLedgerResult<Option<String>> {
+        self.namespace_network(namespace)?
+            .ledger_read
+            .get_txn_author_agreement()
+            .await
+    }
+
+    /// Reads the transaction with sequence number `seq_no` from the network of `namespace`
+    pub async fn get_ledger_txn_in(
+        &self,
+        namespace: &str,
+        seq_no: i32,
+        submitter_did: Option<&Did>,
+    ) -> VcxLedgerResult<String> {
+        let submitter_did = submitter_did.map(ledger_did).transpose()?;
+        self.namespace_network(namespace)?
+            .ledger_read
+            .get_ledger_txn(seq_no, submitter_did.as_ref())
+            .await
+    }
+}
+
+/// `did` as the ledger knows it, i.e. did:indy DIDs in their legacy form
+fn ledger_did(did: &Did) -> VcxLedgerResult<Did> {
+    if did.method() == Some("indy") {
+        return Ok(Did::parse(did.id().to_string())?);
+    }
+    Ok(did.clone())
+}
+
+/// DID reads are routed by [IndyPoolManager::did_network]. Ledger transactions are read from the
+/// network of the submitter DID, reads without a DID go to the first network resolving legacy
+/// identifiers. Use [IndyPoolManager::get_txn_author_agreement_in] and
+/// [IndyPoolManager::get_ledger_txn_in] to read from a specific network.
+#[async_trait]
+impl<R> IndyLedgerRead for IndyPoolManager<R>
+where
+    R: IndyLedgerRead,
+{
+    async fn get_attr(&self, target_did: &Did, attr_name: &str) -> VcxLedgerResult<String> {
+        let (network, did) = self.did_network(target_did)?;
+        network.ledger_read.get_attr(&did, attr_name).await
+    }
+
+    async fn get_nym(&self, did: &Did) -> VcxLedgerResult<String> {
+        let (network, did) = self.did_network(did)?;
+        network.ledger_read.get_nym(&did).await
+    }
+
+    async fn get_nym_version(
+        &self,
+        did: &Did,
+        seq_no: Option<i32>,
+        timestamp: Option<u64>,
+    ) -> VcxLedgerResult<String> {
+        let (network, did) = self.did_network(did)?;
+        network
+            .ledger_read
+            .get_nym_version(&did, seq_no, timestamp)
+            .await
+    }
+
+    async fn get_txn_author_agreement(&self) -> VcxLedgerResult<Option<String>> {
+        self.default_network()?
+            .ledger_read
+            .get_txn_author_agreement()
+            .await
+    }
+
+    async fn get_ledger_txn(
+        &self,
+        seq_no: i32,
+        submitter_did: Option<&Did>,
+    ) -> VcxLedgerResult<String> {
+        match submitter_did {
+            Some(submitter_did) => {
+                let (network, did) = self.did_network(submitter_did)?;
+                network.ledger_read.get_ledger_txn(seq_no, Some(&did)).await
+            }
+            None => {
+                self.default_network()?
+                    .ledger_read
+                    .get_ledger_txn(seq_no, None)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    mock! {
+        #[derive(Debug)]
+        pub Reader {}
+        #[async_trait]
+        impl AnoncredsLedgerRead for Reader {
+            type RevocationRegistryDefinitionAdditionalMetadata = Value;
+
+            // NOTE: these method signatures were generated as a result of the expanded #[async_trait] form.
+            //  this was needed to escape some #[async_trait] compiling issues
+            fn get_schema<'life0,'life1,'life2,'async_trait>(&'life0 self,schema_id: &'life1 SchemaId,submitter_did:Option< &'life2 Did> ,) ->  ::core::pin::Pin<Box<dyn ::core::future::Future<Output = VcxLedgerResult<Schema> > + ::core::marker::Send+'async_trait> >where 'life0:'async_trait,'life1:'async_trait,'life2:'async_trait,Self:'async_trait;
+            fn get_cred_def<'life0,'life1,'life2,'async_trait>(&'life0 self,cred_def_id: &'life1 CredentialDefinitionId,submitter_did:Option< &'life2 Did> ,) ->  ::core::pin::Pin<Box<dyn ::core::future::Future<Output = VcxLedgerResult<CredentialDefinition> > + ::core::marker::Send+'async_trait> >where 'life0:'async_trait,'life1:'async_trait,'life2:'async_trait,Self:'async_trait;
+            async fn get_rev_reg_def_json(&self, rev_reg_id: &RevocationRegistryDefinitionId) -> VcxLedgerResult<(RevocationRegistryDefinition, Value)>;
+            async fn get_rev_reg_delta_json(&self, rev_reg_id: &RevocationRegistryDefinitionId, from: Option<u64>, to: Option<u64>) -> VcxLedgerResult<(RevocationRegistryDelta, u64)>;
+            #[allow(clippy::type_complexity)] // generated
+            fn get_rev_status_list<'life0,'life1,'life2,'async_trait>(&'life0 self,rev_reg_id: &'life1 RevocationRegistryDefinitionId,timestamp:u64,rev_reg_def_meta:Option< &'life2 Value>) ->  ::core::pin::Pin<Box<dyn ::core::future::Future<Output = VcxLedgerResult<(RevocationStatusList,u64)> > + ::core::marker::Send+'async_trait> >where 'life0:'async_trait,'life1:'async_trait,'life2:'async_trait,Self:'async_trait;
+            async fn get_rev_reg(&self, rev_reg_id: &RevocationRegistryDefinitionId, timestamp: u64) -> VcxLedgerResult<(RevocationRegistry, u64)>;
+        }
+        impl AnoncredsLedgerSupport for Reader {
+            fn supports_schema(&self, id: &SchemaId) -> bool;
+            fn supports_credential_definition(&self, id: &CredentialDefinitionId) -> bool;
+            fn supports_revocation_registry(&self, id: &RevocationRegistryDefinitionId) -> bool;
+        }
+        #[async_trait]
+        impl IndyLedgerRead for Reader {
+            async fn get_attr(&self, target_did: &Did, attr_name: &str) -> VcxLedgerResult<String>;
+            async fn get_nym(&self, did: &Did) -> VcxLedgerResult<String>;
+            async fn get_nym_version(&self, did: &Did, seq_no: Option<i32>, timestamp: Option<u64>) -> VcxLedgerResult<String>;
+            async fn get_txn_author_agreement(&self) -> VcxLedgerResult<Option<String>>;
+            fn get_ledger_txn<'life0,'life1,'async_trait>(&'life0 self,seq_no:i32,submitter_did:Option< &'life1 Did> ,) ->  ::core::pin::Pin<Box<dyn ::core::future::Future<Output = VcxLedgerResult<String> > + ::core::marker::Send+'async_trait> >where 'life0:'async_trait,'life1:'async_trait,Self:'async_trait;
+        }
+    }
+
+    const LEGACY_SCHEMA_ID: &str = "2hoqvcwupRTUNkXn6ArYzs:2:test-licence:4.4.4";
+
+    fn schema() -> Schema {
+        serde_json::from_value(json!({
+            "id": LEGACY_SCHEMA_ID,
+            "issuerId": "2hoqvcwupRTUNkXn6ArYzs",
+            "name": "test-licence",
+            "version": "4.4.4",
+            "attrNames": ["name", "age", "vmax"]
+        }))
+        .unwrap()
+    }
+
+    fn reader_returning(schema: Option<Schema>, times: usize) -> MockReader {
+        let mut reader = MockReader::new();
+        reader.expect_supports_schema().return_const(true);
+        reader
+            .expect_get_schema()
+            .times(times)
+            .withf(|id, _| id.0 == LEGACY_SCHEMA_ID)
+            .returning(move |_, _| {
+                let schema = schema.clone();
+                Box::pin(async move { schema.ok_or(VcxLedgerError::LedgerItemNotFound) })
+            });
+        reader
+    }
+
+    #[tokio::test]
+    async fn test_get_did_indy_schema_from_namespace_network() {
+        let manager = IndyPoolManager::new()
+            .register_network("sovrin", MockReader::new(), true)
+            .unwrap()
+            .register_network("sovrin:staging", reader_returning(Some(schema()), 1), false)
+            .unwrap();
+
+        let id = SchemaId::new_unchecked(
+            "did:indy:sovrin:staging:2hoqvcwupRTUNkXn6ArYzs/anoncreds/v0/SCHEMA/test-licence/4.4.4",
+        );
+        assert!(manager.supports_schema(&id));
+        assert_eq!(manager.get_schema(&id, None).await.unwrap(), schema());
+
+        let unknown_namespace = SchemaId::new_unchecked(
+            "did:indy:idunion:2hoqvcwupRTUNkXn6ArYzs/anoncreds/v0/SCHEMA/test-licence/4.4.4",
+        );
+        assert!(!manager.supports_schema(&unknown_namespace));
+        assert!(matches!(
+            manager.get_schema(&unknown_namespace, None).await,
+            Err(VcxLedgerError::UnsupportedLedgerIdentifier(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_legacy_schema_falls_back_and_remembers_network() {
+        // the first lookup misses on "sovrin", the second one goes straight to "bcovrin:test"
+        let manager = IndyPoolManager::new()
+            .register_network("sovrin", reader_returning(None, 1), true)
+            .unwrap()
+            .register_network("bcovrin:test", reader_returning(Some(schema()), 2), true)
+            .unwrap()
+            .register_network("idunion", MockReader::new(), false)
+            .unwrap();
+
+        let id = SchemaId::new_unchecked(LEGACY_SCHEMA_ID);
+        assert_eq!(manager.get_schema(&id, None).await.unwrap(), schema());
+        assert_eq!(manager.get_schema(&id, None).await.unwrap(), schema());
+    }
+
+    #[tokio::test]
+    async fn test_get_legacy_schema_not_found_on_any_network() {
+        let manager = IndyPoolManager::new()
+            .register_network("sovrin", reader_returning(None, 1), true)
+            .unwrap()
+            .register_network("bcovrin:test", reader_returning(None, 1), true)
+            .unwrap();
+
+        let id = SchemaId::new_unchecked(LEGACY_SCHEMA_ID);
+        assert!(matches!(
+            manager.get_schema(&id, None).await,
+            Err(VcxLedgerError::LedgerItemNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unmanaged_network_health() {
+        let duplicate = IndyPoolManager::new()
+            .register_network("sovrin", MockReader::new(), true)
+            .unwrap()
+            .register_network("sovrin", MockReader::new(), true);
+        assert!(duplicate.is_err());
+
+        let manager = IndyPoolManager::new()
+            .register_network("sovrin", MockReader::new(), true)
+            .unwrap();
+        assert!(!manager.refresh("sovrin").await.unwrap());
+        assert!(manager.refresh("idunion").await.is_err());
+        assert_eq!(
+            manager.health(),
+            vec![NetworkHealth {
+                namespace: "sovrin".to_string(),
+                status: NetworkStatus::Unknown,
+                pool_transactions: None,
+                last_checked: None,
+                last_updated: None,
+            }]
+        );
+    }
+
+    fn txn_reader(
+        seq_no: i32,
+        submitter_did: Option<&'static str>,
+        txn: &'static str,
+    ) -> MockReader {
+        let mut reader = MockReader::new();
+        reader
+            .expect_get_ledger_txn()
+            .times(1)
+            .withf(move |no, did| {
+                *no == seq_no && did.map(|did| did.to_string()).as_deref() == submitter_did
+            })
+            .returning(move |_, _| Box::pin(async move { Ok(txn.to_string()) }));
+        reader
+    }
+
+    #[tokio::test]
+    async fn test_get_ledger_txn_routed_by_submitter_did() {
+        let manager = IndyPoolManager::new()
+            .register_network("sovrin", txn_reader(7, None, "sovrin txn"), true)
+            .unwrap()
+            .register_network(
+                "sovrin:staging",
+                txn_reader(7, Some("2hoqvcwupRTUNkXn6ArYzs"), "staging txn"),
+                false,
+            )
+            .unwrap();
+
+        let submitter =
+            Did::parse("did:indy:sovrin:staging:2hoqvcwupRTUNkXn6ArYzs".to_string()).unwrap();
+        assert_eq!(
+            manager.get_ledger_txn(7, Some(&submitter)).await.unwrap(),
+            "staging txn"
+        );
+        assert_eq!(manager.get_ledger_txn(7, None).await.unwrap(), "sovrin txn");
+
+        let unknown_namespace =
+            Did::parse("did:indy:idunion:2hoqvcwupRTUNkXn6ArYzs".to_string()).unwrap();
+        assert!(matches!(
+            manager.get_ledger_txn(7, Some(&unknown_namespace)).await,
+            Err(VcxLedgerError::UnsupportedLedgerIdentifier(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reads_from_namespace_network() {
+        let mut staging = txn_reader(3, None, "staging txn");
+        staging
+            .expect_get_txn_author_agreement()
+            .times(1)
+            .returning(|| Ok(Some("staging taa".to_string())));
+        let mut sovrin = MockReader::new();
+        sovrin
+            .expect_get_txn_author_agreement()
+            .times(1)
+            .returning(|| Ok(Some("sovrin taa".to_string())));
+        let manager = IndyPoolManager::new()
+            .register_network("sovrin", sovrin, true)
+            .unwrap()
+            .register_network("sovrin:staging", staging, false)
+            .unwrap();
+
+        assert_eq!(
+            manager
+                .get_txn_author_agreement_in("sovrin:staging")
+                .await
+                .unwrap(),
+            Some("staging taa".to_string())
+        );
+        assert_eq!(
+            manager.get_txn_author_agreement().await.unwrap(),
+            Some("sovrin taa".to_string())
+        );
+        assert_eq!(
+            manager
+                .get_ledger_txn_in("sovrin:staging", 3, None)
+                .await
+                .unwrap(),
+            "staging txn"
+        );
+        assert!(matches!(
+            manager.get_txn_author_agreement_in("idunion").await,
+            Err(VcxLedgerError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_record_refresh_updates_health() {
+        let manager = IndyPoolManager::new()
+            .register_network("sovrin", MockReader::new(), true)
+            .unwrap();
+        let network = manager.network("sovrin").unwrap();
+
+        network.record_refresh(&Ok((false, 4))).unwrap();
+        let health = manager.network_health("sovrin").unwrap();
+        assert_eq!(health.status, NetworkStatus::Healthy);
+        assert_eq!(health.pool_transactions, Some(4));
+        assert!(health.last_checked.is_some());
+        assert_eq!(health.last_updated, None);
+
+        network.record_refresh(&Ok((true, 5))).unwrap();
+        let health = manager.network_health("sovrin").unwrap();
+        assert_eq!(health.pool_transactions, Some(5));
+        assert_eq!(health.last_updated, health.last_checked);
+
+        network
+            .record_refresh(&Err(VcxLedgerError::InvalidState(
+                "pool timeout".to_string(),
+            )))
+            .unwrap();
+        let health = manager.network_health("sovrin").unwrap();
+        assert!(matches!(health.status, NetworkStatus::Unreachable(_)));
+        // the last known pool transactions are kept
+        assert_eq!(health.pool_transactions, Some(5));
+    }
+
+    #[test]
+    fn test_persist_genesis_replaces_file() {
+        let path = std::env::temp_dir().join(format!("genesis-{}.txn", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        fs::write(path, "old txn").unwrap();
+
+        let txns = vec!["{\"txn\":1}".to_string(), "{\"txn\":2}".to_string()];
+        persist_genesis(path, &txns).unwrap();
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "{\"txn\":1}\n{\"txn\":2}"
+        );
+        assert!(!std::path::Path::new(&format!("{path}.tmp")).exists());
+        fs::remove_file(path).unwrap();
+
+        let missing_dir = std::env::temp_dir()
+            .join(uuid::Uuid::new_v4().to_string())
+            .join("genesis.txn");
+        assert!(matches!(
+            persist_genesis(missing_dir.to_str().unwrap(), &txns),
+            Err(VcxLedgerError::UnknownError(_))
+        ));
+    }
+}
@@ -33,7 +33,7 @@ use indy_vdr::{
     },
     utils::{did::DidValue, Validatable},
 };
-use log::{debug, trace};
+use log::{debug, trace, warn};
 use public_key::Key;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -67,7 +67,7 @@ use super::{
     },
     response_cacher::{
         in_memory::{InMemoryResponseCacher, InMemoryResponseCacherConfig},
-        ResponseCacher,
+        LedgerObjectType, ResponseCacher,
     },
 };
 use crate::{
@@ -113,6 +113,7 @@ where
     response_parser: ResponseParser,
     response_cacher: V,
     protocol_version: ProtocolVersion,
+    cache_ledger_objects: bool,
 }
 
 pub struct IndyVdrLedgerWrite<T>
@@ -135,13 +136,28 @@ where
             response_parser: config.response_parser,
             response_cacher: config.response_cacher,
             protocol_version: config.protocol_version,
+            cache_ledger_objects: false,
         }
     }
 
+    /// Enables caching of schema and credential definition responses, which are not cached by
+    /// default. Both are immutable once written to the ledger, but a cached response hides a
+    /// credential definition written after it was looked up until the entry expires.
+    pub fn with_ledger_object_caching(mut self) -> Self {
+        self.cache_ledger_objects = true;
+        self
+    }
+
     pub fn request_builder(&self) -> VcxLedgerResult<RequestBuilder> {
         Ok(RequestBuilder::new(self.protocol_version))
     }
 
+    /// Id to cache the response for the ledger object `id` under, if ledger object caching is
+    /// enabled
+    fn ledger_object_cache_id(&self, id: impl ToString) -> Option<String> {
+        self.cache_ledger_objects.then(|| id.to_string())
+    }
+
     async fn submit_request(
         &self,
         cache_id: Option<&str>,
@@ -163,7 +179,9 @@ where
                          cached."
                     );
                     let response = self.request_submitter.submit(request).await?;
-                    self.response_cacher.put(cache_id, response.clone()).await?;
+                    if is_cacheable_response(&response) {
+                        self.response_cacher.put(cache_id, response.clone()).await?;
+                    }
                     (response, false)
                 }
             },
@@ -177,6 +195,40 @@ where
         Ok(response)
     }
 
+    /// Fetches the schemas, credential definitions and revocation registry definitions with the
+    /// given ids, populating the response cache. Ids which fail to resolve are skipped, returns
+    /// the number of ids which were resolved. Schemas and credential definitions are only cached
+    /// with ledger object caching enabled, see [IndyVdrLedgerRead::with_ledger_object_caching].
+    pub async fn warm_cache(&self, ids: &[String]) -> VcxLedgerResult<usize> {
+        let mut warmed = 0;
+        for id in ids {
+            let result = match LedgerObjectType::from_cache_id(id) {
+                LedgerObjectType::Schema => self
+                    .get_schema(&SchemaId::new_unchecked(id.as_str()), None)
+                    .await
+                    .map(|_| ()),
+                LedgerObjectType::CredentialDefinition => self
+                    .get_cred_def(&CredentialDefinitionId::new_unchecked(id.as_str()), None)
+                    .await
+                    .map(|_| ()),
+                LedgerObjectType::RevocationRegistryDefinition => self
+                    .get_rev_reg_def_json(&RevocationRegistryDefinitionId::new_unchecked(
+                        id.as_str(),
+                    ))
+                    .await
+                    .map(|_| ()),
+                LedgerObjectType::Other => Err(VcxLedgerError::InvalidInput(format!(
+                    "Unable to warm response cache, unsupported ledger object id: {id}"
+                ))),
+            };
+            match result {
+                Ok(()) => warmed += 1,
+                Err(err) => warn!("warm_cache >> failed to fetch {id}: {err}"),
+            }
+        }
+        Ok(warmed)
+    }
+
     async fn resolve_schema_id_from_seq_no(
         &self,
         seq_no: i32,
@@ -326,6 +378,24 @@ where
         Ok(response)
     }
 
+    async fn get_nym_version(
+        &self,
+        did: &Did,
+        seq_no: Option<i32>,
+        timestamp: Option<u64>,
+    ) -> VcxLedgerResult<String> {
+        debug!("get_nym_version >> did: {did}, seq_no: {seq_no:?}, timestamp: {timestamp:?}");
+        let request = self.request_builder()?.build_get_nym_request(
+            None,
+            &did.convert(())?,
+            seq_no,
+            timestamp,
+        )?;
+        let response = self.submit_request(None, request).await?;
+        debug!("get_nym_version << response: {response}");
+        Ok(response)
+    }
+
     async fn get_txn_author_agreement(&self) -> VcxLedgerResult<Option<String>> {
         debug!("get_txn_author_agreement >>");
         let request = self
@@ -519,7 +589,8 @@ where
         let request = self
             .request_builder()?
             .build_get_schema_request(None, &schema_id.convert(())?)?;
-        let response = self.submit_request(None, request).await?;
+        let cache_id = self.ledger_object_cache_id(schema_id);
+        let response = self.submit_request(cache_id.as_deref(), request).await?;
         debug!("get_schema << response: {response}");
         let schema = self
             .response_parser
@@ -539,12 +610,10 @@ where
             .request_builder()?
             .build_get_cred_def_request(identifier.as_ref(), &id)?;
         // note: Before we try to create credential definition, we are checking if it already
-        //       doesn't exist on the ledger to prevent invalidating the old one.
-        //       When we make the first request, it typically doesn't exist, but we don't want to
-        //       cache such as result. So caching strategy should perhaps only store data in cache
-        //       if ledger response was found / the response is success.
-        //       Therefore parsing should happen prior to caching.
-        let response = self.submit_request(None, request).await?;
+        //       doesn't exist on the ledger to prevent invalidating the old one. Responses for
+        //       objects not found on the ledger are not cached, see `is_cacheable_response`.
+        let cache_id = self.ledger_object_cache_id(cred_def_id);
+        let response = self.submit_request(cache_id.as_deref(), request).await?;
         debug!("get_cred_def << response: {response}");
         let cred_def = self
             .response_parser
@@ -719,6 +788,17 @@ fn did_method_is_supported(id: &str) -> bool {
     is_sov || is_unqualified
 }
 
+/// Only successful replies for objects found on the ledger are cached, i.e. replies carrying a
+/// transaction sequence number (`result.seqNo` in the node 1.4 reply format, otherwise
+/// `result.txnMetadata.seqNo`).
+fn is_cacheable_response(response: &str) -> bool {
+    let Ok(response) = serde_json::from_str::<Value>(response) else {
+        return false;
+    };
+    let result = &response["result"];
+    !result["seqNo"].is_null() || !result["txnMetadata"]["seqNo"].is_null()
+}
+
 #[async_trait]
 impl<T> AnoncredsLedgerWrite for IndyVdrLedgerWrite<T>
 where
@@ -887,6 +967,81 @@ mod unit_tests {
         })
     }
 
+    const SCHEMA_ID: &str = "7Tqg6BwSSWapxgUDm9KKgg:2:test_schema:1.0";
+
+    fn caching_indy_vdr_reader(
+        submissions: usize,
+    ) -> IndyVdrLedgerRead<MockRequestSubmitter, InMemoryResponseCacher> {
+        let mut request_submitter = MockRequestSubmitter::new();
+        request_submitter
+            .expect_submit()
+            .times(submissions)
+            .returning(|_| {
+                Ok(json!({
+                    "op": "REPLY",
+                    "result": {
+                        "type": "107",
+                        "seqNo": 16,
+                        "dest": "7Tqg6BwSSWapxgUDm9KKgg",
+                        "data": {"name": "test_schema", "version": "1.0", "attr_names": ["name"]}
+                    }
+                })
+                .to_string())
+            });
+        let cache_config = InMemoryResponseCacherConfig::builder()
+            .ttl(std::time::Duration::from_secs(60))
+            .capacity(10)
+            .unwrap()
+            .build();
+        IndyVdrLedgerRead::new(IndyVdrLedgerReadConfig {
+            request_submitter,
+            response_parser: indy_ledger_response_parser::ResponseParser,
+            response_cacher: InMemoryResponseCacher::new(cache_config),
+            protocol_version: ProtocolVersion::Node1_4,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_schema_responses_are_not_cached_by_default() {
+        let reader = caching_indy_vdr_reader(2);
+        let schema_id = SchemaId::new_unchecked(SCHEMA_ID);
+
+        reader.get_schema(&schema_id, None).await.unwrap();
+        let schema = reader.get_schema(&schema_id, None).await.unwrap();
+        assert_eq!(schema.name, "test_schema");
+    }
+
+    #[tokio::test]
+    async fn test_schema_responses_are_cached_with_ledger_object_caching() {
+        let reader = caching_indy_vdr_reader(1).with_ledger_object_caching();
+        let schema_id = SchemaId::new_unchecked(SCHEMA_ID);
+
+        reader.get_schema(&schema_id, None).await.unwrap();
+        let schema = reader.get_schema(&schema_id, None).await.unwrap();
+        assert_eq!(schema.name, "test_schema");
+        assert_eq!(
+            reader.warm_cache(&[SCHEMA_ID.to_string()]).await.unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_is_cacheable_response() {
+        assert!(is_cacheable_response(
+            &json!({"op": "REPLY", "result": {"seqNo": 16, "data": {}}}).to_string()
+        ));
+        assert!(is_cacheable_response(
+            &json!({"op": "REPLY", "result": {"txnMetadata": {"seqNo": 16}}}).to_string()
+        ));
+        assert!(!is_cacheable_response(
+            &json!({"op": "REPLY", "result": {"seqNo": null, "data": null}}).to_string()
+        ));
+        assert!(!is_cacheable_response(
+            &json!({"op": "REQNACK", "reason": "invalid request"}).to_string()
+        ));
+        assert!(!is_cacheable_response("not json"));
+    }
+
     #[test]
     fn test_anoncreds_schema_support() {
         let reader = dummy_indy_vdr_reader();
@@ -28,6 +28,8 @@ use crate::errors::error::{VcxLedgerError, VcxLedgerResult};
 // * fallback/chain system - try A resolver, if it fails, try B resolver
 // Alternatively these enhancements can be skipped if qualified DIDs/objects are used instead,
 // e.g. did:indy:a:123, did:indy:b:123
+// For indy networks, [crate::ledger::indy::pool_manager::IndyPoolManager] implements the
+// namespace routing and fallback described above, and can be registered here as a single reader.
 
 /// Struct to aggregate multiple [AnoncredsLedgerRead] implementations into a single
 /// [AnoncredsLedgerRead]. The child [AnoncredsLedgerRead] implementations are
@@ -56,6 +56,35 @@ impl IndyVdrLedgerPool {
             runner: Arc::new(runner),
         })
     }
+
+    /// Fetches the latest pool transactions from the validators, switching over to the updated
+    /// validator set if it changed. Returns whether new transactions were found.
+    pub async fn refresh(&self) -> VcxLedgerResult<bool> {
+        let (sender, recv) = oneshot::channel();
+        self.runner.refresh(Box::new(move |result| {
+            // unable to handle a failure from `send` here
+            sender.send(result.map(|(txns, _)| txns.is_some())).ok();
+        }))?;
+
+        let refreshed = recv
+            .await
+            .map_err(|e| VcxLedgerError::InvalidState(e.to_string()))??;
+        Ok(refreshed)
+    }
+
+    /// Returns the pool transactions currently in use, in the genesis file format
+    pub async fn transactions(&self) -> VcxLedgerResult<Vec<String>> {
+        let (sender, recv) = oneshot::channel();
+        self.runner.get_transactions(Box::new(move |result| {
+            // unable to handle a failure from `send` here
+            sender.send(result).ok();
+        }))?;
+
+        let txns = recv
+            .await
+            .map_err(|e| VcxLedgerError::InvalidState(e.to_string()))??;
+        Ok(txns)
+    }
 }
 
 impl Debug for IndyVdrLedgerPool {
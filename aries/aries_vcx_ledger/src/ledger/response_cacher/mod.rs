@@ -1,5 +1,6 @@
 pub mod in_memory;
 pub mod noop;
+pub mod persistent;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -20,3 +21,82 @@ pub trait ResponseCacher: Send + Sync {
         S: ToString + Send,
         T: Serialize + for<'de> Deserialize<'de> + Send;
 }
+
+/// Kind of ledger object a response cache entry holds, derived from the identifier it is cached
+/// under. Allows cachers to treat immutable objects differently from mutable ledger state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LedgerObjectType {
+    Schema,
+    CredentialDefinition,
+    RevocationRegistryDefinition,
+    Other,
+}
+
+impl LedgerObjectType {
+    /// Classifies a cache id, supporting both legacy indy and did:indy anoncreds identifiers.
+    pub fn from_cache_id(id: &str) -> Self {
+        if (id.contains(":4:") && id.contains(":CL_ACCUM:")) || id.contains("/REV_REG_DEF/") {
+            Self::RevocationRegistryDefinition
+        } else if id.contains(":3:CL:") || id.contains("/CLAIM_DEF/") {
+            Self::CredentialDefinition
+        } else if id.contains(":2:") || id.contains("/SCHEMA/") {
+            Self::Schema
+        } else {
+            Self::Other
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Schema => "schema",
+            Self::CredentialDefinition => "cred_def",
+            Self::RevocationRegistryDefinition => "rev_reg_def",
+            Self::Other => "other",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LedgerObjectType;
+
+    #[test]
+    fn test_ledger_object_type_from_cache_id() {
+        assert_eq!(
+            LedgerObjectType::from_cache_id("7Tqg6BwSSWapxgUDm9KKgg:2:test_schema:1.0"),
+            LedgerObjectType::Schema
+        );
+        assert_eq!(
+            LedgerObjectType::from_cache_id("7Tqg6BwSSWapxgUDm9KKgg:3:CL:16:tag"),
+            LedgerObjectType::CredentialDefinition
+        );
+        assert_eq!(
+            LedgerObjectType::from_cache_id(
+                "7Tqg6BwSSWapxgUDm9KKgg:4:7Tqg6BwSSWapxgUDm9KKgg:3:CL:16:tag:CL_ACCUM:rev_tag"
+            ),
+            LedgerObjectType::RevocationRegistryDefinition
+        );
+        assert_eq!(
+            LedgerObjectType::from_cache_id(
+                "did:indy:sovrin:7Tqg6BwSSWapxgUDm9KKgg/anoncreds/v0/SCHEMA/test_schema/1.0"
+            ),
+            LedgerObjectType::Schema
+        );
+        assert_eq!(
+            LedgerObjectType::from_cache_id(
+                "did:indy:sovrin:7Tqg6BwSSWapxgUDm9KKgg/anoncreds/v0/CLAIM_DEF/16/tag"
+            ),
+            LedgerObjectType::CredentialDefinition
+        );
+        assert_eq!(
+            LedgerObjectType::from_cache_id(
+                "did:indy:sovrin:7Tqg6BwSSWapxgUDm9KKgg/anoncreds/v0/REV_REG_DEF/16/tag/rev_tag"
+            ),
+            LedgerObjectType::RevocationRegistryDefinition
+        );
+        assert_eq!(
+            LedgerObjectType::from_cache_id("7Tqg6BwSSWapxgUDm9KKgg"),
+            LedgerObjectType::Other
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::ledger::response_cacher::LedgerObjectType;
+
+/// Expiration policy of [`super::PersistentResponseCacher`]. A TTL of `None` means entries of the
+/// given object type never expire.
+///
+/// By default schemas, credential definitions and revocation registry definitions are cached
+/// forever, as they are immutable once written to the ledger; all other entries expire after the
+/// default TTL.
+#[derive(Clone, Debug)]
+pub struct PersistentResponseCacherConfig {
+    default_ttl: Duration,
+    ttls: HashMap<LedgerObjectType, Option<Duration>>,
+}
+
+impl PersistentResponseCacherConfig {
+    pub fn builder() -> PersistentResponseCacherConfigBuilder {
+        PersistentResponseCacherConfigBuilder::default()
+    }
+
+    pub fn default_ttl(&self) -> Duration {
+        self.default_ttl
+    }
+
+    pub fn ttl(&self, object_type: LedgerObjectType) -> Option<Duration> {
+        self.ttls
+            .get(&object_type)
+            .copied()
+            .unwrap_or(Some(self.default_ttl))
+    }
+}
+
+impl Default for PersistentResponseCacherConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+pub struct PersistentResponseCacherConfigBuilder {
+    default_ttl: Duration,
+    ttls: HashMap<LedgerObjectType, Option<Duration>>,
+}
+
+impl Default for PersistentResponseCacherConfigBuilder {
+    fn default() -> Self {
+        Self {
+            default_ttl: Duration::from_secs(60 * 60),
+            ttls: HashMap::from([
+                (LedgerObjectType::Schema, None),
+                (LedgerObjectType::CredentialDefinition, None),
+                (LedgerObjectType::RevocationRegistryDefinition, None),
+            ]),
+        }
+    }
+}
+
+impl PersistentResponseCacherConfigBuilder {
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    pub fn ttl(mut self, object_type: LedgerObjectType, ttl: Option<Duration>) -> Self {
+        self.ttls.insert(object_type, ttl);
+        self
+    }
+
+    pub fn build(self) -> PersistentResponseCacherConfig {
+        PersistentResponseCacherConfig {
+            default_ttl: self.default_ttl,
+            ttls: self.ttls,
+        }
+    }
+}
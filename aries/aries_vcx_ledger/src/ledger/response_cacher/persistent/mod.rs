@@ -0,0 +1,453 @@
+mod config;
+mod options;
+mod statistics;
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aries_vcx_wallet::{
+    errors::error::VcxWalletError,
+    wallet::{
+        base_wallet::{
+            record::Record, record_category::RecordCategory, record_wallet::RecordWallet,
+        },
+        record_tags::{RecordTag, RecordTags},
+    },
+};
+use async_trait::async_trait;
+pub use config::*;
+use log::{info, trace, warn};
+pub use options::*;
+use serde::{Deserialize, Serialize};
+pub use statistics::ResponseCacherStatistics;
+use statistics::StatisticsCounters;
+
+use super::{LedgerObjectType, ResponseCacher};
+use crate::errors::error::VcxLedgerResult;
+
+const OBJECT_TYPE_TAG: &str = "object_type";
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<T> {
+    /// Unix timestamp in milliseconds
+    stored_at: u64,
+    object: T,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Response cacher persisting entries in the wallet record storage
+/// ([`RecordCategory::LedgerResponse`]), so that they survive restarts and can be shared by every
+/// process opening the same wallet.
+pub struct PersistentResponseCacher<W: RecordWallet + Send + Sync + ?Sized> {
+    wallet: Arc<W>,
+    config: PersistentResponseCacherConfig,
+    statistics: StatisticsCounters,
+}
+
+impl<W: RecordWallet + Send + Sync + ?Sized> PersistentResponseCacher<W> {
+    pub fn new(wallet: Arc<W>, config: PersistentResponseCacherConfig) -> Self {
+        info!("PersistentResponseCacher::new >> config: {config:?}");
+        Self {
+            wallet,
+            config,
+            statistics: StatisticsCounters::default(),
+        }
+    }
+
+    pub fn config(&self) -> &PersistentResponseCacherConfig {
+        &self.config
+    }
+
+    pub fn statistics(&self) -> ResponseCacherStatistics {
+        self.statistics.snapshot()
+    }
+
+    pub fn reset_statistics(&self) {
+        self.statistics.reset()
+    }
+
+    /// Removes a single entry, returns whether it was present.
+    pub async fn remove(&self, id: &str) -> VcxLedgerResult<bool> {
+        match self
+            .wallet
+            .delete_record(RecordCategory::LedgerResponse, id)
+            .await
+        {
+            Ok(()) => Ok(true),
+            Err(VcxWalletError::RecordNotFound(_)) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Removes all expired entries from the storage, returns the number of removed entries.
+    /// Expired entries are otherwise only evicted when looked up.
+    pub async fn purge_expired(&self) -> VcxLedgerResult<usize> {
+        let records = self
+            .wallet
+            .search_record(RecordCategory::LedgerResponse, None)
+            .await?;
+        let mut purged = 0;
+        for record in records {
+            let ttl = self
+                .config
+                .ttl(LedgerObjectType::from_cache_id(record.name()));
+            let Ok(entry) = serde_json::from_str::<CacheEntry<serde_json::Value>>(record.value())
+            else {
+                continue;
+            };
+            if Self::is_expired(entry.stored_at, ttl) && self.remove(record.name()).await? {
+                purged += 1;
+            }
+        }
+        Ok(purged)
+    }
+
+    fn is_expired(stored_at: u64, ttl: Option<Duration>) -> bool {
+        match ttl {
+            Some(ttl) => now_millis().saturating_sub(stored_at) > ttl.as_millis() as u64,
+            None => false,
+        }
+    }
+}
+
+#[async_trait]
+impl<W: RecordWallet + Send + Sync + ?Sized> ResponseCacher for PersistentResponseCacher<W> {
+    type Options = PersistentResponseCacherOptions;
+
+    async fn put<S, T>(&self, id: S, obj: T) -> VcxLedgerResult<()>
+    where
+        S: ToString + Send,
+        T: Serialize + for<'de> Deserialize<'de> + Send,
+    {
+        let id = id.to_string();
+        let object_type = LedgerObjectType::from_cache_id(&id);
+        let value = serde_json::to_string(&CacheEntry {
+            stored_at: now_millis(),
+            object: obj,
+        })?;
+        let tags = RecordTags::new(vec![RecordTag::new(OBJECT_TYPE_TAG, object_type.as_str())]);
+
+        let record = Record::builder()
+            .category(RecordCategory::LedgerResponse)
+            .name(id.clone())
+            .value(value.clone())
+            .tags(tags)
+            .build();
+        match self.wallet.add_record(record).await {
+            Ok(()) => {}
+            Err(VcxWalletError::DuplicateRecord(_)) => {
+                self.wallet
+                    .update_record_value(RecordCategory::LedgerResponse, &id, &value)
+                    .await?
+            }
+            Err(err) => return Err(err.into()),
+        }
+        self.statistics.record_write();
+        Ok(())
+    }
+
+    async fn get<S, T>(&self, id: S, opt: Option<Self::Options>) -> VcxLedgerResult<Option<T>>
+    where
+        S: ToString + Send,
+        T: Serialize + for<'de> Deserialize<'de> + Send,
+    {
+        let id = id.to_string();
+        let ttl = match opt.and_then(|opt| opt.ttl()) {
+            Some(ttl) => Some(ttl),
+            None => self.config.ttl(LedgerObjectType::from_cache_id(&id)),
+        };
+
+        let record = match self
+            .wallet
+            .get_record(RecordCategory::LedgerResponse, &id)
+            .await
+        {
+            Ok(record) => record,
+            Err(VcxWalletError::RecordNotFound(_)) => {
+                self.statistics.record_miss();
+                return Ok(None);
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        // entries written by an older version or for a different type are refetched
+        let entry: CacheEntry<T> = match serde_json::from_str(record.value()) {
+            Ok(entry) => entry,
+            Err(err) => {
+                warn!(
+                    "PersistentResponseCacher::get >> unable to read entry {id}, evicting: {err}"
+                );
+                self.remove(&id).await?;
+                self.statistics.record_miss();
+                return Ok(None);
+            }
+        };
+        if Self::is_expired(entry.stored_at, ttl) {
+            trace!("PersistentResponseCacher::get >> entry {id} expired, evicting");
+            self.remove(&id).await?;
+            self.statistics.record_expiration();
+            return Ok(None);
+        }
+        self.statistics.record_hit();
+        Ok(Some(entry.object))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Mutex};
+
+    use aries_vcx_wallet::{
+        errors::error::{NotFoundInfo, VcxWalletResult},
+        wallet::base_wallet::record::AllRecords,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct InMemoryRecordWallet {
+        records: Mutex<HashMap<(String, String), Record>>,
+    }
+
+    #[async_trait]
+    impl RecordWallet for InMemoryRecordWallet {
+        async fn all_records(&self) -> VcxWalletResult<Box<dyn AllRecords + Send>> {
+            Err(VcxWalletError::Unimplemented("all_records".into()))
+        }
+
+        async fn add_record(&self, record: Record) -> VcxWalletResult<()> {
+            let key = (record.category().to_string(), record.name().to_string());
+            let mut records = self.records.lock().unwrap();
+            if records.contains_key(&key) {
+                return Err(VcxWalletError::DuplicateRecord(key.1));
+            }
+            records.insert(key, record);
+            Ok(())
+        }
+
+        async fn get_record(
+            &self,
+            category: RecordCategory,
+            name: &str,
+        ) -> VcxWalletResult<Record> {
+            self.records
+                .lock()
+                .unwrap()
+                .get(&(category.to_string(), name.to_string()))
+                .cloned()
+                .ok_or_else(|| {
+                    VcxWalletError::RecordNotFound(NotFoundInfo::new_with_details(category, name))
+                })
+        }
+
+        async fn update_record_tags(
+            &self,
+            _category: RecordCategory,
+            _name: &str,
+            _new_tags: RecordTags,
+        ) -> VcxWalletResult<()> {
+            Err(VcxWalletError::Unimplemented("update_record_tags".into()))
+        }
+
+        async fn update_record_value(
+            &self,
+            category: RecordCategory,
+            name: &str,
+            new_value: &str,
+        ) -> VcxWalletResult<()> {
+            let record = self.get_record(category, name).await?;
+            let record = Record::builder()
+                .category(category)
+                .name(name.to_string())
+                .value(new_value.to_string())
+                .tags(record.tags().clone())
+                .build();
+            self.records
+                .lock()
+                .unwrap()
+                .insert((category.to_string(), name.to_string()), record);
+            Ok(())
+        }
+
+        async fn delete_record(&self, category: RecordCategory, name: &str) -> VcxWalletResult<()> {
+            self.records
+                .lock()
+                .unwrap()
+                .remove(&(category.to_string(), name.to_string()))
+                .map(|_| ())
+                .ok_or_else(|| {
+                    VcxWalletError::RecordNotFound(NotFoundInfo::new_with_details(category, name))
+                })
+        }
+
+        async fn search_record(
+            &self,
+            category: RecordCategory,
+            _search_filter: Option<String>,
+        ) -> VcxWalletResult<Vec<Record>> {
+            Ok(self
+                .records
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|record| *record.category() == category)
+                .cloned()
+                .collect())
+        }
+    }
+
+    const SCHEMA_ID: &str = "7Tqg6BwSSWapxgUDm9KKgg:2:test_schema:1.0";
+    const OTHER_ID: &str = "7Tqg6BwSSWapxgUDm9KKgg";
+
+    fn _cacher(default_ttl: Duration) -> PersistentResponseCacher<InMemoryRecordWallet> {
+        _cacher_with_wallet(Arc::new(InMemoryRecordWallet::default()), default_ttl)
+    }
+
+    fn _cacher_with_wallet(
+        wallet: Arc<InMemoryRecordWallet>,
+        default_ttl: Duration,
+    ) -> PersistentResponseCacher<InMemoryRecordWallet> {
+        PersistentResponseCacher::new(
+            wallet,
+            PersistentResponseCacherConfig::builder()
+                .default_ttl(default_ttl)
+                .build(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_put_and_get() -> VcxLedgerResult<()> {
+        let cacher = _cacher(Duration::from_secs(1));
+
+        cacher.put(SCHEMA_ID, "response".to_string()).await?;
+        cacher.put(SCHEMA_ID, "updated".to_string()).await?;
+
+        let cached: Option<String> = cacher.get(SCHEMA_ID, None).await?;
+        assert_eq!(Some("updated".to_string()), cached);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_entries_persist_across_cacher_instances() -> VcxLedgerResult<()> {
+        let wallet = Arc::new(InMemoryRecordWallet::default());
+        _cacher_with_wallet(wallet.clone(), Duration::from_secs(1))
+            .put(SCHEMA_ID, "response".to_string())
+            .await?;
+
+        let cached: Option<String> = _cacher_with_wallet(wallet, Duration::from_secs(1))
+            .get(SCHEMA_ID, None)
+            .await?;
+        assert_eq!(Some("response".to_string()), cached);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_entry_is_a_miss() -> VcxLedgerResult<()> {
+        let wallet = Arc::new(InMemoryRecordWallet::default());
+        let cacher = _cacher_with_wallet(wallet.clone(), Duration::from_secs(1));
+
+        cacher.put(SCHEMA_ID, "response".to_string()).await?;
+        let cached: Option<u64> = cacher.get(SCHEMA_ID, None).await?;
+        assert_eq!(None, cached);
+        assert!(wallet
+            .get_record(RecordCategory::LedgerResponse, SCHEMA_ID)
+            .await
+            .is_err());
+        assert_eq!(1, cacher.statistics().misses);
+
+        cacher.put(SCHEMA_ID, 16u64).await?;
+        let cached: Option<u64> = cacher.get(SCHEMA_ID, None).await?;
+        assert_eq!(Some(16), cached);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_per_object_type_ttl() -> VcxLedgerResult<()> {
+        let cacher = _cacher(Duration::from_millis(1));
+
+        cacher.put(SCHEMA_ID, "schema".to_string()).await?;
+        cacher.put(OTHER_ID, "other".to_string()).await?;
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let schema: Option<String> = cacher.get(SCHEMA_ID, None).await?;
+        assert_eq!(Some("schema".to_string()), schema);
+        let other: Option<String> = cacher.get(OTHER_ID, None).await?;
+        assert_eq!(None, other);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_options_ttl_override_config_ttl() -> VcxLedgerResult<()> {
+        let cacher = _cacher(Duration::from_secs(1));
+
+        cacher.put(SCHEMA_ID, "schema".to_string()).await?;
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let options = PersistentResponseCacherOptions::builder()
+            .ttl(Duration::from_millis(1))
+            .build();
+        let schema: Option<String> = cacher.get(SCHEMA_ID, Some(options)).await?;
+        assert_eq!(None, schema);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_statistics() -> VcxLedgerResult<()> {
+        let cacher = _cacher(Duration::from_millis(1));
+
+        let _: Option<String> = cacher.get(SCHEMA_ID, None).await?;
+        cacher.put(SCHEMA_ID, "schema".to_string()).await?;
+        cacher.put(OTHER_ID, "other".to_string()).await?;
+        let _: Option<String> = cacher.get(SCHEMA_ID, None).await?;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let _: Option<String> = cacher.get(OTHER_ID, None).await?;
+
+        let statistics = cacher.statistics();
+        assert_eq!(
+            ResponseCacherStatistics {
+                hits: 1,
+                misses: 2,
+                expirations: 1,
+                writes: 2,
+            },
+            statistics
+        );
+        assert_eq!(Some(1.0 / 3.0), statistics.hit_ratio());
+
+        cacher.reset_statistics();
+        assert_eq!(None, cacher.statistics().hit_ratio());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired() -> VcxLedgerResult<()> {
+        let cacher = _cacher(Duration::from_millis(1));
+
+        cacher.put(SCHEMA_ID, "schema".to_string()).await?;
+        cacher.put(OTHER_ID, "other".to_string()).await?;
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        assert_eq!(1, cacher.purge_expired().await?);
+        assert!(cacher.remove(SCHEMA_ID).await?);
+        assert!(!cacher.remove(OTHER_ID).await?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+#[derive(Default)]
+pub struct PersistentResponseCacherOptions {
+    ttl: Option<Duration>,
+}
+
+impl PersistentResponseCacherOptions {
+    pub fn builder() -> PersistentResponseCacherOptionsBuilder {
+        PersistentResponseCacherOptionsBuilder::default()
+    }
+
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+}
+
+#[derive(Default)]
+pub struct PersistentResponseCacherOptionsBuilder {
+    ttl: Option<Duration>,
+}
+
+impl PersistentResponseCacherOptionsBuilder {
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn build(self) -> PersistentResponseCacherOptions {
+        PersistentResponseCacherOptions { ttl: self.ttl }
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Snapshot of the counters of a [`super::PersistentResponseCacher`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResponseCacherStatistics {
+    pub hits: u64,
+    /// Lookups which did not find a usable entry, including expired ones.
+    pub misses: u64,
+    /// Entries found expired and evicted during lookups.
+    pub expirations: u64,
+    pub writes: u64,
+}
+
+impl ResponseCacherStatistics {
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Ratio of lookups served from the cache, `None` if there were no lookups yet.
+    pub fn hit_ratio(&self) -> Option<f64> {
+        match self.lookups() {
+            0 => None,
+            lookups => Some(self.hits as f64 / lookups as f64),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct StatisticsCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    expirations: AtomicU64,
+    writes: AtomicU64,
+}
+
+impl StatisticsCounters {
+    pub(super) fn record_hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_expiration(&self) {
+        self.expirations.fetch_add(1, Ordering::Relaxed);
+        self.record_miss();
+    }
+
+    pub(super) fn record_write(&self) {
+        self.writes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn snapshot(&self) -> ResponseCacherStatistics {
+        ResponseCacherStatistics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
+            writes: self.writes.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(super) fn reset(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+        self.expirations.store(0, Ordering::Relaxed);
+        self.writes.store(0, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,133 @@
+//! Compares the batch wallet operations against their one-by-one counterparts.
+//!
+//! Run with `cargo bench -p aries_vcx_wallet --features askar_wallet`. The number of items per
+//! run can be overridden through the `BATCH_SIZE` environment variable.
+
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use aries_vcx_wallet::wallet::{
+    askar::{askar_wallet_config::AskarWalletConfig, key_method::KeyMethod, AskarWallet},
+    base_wallet::{
+        did_wallet::DidWallet, record::Record, record_category::RecordCategory,
+        record_wallet::RecordWallet, ManageWallet,
+    },
+};
+use uuid::Uuid;
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+
+async fn setup_wallet() -> AskarWallet {
+    AskarWalletConfig::new(
+        "sqlite://:memory:",
+        KeyMethod::Unprotected,
+        "",
+        &Uuid::new_v4().to_string(),
+    )
+    .create_wallet()
+    .await
+    .unwrap()
+}
+
+fn build_records(prefix: &str, size: usize) -> Vec<Record> {
+    (0..size)
+        .map(|idx| {
+            Record::builder()
+                .category(RecordCategory::Cred)
+                .name(format!("{prefix}-{idx}"))
+                .value(format!("{{\"idx\":{idx}}}"))
+                .build()
+        })
+        .collect()
+}
+
+async fn measure<F: Future<Output = ()>>(fut: F) -> Duration {
+    let start = Instant::now();
+    fut.await;
+    start.elapsed()
+}
+
+fn report(name: &str, size: usize, single: Duration, batch: Duration) {
+    println!(
+        "{name:<24} n={size:<6} single: {single:>10.2?}  batch: {batch:>10.2?}  speedup: {:.2}x",
+        single.as_secs_f64() / batch.as_secs_f64()
+    );
+}
+
+async fn bench_create_dids(size: usize) {
+    let wallet = setup_wallet().await;
+    let single = measure(async {
+        for _ in 0..size {
+            wallet.create_and_store_my_did(None, None).await.unwrap();
+        }
+    })
+    .await;
+
+    let wallet = setup_wallet().await;
+    let batch = measure(async {
+        wallet.create_and_store_my_dids(size, None).await.unwrap();
+    })
+    .await;
+
+    report("create_and_store_my_did", size, single, batch);
+}
+
+async fn bench_sign(size: usize) {
+    let wallet = setup_wallet().await;
+    let did_data = wallet.create_and_store_my_did(None, None).await.unwrap();
+    let messages = (0..size)
+        .map(|idx| format!("message {idx}").into_bytes())
+        .collect::<Vec<_>>();
+
+    let single = measure(async {
+        for msg in messages.iter() {
+            wallet.sign(did_data.verkey(), msg).await.unwrap();
+        }
+    })
+    .await;
+
+    let items = messages
+        .iter()
+        .map(|msg| (did_data.verkey(), msg.as_slice()))
+        .collect::<Vec<_>>();
+    let batch = measure(async {
+        wallet.sign_batch(&items).await.unwrap();
+    })
+    .await;
+
+    report("sign", size, single, batch);
+}
+
+async fn bench_add_records(size: usize) {
+    let wallet = setup_wallet().await;
+    let records = build_records("single", size);
+    let single = measure(async move {
+        for record in records {
+            wallet.add_record(record).await.unwrap();
+        }
+    })
+    .await;
+
+    let wallet = setup_wallet().await;
+    let records = build_records("batch", size);
+    let batch = measure(async move {
+        wallet.add_records(records).await.unwrap();
+    })
+    .await;
+
+    report("add_record", size, single, batch);
+}
+
+#[tokio::main]
+async fn main() {
+    let size = std::env::var("BATCH_SIZE")
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(DEFAULT_BATCH_SIZE);
+
+    bench_create_dids(size).await;
+    bench_sign(size).await;
+    bench_add_records(size).await;
+}
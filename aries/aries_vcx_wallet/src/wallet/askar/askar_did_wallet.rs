@@ -1,6 +1,9 @@
+use std::collections::HashMap;
+
 use aries_askar::{
     crypto::alg::Chacha20Types,
     kms::{KeyAlg, LocalKey},
+    Session,
 };
 use async_trait::async_trait;
 use public_key::Key;
@@ -37,34 +40,23 @@ impl DidWallet for AskarWallet {
         _did_method_name: Option<&str>,
     ) -> VcxWalletResult<DidData> {
         let mut tx = self.transaction().await?;
-        let (_vk, local_key) = self
-            .insert_key(&mut tx, KeyAlg::Ed25519, seed_from_opt(seed).as_bytes())
-            .await?;
-
-        let verkey = local_key_to_public_key(&local_key)?;
-
-        // construct NYM from first half of verkey as expected output from this method
-        let nym = {
-            let pk = verkey.key();
-            if pk.len() != 32 {
-                return Err(VcxWalletError::InvalidInput(format!(
-                    "Invalid key length: {}",
-                    pk.len()
-                )));
-            }
-            bs58::encode(&pk[0..16]).into_string()
-        };
+        let did_data = self.insert_my_did(&mut tx, seed).await?;
+        tx.commit().await?;
+        Ok(did_data)
+    }
 
-        self.insert_did(
-            &mut tx,
-            &nym,
-            &RecordCategory::Did.to_string(),
-            &verkey,
-            None,
-        )
-        .await?;
+    async fn create_and_store_my_dids(
+        &self,
+        count: usize,
+        _did_method_name: Option<&str>,
+    ) -> VcxWalletResult<Vec<DidData>> {
+        let mut tx = self.transaction().await?;
+        let mut dids = Vec::with_capacity(count);
+        for _ in 0..count {
+            dids.push(self.insert_my_did(&mut tx, None).await?);
+        }
         tx.commit().await?;
-        Ok(DidData::new(&nym, &verkey))
+        Ok(dids)
     }
 
     async fn key_for_did(&self, did: &str) -> VcxWalletResult<Key> {
@@ -150,6 +142,25 @@ impl DidWallet for AskarWallet {
         Ok(local_key.sign_message(msg, Some(key_alg.into()))?)
     }
 
+    async fn sign_batch(&self, items: &[(&Key, &[u8])]) -> VcxWalletResult<Vec<Vec<u8>>> {
+        let mut session = self.session().await?;
+        let mut local_keys: HashMap<String, LocalKey> = HashMap::new();
+        let mut signatures = Vec::with_capacity(items.len());
+
+        for (key, msg) in items {
+            let key_name = key.base58();
+            if !local_keys.contains_key(&key_name) {
+                let local_key = self.fetch_local_key(&mut session, &key_name).await?;
+                local_keys.insert(key_name.clone(), local_key);
+            }
+            let local_key = &local_keys[&key_name];
+            let key_alg = SigType::try_from_key_alg(local_key.algorithm())?;
+            signatures.push(local_key.sign_message(msg, Some(key_alg.into()))?);
+        }
+
+        Ok(signatures)
+    }
+
     async fn verify(&self, key: &Key, msg: &[u8], signature: &[u8]) -> VcxWalletResult<bool> {
         let local_key = public_key_to_local_key(key)?;
 
@@ -189,3 +200,39 @@ impl DidWallet for AskarWallet {
         Ok(unpack(serde_json::from_slice(msg)?, &mut self.session().await?).await?)
     }
 }
+
+impl AskarWallet {
+    async fn insert_my_did(
+        &self,
+        session: &mut Session,
+        seed: Option<&str>,
+    ) -> VcxWalletResult<DidData> {
+        let (_vk, local_key) = self
+            .insert_key(session, KeyAlg::Ed25519, seed_from_opt(seed).as_bytes())
+            .await?;
+
+        let verkey = local_key_to_public_key(&local_key)?;
+
+        // construct NYM from first half of verkey as expected output from this method
+        let nym = {
+            let pk = verkey.key();
+            if pk.len() != 32 {
+                return Err(VcxWalletError::InvalidInput(format!(
+                    "Invalid key length: {}",
+                    pk.len()
+                )));
+            }
+            bs58::encode(&pk[0..16]).into_string()
+        };
+
+        self.insert_did(
+            session,
+            &nym,
+            &RecordCategory::Did.to_string(),
+            &verkey,
+            None,
+        )
+        .await?;
+        Ok(DidData::new(&nym, &verkey))
+    }
+}
@@ -33,6 +33,22 @@ impl RecordWallet for AskarWallet {
             .await?)
     }
 
+    async fn add_records(&self, records: Vec<Record>) -> VcxWalletResult<()> {
+        let mut tx = self.transaction().await?;
+        for record in records {
+            let tags: Vec<EntryTag> = record.tags().clone().into();
+            tx.insert(
+                &record.category().to_string(),
+                record.name(),
+                record.value().as_bytes(),
+                Some(&tags),
+                None,
+            )
+            .await?;
+        }
+        Ok(tx.commit().await?)
+    }
+
     async fn get_record(&self, category: RecordCategory, name: &str) -> VcxWalletResult<Record> {
         let mut session = self.session().await?;
 
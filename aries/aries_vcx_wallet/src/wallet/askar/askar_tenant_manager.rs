@@ -0,0 +1,333 @@
+use aries_askar::kms::KeyAlg;
+use public_key::{Key, KeyType};
+use serde_json::json;
+
+use super::{unpack::recipient_kids, AskarWallet};
+use crate::{
+    errors::error::{VcxWalletError, VcxWalletResult},
+    wallet::{
+        base_wallet::{
+            did_data::DidData, did_wallet::DidWallet, record::Record,
+            record_category::RecordCategory, record_wallet::RecordWallet,
+        },
+        record_tags::RecordTag,
+        structs_io::UnpackMessageOutput,
+    },
+};
+
+const TENANT_ID_TAG: &str = "tenant_id";
+
+/// Manages tenants hosted within a single askar store. Every tenant is backed by its own askar
+/// profile, so records and keys of one tenant are never visible to another. The bookkeeping
+/// (list of tenants and the recipient key → tenant routing table) lives in the profile of the
+/// admin wallet the manager was created with.
+#[derive(Clone, Debug)]
+pub struct AskarTenantManager {
+    admin: AskarWallet,
+}
+
+impl AskarTenantManager {
+    pub fn new(admin: AskarWallet) -> Self {
+        Self { admin }
+    }
+
+    pub fn admin_wallet(&self) -> &AskarWallet {
+        &self.admin
+    }
+
+    pub async fn create_tenant(&self, tenant_id: &str) -> VcxWalletResult<AskarWallet> {
+        if tenant_id == self.admin.profile() {
+            return Err(VcxWalletError::InvalidInput(format!(
+                "tenant id {tenant_id} collides with the admin profile"
+            )));
+        }
+        if self.tenant_exists(tenant_id).await? {
+            return Err(VcxWalletError::DuplicateRecord(format!(
+                "category: {}, name: {}",
+                RecordCategory::Tenant,
+                tenant_id
+            )));
+        }
+
+        let profile = self.admin.create_profile(tenant_id).await?;
+        let record = Record::builder()
+            .category(RecordCategory::Tenant)
+            .name(tenant_id.into())
+            .value(profile.clone())
+            .build();
+        if let Err(err) = self.admin.add_record(record).await {
+            // do not leave behind a profile no tenant record points to
+            self.admin.remove_profile(&profile).await?;
+            return Err(err);
+        }
+
+        Ok(self.admin.with_profile(&profile))
+    }
+
+    pub async fn open_tenant(&self, tenant_id: &str) -> VcxWalletResult<AskarWallet> {
+        let record = self
+            .admin
+            .get_record(RecordCategory::Tenant, tenant_id)
+            .await?;
+
+        Ok(self.admin.with_profile(record.value()))
+    }
+
+    pub async fn list_tenants(&self) -> VcxWalletResult<Vec<String>> {
+        Ok(self
+            .admin
+            .search_record(RecordCategory::Tenant, None)
+            .await?
+            .iter()
+            .map(|record| record.name().to_owned())
+            .collect())
+    }
+
+    /// Removes the tenant profile together with all of its records and keys, and drops its
+    /// entries from the routing table.
+    pub async fn remove_tenant(&self, tenant_id: &str) -> VcxWalletResult<()> {
+        let tenant = self.open_tenant(tenant_id).await?;
+
+        for route in self.tenant_routes(tenant_id).await? {
+            self.admin
+                .delete_record(RecordCategory::TenantRoute, route.name())
+                .await?;
+        }
+        self.admin.remove_profile(tenant.profile()).await?;
+        self.admin
+            .delete_record(RecordCategory::Tenant, tenant_id)
+            .await
+    }
+
+    /// Makes messages packed for `key` routable to the given tenant.
+    pub async fn register_route_key(&self, tenant_id: &str, key: &Key) -> VcxWalletResult<()> {
+        if !self.tenant_exists(tenant_id).await? {
+            return Err(VcxWalletError::record_not_found_from_details(
+                RecordCategory::Tenant,
+                tenant_id,
+            ));
+        }
+
+        let key_name = key.base58();
+        match self
+            .admin
+            .get_record(RecordCategory::TenantRoute, &key_name)
+            .await
+        {
+            Ok(route) if route.value() == tenant_id => Ok(()),
+            Ok(_) => Err(VcxWalletError::DuplicateRecord(format!(
+                "key {key_name} is already routed to another tenant"
+            ))),
+            Err(VcxWalletError::RecordNotFound(_)) => {
+                let record = Record::builder()
+                    .category(RecordCategory::TenantRoute)
+                    .name(key_name)
+                    .value(tenant_id.into())
+                    .tags(vec![RecordTag::new(TENANT_ID_TAG, tenant_id)].into())
+                    .build();
+                self.admin.add_record(record).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Creates a DID in the tenant's profile and registers its verkey for routing.
+    pub async fn create_and_store_tenant_did(
+        &self,
+        tenant_id: &str,
+        seed: Option<&str>,
+    ) -> VcxWalletResult<DidData> {
+        let tenant = self.open_tenant(tenant_id).await?;
+        let did_data = tenant.create_and_store_my_did(seed, None).await?;
+        self.register_route_key(tenant_id, did_data.verkey())
+            .await?;
+
+        Ok(did_data)
+    }
+
+    /// Registers every Ed25519 key currently held by the tenant for routing. Useful when keys
+    /// were created directly through the tenant wallet rather than through the manager. Keys of
+    /// other algorithms cannot be recipients of packed messages and are skipped.
+    pub async fn sync_route_keys(&self, tenant_id: &str) -> VcxWalletResult<usize> {
+        let tenant = self.open_tenant(tenant_id).await?;
+        let keys = tenant
+            .session()
+            .await?
+            .fetch_all_keys(None, None, None, None, false)
+            .await?;
+
+        let mut count = 0;
+        for key_entry in keys {
+            if key_entry.load_local_key()?.algorithm() != KeyAlg::Ed25519 {
+                continue;
+            }
+            let key = Key::from_base58(key_entry.name(), KeyType::Ed25519)?;
+            self.register_route_key(tenant_id, &key).await?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Finds the tenant a packed message is addressed to, based on its recipient keys.
+    pub async fn route_message(&self, msg: &[u8]) -> VcxWalletResult<(String, AskarWallet)> {
+        for kid in recipient_kids(msg)? {
+            match self
+                .admin
+                .get_record(RecordCategory::TenantRoute, &kid)
+                .await
+            {
+                Ok(route) => {
+                    let tenant = self.open_tenant(route.value()).await?;
+                    return Ok((route.value().to_owned(), tenant));
+                }
+                Err(VcxWalletError::RecordNotFound(_)) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(VcxWalletError::NoRecipientKeyFound)
+    }
+
+    /// Routes a packed message to its tenant and unpacks it with the tenant's keys.
+    pub async fn unpack_tenant_message(
+        &self,
+        msg: &[u8],
+    ) -> VcxWalletResult<(String, UnpackMessageOutput)> {
+        let (tenant_id, tenant) = self.route_message(msg).await?;
+        let unpacked = tenant.unpack_message(msg).await?;
+
+        Ok((tenant_id, unpacked))
+    }
+
+    async fn tenant_exists(&self, tenant_id: &str) -> VcxWalletResult<bool> {
+        match self
+            .admin
+            .get_record(RecordCategory::Tenant, tenant_id)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(VcxWalletError::RecordNotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn tenant_routes(&self, tenant_id: &str) -> VcxWalletResult<Vec<Record>> {
+        self.admin
+            .search_record(
+                RecordCategory::TenantRoute,
+                Some(json!({ TENANT_ID_TAG: tenant_id }).to_string()),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aries_askar::kms::{KeyAlg, LocalKey};
+
+    use super::AskarTenantManager;
+    use crate::{
+        errors::error::VcxWalletError,
+        wallet::{askar::tests::dev_setup_askar_wallet, base_wallet::did_wallet::DidWallet},
+    };
+
+    async fn build_tenant_manager() -> AskarTenantManager {
+        AskarTenantManager::new(dev_setup_askar_wallet().await)
+    }
+
+    #[tokio::test]
+    async fn tenant_manager_should_isolate_tenants() {
+        let manager = build_tenant_manager().await;
+        let alice = manager.create_tenant("alice").await.unwrap();
+        let bob = manager.create_tenant("bob").await.unwrap();
+
+        let did_data = alice.create_and_store_my_did(None, None).await.unwrap();
+
+        assert_eq!(1, alice.key_count().await.unwrap());
+        assert_eq!(0, bob.key_count().await.unwrap());
+        let err = bob.key_for_did(did_data.did()).await.unwrap_err();
+        assert!(matches!(err, VcxWalletError::RecordNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn tenant_manager_should_list_and_remove_tenants() {
+        let manager = build_tenant_manager().await;
+        manager.create_tenant("alice").await.unwrap();
+        manager.create_tenant("bob").await.unwrap();
+
+        let err = manager.create_tenant("alice").await.unwrap_err();
+        assert!(matches!(err, VcxWalletError::DuplicateRecord(_)));
+
+        let mut tenants = manager.list_tenants().await.unwrap();
+        tenants.sort();
+        assert_eq!(vec!["alice".to_string(), "bob".to_string()], tenants);
+
+        manager.remove_tenant("alice").await.unwrap();
+        assert_eq!(
+            vec!["bob".to_string()],
+            manager.list_tenants().await.unwrap()
+        );
+        let err = manager.open_tenant("alice").await.unwrap_err();
+        assert!(matches!(err, VcxWalletError::RecordNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn tenant_manager_should_route_message_by_recipient_key() {
+        let manager = build_tenant_manager().await;
+        manager.create_tenant("alice").await.unwrap();
+        manager.create_tenant("bob").await.unwrap();
+        let bob_did = manager
+            .create_and_store_tenant_did("bob", None)
+            .await
+            .unwrap();
+
+        let msg = "route me";
+        let packed = manager
+            .admin_wallet()
+            .pack_message(None, vec![bob_did.verkey().clone()], msg.as_bytes())
+            .await
+            .unwrap();
+
+        let (tenant_id, unpacked) = manager.unpack_tenant_message(&packed).await.unwrap();
+        assert_eq!("bob", tenant_id);
+        assert_eq!(msg, unpacked.message);
+    }
+
+    #[tokio::test]
+    async fn tenant_manager_should_sync_route_keys() {
+        let manager = build_tenant_manager().await;
+        let alice = manager.create_tenant("alice").await.unwrap();
+        let did_data = alice.create_and_store_my_did(None, None).await.unwrap();
+
+        let packed = alice
+            .pack_message(None, vec![did_data.verkey().clone()], b"hello")
+            .await
+            .unwrap();
+        let err = manager.route_message(&packed).await.unwrap_err();
+        assert!(matches!(err, VcxWalletError::NoRecipientKeyFound));
+
+        assert_eq!(1, manager.sync_route_keys("alice").await.unwrap());
+        let (tenant_id, _) = manager.route_message(&packed).await.unwrap();
+        assert_eq!("alice", tenant_id);
+    }
+
+    #[tokio::test]
+    async fn tenant_manager_should_skip_non_ed25519_keys_on_sync() {
+        let manager = build_tenant_manager().await;
+        let alice = manager.create_tenant("alice").await.unwrap();
+        alice.create_and_store_my_did(None, None).await.unwrap();
+
+        let x25519_key = LocalKey::generate_with_rng(KeyAlg::X25519, true).unwrap();
+        alice
+            .session()
+            .await
+            .unwrap()
+            .insert_key("x25519", &x25519_key, None, None, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(2, alice.key_count().await.unwrap());
+        assert_eq!(1, manager.sync_route_keys("alice").await.unwrap());
+    }
+}
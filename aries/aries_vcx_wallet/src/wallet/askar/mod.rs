@@ -19,6 +19,7 @@ mod all_askar_records;
 mod askar_did_wallet;
 pub mod askar_import_config;
 mod askar_record_wallet;
+pub mod askar_tenant_manager;
 mod askar_utils;
 pub mod askar_wallet_config;
 mod entry;
@@ -31,7 +32,7 @@ mod rng_method;
 mod sig_type;
 mod unpack;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct AskarWallet {
     backend: Store,
     profile: String,
@@ -95,6 +96,31 @@ impl AskarWallet {
         })
     }
 
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// Returns a handle to another profile of the same underlying store. The profile must
+    /// already exist, see [`AskarWallet::create_profile`].
+    pub fn with_profile(&self, profile: &str) -> Self {
+        Self {
+            backend: self.backend.clone(),
+            profile: profile.into(),
+        }
+    }
+
+    pub async fn create_profile(&self, profile: &str) -> VcxWalletResult<String> {
+        Ok(self.backend.create_profile(Some(profile.into())).await?)
+    }
+
+    pub async fn list_profiles(&self) -> VcxWalletResult<Vec<String>> {
+        Ok(self.backend.list_profiles().await?)
+    }
+
+    pub async fn remove_profile(&self, profile: &str) -> VcxWalletResult<bool> {
+        Ok(self.backend.remove_profile(profile.into()).await?)
+    }
+
     async fn fetch(
         &self,
         session: &mut Session,
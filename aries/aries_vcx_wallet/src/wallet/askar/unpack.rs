@@ -43,6 +43,16 @@ pub async fn unpack(jwe: Jwe, session: &mut Session) -> VcxWalletResult<UnpackMe
     local_key.unpack(recipient, jwe)
 }
 
+/// Returns the key ids of all recipients of a packed message, without decrypting it
+pub fn recipient_kids(msg: &[u8]) -> VcxWalletResult<Vec<String>> {
+    let jwe: Jwe = serde_json::from_slice(msg)?;
+    Ok(unpack_protected_data(&jwe)?
+        .recipients
+        .iter()
+        .map(|recipient| recipient.unwrap_kid().to_owned())
+        .collect())
+}
+
 /// Returns the shared encryption key, and the sender key (if any)
 fn unpack_recipient(
     recipient: &Recipient,
@@ -12,6 +12,20 @@ pub trait DidWallet {
         kdf_method_name: Option<&str>,
     ) -> VcxWalletResult<DidData>;
 
+    /// Creates `count` DIDs with random keys. Implementations should store all of them within
+    /// a single transaction; the default implementation creates them one by one.
+    async fn create_and_store_my_dids(
+        &self,
+        count: usize,
+        kdf_method_name: Option<&str>,
+    ) -> VcxWalletResult<Vec<DidData>> {
+        let mut dids = Vec::with_capacity(count);
+        for _ in 0..count {
+            dids.push(self.create_and_store_my_did(None, kdf_method_name).await?);
+        }
+        Ok(dids)
+    }
+
     async fn key_count(&self) -> VcxWalletResult<usize>;
 
     async fn key_for_did(&self, did: &str) -> VcxWalletResult<Key>;
@@ -22,6 +36,15 @@ pub trait DidWallet {
 
     async fn sign(&self, key: &Key, msg: &[u8]) -> VcxWalletResult<Vec<u8>>;
 
+    /// Signs each message with its paired key, returning signatures in the input order.
+    async fn sign_batch(&self, items: &[(&Key, &[u8])]) -> VcxWalletResult<Vec<Vec<u8>>> {
+        let mut signatures = Vec::with_capacity(items.len());
+        for (key, msg) in items {
+            signatures.push(self.sign(key, msg).await?);
+        }
+        Ok(signatures)
+    }
+
     async fn verify(&self, key: &Key, msg: &[u8], signature: &[u8]) -> VcxWalletResult<bool>;
 
     async fn pack_message(
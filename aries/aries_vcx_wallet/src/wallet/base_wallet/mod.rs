@@ -117,6 +117,40 @@ mod tests {
         assert!(res);
     }
 
+    #[tokio::test]
+    async fn did_wallet_should_create_dids_in_batch() {
+        let wallet = build_test_wallet().await;
+
+        let dids = wallet.create_and_store_my_dids(5, None).await.unwrap();
+
+        assert_eq!(5, dids.len());
+        assert_eq!(5, wallet.key_count().await.unwrap());
+        for did_data in dids {
+            let verkey = wallet.key_for_did(did_data.did()).await.unwrap();
+            assert_eq!(did_data.verkey().base58(), verkey.base58());
+        }
+    }
+
+    #[tokio::test]
+    async fn did_wallet_should_sign_batch_and_verify() {
+        let wallet = build_test_wallet().await;
+
+        let first = wallet.create_and_store_my_did(None, None).await.unwrap();
+        let second = wallet.create_and_store_my_did(None, None).await.unwrap();
+        let items = vec![
+            (first.verkey(), "first".as_bytes()),
+            (second.verkey(), "second".as_bytes()),
+            (first.verkey(), "third".as_bytes()),
+        ];
+
+        let signatures = wallet.sign_batch(&items).await.unwrap();
+
+        assert_eq!(items.len(), signatures.len());
+        for ((key, msg), sig) in items.iter().zip(signatures.iter()) {
+            assert!(wallet.verify(key, msg, sig).await.unwrap());
+        }
+    }
+
     #[tokio::test]
     async fn did_wallet_should_return_correct_key() {
         let wallet = build_test_wallet().await;
@@ -283,6 +317,53 @@ mod tests {
         assert_eq!(value, res.value());
     }
 
+    #[tokio::test]
+    async fn record_wallet_should_add_records_in_batch() {
+        let wallet = build_test_wallet().await;
+
+        let category = RecordCategory::default();
+        let records = (0..3)
+            .map(|idx| {
+                Record::builder()
+                    .name(format!("foo{idx}"))
+                    .category(category)
+                    .value(format!("bar{idx}"))
+                    .build()
+            })
+            .collect();
+
+        wallet.add_records(records).await.unwrap();
+
+        let res = wallet.search_record(category, None).await.unwrap();
+        assert_eq!(3, res.len());
+        let res = wallet.get_record(category, "foo1").await.unwrap();
+        assert_eq!("bar1", res.value());
+    }
+
+    #[tokio::test]
+    async fn record_wallet_should_not_add_any_record_of_failed_batch() {
+        let wallet = build_test_wallet().await;
+
+        let category = RecordCategory::default();
+        let record = Record::builder()
+            .name("foo".into())
+            .category(category)
+            .value("bar".into())
+            .build();
+        wallet.add_record(record.clone()).await.unwrap();
+
+        let other = Record::builder()
+            .name("baz".into())
+            .category(category)
+            .value("box".into())
+            .build();
+        let err = wallet.add_records(vec![other, record]).await.unwrap_err();
+        assert!(matches!(err, VcxWalletError::DuplicateRecord(_)));
+
+        let err = wallet.get_record(category, "baz").await.unwrap_err();
+        assert!(matches!(err, VcxWalletError::RecordNotFound { .. }));
+    }
+
     #[tokio::test]
     async fn record_wallet_should_delete_record() {
         let wallet = build_test_wallet().await;
@@ -20,6 +20,12 @@ const REV_REG_DEF_PRIV: &str = "VCX_REV_REG_DEF_PRIV";
 const DID: &str = "Indy::Did";
 const TMP_DID: &str = "Indy::TemporaryDid";
 const KEY: &str = "Indy::Key";
+const TENANT: &str = "VCX_TENANT";
+const TENANT_ROUTE: &str = "VCX_TENANT_ROUTE";
+const LEDGER_RESPONSE: &str = "VCX_LEDGER_RESPONSE";
+const ISSUER_OBJECT: &str = "VCX_ISSUER_OBJECT";
+const REV_REG_ISSUANCE: &str = "VCX_REV_REG_ISSUANCE";
+const RETIRED_REV_REG: &str = "VCX_RETIRED_REV_REG";
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum RecordCategory {
@@ -39,6 +45,12 @@ pub enum RecordCategory {
     Did,
     TmpDid,
     Key,
+    Tenant,
+    TenantRoute,
+    LedgerResponse,
+    IssuerObject,
+    RevRegIssuance,
+    RetiredRevReg,
 }
 
 impl FromStr for RecordCategory {
@@ -61,6 +73,12 @@ impl FromStr for RecordCategory {
             DID => Ok(RecordCategory::Did),
             TMP_DID => Ok(RecordCategory::TmpDid),
             KEY => Ok(RecordCategory::Key),
+            TENANT => Ok(RecordCategory::Tenant),
+            TENANT_ROUTE => Ok(RecordCategory::TenantRoute),
+            LEDGER_RESPONSE => Ok(RecordCategory::LedgerResponse),
+            ISSUER_OBJECT => Ok(RecordCategory::IssuerObject),
+            REV_REG_ISSUANCE => Ok(RecordCategory::RevRegIssuance),
+            RETIRED_REV_REG => Ok(RecordCategory::RetiredRevReg),
             _ => Err(Self::Err::UnknownRecordCategory(s.into())),
         }
     }
@@ -84,6 +102,12 @@ impl Display for RecordCategory {
             RecordCategory::Did => DID,
             RecordCategory::TmpDid => TMP_DID,
             RecordCategory::Key => KEY,
+            RecordCategory::Tenant => TENANT,
+            RecordCategory::TenantRoute => TENANT_ROUTE,
+            RecordCategory::LedgerResponse => LEDGER_RESPONSE,
+            RecordCategory::IssuerObject => ISSUER_OBJECT,
+            RecordCategory::RevRegIssuance => REV_REG_ISSUANCE,
+            RecordCategory::RetiredRevReg => RETIRED_REV_REG,
         };
 
         write!(f, "{value}")
@@ -12,6 +12,15 @@ pub trait RecordWallet {
 
     async fn add_record(&self, record: Record) -> VcxWalletResult<()>;
 
+    /// Inserts all records, failing on the first duplicate. Implementations should insert them
+    /// atomically; the default implementation inserts them one by one.
+    async fn add_records(&self, records: Vec<Record>) -> VcxWalletResult<()> {
+        for record in records {
+            self.add_record(record).await?;
+        }
+        Ok(())
+    }
+
     async fn get_record(&self, category: RecordCategory, name: &str) -> VcxWalletResult<Record>;
 
     async fn update_record_tags(
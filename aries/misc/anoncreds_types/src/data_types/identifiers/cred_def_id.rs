@@ -1,6 +1,8 @@
 use crate::{
     error::Error,
-    utils::validation::{Validatable, LEGACY_CRED_DEF_IDENTIFIER, URI_IDENTIFIER},
+    utils::validation::{
+        Validatable, DID_INDY_CRED_DEF_IDENTIFIER, LEGACY_CRED_DEF_IDENTIFIER, URI_IDENTIFIER,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, Default)]
@@ -24,6 +26,43 @@ impl CredentialDefinitionId {
     pub fn is_uri(&self) -> bool {
         URI_IDENTIFIER.captures(&self.0).is_some()
     }
+
+    /// e.g. `did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9/anoncreds/v0/CLAIM_DEF/56495/npdb`
+    pub fn is_did_indy(&self) -> bool {
+        DID_INDY_CRED_DEF_IDENTIFIER.captures(&self.0).is_some()
+    }
+
+    pub fn did_indy_namespace(&self) -> Option<&str> {
+        let captures = DID_INDY_CRED_DEF_IDENTIFIER.captures(&self.0)?;
+        captures
+            .name("namespace")
+            .map(|namespace| namespace.as_str())
+    }
+
+    /// Converts a did:indy identifier into its legacy form, legacy identifiers are returned as is.
+    pub fn to_legacy(&self) -> Option<Self> {
+        if self.is_legacy_cred_def_identifier() {
+            return Some(self.clone());
+        }
+        let captures = DID_INDY_CRED_DEF_IDENTIFIER.captures(&self.0)?;
+        Some(Self(format!(
+            "{}:3:CL:{}:{}",
+            &captures["did"], &captures["schema_seq_no"], &captures["tag"]
+        )))
+    }
+
+    /// Converts a legacy identifier into the did:indy identifier of the indy network `namespace`.
+    /// Only legacy identifiers referencing their schema by sequence number can be converted.
+    pub fn to_did_indy(&self, namespace: &str) -> Option<Self> {
+        let captures = LEGACY_CRED_DEF_IDENTIFIER.captures(&self.0)?;
+        let id = Self(format!(
+            "did:indy:{namespace}:{}/anoncreds/v0/CLAIM_DEF/{}/{}",
+            &captures["did"],
+            captures.name("schema_seq_no")?.as_str(),
+            captures.name("tag")?.as_str()
+        ));
+        id.is_did_indy().then_some(id)
+    }
 }
 
 impl Validatable for CredentialDefinitionId {
@@ -74,3 +113,29 @@ impl std::fmt::Display for CredentialDefinitionId {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(test)]
+mod test_cred_def_identifiers {
+    use super::*;
+
+    #[test]
+    fn should_convert_between_legacy_and_did_indy_identifiers() {
+        let legacy = CredentialDefinitionId::new("5nDyJVP1NrcPAttP3xwMB9:3:CL:56495:npdb").unwrap();
+        let did_indy = CredentialDefinitionId::new(
+            "did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9/anoncreds/v0/CLAIM_DEF/56495/npdb",
+        )
+        .unwrap();
+
+        assert!(did_indy.is_did_indy());
+        assert_eq!(did_indy.did_indy_namespace(), Some("sovrin"));
+        assert_eq!(did_indy.to_legacy(), Some(legacy.clone()));
+        assert_eq!(legacy.to_legacy(), Some(legacy.clone()));
+        assert_eq!(legacy.to_did_indy("sovrin"), Some(did_indy));
+
+        let legacy_with_schema_id = CredentialDefinitionId::new(
+            "5nDyJVP1NrcPAttP3xwMB9:3:CL:5nDyJVP1NrcPAttP3xwMB9:2:npdb:4.3.4:npdb",
+        )
+        .unwrap();
+        assert_eq!(legacy_with_schema_id.to_did_indy("sovrin"), None);
+    }
+}
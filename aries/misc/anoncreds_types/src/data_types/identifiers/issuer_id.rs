@@ -1,6 +1,6 @@
 use crate::{
     error::Error,
-    utils::validation::{Validatable, LEGACY_DID_IDENTIFIER, URI_IDENTIFIER},
+    utils::validation::{Validatable, DID_INDY_IDENTIFIER, LEGACY_DID_IDENTIFIER, URI_IDENTIFIER},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, Default)]
@@ -24,6 +24,35 @@ impl IssuerId {
     pub fn is_uri(&self) -> bool {
         URI_IDENTIFIER.captures(&self.0).is_some()
     }
+
+    pub fn is_did_indy(&self) -> bool {
+        DID_INDY_IDENTIFIER.captures(&self.0).is_some()
+    }
+
+    pub fn did_indy_namespace(&self) -> Option<&str> {
+        let captures = DID_INDY_IDENTIFIER.captures(&self.0)?;
+        captures
+            .name("namespace")
+            .map(|namespace| namespace.as_str())
+    }
+
+    /// Converts a did:indy identifier into its legacy form, legacy identifiers are returned as is.
+    pub fn to_legacy(&self) -> Option<Self> {
+        if self.is_legacy() {
+            return Some(self.clone());
+        }
+        let captures = DID_INDY_IDENTIFIER.captures(&self.0)?;
+        Some(Self(captures["did"].to_string()))
+    }
+
+    /// Converts a legacy identifier into the did:indy identifier of the indy network `namespace`.
+    pub fn to_did_indy(&self, namespace: &str) -> Option<Self> {
+        if !self.is_legacy() {
+            return None;
+        }
+        let id = Self(format!("did:indy:{namespace}:{}", self.0));
+        id.is_did_indy().then_some(id)
+    }
 }
 
 impl Validatable for IssuerId {
@@ -118,4 +147,19 @@ mod test_issuer_identifiers {
         assert!(IssuerId::new(illegal_base58_legacy_identifier_captial_i).is_err());
         assert!(IssuerId::new(illegal_base58_legacy_identifier_lower_l).is_err());
     }
+
+    #[test]
+    fn should_convert_between_legacy_and_did_indy_identifiers() {
+        let legacy = IssuerId::new("6cgbu8ZPoWTnR5Rv5JcSMB").unwrap();
+        let did_indy = IssuerId::new("did:indy:sovrin:staging:6cgbu8ZPoWTnR5Rv5JcSMB").unwrap();
+
+        assert!(did_indy.is_did_indy());
+        assert_eq!(did_indy.did_indy_namespace(), Some("sovrin:staging"));
+        assert_eq!(did_indy.to_legacy(), Some(legacy.clone()));
+        assert_eq!(legacy.to_did_indy("sovrin:staging"), Some(did_indy));
+        assert_eq!(
+            IssuerId::new("did:web:example.com").unwrap().to_legacy(),
+            None
+        );
+    }
 }
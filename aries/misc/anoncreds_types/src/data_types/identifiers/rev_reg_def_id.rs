@@ -1,6 +1,9 @@
 use crate::{
     error::Error,
-    utils::validation::{Validatable, LEGACY_DID_IDENTIFIER, URI_IDENTIFIER},
+    utils::validation::{
+        Validatable, DID_INDY_REV_REG_DEF_IDENTIFIER, LEGACY_DID_IDENTIFIER,
+        LEGACY_REV_REG_DEF_IDENTIFIER, URI_IDENTIFIER,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, Default)]
@@ -24,6 +27,44 @@ impl RevocationRegistryDefinitionId {
     pub fn is_uri(&self) -> bool {
         URI_IDENTIFIER.captures(&self.0).is_some()
     }
+
+    /// e.g. `did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9/anoncreds/v0/REV_REG_DEF/56495/npdb/TAG1`
+    pub fn is_did_indy(&self) -> bool {
+        DID_INDY_REV_REG_DEF_IDENTIFIER.captures(&self.0).is_some()
+    }
+
+    pub fn did_indy_namespace(&self) -> Option<&str> {
+        let captures = DID_INDY_REV_REG_DEF_IDENTIFIER.captures(&self.0)?;
+        captures
+            .name("namespace")
+            .map(|namespace| namespace.as_str())
+    }
+
+    /// Converts a did:indy identifier into its legacy form, legacy identifiers are returned as is.
+    pub fn to_legacy(&self) -> Option<Self> {
+        if LEGACY_REV_REG_DEF_IDENTIFIER.captures(&self.0).is_some() {
+            return Some(self.clone());
+        }
+        let captures = DID_INDY_REV_REG_DEF_IDENTIFIER.captures(&self.0)?;
+        let did = &captures["did"];
+        Some(Self(format!(
+            "{did}:4:{did}:3:CL:{}:{}:CL_ACCUM:{}",
+            &captures["schema_seq_no"], &captures["cred_def_tag"], &captures["tag"]
+        )))
+    }
+
+    /// Converts a legacy identifier into the did:indy identifier of the indy network `namespace`.
+    pub fn to_did_indy(&self, namespace: &str) -> Option<Self> {
+        let captures = LEGACY_REV_REG_DEF_IDENTIFIER.captures(&self.0)?;
+        let id = Self(format!(
+            "did:indy:{namespace}:{}/anoncreds/v0/REV_REG_DEF/{}/{}/{}",
+            &captures["did"],
+            &captures["schema_seq_no"],
+            &captures["cred_def_tag"],
+            &captures["tag"]
+        ));
+        id.is_did_indy().then_some(id)
+    }
 }
 
 impl Validatable for RevocationRegistryDefinitionId {
@@ -74,3 +115,26 @@ impl std::fmt::Display for RevocationRegistryDefinitionId {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(test)]
+mod test_rev_reg_def_identifiers {
+    use super::*;
+
+    #[test]
+    fn should_convert_between_legacy_and_did_indy_identifiers() {
+        let legacy = RevocationRegistryDefinitionId::new(
+            "5nDyJVP1NrcPAttP3xwMB9:4:5nDyJVP1NrcPAttP3xwMB9:3:CL:56495:npdb:CL_ACCUM:TAG1",
+        )
+        .unwrap();
+        let did_indy = RevocationRegistryDefinitionId::new(
+            "did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9/anoncreds/v0/REV_REG_DEF/56495/npdb/TAG1",
+        )
+        .unwrap();
+
+        assert!(did_indy.is_did_indy());
+        assert_eq!(did_indy.did_indy_namespace(), Some("sovrin"));
+        assert_eq!(did_indy.to_legacy(), Some(legacy.clone()));
+        assert_eq!(legacy.to_legacy(), Some(legacy.clone()));
+        assert_eq!(legacy.to_did_indy("sovrin"), Some(did_indy));
+    }
+}
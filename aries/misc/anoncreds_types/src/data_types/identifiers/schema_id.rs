@@ -3,7 +3,9 @@
 // impl_anoncreds_object_identifier!(SchemaId);
 use crate::{
     error::Error,
-    utils::validation::{Validatable, LEGACY_SCHEMA_IDENTIFIER, URI_IDENTIFIER},
+    utils::validation::{
+        Validatable, DID_INDY_SCHEMA_IDENTIFIER, LEGACY_SCHEMA_IDENTIFIER, URI_IDENTIFIER,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, Default)]
@@ -27,6 +29,40 @@ impl SchemaId {
     pub fn is_uri(&self) -> bool {
         URI_IDENTIFIER.captures(&self.0).is_some()
     }
+
+    /// e.g. `did:indy:sovrin:F72i3Y3Q4i466efjYJYCHM/anoncreds/v0/SCHEMA/npdb/4.3.4`
+    pub fn is_did_indy(&self) -> bool {
+        DID_INDY_SCHEMA_IDENTIFIER.captures(&self.0).is_some()
+    }
+
+    pub fn did_indy_namespace(&self) -> Option<&str> {
+        let captures = DID_INDY_SCHEMA_IDENTIFIER.captures(&self.0)?;
+        captures
+            .name("namespace")
+            .map(|namespace| namespace.as_str())
+    }
+
+    /// Converts a did:indy identifier into its legacy form, legacy identifiers are returned as is.
+    pub fn to_legacy(&self) -> Option<Self> {
+        if self.is_legacy() {
+            return Some(self.clone());
+        }
+        let captures = DID_INDY_SCHEMA_IDENTIFIER.captures(&self.0)?;
+        Some(Self(format!(
+            "{}:2:{}:{}",
+            &captures["did"], &captures["name"], &captures["version"]
+        )))
+    }
+
+    /// Converts a legacy identifier into the did:indy identifier of the indy network `namespace`.
+    pub fn to_did_indy(&self, namespace: &str) -> Option<Self> {
+        let captures = LEGACY_SCHEMA_IDENTIFIER.captures(&self.0)?;
+        let id = Self(format!(
+            "did:indy:{namespace}:{}/anoncreds/v0/SCHEMA/{}/{}",
+            &captures["did"], &captures["name"], &captures["version"]
+        ));
+        id.is_did_indy().then_some(id)
+    }
 }
 
 impl Validatable for SchemaId {
@@ -77,3 +113,28 @@ impl std::fmt::Display for SchemaId {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(test)]
+mod test_schema_identifiers {
+    use super::*;
+
+    #[test]
+    fn should_convert_between_legacy_and_did_indy_identifiers() {
+        let legacy = SchemaId::new("F72i3Y3Q4i466efjYJYCHM:2:npdb:4.3.4").unwrap();
+        let did_indy =
+            SchemaId::new("did:indy:sovrin:F72i3Y3Q4i466efjYJYCHM/anoncreds/v0/SCHEMA/npdb/4.3.4")
+                .unwrap();
+
+        assert!(did_indy.is_did_indy());
+        assert!(!legacy.is_did_indy());
+        assert_eq!(did_indy.did_indy_namespace(), Some("sovrin"));
+        assert_eq!(did_indy.to_legacy(), Some(legacy.clone()));
+        assert_eq!(legacy.to_legacy(), Some(legacy.clone()));
+        assert_eq!(legacy.to_did_indy("sovrin"), Some(did_indy));
+        assert_eq!(legacy.to_did_indy("Invalid Namespace"), None);
+        assert_eq!(
+            SchemaId::new_unchecked("did:web:example.com").to_legacy(),
+            None
+        );
+    }
+}
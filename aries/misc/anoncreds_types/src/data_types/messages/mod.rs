@@ -6,5 +6,6 @@ pub mod credential;
 pub mod link_secret;
 pub mod nonce;
 pub mod pres_request;
+pub mod pres_request_builder;
 pub mod presentation;
 pub mod revocation_state;
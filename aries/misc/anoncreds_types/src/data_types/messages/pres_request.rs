@@ -377,6 +377,7 @@ fn _check_restriction(
     if *version == PresentationRequestVersion::V1
         && Credential::QUALIFIABLE_TAGS.contains(&tag_name)
         && validation::is_uri_identifier(tag_value)
+        && !validation::is_legacy_identifier(tag_value)
     {
         return Err(invalid!(
             "Presentation request validation failed: fully qualified identifiers can not be used \
@@ -429,6 +430,39 @@ mod tests {
         }
     }
 
+    mod restrictions {
+        use super::*;
+
+        fn request(ver: &str, cred_def_id: &str) -> PresentationRequest {
+            serde_json::from_value(json!({
+                "nonce": "123456",
+                "name": "name",
+                "version": "1.0",
+                "ver": ver,
+                "requested_attributes": {
+                    "name_ref": {"name": "name", "restrictions": {"cred_def_id": cred_def_id}},
+                },
+                "requested_predicates": {},
+            }))
+            .unwrap()
+        }
+
+        #[test]
+        fn presentation_request_v1_accepts_legacy_identifiers() {
+            request("1.0", "V4SGRU86Z58d6TV7PBUe6f:3:CL:16:tag")
+                .validate()
+                .unwrap();
+        }
+
+        #[test]
+        fn presentation_request_v1_rejects_qualified_identifiers() {
+            let did_indy_id =
+                "did:indy:sovrin:V4SGRU86Z58d6TV7PBUe6f/anoncreds/v0/CLAIM_DEF/16/tag";
+            request("1.0", did_indy_id).validate().unwrap_err();
+            request("2.0", did_indy_id).validate().unwrap();
+        }
+    }
+
     #[test]
     fn override_works() {
         let mut interval = NonRevokedInterval::default();
@@ -0,0 +1,443 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::{
+    nonce::Nonce,
+    pres_request::{
+        AttributeInfo, NonRevokedInterval, PredicateInfo, PredicateTypes, PredicateValue,
+        PresentationRequest, PresentationRequestPayload, PresentationRequestVersion,
+    },
+};
+use crate::{
+    invalid,
+    utils::{query::Query, validation::Validatable},
+};
+
+/// Fluent builder of [`PresentationRequest`]s. Problems with individual referents are collected
+/// while building and reported together by [`PresentationRequestBuilder::build`], which also
+/// generates the nonce of the request.
+#[derive(Clone, Debug, Default)]
+pub struct PresentationRequestBuilder {
+    template: PresentationRequestTemplate,
+    errors: Vec<String>,
+}
+
+impl PresentationRequestBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            template: PresentationRequestTemplate {
+                name: name.into(),
+                version: String::from("1.0"),
+                ..Default::default()
+            },
+            errors: vec![],
+        }
+    }
+
+    /// Version of the request itself, defaults to `1.0`.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.template.version = version.into();
+        self
+    }
+
+    /// Version of the presentation request format, required to be
+    /// [`PresentationRequestVersion::V2`] for restrictions on fully qualified identifiers.
+    pub fn ver(mut self, ver: PresentationRequestVersion) -> Self {
+        self.template.ver = ver;
+        self
+    }
+
+    /// Non-revocation interval of all referents without an interval of their own.
+    pub fn non_revoked(mut self, interval: NonRevokedInterval) -> Self {
+        self.template.non_revoked = Some(interval);
+        self
+    }
+
+    /// Requests the attribute `name` to be revealed.
+    pub fn attribute(self, referent: impl Into<String>, name: impl Into<String>) -> Self {
+        self.attribute_info(referent, AttributeInfo::builder().name(name.into()).build())
+    }
+
+    /// Requests the attributes `names` to be revealed from a single credential.
+    pub fn attribute_group<I, S>(self, referent: impl Into<String>, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let names = names.into_iter().map(Into::into).collect::<Vec<String>>();
+        self.attribute_info(referent, AttributeInfo::builder().names(names).build())
+    }
+
+    pub fn attribute_info(mut self, referent: impl Into<String>, info: AttributeInfo) -> Self {
+        let referent = referent.into();
+        if self.check_new_referent(&referent) {
+            self.template.requested_attributes.insert(referent, info);
+        }
+        self
+    }
+
+    pub fn predicate(
+        self,
+        referent: impl Into<String>,
+        name: impl Into<String>,
+        p_type: PredicateTypes,
+        p_value: PredicateValue,
+    ) -> Self {
+        let info = PredicateInfo::builder()
+            .name(name.into())
+            .p_type(p_type)
+            .p_value(p_value)
+            .build();
+        self.predicate_info(referent, info)
+    }
+
+    pub fn predicate_info(mut self, referent: impl Into<String>, info: PredicateInfo) -> Self {
+        let referent = referent.into();
+        if self.check_new_referent(&referent) {
+            self.template.requested_predicates.insert(referent, info);
+        }
+        self
+    }
+
+    /// Restricts the credentials accepted for an already added referent.
+    pub fn restrictions(mut self, referent: &str, restrictions: Query) -> Self {
+        if let Some(attribute) = self.template.requested_attributes.get_mut(referent) {
+            attribute.restrictions = Some(restrictions);
+        } else if let Some(predicate) = self.template.requested_predicates.get_mut(referent) {
+            predicate.restrictions = Some(restrictions);
+        } else {
+            self.errors.push(format!(
+                "restrictions set on unknown referent \"{referent}\""
+            ));
+        }
+        self
+    }
+
+    /// Restricts the credentials accepted for an already added referent by a WQL query, e.g.
+    /// `{"cred_def_id": "..."}`.
+    pub fn restrictions_json(self, referent: &str, restrictions: &str) -> Self {
+        match serde_json::from_str::<Query>(restrictions) {
+            Ok(query) => self.restrictions(referent, query),
+            Err(err) => {
+                let mut builder = self;
+                builder.errors.push(format!(
+                    "invalid restrictions of referent \"{referent}\": {err}"
+                ));
+                builder
+            }
+        }
+    }
+
+    /// Non-revocation interval of an already added referent.
+    pub fn referent_non_revoked(mut self, referent: &str, interval: NonRevokedInterval) -> Self {
+        if let Some(attribute) = self.template.requested_attributes.get_mut(referent) {
+            attribute.non_revoked = Some(interval);
+        } else if let Some(predicate) = self.template.requested_predicates.get_mut(referent) {
+            predicate.non_revoked = Some(interval);
+        } else {
+            self.errors.push(format!(
+                "non-revoked interval set on unknown referent \"{referent}\""
+            ));
+        }
+        self
+    }
+
+    fn check_new_referent(&mut self, referent: &str) -> bool {
+        if referent.is_empty() {
+            self.errors.push("referents must not be empty".to_string());
+            return false;
+        }
+        if self.template.requested_attributes.contains_key(referent)
+            || self.template.requested_predicates.contains_key(referent)
+        {
+            self.errors.push(format!(
+                "referent \"{referent}\" is requested more than once"
+            ));
+            return false;
+        }
+        true
+    }
+
+    /// Builds the request with a freshly generated nonce.
+    pub fn build(self) -> Result<PresentationRequest, crate::Error> {
+        self.build_with_nonce(Nonce::new()?)
+    }
+
+    pub fn build_with_nonce(self, nonce: Nonce) -> Result<PresentationRequest, crate::Error> {
+        if !self.errors.is_empty() {
+            return Err(invalid!(
+                "Presentation request validation failed: {}",
+                self.errors.join("; ")
+            ));
+        }
+        self.template.instantiate_with_nonce(nonce)
+    }
+
+    /// Validates the request and turns it into a reusable template.
+    pub fn into_template(self) -> Result<PresentationRequestTemplate, crate::Error> {
+        let template = self.template.clone();
+        self.build()?;
+        Ok(template)
+    }
+}
+
+/// Shape of a presentation request without its nonce, to be stored by the verifier and
+/// instantiated into a new request for every presentation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PresentationRequestTemplate {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub ver: PresentationRequestVersion,
+    #[serde(default)]
+    pub requested_attributes: HashMap<String, AttributeInfo>,
+    #[serde(default)]
+    pub requested_predicates: HashMap<String, PredicateInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevokedInterval>,
+}
+
+impl PresentationRequestTemplate {
+    /// Builder pre-filled with the template, to derive a modified template or request from it.
+    pub fn to_builder(&self) -> PresentationRequestBuilder {
+        PresentationRequestBuilder {
+            template: self.clone(),
+            errors: vec![],
+        }
+    }
+
+    /// Instantiates the template into a request with a freshly generated nonce.
+    pub fn instantiate(&self) -> Result<PresentationRequest, crate::Error> {
+        self.instantiate_with_nonce(Nonce::new()?)
+    }
+
+    pub fn instantiate_with_nonce(
+        &self,
+        nonce: Nonce,
+    ) -> Result<PresentationRequest, crate::Error> {
+        self.validate_intervals()?;
+        let payload = PresentationRequestPayload::builder()
+            .nonce(nonce)
+            .name(self.name.clone())
+            .version(self.version.clone())
+            .requested_attributes(self.requested_attributes.clone())
+            .requested_predicates(self.requested_predicates.clone())
+            .non_revoked(self.non_revoked.clone())
+            .build();
+        let request = match self.ver {
+            PresentationRequestVersion::V1 => payload.into_v1(),
+            PresentationRequestVersion::V2 => payload.into_v2(),
+        };
+        request.validate()?;
+        Ok(request)
+    }
+
+    fn validate_intervals(&self) -> Result<(), crate::Error> {
+        let intervals = self
+            .requested_attributes
+            .iter()
+            .map(|(referent, info)| (referent.as_str(), &info.non_revoked))
+            .chain(
+                self.requested_predicates
+                    .iter()
+                    .map(|(referent, info)| (referent.as_str(), &info.non_revoked)),
+            )
+            .chain(std::iter::once(("request", &self.non_revoked)));
+        for (referent, interval) in intervals {
+            if let Some(interval) = interval {
+                validate_interval(referent, interval)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_interval(referent: &str, interval: &NonRevokedInterval) -> Result<(), crate::Error> {
+    match (interval.from, interval.to) {
+        (None, None) => Err(invalid!(
+            "Presentation request validation failed: non-revoked interval of {referent} has \
+             neither `from` nor `to`"
+        )),
+        (Some(from), Some(to)) if from > to => Err(invalid!(
+            "Presentation request validation failed: non-revoked interval of {referent} ends \
+             ({to}) before it starts ({from})"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Named presentation request templates of a verifier.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct PresentationRequestTemplates {
+    templates: BTreeMap<String, PresentationRequestTemplate>,
+}
+
+impl PresentationRequestTemplates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the template under `template_name`, returning the template it replaced.
+    pub fn insert(
+        &mut self,
+        template_name: impl Into<String>,
+        template: PresentationRequestTemplate,
+    ) -> Option<PresentationRequestTemplate> {
+        self.templates.insert(template_name.into(), template)
+    }
+
+    pub fn remove(&mut self, template_name: &str) -> Option<PresentationRequestTemplate> {
+        self.templates.remove(template_name)
+    }
+
+    pub fn get(&self, template_name: &str) -> Option<&PresentationRequestTemplate> {
+        self.templates.get(template_name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// Instantiates the named template into a request with a freshly generated nonce.
+    pub fn instantiate(&self, template_name: &str) -> Result<PresentationRequest, crate::Error> {
+        self.get(template_name)
+            .ok_or_else(|| {
+                crate::Error::from_msg(
+                    crate::ErrorKind::Input,
+                    format!("Presentation request template \"{template_name}\" not found"),
+                )
+            })?
+            .instantiate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder() -> PresentationRequestBuilder {
+        PresentationRequestBuilder::new("proof_req")
+            .attribute("name_ref", "name")
+            .attribute_group("address_ref", ["street", "city"])
+            .predicate("age_ref", "age", PredicateTypes::GE, 18)
+            .restrictions_json("age_ref", &json!({ "schema_name": "gvt" }).to_string())
+    }
+
+    #[test]
+    fn test_build() {
+        let request = builder()
+            .non_revoked(NonRevokedInterval::new(None, Some(100)))
+            .build_with_nonce(Nonce::from_dec("123456").unwrap())
+            .unwrap();
+        let expected: PresentationRequest = serde_json::from_value(json!({
+            "nonce": "123456",
+            "name": "proof_req",
+            "version": "1.0",
+            "requested_attributes": {
+                "name_ref": {"name": "name"},
+                "address_ref": {"names": ["street", "city"]},
+            },
+            "requested_predicates": {
+                "age_ref": {
+                    "name": "age",
+                    "p_type": ">=",
+                    "p_value": 18,
+                    "restrictions": {"schema_name": "gvt"},
+                },
+            },
+            "non_revoked": {"from": null, "to": 100},
+        }))
+        .unwrap();
+        assert_eq!(request, expected);
+    }
+
+    #[test]
+    fn test_build_generates_nonce() {
+        let first = builder().build().unwrap();
+        let second = builder().build().unwrap();
+        assert_ne!(first.value().nonce, second.value().nonce);
+    }
+
+    #[test]
+    fn test_duplicate_and_unknown_referents() {
+        let err = builder()
+            .attribute("age_ref", "age")
+            .restrictions(
+                "unknown_ref",
+                Query::Exist(vec!["attr::name::value".into()]),
+            )
+            .build()
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("\"age_ref\" is requested more than once"));
+        assert!(message.contains("unknown referent \"unknown_ref\""));
+    }
+
+    #[test]
+    fn test_invalid_restrictions() {
+        assert!(builder()
+            .restrictions_json("name_ref", r#"{"$and": "cred_def_id"}"#)
+            .build()
+            .is_err());
+
+        // fully qualified identifiers require the second version of the request format
+        let did_indy_id = "did:indy:sovrin:V4SGRU86Z58d6TV7PBUe6f/anoncreds/v0/CLAIM_DEF/16/tag";
+        let restrictions = json!({ "cred_def_id": did_indy_id }).to_string();
+        assert!(builder()
+            .restrictions_json("name_ref", &restrictions)
+            .build()
+            .is_err());
+        assert!(builder()
+            .ver(PresentationRequestVersion::V2)
+            .restrictions_json("name_ref", &restrictions)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_invalid_non_revoked_intervals() {
+        assert!(builder()
+            .non_revoked(NonRevokedInterval::new(Some(200), Some(100)))
+            .build()
+            .is_err());
+        assert!(builder()
+            .referent_non_revoked("name_ref", NonRevokedInterval::new(None, None))
+            .build()
+            .is_err());
+        assert!(builder()
+            .referent_non_revoked("name_ref", NonRevokedInterval::new(Some(100), Some(100)))
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_templates() {
+        let template = builder().into_template().unwrap();
+        let serialized = serde_json::to_string(&template).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PresentationRequestTemplate>(&serialized).unwrap(),
+            template
+        );
+
+        let mut templates = PresentationRequestTemplates::new();
+        assert!(templates.insert("adult", template.clone()).is_none());
+        let request = templates.instantiate("adult").unwrap();
+        assert_eq!(request.value().name, "proof_req");
+        assert_eq!(
+            request.value().requested_predicates,
+            template.requested_predicates
+        );
+        assert_ne!(
+            templates.instantiate("adult").unwrap().value().nonce,
+            request.value().nonce
+        );
+        assert!(templates.instantiate("unknown").is_err());
+
+        let extended = template
+            .to_builder()
+            .attribute("email_ref", "email")
+            .into_template()
+            .unwrap();
+        assert_eq!(extended.requested_attributes.len(), 3);
+    }
+}
@@ -15,21 +15,73 @@ pub static URI_IDENTIFIER: Lazy<Regex> =
 pub static LEGACY_DID_IDENTIFIER: Lazy<Regex> =
     Lazy::new(|| Regex::new("^[1-9A-HJ-NP-Za-km-z]{21,22}$").unwrap());
 
-pub static LEGACY_SCHEMA_IDENTIFIER: Lazy<Regex> =
-    Lazy::new(|| Regex::new("^[1-9A-HJ-NP-Za-km-z]{21,22}:2:.+:[0-9.]+$").unwrap());
+pub static LEGACY_SCHEMA_IDENTIFIER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("^(?P<did>[1-9A-HJ-NP-Za-km-z]{21,22}):2:(?P<name>.+):(?P<version>[0-9.]+)$")
+        .unwrap()
+});
 
 pub static LEGACY_CRED_DEF_IDENTIFIER: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
-        "^[1-9A-HJ-NP-Za-km-z]{21,22}:3:CL:(([1-9][0-9]*)|([a-zA-Z0-9]{21,22}:2:.+:[0-9.]+)):(.+)?\
-         $",
+        "^(?P<did>[1-9A-HJ-NP-Za-km-z]{21,22}):3:CL:((?P<schema_seq_no>[1-9][0-9]*)|([a-zA-Z0-9]{21,\
+         22}:2:.+:[0-9.]+)):(?P<tag>.+)?$",
+    )
+    .unwrap()
+});
+
+/// Legacy revocation registry identifiers referencing their credential definition by schema
+/// sequence number, e.g. `did:4:did:3:CL:<seq_no>:<cred_def_tag>:CL_ACCUM:<tag>`
+pub static LEGACY_REV_REG_DEF_IDENTIFIER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        "^(?P<did>[1-9A-HJ-NP-Za-km-z]{21,22}):4:[1-9A-HJ-NP-Za-km-z]{21,22}:3:CL:(?P<schema_seq_no>\
+         [1-9][0-9]*):(?P<cred_def_tag>.+):CL_ACCUM:(?P<tag>.+)$",
     )
     .unwrap()
 });
 
+/// did:indy namespace and namespace identifier, see
+/// [indy-did-method](https://hyperledger.github.io/indy-did-method/#indy-did-method-identifiers)
+const DID_INDY: &str =
+    "did:indy:(?P<namespace>[a-z0-9_-]+(:[a-z0-9_-]+)?):(?P<did>[1-9A-HJ-NP-Za-km-z]{21,22})";
+
+pub static DID_INDY_IDENTIFIER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!("^{DID_INDY}$")).unwrap());
+
+/// See [indy-did-method](https://hyperledger.github.io/indy-did-method/#schema)
+pub static DID_INDY_SCHEMA_IDENTIFIER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        "^{DID_INDY}/anoncreds/v0/SCHEMA/(?P<name>[^/]+)/(?P<version>[0-9.]+)$"
+    ))
+    .unwrap()
+});
+
+/// See [indy-did-method](https://hyperledger.github.io/indy-did-method/#cred_def)
+pub static DID_INDY_CRED_DEF_IDENTIFIER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        "^{DID_INDY}/anoncreds/v0/CLAIM_DEF/(?P<schema_seq_no>[1-9][0-9]*)/(?P<tag>[^/]+)$"
+    ))
+    .unwrap()
+});
+
+/// See [indy-did-method](https://hyperledger.github.io/indy-did-method/#revoc_reg_def)
+pub static DID_INDY_REV_REG_DEF_IDENTIFIER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        "^{DID_INDY}/anoncreds/v0/REV_REG_DEF/(?P<schema_seq_no>[1-9][0-9]*)/(?P<cred_def_tag>[^/\
+         ]+)/(?P<tag>[^/]+)$"
+    ))
+    .unwrap()
+});
+
 pub fn is_uri_identifier(id: &str) -> bool {
     URI_IDENTIFIER.captures(id).is_some()
 }
 
+/// Legacy indy identifiers, e.g. `did:3:CL:<seq_no>:<tag>`, also match [`URI_IDENTIFIER`], but
+/// start with an unqualified DID rather than a URI scheme.
+pub fn is_legacy_identifier(id: &str) -> bool {
+    let did = id.split(':').next().unwrap_or(id);
+    LEGACY_DID_IDENTIFIER.captures(did).is_some()
+}
+
 #[macro_export]
 macro_rules! invalid {
     ($($arg:tt)+) => {
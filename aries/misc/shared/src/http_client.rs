@@ -3,6 +3,7 @@ use std::time::Duration;
 use reqwest::{
     self,
     header::{CONTENT_TYPE, USER_AGENT},
+    multipart::{Form, Part},
     Client, Response, Url,
 };
 
@@ -28,6 +29,70 @@ pub async fn post_message(body_content: Vec<u8>, url: &Url) -> HttpResult<Vec<u8
     process_response(response).await
 }
 
+/// Downloads binary content, passing it to `on_chunk` as it arrives instead of buffering the
+/// whole body. Fails on non-success HTTP statuses.
+pub async fn get_chunked<F>(url: &Url, mut on_chunk: F) -> HttpResult<()>
+where
+    F: FnMut(&[u8]) -> HttpResult<()> + Send,
+{
+    debug!("get_chunked >> http client sending request GET {}", &url);
+
+    let mut response = HTTP_CLIENT
+        .get(url.clone())
+        .header(USER_AGENT, "reqwest")
+        .send()
+        .await
+        .map_err(|err| HttpError::from_msg(format!("HTTP Client could not connect, err: {err}")))?;
+    let response_status = response.status();
+    if !response_status.is_success() {
+        return Err(HttpError::from_msg(format!(
+            "GET failed due to non-success HTTP status: {response_status}"
+        )));
+    }
+    while let Some(chunk) = response.chunk().await.map_err(|err| {
+        HttpError::from_msg(format!("GET failed to read response body, err: {err}"))
+    })? {
+        on_chunk(&chunk)?;
+    }
+    Ok(())
+}
+
+/// A file sent as one part of a `multipart/form-data` request.
+pub struct MultipartFile {
+    pub field_name: String,
+    pub file_name: String,
+    pub content: Vec<u8>,
+}
+
+/// Sends files as a `multipart/form-data` PUT request, returning the response body. The
+/// `bearer_token`, if any, is sent in the `Authorization` header.
+pub async fn put_multipart(
+    url: &Url,
+    files: Vec<MultipartFile>,
+    bearer_token: Option<&str>,
+) -> HttpResult<Vec<u8>> {
+    debug!("put_multipart >> http client sending request PUT {}", &url);
+
+    let form = files.into_iter().fold(Form::new(), |form, file| {
+        form.part(
+            file.field_name,
+            Part::bytes(file.content).file_name(file.file_name),
+        )
+    });
+    let mut request = HTTP_CLIENT
+        .put(url.clone())
+        .multipart(form)
+        .header(USER_AGENT, "reqwest");
+    if let Some(bearer_token) = bearer_token {
+        request = request.bearer_auth(bearer_token);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|err| HttpError::from_msg(format!("HTTP Client could not connect, err: {err}")))?;
+    process_response(response).await
+}
+
 async fn send_post_request(url: &Url, body_content: Vec<u8>) -> HttpResult<Response> {
     HTTP_CLIENT
         .post(url.clone())
@@ -48,12 +113,12 @@ async fn process_response(response: Response) -> HttpResult<Vec<u8>> {
                 Ok(payload.into_bytes())
             } else {
                 Err(HttpError::from_msg(format!(
-                    "POST failed due to non-success HTTP status: {response_status}, response body: {payload}"
+                    "Request failed due to non-success HTTP status: {response_status}, response body: {payload}"
                 )))
             }
         }
         Err(error) => Err(HttpError::from_msg(format!(
-            "POST failed because response could not be decoded as utf-8, HTTP status: {response_status}, \
+            "Request failed because response could not be decoded as utf-8, HTTP status: {response_status}, \
              content-length header: {content_length:?}, error: {error:?}"
         ))),
     }
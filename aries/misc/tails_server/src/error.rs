@@ -0,0 +1,39 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TailsServerError {
+    #[error("Invalid tails file: {0}")]
+    InvalidTailsFile(String),
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("A different tails file is already stored for revocation registry {0}")]
+    Conflict(String),
+    #[error("Missing or invalid upload token")]
+    Unauthorized,
+    #[error("Tails file not found")]
+    NotFound,
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl IntoResponse for TailsServerError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            TailsServerError::InvalidTailsFile(_) | TailsServerError::InvalidRequest(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            TailsServerError::Unauthorized => StatusCode::UNAUTHORIZED,
+            TailsServerError::Conflict(_) => StatusCode::CONFLICT,
+            TailsServerError::NotFound => StatusCode::NOT_FOUND,
+            TailsServerError::IoError(ref err) => {
+                log::error!("Tails storage failed: {err}");
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        (status, self.to_string()).into_response()
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use log::info;
+use tails_server::{server::build_router, store::TailsStore};
+
+#[tokio::main]
+async fn main() {
+    load_dot_env();
+    setup_logging();
+    let address = std::env::var("TAILS_SERVER_ADDRESS").unwrap_or("127.0.0.1:6543".into());
+    let storage_dir = std::env::var("TAILS_SERVER_STORAGE_DIR").unwrap_or("./tails".into());
+    let upload_token = std::env::var("TAILS_SERVER_UPLOAD_TOKEN")
+        .expect("TAILS_SERVER_UPLOAD_TOKEN must be set to authorize uploads");
+
+    let store = TailsStore::new(&storage_dir).await.unwrap();
+    let router = build_router(Arc::new(store), &upload_token);
+    info!("Serving tails files stored in {storage_dir} on {address}");
+    let listener = tokio::net::TcpListener::bind(&address).await.unwrap();
+    axum::serve(listener, router.into_make_service())
+        .await
+        .unwrap();
+}
+
+fn setup_logging() {
+    let env = env_logger::Env::default().default_filter_or("info");
+    env_logger::init_from_env(env);
+}
+
+fn load_dot_env() {
+    let _ = dotenvy::dotenv();
+}
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{DefaultBodyLimit, FromRef, Multipart, Path, State},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap,
+    },
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{error::TailsServerError, store::TailsStore};
+
+/// Tails files grow with the capacity of the revocation registry, 128 bytes per credential
+pub const MAX_TAILS_FILE_SIZE: usize = 256 * 1024 * 1024;
+
+const OCTET_STREAM_CONTENT_TYPE: &str = "application/octet-stream";
+
+#[derive(Clone)]
+struct ServerState {
+    store: Arc<TailsStore>,
+    upload_token: Arc<str>,
+}
+
+impl FromRef<ServerState> for Arc<TailsStore> {
+    fn from_ref(state: &ServerState) -> Self {
+        state.store.clone()
+    }
+}
+
+/// Uploads must carry the configured token as `Authorization: Bearer <token>`, otherwise anyone
+/// could claim a revocation registry by uploading first. Digests are compared so that the
+/// comparison does not leak the token through timing.
+fn authorize_upload(headers: &HeaderMap, upload_token: &str) -> Result<(), TailsServerError> {
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(TailsServerError::Unauthorized)?;
+    if Sha256::digest(token) != Sha256::digest(upload_token) {
+        return Err(TailsServerError::Unauthorized);
+    }
+    Ok(())
+}
+
+async fn upload(
+    State(state): State<ServerState>,
+    Path(rev_reg_def_id): Path<String>,
+    headers: HeaderMap,
+    mut multipart: Multipart,
+) -> Result<String, TailsServerError> {
+    authorize_upload(&headers, &state.upload_token)?;
+    let mut tails = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|err| TailsServerError::InvalidRequest(err.to_string()))?
+    {
+        // the genesis transactions are only needed to verify uploads against the ledger
+        if field.name() == Some("tails") {
+            let content = field
+                .bytes()
+                .await
+                .map_err(|err| TailsServerError::InvalidRequest(err.to_string()))?;
+            tails = Some(content);
+        }
+    }
+    let tails =
+        tails.ok_or_else(|| TailsServerError::InvalidRequest("missing tails file".into()))?;
+    state.store.put(&rev_reg_def_id, &tails).await
+}
+
+async fn download_by_rev_reg_def_id(
+    State(store): State<Arc<TailsStore>>,
+    Path(rev_reg_def_id): Path<String>,
+) -> Result<impl IntoResponse, TailsServerError> {
+    let content = store.get_by_rev_reg_def_id(&rev_reg_def_id).await?;
+    Ok(([(CONTENT_TYPE, OCTET_STREAM_CONTENT_TYPE)], content))
+}
+
+async fn download_by_hash(
+    State(store): State<Arc<TailsStore>>,
+    Path(tails_hash): Path<String>,
+) -> Result<impl IntoResponse, TailsServerError> {
+    let content = store.get_by_hash(&tails_hash).await?;
+    Ok(([(CONTENT_TYPE, OCTET_STREAM_CONTENT_TYPE)], content))
+}
+
+/// Serves the tails files of `store` through the indy-tails-server API:
+/// `PUT /{rev_reg_def_id}` uploads the `tails` part of a multipart form, `GET /{rev_reg_def_id}`
+/// and `GET /hash/{tails_hash}` download tails files. Uploads are only accepted with
+/// `upload_token` as bearer token.
+pub fn build_router<S>(store: Arc<TailsStore>, upload_token: &str) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route(
+            "/{rev_reg_def_id}",
+            get(download_by_rev_reg_def_id).put(upload),
+        )
+        .route("/hash/{tails_hash}", get(download_by_hash))
+        .layer(DefaultBodyLimit::max(MAX_TAILS_FILE_SIZE))
+        .with_state(ServerState {
+            store,
+            upload_token: upload_token.into(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_authorize_upload() {
+        authorize_upload(&headers("Bearer secret"), "secret").unwrap();
+        assert!(matches!(
+            authorize_upload(&headers("Bearer other"), "secret"),
+            Err(TailsServerError::Unauthorized)
+        ));
+        assert!(matches!(
+            authorize_upload(&headers("secret"), "secret"),
+            Err(TailsServerError::Unauthorized)
+        ));
+        assert!(matches!(
+            authorize_upload(&HeaderMap::new(), "secret"),
+            Err(TailsServerError::Unauthorized)
+        ));
+    }
+}
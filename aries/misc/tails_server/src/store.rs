@@ -0,0 +1,221 @@
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::error::TailsServerError;
+
+const TAILS_FILE_VERSION: [u8; 2] = [0, 2];
+const TAILS_POINT_SIZE: usize = 128;
+
+/// Base58 encoded SHA-256 digest of a tails file, as referenced by revocation registry
+/// definitions.
+pub fn tails_hash(content: &[u8]) -> String {
+    bs58::encode(Sha256::digest(content)).into_string()
+}
+
+/// Checks the tails file layout written by anoncreds: a two byte version header followed by
+/// 128 byte points.
+pub fn validate_tails_file(content: &[u8]) -> Result<(), TailsServerError> {
+    if content.len() <= TAILS_FILE_VERSION.len() || content[..2] != TAILS_FILE_VERSION {
+        return Err(TailsServerError::InvalidTailsFile(
+            "unsupported tails file version".into(),
+        ));
+    }
+    if (content.len() - TAILS_FILE_VERSION.len()) % TAILS_POINT_SIZE != 0 {
+        return Err(TailsServerError::InvalidTailsFile(format!(
+            "tails file size is not a multiple of the point size ({TAILS_POINT_SIZE} bytes)"
+        )));
+    }
+    Ok(())
+}
+
+/// Filesystem storage of tails files. Files are stored under their hash in `{dir}/hash`, the
+/// revocation registries referencing them in `{dir}/rev_reg`.
+pub struct TailsStore {
+    dir: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl TailsStore {
+    pub async fn new(dir: impl Into<PathBuf>) -> Result<Self, TailsServerError> {
+        let dir = dir.into();
+        tokio::fs::create_dir_all(dir.join("hash")).await?;
+        tokio::fs::create_dir_all(dir.join("rev_reg")).await?;
+        Ok(Self {
+            dir,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn tails_path(&self, tails_hash: &str) -> Result<PathBuf, TailsServerError> {
+        match bs58::decode(tails_hash).into_vec() {
+            Ok(digest) if digest.len() == 32 => Ok(self.dir.join("hash").join(tails_hash)),
+            _ => Err(TailsServerError::InvalidRequest(format!(
+                "invalid tails hash: {tails_hash}"
+            ))),
+        }
+    }
+
+    // revocation registry ids contain characters which are not valid in file names on every
+    // platform
+    fn rev_reg_path(&self, rev_reg_def_id: &str) -> PathBuf {
+        self.dir
+            .join("rev_reg")
+            .join(bs58::encode(rev_reg_def_id).into_string())
+    }
+
+    /// Stores the tails file of a revocation registry, returning its tails hash. Uploading the
+    /// same file again is a no-op.
+    pub async fn put(
+        &self,
+        rev_reg_def_id: &str,
+        content: &[u8],
+    ) -> Result<String, TailsServerError> {
+        if rev_reg_def_id.is_empty() {
+            return Err(TailsServerError::InvalidRequest(
+                "missing revocation registry id".into(),
+            ));
+        }
+        validate_tails_file(content)?;
+        let tails_hash = tails_hash(content);
+
+        let _guard = self.write_lock.lock().await;
+        match self.hash_of(rev_reg_def_id).await? {
+            Some(existing) if existing == tails_hash => return Ok(tails_hash),
+            Some(_) => return Err(TailsServerError::Conflict(rev_reg_def_id.to_string())),
+            None => {}
+        }
+
+        let tails_path = self.tails_path(&tails_hash)?;
+        if !tokio::fs::try_exists(&tails_path).await? {
+            self.write_atomically(&tails_path, content).await?;
+        }
+        self.write_atomically(&self.rev_reg_path(rev_reg_def_id), tails_hash.as_bytes())
+            .await?;
+        log::info!("Stored tails file {tails_hash} of revocation registry {rev_reg_def_id}");
+        Ok(tails_hash)
+    }
+
+    /// Tails hash of the file stored for the revocation registry.
+    pub async fn hash_of(&self, rev_reg_def_id: &str) -> Result<Option<String>, TailsServerError> {
+        match tokio::fs::read_to_string(self.rev_reg_path(rev_reg_def_id)).await {
+            Ok(tails_hash) => Ok(Some(tails_hash)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub async fn get_by_hash(&self, tails_hash: &str) -> Result<Vec<u8>, TailsServerError> {
+        match tokio::fs::read(self.tails_path(tails_hash)?).await {
+            Ok(content) => Ok(content),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                Err(TailsServerError::NotFound)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub async fn get_by_rev_reg_def_id(
+        &self,
+        rev_reg_def_id: &str,
+    ) -> Result<Vec<u8>, TailsServerError> {
+        let tails_hash = self
+            .hash_of(rev_reg_def_id)
+            .await?
+            .ok_or(TailsServerError::NotFound)?;
+        self.get_by_hash(&tails_hash).await
+    }
+
+    async fn write_atomically(&self, path: &Path, content: &[u8]) -> Result<(), TailsServerError> {
+        let tmp_path = self.dir.join(format!("{}.tmp", Uuid::new_v4()));
+        tokio::fs::write(&tmp_path, content).await?;
+        if let Err(err) = tokio::fs::rename(&tmp_path, path).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(err.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tails_file(points: u8) -> Vec<u8> {
+        let mut content = TAILS_FILE_VERSION.to_vec();
+        for point in 0..points {
+            content.extend([point; TAILS_POINT_SIZE]);
+        }
+        content
+    }
+
+    async fn temp_store() -> TailsStore {
+        TailsStore::new(std::env::temp_dir().join(format!("tails_store_{}", Uuid::new_v4())))
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_validate_tails_file() {
+        validate_tails_file(&tails_file(2)).unwrap();
+        assert!(validate_tails_file(&tails_file(0)).is_err());
+        assert!(validate_tails_file(&[1, 2, 3]).is_err());
+        let mut truncated = tails_file(2);
+        truncated.pop();
+        assert!(validate_tails_file(&truncated).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_put_and_get() {
+        let store = temp_store().await;
+        let content = tails_file(3);
+
+        let tails_hash = store.put("rev_reg:1", &content).await.unwrap();
+        assert_eq!(tails_hash, super::tails_hash(&content));
+        // idempotent
+        assert_eq!(store.put("rev_reg:1", &content).await.unwrap(), tails_hash);
+
+        assert_eq!(
+            store.get_by_rev_reg_def_id("rev_reg:1").await.unwrap(),
+            content
+        );
+        assert_eq!(store.get_by_hash(&tails_hash).await.unwrap(), content);
+        assert!(matches!(
+            store.get_by_rev_reg_def_id("rev_reg:2").await,
+            Err(TailsServerError::NotFound)
+        ));
+
+        tokio::fs::remove_dir_all(store.dir()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_put_conflicting_file() {
+        let store = temp_store().await;
+
+        store.put("rev_reg:1", &tails_file(3)).await.unwrap();
+        assert!(matches!(
+            store.put("rev_reg:1", &tails_file(4)).await,
+            Err(TailsServerError::Conflict(_))
+        ));
+
+        tokio::fs::remove_dir_all(store.dir()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_by_invalid_hash() {
+        let store = temp_store().await;
+
+        assert!(matches!(
+            store.get_by_hash("../rev_reg").await,
+            Err(TailsServerError::InvalidRequest(_))
+        ));
+
+        tokio::fs::remove_dir_all(store.dir()).await.unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use reqwest::{
+    multipart::{Form, Part},
+    StatusCode,
+};
+use tails_server::{
+    server::build_router,
+    store::{tails_hash, TailsStore},
+};
+
+const UPLOAD_TOKEN: &str = "upload-token";
+const REV_REG_DEF_ID: &str =
+    "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:16:tag:CL_ACCUM:1";
+
+fn tails_file(points: u8) -> Vec<u8> {
+    let mut content = vec![0, 2];
+    for point in 0..points {
+        content.extend([point; 128]);
+    }
+    content
+}
+
+fn upload_form(content: Vec<u8>) -> Form {
+    Form::new()
+        .part("genesis", Part::bytes(b"{}".to_vec()).file_name("genesis"))
+        .part("tails", Part::bytes(content).file_name("tails"))
+}
+
+#[tokio::test]
+async fn test_upload_and_download_tails_file() {
+    let storage_dir = std::env::temp_dir().join(format!("tails_server_{}", uuid::Uuid::new_v4()));
+    let store = TailsStore::new(&storage_dir).await.unwrap();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            build_router(Arc::new(store), UPLOAD_TOKEN).into_make_service(),
+        )
+        .await
+        .unwrap()
+    });
+    let client = reqwest::Client::new();
+    let content = tails_file(4);
+
+    // uploads without the token are rejected, so nobody else can claim the registry
+    let response = client
+        .put(format!("{base_url}/{REV_REG_DEF_ID}"))
+        .multipart(upload_form(tails_file(5)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = client
+        .put(format!("{base_url}/{REV_REG_DEF_ID}"))
+        .bearer_auth("wrong-token")
+        .multipart(upload_form(tails_file(5)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = client
+        .put(format!("{base_url}/{REV_REG_DEF_ID}"))
+        .bearer_auth(UPLOAD_TOKEN)
+        .multipart(upload_form(content.clone()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let hash = response.text().await.unwrap();
+    assert_eq!(hash, tails_hash(&content));
+
+    let response = client
+        .get(format!("{base_url}/{REV_REG_DEF_ID}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.bytes().await.unwrap().to_vec(), content);
+
+    let response = client
+        .get(format!("{base_url}/hash/{hash}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.bytes().await.unwrap().to_vec(), content);
+
+    let response = client
+        .put(format!("{base_url}/{REV_REG_DEF_ID}"))
+        .bearer_auth(UPLOAD_TOKEN)
+        .multipart(upload_form(tails_file(5)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = client
+        .put(format!("{base_url}/other_rev_reg"))
+        .bearer_auth(UPLOAD_TOKEN)
+        .multipart(upload_form(vec![1, 2, 3]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = client
+        .get(format!("{base_url}/unknown_rev_reg"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    tokio::fs::remove_dir_all(storage_dir).await.unwrap();
+}
@@ -239,6 +239,16 @@ impl BaseAnonCreds for MockAnoncreds {
         Ok(())
     }
 
+    async fn revoke_credentials_local(
+        &self,
+        _wallet: &impl BaseWallet,
+        _rev_reg_id: &RevocationRegistryDefinitionId,
+        _cred_rev_ids: &[u32],
+        _rev_reg_delta_json: RevocationRegistryDelta,
+    ) -> VcxAnoncredsResult<()> {
+        Ok(())
+    }
+
     async fn get_rev_reg_delta(
         &self,
         _wallet: &impl BaseWallet,
@@ -43,6 +43,17 @@ impl IndyLedgerRead for MockLedger {
         ))
     }
 
+    async fn get_nym_version(
+        &self,
+        did: &Did,
+        seq_no: Option<i32>,
+        timestamp: Option<u64>,
+    ) -> VcxLedgerResult<String> {
+        Err(VcxLedgerError::UnimplementedFeature(
+            "unimplemented mock method: get_nym_version".into(),
+        ))
+    }
+
     async fn get_attr(&self, target_did: &Did, attr_name: &str) -> VcxLedgerResult<String> {
         Ok(r#"{"rc":"success"}"#.to_string())
     }
@@ -1,3 +1,5 @@
+use public_key::KeyType;
+
 use crate::schema::verification_method::{error::KeyDecodingError, VerificationMethodType};
 
 #[derive(Debug)]
@@ -7,6 +9,7 @@ pub enum DidDocumentBuilderError {
     JsonError(serde_json::Error),
     KeyDecodingError(KeyDecodingError),
     UnsupportedVerificationMethodType(VerificationMethodType),
+    UnsupportedKeyType(KeyType, VerificationMethodType),
     PublicKeyError(public_key::PublicKeyError),
 }
 
@@ -22,6 +25,13 @@ impl std::fmt::Display for DidDocumentBuilderError {
             DidDocumentBuilderError::UnsupportedVerificationMethodType(vm_type) => {
                 write!(f, "Unsupported verification method type: {vm_type}")
             }
+            DidDocumentBuilderError::UnsupportedKeyType(key_type, vm_type) => {
+                write!(
+                    f,
+                    "Key type {key_type} can not be represented by verification method type \
+                     {vm_type}"
+                )
+            }
             DidDocumentBuilderError::PublicKeyError(error) => {
                 write!(f, "Public key error: {error}")
             }
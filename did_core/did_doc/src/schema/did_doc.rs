@@ -1,3 +1,5 @@
+mod diff;
+
 use std::collections::HashMap;
 
 use did_parser_nom::{Did, DidUrl};
@@ -5,12 +7,18 @@ use display_as_json::Display;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+pub use self::diff::{
+    DidDocumentOperation, DidDocumentPatch, DidDocumentPatchError, VerificationRelationship,
+};
 use super::{
     types::uri::Uri,
     utils::OneOrList,
     verification_method::{VerificationMethod, VerificationMethodKind},
 };
-use crate::{error::DidDocumentBuilderError, schema::service::Service};
+use crate::{
+    error::DidDocumentBuilderError,
+    schema::{contexts, service::Service},
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, Display)]
 #[serde(default)]
@@ -324,10 +332,79 @@ impl DidDocument {
     pub fn remove_extra_field(&mut self, key: &str) {
         self.extra.remove(key);
     }
+
+    /// Converts every verification method embedded in the document to `Multikey`, adding the
+    /// Multikey context to `@context` if the document has one. References to verification
+    /// methods are kept as they are. The document is left unchanged on error.
+    pub fn normalize_to_multikey(&mut self) -> Result<(), DidDocumentBuilderError> {
+        let verification_method = self
+            .verification_method
+            .iter()
+            .map(VerificationMethod::to_multikey)
+            .collect::<Result<Vec<_>, _>>()?;
+        let authentication = relationship_to_multikey(&self.authentication)?;
+        let assertion_method = relationship_to_multikey(&self.assertion_method)?;
+        let key_agreement = relationship_to_multikey(&self.key_agreement)?;
+        let capability_invocation = relationship_to_multikey(&self.capability_invocation)?;
+        let capability_delegation = relationship_to_multikey(&self.capability_delegation)?;
+
+        let has_embedded_methods = !verification_method.is_empty()
+            || [
+                &authentication,
+                &assertion_method,
+                &key_agreement,
+                &capability_invocation,
+                &capability_delegation,
+            ]
+            .iter()
+            .flat_map(|methods| methods.iter())
+            .any(|method| method.resolved().is_some());
+        if let (true, Some(context)) = (has_embedded_methods, self.extra.get_mut("@context")) {
+            let multikey = Value::from(contexts::W3C_MULTIKEY_V1);
+            match context {
+                Value::Array(values) => {
+                    if !values.contains(&multikey) {
+                        values.push(multikey);
+                    }
+                }
+                context => {
+                    if *context != multikey {
+                        *context = Value::Array(vec![context.take(), multikey]);
+                    }
+                }
+            }
+        }
+
+        self.verification_method = verification_method;
+        self.authentication = authentication;
+        self.assertion_method = assertion_method;
+        self.key_agreement = key_agreement;
+        self.capability_invocation = capability_invocation;
+        self.capability_delegation = capability_delegation;
+        Ok(())
+    }
+}
+
+fn relationship_to_multikey(
+    methods: &[VerificationMethodKind],
+) -> Result<Vec<VerificationMethodKind>, DidDocumentBuilderError> {
+    methods
+        .iter()
+        .map(|method| match method {
+            VerificationMethodKind::Resolved(vm) => {
+                Ok(VerificationMethodKind::Resolved(vm.to_multikey()?))
+            }
+            VerificationMethodKind::Resolvable(reference) => {
+                Ok(VerificationMethodKind::Resolvable(reference.clone()))
+            }
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use public_key::{Key, KeyType};
+
     use super::*;
     use crate::schema::verification_method::{PublicKeyField, VerificationMethodType};
 
@@ -655,4 +732,109 @@ mod tests {
             assert_eq!(vm.id().to_string(), "#keys-3");
         }
     }
+
+    #[test]
+    fn test_normalize_to_multikey() {
+        let mut did_doc: DidDocument = serde_json::from_value(serde_json::json!({
+            "@context": [
+                "https://www.w3.org/ns/did/v1",
+                "https://w3id.org/security/suites/ed25519-2018/v1",
+                "https://w3id.org/security/suites/x25519-2019/v1"
+            ],
+            "id": "did:web:did-actor-alice",
+            "verificationMethod": [{
+                "id": "did:web:did-actor-alice#key-1",
+                "type": "Ed25519VerificationKey2018",
+                "controller": "did:web:did-actor-alice",
+                "publicKeyBase58": "DK7uJiq9PnPnj7AmNZqVBFoLuwTjT1hFPrk6LSjZ2JRz"
+            }],
+            "authentication": ["did:web:did-actor-alice#key-1"],
+            "keyAgreement": [{
+                "id": "did:web:did-actor-alice#key-2",
+                "type": "X25519KeyAgreementKey2019",
+                "controller": "did:web:did-actor-alice",
+                "publicKeyBase58": "CaSHXEvLKS6SfN9aBfkVGBpp15jSnaHazqHgLHp8KZ3Y"
+            }]
+        }))
+        .unwrap();
+        let original = did_doc.clone();
+
+        did_doc.normalize_to_multikey().unwrap();
+
+        let ed25519_key = Key::from_base58(
+            "DK7uJiq9PnPnj7AmNZqVBFoLuwTjT1hFPrk6LSjZ2JRz",
+            KeyType::Ed25519,
+        )
+        .unwrap();
+        let x25519_key = Key::from_base58(
+            "CaSHXEvLKS6SfN9aBfkVGBpp15jSnaHazqHgLHp8KZ3Y",
+            KeyType::X25519,
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&did_doc).unwrap(),
+            serde_json::json!({
+                "@context": [
+                    "https://www.w3.org/ns/did/v1",
+                    "https://w3id.org/security/suites/ed25519-2018/v1",
+                    "https://w3id.org/security/suites/x25519-2019/v1",
+                    "https://w3id.org/security/multikey/v1"
+                ],
+                "id": "did:web:did-actor-alice",
+                "verificationMethod": [{
+                    "id": "did:web:did-actor-alice#key-1",
+                    "type": "Multikey",
+                    "controller": "did:web:did-actor-alice",
+                    "publicKeyMultibase": ed25519_key.fingerprint()
+                }],
+                "authentication": ["did:web:did-actor-alice#key-1"],
+                "keyAgreement": [{
+                    "id": "did:web:did-actor-alice#key-2",
+                    "type": "Multikey",
+                    "controller": "did:web:did-actor-alice",
+                    "publicKeyMultibase": x25519_key.fingerprint()
+                }]
+            })
+        );
+        for (normalized, original) in did_doc
+            .verification_method()
+            .iter()
+            .zip(original.verification_method())
+        {
+            assert_eq!(
+                normalized.public_key().unwrap(),
+                original.public_key().unwrap()
+            );
+        }
+
+        // normalizing is idempotent
+        let normalized = did_doc.clone();
+        did_doc.normalize_to_multikey().unwrap();
+        assert_eq!(did_doc, normalized);
+    }
+
+    #[test]
+    fn test_normalize_to_multikey_failure_leaves_document_unchanged() {
+        let mut did_doc: DidDocument = serde_json::from_value(serde_json::json!({
+            "@context": "https://www.w3.org/ns/did/v1",
+            "id": "did:example:123",
+            "verificationMethod": [{
+                "id": "did:example:123#key-1",
+                "type": "Ed25519VerificationKey2018",
+                "controller": "did:example:123",
+                "publicKeyBase58": "DK7uJiq9PnPnj7AmNZqVBFoLuwTjT1hFPrk6LSjZ2JRz"
+            }],
+            "assertionMethod": [{
+                "id": "did:example:123#key-2",
+                "type": "PgpVerificationKey2021",
+                "controller": "did:example:123",
+                "publicKeyPgp": "-----BEGIN PGP PUBLIC KEY BLOCK-----"
+            }]
+        }))
+        .unwrap();
+        let original = did_doc.clone();
+
+        assert!(did_doc.normalize_to_multikey().is_err());
+        assert_eq!(did_doc, original);
+    }
 }
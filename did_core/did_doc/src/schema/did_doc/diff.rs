@@ -0,0 +1,626 @@
+use std::fmt::{self, Display, Formatter};
+
+use did_parser_nom::{Did, DidUrl};
+use display_as_json::Display;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::DidDocument;
+use crate::schema::{
+    service::Service,
+    types::uri::Uri,
+    utils::OneOrList,
+    verification_method::{VerificationMethod, VerificationMethodKind},
+};
+
+/// Verification relationship of a DID document, see
+/// https://www.w3.org/TR/did-core/#verification-relationships
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationRelationship {
+    Authentication,
+    AssertionMethod,
+    KeyAgreement,
+    CapabilityInvocation,
+    CapabilityDelegation,
+}
+
+impl VerificationRelationship {
+    pub const ALL: [VerificationRelationship; 5] = [
+        VerificationRelationship::Authentication,
+        VerificationRelationship::AssertionMethod,
+        VerificationRelationship::KeyAgreement,
+        VerificationRelationship::CapabilityInvocation,
+        VerificationRelationship::CapabilityDelegation,
+    ];
+}
+
+impl Display for VerificationRelationship {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationRelationship::Authentication => write!(f, "authentication"),
+            VerificationRelationship::AssertionMethod => write!(f, "assertionMethod"),
+            VerificationRelationship::KeyAgreement => write!(f, "keyAgreement"),
+            VerificationRelationship::CapabilityInvocation => write!(f, "capabilityInvocation"),
+            VerificationRelationship::CapabilityDelegation => write!(f, "capabilityDelegation"),
+        }
+    }
+}
+
+/// Single change of a DID document. Verification methods, verification relationship entries
+/// and services are addressed by their id; a relationship entry is replaced when it changes
+/// between a reference and an embedded verification method.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum DidDocumentOperation {
+    SetId {
+        value: Did,
+    },
+    SetAlsoKnownAs {
+        value: Vec<Uri>,
+    },
+    SetController {
+        value: Option<OneOrList<Did>>,
+    },
+    AddVerificationMethod {
+        value: VerificationMethod,
+    },
+    ReplaceVerificationMethod {
+        value: VerificationMethod,
+    },
+    RemoveVerificationMethod {
+        id: DidUrl,
+    },
+    AddRelationship {
+        relationship: VerificationRelationship,
+        value: VerificationMethodKind,
+    },
+    ReplaceRelationship {
+        relationship: VerificationRelationship,
+        value: VerificationMethodKind,
+    },
+    RemoveRelationship {
+        relationship: VerificationRelationship,
+        id: DidUrl,
+    },
+    AddService {
+        value: Service,
+    },
+    ReplaceService {
+        value: Service,
+    },
+    RemoveService {
+        id: Uri,
+    },
+    SetExtraField {
+        key: String,
+        value: Value,
+    },
+    RemoveExtraField {
+        key: String,
+    },
+}
+
+/// Ordered list of [DidDocumentOperation]s, serialized as a JSON array in the style of
+/// JSON Patch (RFC 6902).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, Display)]
+#[serde(transparent)]
+pub struct DidDocumentPatch {
+    operations: Vec<DidDocumentOperation>,
+}
+
+impl DidDocumentPatch {
+    pub fn new(operations: Vec<DidDocumentOperation>) -> Self {
+        Self { operations }
+    }
+
+    pub fn operations(&self) -> &[DidDocumentOperation] {
+        &self.operations
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub fn push(&mut self, operation: DidDocumentOperation) {
+        self.operations.push(operation);
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DidDocumentPatchError {
+    #[error("Cannot add {kind} {id}, an entry with this id already exists")]
+    AlreadyExists { kind: String, id: String },
+    #[error("Cannot update {kind} {id}, no entry with this id exists")]
+    NotFound { kind: String, id: String },
+}
+
+impl DidDocument {
+    pub fn relationship(
+        &self,
+        relationship: VerificationRelationship,
+    ) -> &[VerificationMethodKind] {
+        match relationship {
+            VerificationRelationship::Authentication => self.authentication(),
+            VerificationRelationship::AssertionMethod => self.assertion_method(),
+            VerificationRelationship::KeyAgreement => self.key_agreement(),
+            VerificationRelationship::CapabilityInvocation => self.capability_invocation(),
+            VerificationRelationship::CapabilityDelegation => self.capability_delegation(),
+        }
+    }
+
+    fn relationship_mut(
+        &mut self,
+        relationship: VerificationRelationship,
+    ) -> &mut Vec<VerificationMethodKind> {
+        match relationship {
+            VerificationRelationship::Authentication => &mut self.authentication,
+            VerificationRelationship::AssertionMethod => &mut self.assertion_method,
+            VerificationRelationship::KeyAgreement => &mut self.key_agreement,
+            VerificationRelationship::CapabilityInvocation => &mut self.capability_invocation,
+            VerificationRelationship::CapabilityDelegation => &mut self.capability_delegation,
+        }
+    }
+
+    /// Computes the operations turning this document into `other`. Verification methods,
+    /// relationship entries and services are matched by id: entries of `other` are added after
+    /// the retained ones, which keep their order.
+    pub fn diff(&self, other: &DidDocument) -> DidDocumentPatch {
+        let mut patch = DidDocumentPatch::default();
+        if self.id != other.id {
+            patch.push(DidDocumentOperation::SetId {
+                value: other.id.clone(),
+            });
+        }
+        if self.also_known_as != other.also_known_as {
+            patch.push(DidDocumentOperation::SetAlsoKnownAs {
+                value: other.also_known_as.clone(),
+            });
+        }
+        if self.controller != other.controller {
+            patch.push(DidDocumentOperation::SetController {
+                value: other.controller.clone(),
+            });
+        }
+
+        let changes = EntryChanges::new(
+            &self.verification_method,
+            &other.verification_method,
+            VerificationMethod::id,
+        );
+        for removed in changes.removed {
+            patch.push(DidDocumentOperation::RemoveVerificationMethod {
+                id: removed.clone(),
+            });
+        }
+        for replaced in changes.replaced {
+            patch.push(DidDocumentOperation::ReplaceVerificationMethod {
+                value: replaced.clone(),
+            });
+        }
+        for added in changes.added {
+            patch.push(DidDocumentOperation::AddVerificationMethod {
+                value: added.clone(),
+            });
+        }
+
+        for relationship in VerificationRelationship::ALL {
+            let changes = EntryChanges::new(
+                self.relationship(relationship),
+                other.relationship(relationship),
+                VerificationMethodKind::id,
+            );
+            for removed in changes.removed {
+                patch.push(DidDocumentOperation::RemoveRelationship {
+                    relationship,
+                    id: removed.clone(),
+                });
+            }
+            for replaced in changes.replaced {
+                patch.push(DidDocumentOperation::ReplaceRelationship {
+                    relationship,
+                    value: replaced.clone(),
+                });
+            }
+            for added in changes.added {
+                patch.push(DidDocumentOperation::AddRelationship {
+                    relationship,
+                    value: added.clone(),
+                });
+            }
+        }
+
+        let changes = EntryChanges::new(&self.service, &other.service, Service::id);
+        for removed in changes.removed {
+            patch.push(DidDocumentOperation::RemoveService {
+                id: removed.clone(),
+            });
+        }
+        for replaced in changes.replaced {
+            patch.push(DidDocumentOperation::ReplaceService {
+                value: replaced.clone(),
+            });
+        }
+        for added in changes.added {
+            patch.push(DidDocumentOperation::AddService {
+                value: added.clone(),
+            });
+        }
+
+        let mut keys = self
+            .extra
+            .keys()
+            .chain(other.extra.keys())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.dedup();
+        for key in keys {
+            match (self.extra.get(key), other.extra.get(key)) {
+                (old, Some(new)) if old != Some(new) => {
+                    patch.push(DidDocumentOperation::SetExtraField {
+                        key: key.clone(),
+                        value: new.clone(),
+                    })
+                }
+                (Some(_), None) => {
+                    patch.push(DidDocumentOperation::RemoveExtraField { key: key.clone() })
+                }
+                _ => {}
+            }
+        }
+        patch
+    }
+
+    /// Applies the operations of `patch` in order. The document is left unchanged on error.
+    pub fn apply_patch(&mut self, patch: &DidDocumentPatch) -> Result<(), DidDocumentPatchError> {
+        let mut patched = self.clone();
+        for operation in patch.operations() {
+            patched.apply_operation(operation)?;
+        }
+        *self = patched;
+        Ok(())
+    }
+
+    fn apply_operation(
+        &mut self,
+        operation: &DidDocumentOperation,
+    ) -> Result<(), DidDocumentPatchError> {
+        const VERIFICATION_METHOD: &str = "verification method";
+        const SERVICE: &str = "service";
+
+        match operation {
+            DidDocumentOperation::SetId { value } => self.id = value.clone(),
+            DidDocumentOperation::SetAlsoKnownAs { value } => self.also_known_as = value.clone(),
+            DidDocumentOperation::SetController { value } => self.controller = value.clone(),
+            DidDocumentOperation::AddVerificationMethod { value } => add_entry(
+                &mut self.verification_method,
+                value,
+                VerificationMethod::id,
+                VERIFICATION_METHOD,
+            )?,
+            DidDocumentOperation::ReplaceVerificationMethod { value } => replace_entry(
+                &mut self.verification_method,
+                value,
+                VerificationMethod::id,
+                VERIFICATION_METHOD,
+            )?,
+            DidDocumentOperation::RemoveVerificationMethod { id } => remove_entry(
+                &mut self.verification_method,
+                id,
+                VerificationMethod::id,
+                VERIFICATION_METHOD,
+            )?,
+            DidDocumentOperation::AddRelationship {
+                relationship,
+                value,
+            } => add_entry(
+                self.relationship_mut(*relationship),
+                value,
+                VerificationMethodKind::id,
+                relationship,
+            )?,
+            DidDocumentOperation::ReplaceRelationship {
+                relationship,
+                value,
+            } => replace_entry(
+                self.relationship_mut(*relationship),
+                value,
+                VerificationMethodKind::id,
+                relationship,
+            )?,
+            DidDocumentOperation::RemoveRelationship { relationship, id } => remove_entry(
+                self.relationship_mut(*relationship),
+                id,
+                VerificationMethodKind::id,
+                relationship,
+            )?,
+            DidDocumentOperation::AddService { value } => {
+                add_entry(&mut self.service, value, Service::id, SERVICE)?
+            }
+            DidDocumentOperation::ReplaceService { value } => {
+                replace_entry(&mut self.service, value, Service::id, SERVICE)?
+            }
+            DidDocumentOperation::RemoveService { id } => {
+                remove_entry(&mut self.service, id, Service::id, SERVICE)?
+            }
+            DidDocumentOperation::SetExtraField { key, value } => {
+                self.extra.insert(key.clone(), value.clone());
+            }
+            DidDocumentOperation::RemoveExtraField { key } => {
+                self.extra.remove(key);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Entries removed from, replaced in and added to a list of entries identified by id
+struct EntryChanges<'a, T, K> {
+    removed: Vec<&'a K>,
+    replaced: Vec<&'a T>,
+    added: Vec<&'a T>,
+}
+
+impl<'a, T: PartialEq, K: PartialEq> EntryChanges<'a, T, K> {
+    fn new(old: &'a [T], new: &'a [T], id: fn(&T) -> &K) -> Self {
+        let find =
+            |entries: &'a [T], entry_id: &K| entries.iter().find(|entry| id(entry) == entry_id);
+        Self {
+            removed: old
+                .iter()
+                .map(id)
+                .filter(|entry_id| find(new, *entry_id).is_none())
+                .collect(),
+            replaced: new
+                .iter()
+                .filter(|entry| find(old, id(*entry)).is_some_and(|old| old != *entry))
+                .collect(),
+            added: new
+                .iter()
+                .filter(|entry| find(old, id(*entry)).is_none())
+                .collect(),
+        }
+    }
+}
+
+fn add_entry<T: Clone, K: PartialEq + Display>(
+    entries: &mut Vec<T>,
+    value: &T,
+    id: fn(&T) -> &K,
+    kind: impl Display,
+) -> Result<(), DidDocumentPatchError> {
+    if entries.iter().any(|entry| id(entry) == id(value)) {
+        return Err(DidDocumentPatchError::AlreadyExists {
+            kind: kind.to_string(),
+            id: id(value).to_string(),
+        });
+    }
+    entries.push(value.clone());
+    Ok(())
+}
+
+fn replace_entry<T: Clone, K: PartialEq + Display>(
+    entries: &mut [T],
+    value: &T,
+    id: fn(&T) -> &K,
+    kind: impl Display,
+) -> Result<(), DidDocumentPatchError> {
+    let entry = entries
+        .iter_mut()
+        .find(|entry| id(entry) == id(value))
+        .ok_or_else(|| DidDocumentPatchError::NotFound {
+            kind: kind.to_string(),
+            id: id(value).to_string(),
+        })?;
+    *entry = value.clone();
+    Ok(())
+}
+
+fn remove_entry<T, K: PartialEq + Display>(
+    entries: &mut Vec<T>,
+    entry_id: &K,
+    id: fn(&T) -> &K,
+    kind: impl Display,
+) -> Result<(), DidDocumentPatchError> {
+    let position = entries
+        .iter()
+        .position(|entry| id(entry) == entry_id)
+        .ok_or_else(|| DidDocumentPatchError::NotFound {
+            kind: kind.to_string(),
+            id: entry_id.to_string(),
+        })?;
+    entries.remove(position);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn original() -> DidDocument {
+        serde_json::from_value(json!({
+            "@context": ["https://www.w3.org/ns/did/v1"],
+            "id": "did:example:123",
+            "verificationMethod": [
+                {
+                    "id": "did:example:123#key-1",
+                    "type": "Ed25519VerificationKey2018",
+                    "controller": "did:example:123",
+                    "publicKeyBase58": "DK7uJiq9PnPnj7AmNZqVBFoLuwTjT1hFPrk6LSjZ2JRz"
+                },
+                {
+                    "id": "did:example:123#key-2",
+                    "type": "X25519KeyAgreementKey2019",
+                    "controller": "did:example:123",
+                    "publicKeyBase58": "CaSHXEvLKS6SfN9aBfkVGBpp15jSnaHazqHgLHp8KZ3Y"
+                }
+            ],
+            "authentication": ["did:example:123#key-1"],
+            "keyAgreement": [{
+                "id": "did:example:123#key-3",
+                "type": "X25519KeyAgreementKey2019",
+                "controller": "did:example:123",
+                "publicKeyBase58": "CaSHXEvLKS6SfN9aBfkVGBpp15jSnaHazqHgLHp8KZ3Y"
+            }],
+            "service": [
+                {
+                    "id": "did:example:123#didcomm",
+                    "type": "DIDCommMessaging",
+                    "serviceEndpoint": "https://example.com/didcomm"
+                },
+                {
+                    "id": "did:example:123#linked-domain",
+                    "type": "LinkedDomains",
+                    "serviceEndpoint": "https://example.com/"
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    /// key-1 rotated to key-4, the embedded key agreement method replaced by a reference to
+    /// key-2, the DIDComm endpoint moved and the linked domain dropped
+    fn rotated() -> DidDocument {
+        serde_json::from_value(json!({
+            "@context": [
+                "https://www.w3.org/ns/did/v1",
+                "https://w3id.org/security/multikey/v1"
+            ],
+            "id": "did:example:123",
+            "verificationMethod": [
+                {
+                    "id": "did:example:123#key-2",
+                    "type": "X25519KeyAgreementKey2019",
+                    "controller": "did:example:123",
+                    "publicKeyBase58": "CaSHXEvLKS6SfN9aBfkVGBpp15jSnaHazqHgLHp8KZ3Y"
+                },
+                {
+                    "id": "did:example:123#key-4",
+                    "type": "Multikey",
+                    "controller": "did:example:123",
+                    "publicKeyMultibase": "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"
+                }
+            ],
+            "authentication": ["did:example:123#key-4"],
+            "keyAgreement": ["did:example:123#key-3"],
+            "service": [{
+                "id": "did:example:123#didcomm",
+                "type": "DIDCommMessaging",
+                "serviceEndpoint": "https://example.org/didcomm"
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn did_url(id: &str) -> DidUrl {
+        DidUrl::parse(id.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_diff_of_equal_documents_is_empty() {
+        assert!(original().diff(&original()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_and_apply() {
+        let original = original();
+        let rotated = rotated();
+
+        let patch = original.diff(&rotated);
+        assert_eq!(
+            patch.operations(),
+            &[
+                DidDocumentOperation::RemoveVerificationMethod {
+                    id: did_url("did:example:123#key-1")
+                },
+                DidDocumentOperation::AddVerificationMethod {
+                    value: rotated.verification_method()[1].clone()
+                },
+                DidDocumentOperation::RemoveRelationship {
+                    relationship: VerificationRelationship::Authentication,
+                    id: did_url("did:example:123#key-1")
+                },
+                DidDocumentOperation::AddRelationship {
+                    relationship: VerificationRelationship::Authentication,
+                    value: VerificationMethodKind::Resolvable(did_url("did:example:123#key-4"))
+                },
+                DidDocumentOperation::ReplaceRelationship {
+                    relationship: VerificationRelationship::KeyAgreement,
+                    value: VerificationMethodKind::Resolvable(did_url("did:example:123#key-3"))
+                },
+                DidDocumentOperation::RemoveService {
+                    id: Uri::new("did:example:123#linked-domain").unwrap()
+                },
+                DidDocumentOperation::ReplaceService {
+                    value: rotated.service()[0].clone()
+                },
+                DidDocumentOperation::SetExtraField {
+                    key: "@context".to_string(),
+                    value: rotated.extra_field("@context").unwrap().clone()
+                },
+            ]
+        );
+
+        let mut patched = original.clone();
+        patched.apply_patch(&patch).unwrap();
+        assert_eq!(patched, rotated);
+        assert!(patched.diff(&rotated).is_empty());
+
+        // the reverse patch restores the original document, up to the order of entries
+        let mut restored = rotated.clone();
+        restored.apply_patch(&rotated.diff(&original)).unwrap();
+        assert!(restored.diff(&original).is_empty());
+    }
+
+    #[test]
+    fn test_patch_serialization() {
+        let patch = original().diff(&rotated());
+        let serialized = serde_json::to_value(&patch).unwrap();
+        assert_eq!(
+            serialized[0],
+            json!({ "op": "removeVerificationMethod", "id": "did:example:123#key-1" })
+        );
+        assert_eq!(
+            serialized[4],
+            json!({
+                "op": "replaceRelationship",
+                "relationship": "keyAgreement",
+                "value": "did:example:123#key-3"
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<DidDocumentPatch>(serialized).unwrap(),
+            patch
+        );
+    }
+
+    #[test]
+    fn test_failed_patch_leaves_document_unchanged() {
+        let mut did_doc = original();
+        let patch = DidDocumentPatch::new(vec![
+            DidDocumentOperation::RemoveVerificationMethod {
+                id: did_url("did:example:123#key-1"),
+            },
+            DidDocumentOperation::RemoveVerificationMethod {
+                id: did_url("did:example:123#key-9"),
+            },
+        ]);
+        assert!(matches!(
+            did_doc.apply_patch(&patch),
+            Err(DidDocumentPatchError::NotFound { id, .. }) if id == "did:example:123#key-9"
+        ));
+        assert_eq!(did_doc, original());
+
+        let patch = DidDocumentPatch::new(vec![DidDocumentOperation::AddRelationship {
+            relationship: VerificationRelationship::Authentication,
+            value: VerificationMethodKind::Resolvable(did_url("did:example:123#key-1")),
+        }]);
+        assert!(matches!(
+            did_doc.apply_patch(&patch),
+            Err(DidDocumentPatchError::AlreadyExists { kind, .. }) if kind == "authentication"
+        ));
+    }
+}
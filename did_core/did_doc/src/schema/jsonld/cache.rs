@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::error::JsonLdError;
+use crate::schema::contexts;
+
+const BUNDLED_CONTEXTS: [(&str, &str); 8] = [
+    (contexts::W3C_DID_V1, include_str!("contexts/did-v1.jsonld")),
+    (
+        contexts::W3C_SUITE_ED25519_2018,
+        include_str!("contexts/ed25519-2018-v1.jsonld"),
+    ),
+    (
+        contexts::W3C_SUITE_ED25519_2020,
+        include_str!("contexts/ed25519-2020-v1.jsonld"),
+    ),
+    (
+        contexts::W3C_SUITE_JWS_2020,
+        include_str!("contexts/jws-2020-v1.jsonld"),
+    ),
+    (
+        contexts::W3C_SUITE_SECP256K1_2019,
+        include_str!("contexts/secp256k1-2019-v1.jsonld"),
+    ),
+    (
+        contexts::W3C_SUITE_X25519_2019,
+        include_str!("contexts/x25519-2019-v1.jsonld"),
+    ),
+    (
+        contexts::W3C_SUITE_X25519_2020,
+        include_str!("contexts/x25519-2020-v1.jsonld"),
+    ),
+    (
+        contexts::W3C_MULTIKEY_V1,
+        include_str!("contexts/multikey-v1.jsonld"),
+    ),
+];
+
+/// Remote contexts available to JSON-LD processing. Contexts are never fetched, a document
+/// referencing a context missing from the cache fails to be processed.
+#[derive(Clone, Debug)]
+pub struct ContextCache {
+    contexts: HashMap<String, Value>,
+}
+
+impl ContextCache {
+    /// Cache without any context
+    pub fn empty() -> Self {
+        Self {
+            contexts: HashMap::new(),
+        }
+    }
+
+    /// Cache holding the DID v1 context and the contexts of the verification method types
+    /// commonly found in DID documents.
+    pub fn bundled() -> Self {
+        let contexts = BUNDLED_CONTEXTS
+            .iter()
+            .map(|(url, document)| {
+                let document = serde_json::from_str(document)
+                    .unwrap_or_else(|err| panic!("bundled context {url} is invalid: {err}"));
+                (url.to_string(), document)
+            })
+            .collect();
+        Self { contexts }
+    }
+
+    /// Adds a context document, which must hold the context under its `@context` entry.
+    pub fn insert(&mut self, url: String, document: Value) -> Result<(), JsonLdError> {
+        if document.get("@context").is_none() {
+            return Err(JsonLdError::InvalidContext(url));
+        }
+        self.contexts.insert(url, document);
+        Ok(())
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        self.contexts.contains_key(url)
+    }
+
+    /// The `@context` of the context document at `url`
+    pub(super) fn get(&self, url: &str) -> Result<&Value, JsonLdError> {
+        self.contexts
+            .get(url)
+            .and_then(|document| document.get("@context"))
+            .ok_or_else(|| JsonLdError::UnknownContext(url.to_string()))
+    }
+}
+
+impl Default for ContextCache {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use sha2::{Digest, Sha256};
+
+use super::{
+    error::JsonLdError,
+    rdf::{Quad, Term},
+};
+
+/// Bounds the calls to the hash N-degree quads algorithm, which is exponential in the number of
+/// indistinguishable blank nodes, per blank node of the dataset.
+const MAX_WORK_FACTOR: usize = 32;
+
+/// Issues identifiers with a prefix and a counter, remembering the order of issuance.
+#[derive(Clone, Debug)]
+struct IdentifierIssuer {
+    prefix: &'static str,
+    issued: HashMap<String, String>,
+    order: Vec<String>,
+}
+
+impl IdentifierIssuer {
+    fn new(prefix: &'static str) -> Self {
+        Self {
+            prefix,
+            issued: HashMap::new(),
+            order: vec![],
+        }
+    }
+
+    fn issue(&mut self, existing: &str) -> String {
+        if let Some(issued) = self.issued.get(existing) {
+            return issued.clone();
+        }
+        let issued = format!("{}{}", self.prefix, self.order.len());
+        self.issued.insert(existing.to_string(), issued.clone());
+        self.order.push(existing.to_string());
+        issued
+    }
+
+    fn get(&self, existing: &str) -> Option<&String> {
+        self.issued.get(existing)
+    }
+}
+
+/// RDF Dataset Canonicalization (RDFC-1.0) with SHA-256, see
+/// https://www.w3.org/TR/rdf-canon/
+struct Canonicalizer<'a> {
+    quads: &'a [Quad],
+    /// Indexes of the quads each blank node appears in
+    blank_node_quads: HashMap<&'a str, Vec<usize>>,
+    canonical_issuer: IdentifierIssuer,
+    remaining_work: usize,
+}
+
+impl<'a> Canonicalizer<'a> {
+    fn new(quads: &'a [Quad]) -> Self {
+        let mut blank_node_quads: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, quad) in quads.iter().enumerate() {
+            for id in blank_nodes(quad) {
+                let indexes = blank_node_quads.entry(id).or_default();
+                if indexes.last() != Some(&index) {
+                    indexes.push(index);
+                }
+            }
+        }
+        let remaining_work = blank_node_quads.len().max(1) * MAX_WORK_FACTOR;
+        Self {
+            quads,
+            blank_node_quads,
+            canonical_issuer: IdentifierIssuer::new("c14n"),
+            remaining_work,
+        }
+    }
+
+    fn canonicalize(mut self) -> Result<Vec<Quad>, JsonLdError> {
+        let mut hash_to_blank_nodes: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        let mut ids = self.blank_node_quads.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        for id in ids {
+            hash_to_blank_nodes
+                .entry(self.hash_first_degree_quads(id))
+                .or_default()
+                .push(id);
+        }
+
+        // blank nodes with a unique first degree hash are labeled in the order of their hashes
+        let mut shared = vec![];
+        for (_, ids) in hash_to_blank_nodes {
+            match ids.as_slice() {
+                [id] => {
+                    self.canonical_issuer.issue(id);
+                }
+                _ => shared.push(ids),
+            }
+        }
+
+        for ids in shared {
+            let mut hash_path_list = vec![];
+            for id in ids {
+                if self.canonical_issuer.get(id).is_some() {
+                    continue;
+                }
+                let mut temporary_issuer = IdentifierIssuer::new("b");
+                temporary_issuer.issue(id);
+                hash_path_list.push(self.hash_n_degree_quads(id, temporary_issuer)?);
+            }
+            hash_path_list.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (_, issuer) in hash_path_list {
+                for existing in &issuer.order {
+                    self.canonical_issuer.issue(existing);
+                }
+            }
+        }
+
+        let mut canonical = self
+            .quads
+            .iter()
+            .map(|quad| relabel(quad, |id| self.canonical_issuer.get(id).cloned()))
+            .collect::<Vec<_>>();
+        canonical.sort_by_cached_key(|quad| quad.to_string());
+        Ok(canonical)
+    }
+
+    fn hash_first_degree_quads(&self, reference: &str) -> String {
+        let mut nquads = self.blank_node_quads[reference]
+            .iter()
+            .map(|&index| {
+                let quad = relabel(&self.quads[index], |id| {
+                    Some(if id == reference { "a" } else { "z" }.to_string())
+                });
+                format!("{quad}\n")
+            })
+            .collect::<Vec<_>>();
+        nquads.sort_unstable();
+        sha256_hex(nquads.concat())
+    }
+
+    fn hash_related_blank_node(
+        &self,
+        related: &str,
+        quad: &Quad,
+        issuer: &IdentifierIssuer,
+        position: char,
+    ) -> String {
+        let mut input = position.to_string();
+        if position != 'g' {
+            if let Term::Iri(predicate) = &quad.predicate {
+                input.push_str(&format!("<{predicate}>"));
+            }
+        }
+        match self
+            .canonical_issuer
+            .get(related)
+            .or_else(|| issuer.get(related))
+        {
+            Some(id) => input.push_str(&format!("_:{id}")),
+            None => input.push_str(&self.hash_first_degree_quads(related)),
+        }
+        sha256_hex(input)
+    }
+
+    fn hash_n_degree_quads(
+        &mut self,
+        identifier: &str,
+        mut issuer: IdentifierIssuer,
+    ) -> Result<(String, IdentifierIssuer), JsonLdError> {
+        self.remaining_work = self
+            .remaining_work
+            .checked_sub(1)
+            .ok_or(JsonLdError::CanonicalizationLimitExceeded)?;
+
+        let quads = self.quads;
+        let mut related_hashes: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
+        for &index in &self.blank_node_quads[identifier] {
+            let quad = &quads[index];
+            let components = [(&quad.subject, 's'), (&quad.object, 'o')]
+                .into_iter()
+                .chain(quad.graph.as_ref().map(|graph| (graph, 'g')));
+            for (component, position) in components {
+                if let Term::BlankNode(related) = component {
+                    if related != identifier {
+                        let hash = self.hash_related_blank_node(related, quad, &issuer, position);
+                        related_hashes
+                            .entry(hash)
+                            .or_default()
+                            .push(related.as_str());
+                    }
+                }
+            }
+        }
+
+        let mut data_to_hash = String::new();
+        for (related_hash, blank_nodes) in related_hashes {
+            data_to_hash.push_str(&related_hash);
+            let mut chosen: Option<(String, IdentifierIssuer)> = None;
+
+            'permutations: for permutation in permutations(&blank_nodes) {
+                let mut issuer_copy = issuer.clone();
+                let mut path = String::new();
+                let mut recursion_list = vec![];
+                let is_worse = |path: &str, chosen: &Option<(String, IdentifierIssuer)>| {
+                    chosen.as_ref().is_some_and(|(chosen_path, _)| {
+                        path.len() >= chosen_path.len() && path > chosen_path.as_str()
+                    })
+                };
+
+                for related in permutation {
+                    match self.canonical_issuer.get(related) {
+                        Some(id) => path.push_str(&format!("_:{id}")),
+                        None => {
+                            if issuer_copy.get(related).is_none() {
+                                recursion_list.push(related);
+                            }
+                            path.push_str(&format!("_:{}", issuer_copy.issue(related)));
+                        }
+                    }
+                    if is_worse(&path, &chosen) {
+                        continue 'permutations;
+                    }
+                }
+                for related in recursion_list {
+                    let (hash, result_issuer) =
+                        self.hash_n_degree_quads(related, issuer_copy.clone())?;
+                    path.push_str(&format!("_:{}", issuer_copy.issue(related)));
+                    path.push_str(&format!("<{hash}>"));
+                    issuer_copy = result_issuer;
+                    if is_worse(&path, &chosen) {
+                        continue 'permutations;
+                    }
+                }
+                let is_better = match &chosen {
+                    Some((chosen_path, _)) => path < *chosen_path,
+                    None => true,
+                };
+                if is_better {
+                    chosen = Some((path, issuer_copy));
+                }
+            }
+
+            if let Some((path, chosen_issuer)) = chosen {
+                data_to_hash.push_str(&path);
+                issuer = chosen_issuer;
+            }
+        }
+        Ok((sha256_hex(data_to_hash), issuer))
+    }
+}
+
+/// Canonicalizes the dataset: blank nodes are relabeled deterministically, statements are
+/// deduplicated and sorted in code point order of their N-Quads serialization.
+pub fn canonicalize(quads: &[Quad]) -> Result<Vec<Quad>, JsonLdError> {
+    // a dataset is a set, repeated statements must not weigh in the blank node hashes
+    let mut seen = HashSet::new();
+    let quads = quads
+        .iter()
+        .filter(|quad| seen.insert(*quad))
+        .cloned()
+        .collect::<Vec<_>>();
+    Canonicalizer::new(&quads).canonicalize()
+}
+
+fn blank_nodes(quad: &Quad) -> impl Iterator<Item = &str> {
+    [Some(&quad.subject), Some(&quad.object), quad.graph.as_ref()]
+        .into_iter()
+        .flatten()
+        .filter_map(|term| match term {
+            Term::BlankNode(id) => Some(id.as_str()),
+            _ => None,
+        })
+}
+
+fn relabel(quad: &Quad, label: impl Fn(&str) -> Option<String>) -> Quad {
+    let relabel_term = |term: &Term| match term {
+        Term::BlankNode(id) => Term::BlankNode(label(id).unwrap_or_else(|| id.clone())),
+        term => term.clone(),
+    };
+    Quad {
+        subject: relabel_term(&quad.subject),
+        predicate: quad.predicate.clone(),
+        object: relabel_term(&quad.object),
+        graph: quad.graph.as_ref().map(relabel_term),
+    }
+}
+
+/// All orderings of `items`, in lexicographic order of their indexes
+fn permutations<'b>(items: &[&'b str]) -> Vec<Vec<&'b str>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+    let mut result = vec![];
+    for (position, item) in items.iter().enumerate() {
+        let mut rest = items.to_vec();
+        rest.remove(position);
+        for mut permutation in permutations(&rest) {
+            permutation.insert(0, item);
+            result.push(permutation);
+        }
+    }
+    result
+}
+
+fn sha256_hex(data: impl AsRef<[u8]>) -> String {
+    hex::encode(Sha256::digest(data.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::jsonld::rdf::to_nquads;
+
+    fn blank(id: &str) -> Term {
+        Term::BlankNode(id.to_string())
+    }
+
+    fn iri(iri: &str) -> Term {
+        Term::Iri(format!("https://example.com/{iri}"))
+    }
+
+    fn quad(subject: Term, predicate: &str, object: Term) -> Quad {
+        Quad {
+            subject,
+            predicate: iri(predicate),
+            object,
+            graph: None,
+        }
+    }
+
+    #[test]
+    fn test_single_blank_node() {
+        let quads = [quad(iri("a"), "knows", blank("x"))];
+        assert_eq!(
+            to_nquads(&canonicalize(&quads).unwrap()),
+            "<https://example.com/a> <https://example.com/knows> _:c14n0 .\n"
+        );
+    }
+
+    #[test]
+    fn test_labels_and_order_do_not_matter() {
+        // two indistinguishable cycles of blank nodes
+        let dataset = |labels: [&str; 4]| {
+            let [a, b, c, d] = labels.map(blank);
+            vec![
+                quad(a.clone(), "next", b.clone()),
+                quad(b, "next", a),
+                quad(c.clone(), "next", d.clone()),
+                quad(d, "next", c),
+            ]
+        };
+        let first = canonicalize(&dataset(["e0", "e1", "e2", "e3"])).unwrap();
+        let mut reversed = dataset(["x", "y", "z", "w"]);
+        reversed.reverse();
+        let second = canonicalize(&reversed).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(
+            to_nquads(&first),
+            "_:c14n0 <https://example.com/next> _:c14n1 .\n\
+             _:c14n1 <https://example.com/next> _:c14n0 .\n\
+             _:c14n2 <https://example.com/next> _:c14n3 .\n\
+             _:c14n3 <https://example.com/next> _:c14n2 .\n"
+        );
+    }
+
+    #[test]
+    fn test_distinguishable_blank_nodes() {
+        let dataset = |first: &str, second: &str| {
+            vec![
+                quad(blank(first), "name", iri("alice")),
+                quad(blank(second), "name", iri("bob")),
+                quad(blank(first), "knows", blank(second)),
+            ]
+        };
+        assert_eq!(
+            canonicalize(&dataset("a", "b")).unwrap(),
+            canonicalize(&dataset("b", "a")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use super::{
+    cache::ContextCache,
+    context::{is_keyword, ActiveContext, TermDefinition},
+    error::JsonLdError,
+};
+
+/// JSON-LD 1.1 compaction of documents produced by [super::expansion::Expander]. IRIs are not
+/// made relative to a base IRI.
+pub(super) struct Compactor<'a> {
+    cache: &'a ContextCache,
+}
+
+impl<'a> Compactor<'a> {
+    pub fn new(cache: &'a ContextCache) -> Self {
+        Self { cache }
+    }
+
+    pub fn compact(&self, expanded: &Value, context: &Value) -> Result<Value, JsonLdError> {
+        let active = ActiveContext::default().process(context, self.cache, true)?;
+        let mut result = match self.compact_element(&active, None, expanded)? {
+            Value::Array(mut items) if items.len() == 1 => match items.pop() {
+                Some(Value::Object(object)) => object,
+                _ => Map::new(),
+            },
+            Value::Array(items) if items.is_empty() => Map::new(),
+            Value::Array(items) => {
+                let mut result = Map::new();
+                result.insert(compact_iri(&active, "@graph", true), Value::Array(items));
+                result
+            }
+            Value::Object(object) => object,
+            _ => Map::new(),
+        };
+        let has_context = match context {
+            Value::Null => false,
+            Value::Array(items) => !items.is_empty(),
+            Value::Object(definitions) => !definitions.is_empty(),
+            _ => true,
+        };
+        if has_context {
+            result.insert("@context".to_string(), context.clone());
+        }
+        Ok(Value::Object(result))
+    }
+
+    /// `property` is the term `element` is the value of.
+    fn compact_element(
+        &self,
+        active: &ActiveContext,
+        property: Option<&str>,
+        element: &Value,
+    ) -> Result<Value, JsonLdError> {
+        let object = match element {
+            Value::Array(items) => {
+                let mut compacted = vec![];
+                for item in items {
+                    match self.compact_element(active, property, item)? {
+                        Value::Null => {}
+                        item => compacted.push(item),
+                    }
+                }
+                return Ok(Value::Array(compacted));
+            }
+            Value::Object(object) => object,
+            scalar => return Ok(scalar.clone()),
+        };
+
+        let definition = property.and_then(|property| active.term(property));
+        let is_value = object.contains_key("@value");
+        let is_reference = object.len() == 1 && object.contains_key("@id");
+        let mut context = match active.previous() {
+            Some(previous) if !is_value && !is_reference => previous.clone(),
+            _ => active.clone(),
+        };
+        if let Some(scoped) = definition.and_then(|definition| definition.context.as_ref()) {
+            context = context.process(scoped, self.cache, true)?;
+        }
+        // the term, and so its definition, were selected in the context of the parent node
+        let definition = definition.cloned();
+
+        if is_value || is_reference {
+            return Ok(compact_value(&context, definition.as_ref(), object));
+        }
+        if let Some(list) = object.get("@list") {
+            let items = self.compact_element(&context, property, list)?;
+            return Ok(match definition {
+                Some(definition) if definition.has_container("@list") => items,
+                _ => {
+                    let mut list = Map::new();
+                    list.insert(compact_iri(&context, "@list", true), items);
+                    Value::Object(list)
+                }
+            });
+        }
+        if let Some(graph) = object.get("@graph") {
+            let mut nodes = match self.compact_element(&context, None, graph)? {
+                Value::Array(nodes) => nodes,
+                node => vec![node],
+            };
+            return Ok(match definition {
+                Some(definition) if definition.has_container("@graph") && nodes.len() == 1 => {
+                    nodes.pop().unwrap_or_default()
+                }
+                _ => {
+                    let mut graph = Map::new();
+                    graph.insert(compact_iri(&context, "@graph", true), Value::Array(nodes));
+                    Value::Object(graph)
+                }
+            });
+        }
+        self.compact_node(&context, object)
+    }
+
+    fn compact_node(
+        &self,
+        active: &ActiveContext,
+        node: &Map<String, Value>,
+    ) -> Result<Value, JsonLdError> {
+        // types are compacted, and select type-scoped contexts, against the context in effect
+        // before type-scoped contexts are applied
+        let types = node
+            .get("@type")
+            .and_then(Value::as_array)
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|type_iri| compact_iri(active, type_iri, true))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let mut context = active.clone();
+        let mut sorted_types = types.clone();
+        sorted_types.sort_unstable();
+        for type_term in &sorted_types {
+            if let Some(scoped) = active
+                .term(type_term)
+                .and_then(|definition| definition.context.as_ref())
+            {
+                context = context.process(scoped, self.cache, false)?;
+            }
+        }
+
+        let mut result = Map::new();
+        for (key, value) in node {
+            match key.as_str() {
+                "@id" => {
+                    if let Some(id) = value.as_str() {
+                        result.insert(
+                            compact_iri(&context, "@id", true),
+                            Value::String(compact_iri(&context, id, false)),
+                        );
+                    }
+                }
+                "@type" => {
+                    let types = match types.as_slice() {
+                        [type_term] => Value::String(type_term.clone()),
+                        types => types.iter().cloned().map(Value::String).collect(),
+                    };
+                    result.insert(compact_iri(&context, "@type", true), types);
+                }
+                key if is_keyword(key) => {
+                    let compacted = self.compact_element(&context, None, value)?;
+                    result.insert(compact_iri(&context, key, true), compacted);
+                }
+                iri => {
+                    let mut by_term: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+                    for item in value.as_array().into_iter().flatten() {
+                        let term = select_term(&context, iri, item);
+                        let compacted = self.compact_element(&context, Some(&term), item)?;
+                        by_term.entry(term).or_default().push(compacted);
+                    }
+                    for (term, mut values) in by_term {
+                        // a single list is compacted to an array already
+                        let value = match context.term(&term) {
+                            Some(definition)
+                                if definition.has_container("@list") && values.len() == 1 =>
+                            {
+                                values.pop().unwrap_or_default()
+                            }
+                            Some(definition) if definition.has_container("@set") => {
+                                Value::Array(values)
+                            }
+                            _ if values.len() == 1 => values.pop().unwrap_or_default(),
+                            _ => Value::Array(values),
+                        };
+                        result.insert(term, value);
+                    }
+                }
+            }
+        }
+        Ok(Value::Object(result))
+    }
+}
+
+fn compact_value(
+    active: &ActiveContext,
+    definition: Option<&TermDefinition>,
+    value: &Map<String, Value>,
+) -> Value {
+    let type_mapping = definition.and_then(|definition| definition.type_mapping.as_deref());
+    if let Some(id) = value.get("@id").and_then(Value::as_str) {
+        return match type_mapping {
+            Some("@id") => Value::String(compact_iri(active, id, false)),
+            Some("@vocab") => Value::String(compact_iri(active, id, true)),
+            _ => {
+                let mut reference = Map::new();
+                reference.insert(
+                    compact_iri(active, "@id", true),
+                    Value::String(compact_iri(active, id, false)),
+                );
+                Value::Object(reference)
+            }
+        };
+    }
+
+    let literal = value.get("@value").cloned().unwrap_or_default();
+    let datatype = value.get("@type").and_then(Value::as_str);
+    match (datatype, type_mapping) {
+        (Some(datatype), Some(type_mapping)) if datatype == type_mapping => literal,
+        (None, None) if value.len() == 1 => literal,
+        _ => {
+            let mut object = Map::new();
+            object.insert(compact_iri(active, "@value", true), literal);
+            if let Some(datatype) = datatype {
+                object.insert(
+                    compact_iri(active, "@type", true),
+                    Value::String(compact_iri(active, datatype, true)),
+                );
+            }
+            Value::Object(object)
+        }
+    }
+}
+
+/// Whether a term of `definition` can express `value` without losing information.
+/// Exact type or container matches are preferred over terms without type mapping.
+fn term_rank(definition: &TermDefinition, value: &Value) -> Option<u8> {
+    let type_mapping = definition.type_mapping.as_deref();
+    if value.get("@list").is_some() {
+        return Some(u8::from(definition.has_container("@list")));
+    }
+    if value.get("@graph").is_some() {
+        return definition.has_container("@graph").then_some(1);
+    }
+    if definition.has_container("@list") || definition.has_container("@graph") {
+        return None;
+    }
+    if value.get("@value").is_some() {
+        return match (value.get("@type").and_then(Value::as_str), type_mapping) {
+            (Some(datatype), Some(type_mapping)) if datatype == type_mapping => Some(1),
+            (_, None) => Some(0),
+            _ => None,
+        };
+    }
+    match type_mapping {
+        Some("@id") | Some("@vocab") => Some(1),
+        None => Some(0),
+        Some(_) => None,
+    }
+}
+
+/// Picks the term to compact property `iri` to for `value`, falling back to a compact IRI or
+/// the IRI itself.
+fn select_term(active: &ActiveContext, iri: &str, value: &Value) -> String {
+    active
+        .terms()
+        .filter(|(_, definition)| definition.iri == iri)
+        .filter_map(|(term, definition)| Some((term, term_rank(definition, value)?)))
+        .max_by(|(term_a, rank_a), (term_b, rank_b)| {
+            rank_a
+                .cmp(rank_b)
+                .then_with(|| shortest_least(term_b, term_a))
+        })
+        .map(|(term, _)| term.clone())
+        .unwrap_or_else(|| compact_iri_without_term(active, iri, true))
+}
+
+/// Compacts an IRI or keyword to a term, a compact IRI or, with `vocab`, a `@vocab` relative
+/// IRI.
+pub(super) fn compact_iri(active: &ActiveContext, iri: &str, vocab: bool) -> String {
+    if vocab {
+        let term = active
+            .terms()
+            .filter(|(_, definition)| definition.iri == iri && definition.type_mapping.is_none())
+            .map(|(term, _)| term)
+            .min_by(|a, b| shortest_least(a, b));
+        if let Some(term) = term {
+            return term.clone();
+        }
+    }
+    compact_iri_without_term(active, iri, vocab)
+}
+
+fn compact_iri_without_term(active: &ActiveContext, iri: &str, vocab: bool) -> String {
+    if is_keyword(iri) {
+        return iri.to_string();
+    }
+    if vocab {
+        if let Some(suffix) = active.vocab().and_then(|vocab| iri.strip_prefix(vocab)) {
+            if !suffix.is_empty() && active.term(suffix).is_none() {
+                return suffix.to_string();
+            }
+        }
+    }
+    active
+        .terms()
+        .filter(|(_, definition)| definition.prefix)
+        .filter_map(|(term, definition)| {
+            let suffix = iri.strip_prefix(definition.iri.as_str())?;
+            (!suffix.is_empty()).then(|| format!("{term}:{suffix}"))
+        })
+        .filter(|compact| active.term(compact).is_none())
+        .min_by(|a, b| shortest_least(a, b))
+        .unwrap_or_else(|| iri.to_string())
+}
+
+fn shortest_least(a: &str, b: &str) -> std::cmp::Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
@@ -0,0 +1,509 @@
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use super::{cache::ContextCache, error::JsonLdError};
+
+/// Bounds the nesting of remote contexts, guarding against contexts which reference each other
+const MAX_CONTEXT_DEPTH: usize = 16;
+
+const KEYWORDS: [&str; 23] = [
+    "@base",
+    "@container",
+    "@context",
+    "@direction",
+    "@graph",
+    "@id",
+    "@import",
+    "@included",
+    "@index",
+    "@json",
+    "@language",
+    "@list",
+    "@nest",
+    "@none",
+    "@prefix",
+    "@propagate",
+    "@protected",
+    "@reverse",
+    "@set",
+    "@type",
+    "@value",
+    "@version",
+    "@vocab",
+];
+
+/// Entries of a local context which configure the context rather than define a term
+const CONTEXT_KEYWORDS: [&str; 8] = [
+    "@base",
+    "@direction",
+    "@import",
+    "@language",
+    "@propagate",
+    "@protected",
+    "@version",
+    "@vocab",
+];
+
+const SUPPORTED_CONTAINERS: [&str; 3] = ["@set", "@list", "@graph"];
+
+pub(super) fn is_keyword(value: &str) -> bool {
+    KEYWORDS.contains(&value)
+}
+
+/// Strings of the `@` followed by letters form are reserved for future keywords and ignored.
+fn has_keyword_form(value: &str) -> bool {
+    value
+        .strip_prefix('@')
+        .is_some_and(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+pub(super) fn is_absolute_iri(value: &str) -> bool {
+    let Some((scheme, _)) = value.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Resolves a relative reference against `base`. Only covers the forms found in DID documents:
+/// fragments, queries, absolute paths and paths relative to a hierarchical base.
+fn resolve_relative(base: &str, reference: &str) -> String {
+    let without = |delimiters: &[char]| {
+        base.find(delimiters)
+            .map_or(base, |position| &base[..position])
+    };
+    match reference.chars().next() {
+        None => base.to_string(),
+        Some('#') => format!("{}{reference}", without(&['#'])),
+        Some('?') => format!("{}{reference}", without(&['?', '#'])),
+        Some('/') => match base.find("://") {
+            Some(scheme_end) => {
+                let authority_end = base[scheme_end + 3..]
+                    .find(['/', '?', '#'])
+                    .map_or(base.len(), |position| scheme_end + 3 + position);
+                format!("{}{reference}", &base[..authority_end])
+            }
+            None => format!("{}{reference}", without(&['/', '?', '#'])),
+        },
+        Some(_) if base.contains("://") => {
+            let path = without(&['?', '#']);
+            match path.rfind('/') {
+                Some(position) if position > base.find("://").unwrap_or_default() + 2 => {
+                    format!("{}{reference}", &path[..=position])
+                }
+                _ => format!("{path}/{reference}"),
+            }
+        }
+        Some(_) => reference.to_string(),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct TermDefinition {
+    /// Absolute IRI, blank node identifier or keyword the term expands to
+    pub iri: String,
+    /// `@id`, `@vocab`, `@json`, `@none` or the IRI of the datatype of values
+    pub type_mapping: Option<String>,
+    pub container: Vec<String>,
+    /// Property-scoped, or type-scoped if the term is used as a type, context
+    pub context: Option<Value>,
+    /// Whether the term can be used as the prefix of compact IRIs
+    pub prefix: bool,
+}
+
+impl TermDefinition {
+    pub fn has_container(&self, container: &str) -> bool {
+        self.container.iter().any(|c| c == container)
+    }
+}
+
+/// Result of JSON-LD context processing. Protected terms are not enforced, redefining them
+/// replaces the definition.
+#[derive(Clone, Debug, Default)]
+pub(super) struct ActiveContext {
+    /// Terms explicitly mapped to `null` are kept as `None` so they do not fall back to `@vocab`
+    terms: HashMap<String, Option<TermDefinition>>,
+    vocab: Option<String>,
+    base: Option<String>,
+    /// Context to revert to when entering a nested node, set by non-propagated (type-scoped)
+    /// contexts
+    previous: Option<Box<ActiveContext>>,
+}
+
+impl ActiveContext {
+    pub fn new(base: Option<String>) -> Self {
+        Self {
+            base,
+            ..Default::default()
+        }
+    }
+
+    pub fn term(&self, term: &str) -> Option<&TermDefinition> {
+        self.terms.get(term).and_then(Option::as_ref)
+    }
+
+    pub fn terms(&self) -> impl Iterator<Item = (&String, &TermDefinition)> {
+        self.terms
+            .iter()
+            .filter_map(|(term, definition)| definition.as_ref().map(|d| (term, d)))
+    }
+
+    pub fn vocab(&self) -> Option<&str> {
+        self.vocab.as_deref()
+    }
+
+    pub fn previous(&self) -> Option<&ActiveContext> {
+        self.previous.as_deref()
+    }
+
+    /// Processes `local` on top of this context. Contexts which do not `propagate` only apply
+    /// to the node they are used in, nested nodes revert to this context.
+    pub fn process(
+        &self,
+        local: &Value,
+        cache: &ContextCache,
+        propagate: bool,
+    ) -> Result<Self, JsonLdError> {
+        let mut result = self.process_with_depth(local, cache, 0)?;
+        if !propagate && result.previous.is_none() {
+            result.previous = Some(Box::new(self.clone()));
+        }
+        Ok(result)
+    }
+
+    fn process_with_depth(
+        &self,
+        local: &Value,
+        cache: &ContextCache,
+        depth: usize,
+    ) -> Result<Self, JsonLdError> {
+        if depth > MAX_CONTEXT_DEPTH {
+            return Err(JsonLdError::InvalidContext(
+                "maximum depth of remote contexts exceeded".to_string(),
+            ));
+        }
+        let mut result = self.clone();
+        let items = match local {
+            Value::Array(items) => items.as_slice(),
+            item => std::slice::from_ref(item),
+        };
+        for item in items {
+            match item {
+                Value::Null => result = ActiveContext::new(self.base.clone()),
+                Value::String(url) => {
+                    result = result.process_with_depth(cache.get(url)?, cache, depth + 1)?
+                }
+                Value::Object(definitions) => result.define(definitions)?,
+                item => return Err(JsonLdError::InvalidContext(item.to_string())),
+            }
+        }
+        Ok(result)
+    }
+
+    fn define(&mut self, definitions: &Map<String, Value>) -> Result<(), JsonLdError> {
+        if let Some(version) = definitions.get("@version") {
+            if version.as_f64() != Some(1.1) {
+                return Err(JsonLdError::InvalidKeywordValue {
+                    keyword: "@version",
+                    value: version.to_string(),
+                });
+            }
+        }
+        if definitions.contains_key("@import") {
+            return Err(JsonLdError::Unsupported("@import".to_string()));
+        }
+        match definitions.get("@base") {
+            None => {}
+            Some(Value::Null) => self.base = None,
+            Some(Value::String(base)) if is_absolute_iri(base) => self.base = Some(base.clone()),
+            Some(base) => {
+                return Err(JsonLdError::InvalidKeywordValue {
+                    keyword: "@base",
+                    value: base.to_string(),
+                })
+            }
+        }
+        match definitions.get("@vocab") {
+            None => {}
+            Some(Value::Null) => self.vocab = None,
+            Some(Value::String(vocab)) => self.vocab = self.expand_iri(vocab, true, true),
+            Some(vocab) => {
+                return Err(JsonLdError::InvalidKeywordValue {
+                    keyword: "@vocab",
+                    value: vocab.to_string(),
+                })
+            }
+        }
+
+        let mut defined = HashMap::new();
+        for term in definitions.keys() {
+            self.create_term(definitions, term, &mut defined)?;
+        }
+        Ok(())
+    }
+
+    /// Defines `term`, after the terms of `definitions` its definition depends on.
+    /// `defined` tracks the terms being defined (`false`) and defined (`true`).
+    fn create_term(
+        &mut self,
+        definitions: &Map<String, Value>,
+        term: &str,
+        defined: &mut HashMap<String, bool>,
+    ) -> Result<(), JsonLdError> {
+        match defined.get(term) {
+            Some(true) => return Ok(()),
+            Some(false) => return Err(JsonLdError::CyclicTermDefinition(term.to_string())),
+            None => {}
+        }
+        if CONTEXT_KEYWORDS.contains(&term) {
+            return Ok(());
+        }
+        if is_keyword(term) {
+            return Err(JsonLdError::InvalidTermDefinition(term.to_string()));
+        }
+        if has_keyword_form(term) {
+            return Ok(());
+        }
+        defined.insert(term.to_string(), false);
+
+        let definition = match &definitions[term] {
+            Value::Null => None,
+            Value::String(iri) => {
+                let mut simple = Map::new();
+                simple.insert("@id".to_string(), Value::String(iri.clone()));
+                self.term_definition(definitions, term, &simple, defined, true)?
+            }
+            Value::Object(definition) => {
+                self.term_definition(definitions, term, definition, defined, false)?
+            }
+            _ => return Err(JsonLdError::InvalidTermDefinition(term.to_string())),
+        };
+        self.terms.insert(term.to_string(), definition);
+        defined.insert(term.to_string(), true);
+        Ok(())
+    }
+
+    fn term_definition(
+        &mut self,
+        definitions: &Map<String, Value>,
+        term: &str,
+        definition: &Map<String, Value>,
+        defined: &mut HashMap<String, bool>,
+        simple: bool,
+    ) -> Result<Option<TermDefinition>, JsonLdError> {
+        let invalid = || JsonLdError::InvalidTermDefinition(term.to_string());
+        if definition.contains_key("@reverse") {
+            return Err(JsonLdError::Unsupported("@reverse".to_string()));
+        }
+
+        let iri = match definition.get("@id") {
+            Some(Value::Null) => return Ok(None),
+            Some(Value::String(id)) if is_keyword(id) => id.clone(),
+            Some(Value::String(id)) if has_keyword_form(id) => return Ok(None),
+            Some(Value::String(id)) => {
+                self.create_dependency(definitions, id, defined)?;
+                let iri = self.expand_iri(id, true, false).ok_or_else(invalid)?;
+                if !is_absolute_iri(&iri) && !iri.starts_with("_:") && !is_keyword(&iri) {
+                    return Err(invalid());
+                }
+                iri
+            }
+            Some(_) => return Err(invalid()),
+            None if term.contains(':') => {
+                self.create_dependency(definitions, term, defined)?;
+                self.expand_iri(term, true, false).ok_or_else(invalid)?
+            }
+            None => match &self.vocab {
+                Some(vocab) => format!("{vocab}{term}"),
+                None => return Err(invalid()),
+            },
+        };
+
+        let type_mapping = match definition.get("@type") {
+            None => None,
+            Some(Value::String(type_mapping)) => match type_mapping.as_str() {
+                "@id" | "@vocab" | "@json" | "@none" => Some(type_mapping.clone()),
+                _ => {
+                    self.create_dependency(definitions, type_mapping, defined)?;
+                    let type_mapping = self
+                        .expand_iri(type_mapping, true, false)
+                        .filter(|iri| is_absolute_iri(iri))
+                        .ok_or_else(invalid)?;
+                    Some(type_mapping)
+                }
+            },
+            Some(_) => return Err(invalid()),
+        };
+
+        let container = match definition.get("@container") {
+            None => vec![],
+            Some(Value::String(container)) => vec![container.clone()],
+            Some(Value::Array(containers)) => containers
+                .iter()
+                .map(|container| container.as_str().map(str::to_string).ok_or_else(invalid))
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(invalid()),
+        };
+        if let Some(container) = container
+            .iter()
+            .find(|container| !SUPPORTED_CONTAINERS.contains(&container.as_str()))
+        {
+            return Err(JsonLdError::Unsupported(format!("{container} containers")));
+        }
+
+        let prefix = match definition.get("@prefix") {
+            Some(Value::Bool(prefix)) => *prefix,
+            Some(_) => return Err(invalid()),
+            None => simple && iri.ends_with([':', '/', '?', '#', '[', ']', '@']),
+        };
+
+        Ok(Some(TermDefinition {
+            iri,
+            type_mapping,
+            container,
+            context: definition.get("@context").cloned(),
+            prefix,
+        }))
+    }
+
+    /// Defines the terms of `definitions` that `value`, a term or compact IRI, expands through.
+    fn create_dependency(
+        &mut self,
+        definitions: &Map<String, Value>,
+        value: &str,
+        defined: &mut HashMap<String, bool>,
+    ) -> Result<(), JsonLdError> {
+        let dependency = match value.split_once(':') {
+            Some((prefix, suffix)) if prefix != "_" && !suffix.starts_with("//") => prefix,
+            Some(_) => return Ok(()),
+            None => value,
+        };
+        if definitions.contains_key(dependency) {
+            self.create_term(definitions, dependency, defined)?;
+        }
+        Ok(())
+    }
+
+    /// Expands a term, compact IRI or relative IRI. `vocab` resolves terms and `@vocab`
+    /// relative IRIs, `document_relative` resolves relative IRIs against the base IRI. `None`
+    /// if the value is explicitly not mapped.
+    pub fn expand_iri(&self, value: &str, vocab: bool, document_relative: bool) -> Option<String> {
+        if is_keyword(value) {
+            return Some(value.to_string());
+        }
+        if has_keyword_form(value) {
+            return None;
+        }
+        if vocab {
+            if let Some(definition) = self.terms.get(value) {
+                return definition.as_ref().map(|definition| definition.iri.clone());
+            }
+        }
+        if let Some((prefix, suffix)) = value.split_once(':') {
+            if prefix == "_" || suffix.starts_with("//") {
+                return Some(value.to_string());
+            }
+            if let Some(Some(definition)) = self.terms.get(prefix) {
+                if definition.prefix {
+                    return Some(format!("{}{suffix}", definition.iri));
+                }
+            }
+            if is_absolute_iri(value) {
+                return Some(value.to_string());
+            }
+        }
+        if vocab {
+            if let Some(vocab) = &self.vocab {
+                return Some(format!("{vocab}{value}"));
+            }
+        }
+        if document_relative {
+            if let Some(base) = &self.base {
+                return Some(resolve_relative(base, value));
+            }
+        }
+        Some(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_relative() {
+        assert_eq!(
+            resolve_relative("did:example:123", "#key-1"),
+            "did:example:123#key-1"
+        );
+        assert_eq!(
+            resolve_relative("did:example:123?versionId=1#key-1", "?versionId=2"),
+            "did:example:123?versionId=2"
+        );
+        assert_eq!(
+            resolve_relative("did:example:123/path", "/resource"),
+            "did:example:123/resource"
+        );
+        assert_eq!(
+            resolve_relative("https://example.com/a/b?q", "c"),
+            "https://example.com/a/c"
+        );
+        assert_eq!(
+            resolve_relative("https://example.com/a/b", "/c"),
+            "https://example.com/c"
+        );
+    }
+
+    #[test]
+    fn test_term_definitions() {
+        let context = ActiveContext::default()
+            .process(
+                &json!({
+                    "ex": "https://example.com/vocab#",
+                    "name": "ex:name",
+                    "knows": { "@id": "ex:knows", "@type": "@id", "@container": "@set" },
+                    "unmapped": null,
+                }),
+                &ContextCache::empty(),
+                true,
+            )
+            .unwrap();
+
+        assert_eq!(
+            context.expand_iri("name", true, false).unwrap(),
+            "https://example.com/vocab#name"
+        );
+        assert_eq!(
+            context.expand_iri("ex:age", true, false).unwrap(),
+            "https://example.com/vocab#age"
+        );
+        assert_eq!(context.expand_iri("unmapped", true, false), None);
+        let knows = context.term("knows").unwrap();
+        assert_eq!(knows.type_mapping.as_deref(), Some("@id"));
+        assert!(knows.has_container("@set"));
+    }
+
+    #[test]
+    fn test_cyclic_term_definition() {
+        let result = ActiveContext::default().process(
+            &json!({ "a": "b:x", "b": "a:y" }),
+            &ContextCache::empty(),
+            true,
+        );
+        assert!(matches!(result, Err(JsonLdError::CyclicTermDefinition(_))));
+    }
+
+    #[test]
+    fn test_unknown_remote_context() {
+        let result = ActiveContext::default().process(
+            &json!("https://example.com/context"),
+            &ContextCache::bundled(),
+            true,
+        );
+        assert!(matches!(result, Err(JsonLdError::UnknownContext(_))));
+    }
+}
@@ -0,0 +1,28 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum JsonLdError {
+    #[error("Document has no @context")]
+    MissingContext,
+    #[error("Context {0} is not available offline")]
+    UnknownContext(String),
+    #[error("Invalid local context: {0}")]
+    InvalidContext(String),
+    #[error("Invalid term definition of {0}")]
+    InvalidTermDefinition(String),
+    #[error("Cyclic definition of term {0}")]
+    CyclicTermDefinition(String),
+    #[error("Unsupported JSON-LD feature: {0}")]
+    Unsupported(String),
+    #[error("Invalid {keyword} value: {value}")]
+    InvalidKeywordValue {
+        keyword: &'static str,
+        value: String,
+    },
+    #[error("Terms not defined by the document @context: {}", .0.join(", "))]
+    UndefinedTerms(Vec<String>),
+    #[error("Canonicalization of the dataset exceeded the maximum amount of work")]
+    CanonicalizationLimitExceeded,
+    #[error("(De)serialization error: {0}")]
+    JsonError(#[from] serde_json::Error),
+}
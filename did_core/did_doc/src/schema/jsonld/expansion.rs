@@ -0,0 +1,324 @@
+use std::collections::BTreeSet;
+
+use serde_json::{json, Map, Value};
+
+use super::{
+    cache::ContextCache,
+    context::{is_keyword, ActiveContext, TermDefinition},
+    error::JsonLdError,
+};
+
+/// JSON-LD 1.1 expansion, limited to the features used by DID documents and their proofs:
+/// `@reverse`, `@nest`, `@included`, language maps and index maps are not supported.
+pub(super) struct Expander<'a> {
+    cache: &'a ContextCache,
+    /// Entries and types dropped by expansion because the active context does not define them
+    undefined: BTreeSet<String>,
+}
+
+impl<'a> Expander<'a> {
+    pub fn new(cache: &'a ContextCache) -> Self {
+        Self {
+            cache,
+            undefined: BTreeSet::new(),
+        }
+    }
+
+    pub fn undefined(&self) -> &BTreeSet<String> {
+        &self.undefined
+    }
+
+    /// Expands `document`, relative IRIs are resolved against `base`. The result is always an
+    /// array of node objects.
+    pub fn expand(&mut self, document: &Value, base: Option<&str>) -> Result<Value, JsonLdError> {
+        let active = ActiveContext::new(base.map(str::to_string));
+        let expanded = match self.expand_element(&active, None, None, document)? {
+            Value::Object(mut object) if object.len() == 1 && object.contains_key("@graph") => {
+                object.remove("@graph").unwrap_or_default()
+            }
+            expanded => expanded,
+        };
+        Ok(match expanded {
+            Value::Null => json!([]),
+            Value::Array(_) => expanded,
+            expanded => json!([expanded]),
+        })
+    }
+
+    /// `property` is the expanded IRI or keyword `element` is the value of, `definition` the
+    /// definition of the term it was found under.
+    fn expand_element(
+        &mut self,
+        active: &ActiveContext,
+        property: Option<&str>,
+        definition: Option<&TermDefinition>,
+        element: &Value,
+    ) -> Result<Value, JsonLdError> {
+        match element {
+            Value::Null => Ok(Value::Null),
+            Value::Array(items) => {
+                let mut expanded = vec![];
+                for item in items {
+                    match self.expand_element(active, property, definition, item)? {
+                        Value::Null => {}
+                        Value::Array(items) => expanded.extend(items),
+                        item => expanded.push(item),
+                    }
+                }
+                Ok(Value::Array(expanded))
+            }
+            Value::Object(object) => self.expand_object(active, property, definition, object),
+            scalar => {
+                // free-floating values are dropped
+                if property.is_none() || property == Some("@graph") {
+                    return Ok(Value::Null);
+                }
+                let active = match definition.and_then(|definition| definition.context.as_ref()) {
+                    Some(context) => active.process(context, self.cache, true)?,
+                    None => active.clone(),
+                };
+                Ok(expand_value(&active, definition, scalar))
+            }
+        }
+    }
+
+    fn expand_object(
+        &mut self,
+        active: &ActiveContext,
+        property: Option<&str>,
+        definition: Option<&TermDefinition>,
+        object: &Map<String, Value>,
+    ) -> Result<Value, JsonLdError> {
+        let expands_to = |key: &str, keyword: &str| {
+            active.expand_iri(key, true, false).as_deref() == Some(keyword)
+        };
+        let is_value = object.keys().any(|key| expands_to(key, "@value"));
+        let is_reference = object.len() == 1 && object.keys().all(|key| expands_to(key, "@id"));
+        let mut active = match active.previous() {
+            Some(previous) if !is_value && !is_reference => previous.clone(),
+            _ => active.clone(),
+        };
+        if let Some(context) = definition.and_then(|definition| definition.context.as_ref()) {
+            active = active.process(context, self.cache, true)?;
+        }
+        if let Some(context) = object.get("@context") {
+            active = active.process(context, self.cache, true)?;
+        }
+
+        // types are expanded, and select type-scoped contexts, against the context in effect
+        // before type-scoped contexts are applied
+        let type_scoped = active.clone();
+        let mut types = object
+            .iter()
+            .filter(|(key, _)| type_scoped.expand_iri(key, true, false).as_deref() == Some("@type"))
+            .flat_map(|(_, value)| match value {
+                Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                value => value.as_str().into_iter().collect::<Vec<_>>(),
+            })
+            .collect::<Vec<_>>();
+        types.sort_unstable();
+        for type_term in types {
+            if let Some(context) = type_scoped
+                .term(type_term)
+                .and_then(|definition| definition.context.as_ref())
+            {
+                active = active.process(context, self.cache, false)?;
+            }
+        }
+
+        let mut result = Map::new();
+        let mut keys = object.keys().collect::<Vec<_>>();
+        keys.sort_unstable();
+        for key in keys {
+            if key == "@context" {
+                continue;
+            }
+            let value = &object[key];
+            let expanded_property = match active.expand_iri(key, true, false) {
+                Some(iri) if is_keyword(&iri) || iri.contains(':') => iri,
+                _ => {
+                    self.undefined.insert(key.clone());
+                    continue;
+                }
+            };
+
+            let expanded_value = if is_keyword(&expanded_property) {
+                self.expand_keyword(&active, &type_scoped, &expanded_property, property, value)?
+            } else {
+                let definition = active.term(key);
+                let expanded = match definition.and_then(|d| d.type_mapping.as_deref()) {
+                    Some("@json") => json!({ "@value": value, "@type": "@json" }),
+                    _ => {
+                        self.expand_element(&active, Some(&expanded_property), definition, value)?
+                    }
+                };
+                match definition {
+                    Some(definition)
+                        if definition.has_container("@list") && !is_list(&expanded) =>
+                    {
+                        json!({ "@list": into_array(expanded) })
+                    }
+                    Some(definition) if definition.has_container("@graph") => {
+                        let graphs = into_array(expanded)
+                            .into_iter()
+                            .map(|node| match node.get("@graph") {
+                                Some(_) => node,
+                                None => json!({ "@graph": [node] }),
+                            })
+                            .collect();
+                        Value::Array(graphs)
+                    }
+                    _ => expanded,
+                }
+            };
+            if expanded_value.is_null() {
+                continue;
+            }
+
+            if is_keyword(&expanded_property) {
+                result.insert(expanded_property, expanded_value);
+            } else {
+                let values = result
+                    .entry(expanded_property)
+                    .or_insert_with(|| Value::Array(vec![]));
+                if let Value::Array(values) = values {
+                    values.extend(into_array(expanded_value));
+                }
+            }
+        }
+
+        if let Some(value) = result.get("@value") {
+            if value.is_null() {
+                return Ok(Value::Null);
+            }
+            // the datatype of a value object is a single IRI
+            if let Some(Value::Array(types)) = result.get_mut("@type") {
+                match types.pop() {
+                    Some(datatype) if types.is_empty() => result.insert("@type".into(), datatype),
+                    _ => {
+                        return Err(JsonLdError::InvalidKeywordValue {
+                            keyword: "@type",
+                            value: Value::Object(object.clone()).to_string(),
+                        })
+                    }
+                };
+            }
+            return Ok(Value::Object(result));
+        }
+        if let Some(Value::Array(types)) = result.get("@type") {
+            if types.is_empty() {
+                result.remove("@type");
+            }
+        }
+        // free-floating node references are dropped
+        if (property.is_none() || property == Some("@graph"))
+            && result.len() == 1
+            && result.contains_key("@id")
+        {
+            return Ok(Value::Null);
+        }
+        Ok(Value::Object(result))
+    }
+
+    fn expand_keyword(
+        &mut self,
+        active: &ActiveContext,
+        type_scoped: &ActiveContext,
+        keyword: &str,
+        property: Option<&str>,
+        value: &Value,
+    ) -> Result<Value, JsonLdError> {
+        let invalid = |keyword: &'static str| JsonLdError::InvalidKeywordValue {
+            keyword,
+            value: value.to_string(),
+        };
+        match keyword {
+            "@id" => match value {
+                Value::String(id) => Ok(active
+                    .expand_iri(id, false, true)
+                    .map(Value::String)
+                    .unwrap_or_default()),
+                _ => Err(invalid("@id")),
+            },
+            "@type" => {
+                let types = match value {
+                    Value::String(type_term) => vec![type_term.as_str()],
+                    Value::Array(types) => types
+                        .iter()
+                        .map(|type_term| type_term.as_str().ok_or_else(|| invalid("@type")))
+                        .collect::<Result<_, _>>()?,
+                    _ => return Err(invalid("@type")),
+                };
+                let mut expanded = vec![];
+                for type_term in types {
+                    // relative IRIs are kept, but can not be expressed in RDF
+                    match type_scoped.expand_iri(type_term, true, true) {
+                        Some(iri) => {
+                            if !iri.contains(':') {
+                                self.undefined.insert(type_term.to_string());
+                            }
+                            expanded.push(Value::String(iri));
+                        }
+                        None => {
+                            self.undefined.insert(type_term.to_string());
+                        }
+                    }
+                }
+                Ok(Value::Array(expanded))
+            }
+            "@value" => match value {
+                Value::Array(_) | Value::Object(_) => Err(invalid("@value")),
+                value => Ok(value.clone()),
+            },
+            "@graph" => Ok(Value::Array(into_array(self.expand_element(
+                active,
+                Some("@graph"),
+                None,
+                value,
+            )?))),
+            "@list" => Ok(Value::Array(into_array(
+                self.expand_element(active, property, None, value)?,
+            ))),
+            "@set" => self.expand_element(active, property, None, value),
+            "@base" | "@context" | "@vocab" | "@version" | "@propagate" | "@protected" => {
+                Ok(Value::Null)
+            }
+            keyword => Err(JsonLdError::Unsupported(keyword.to_string())),
+        }
+    }
+}
+
+/// Expands a scalar according to the type mapping of the term it is the value of.
+fn expand_value(
+    active: &ActiveContext,
+    definition: Option<&TermDefinition>,
+    value: &Value,
+) -> Value {
+    let type_mapping = definition.and_then(|definition| definition.type_mapping.as_deref());
+    match (type_mapping, value) {
+        (Some("@id"), Value::String(id)) => active
+            .expand_iri(id, false, true)
+            .map(|id| json!({ "@id": id }))
+            .unwrap_or_default(),
+        (Some("@vocab"), Value::String(id)) => active
+            .expand_iri(id, true, true)
+            .map(|id| json!({ "@id": id }))
+            .unwrap_or_default(),
+        (Some(datatype), _) if !datatype.starts_with('@') => {
+            json!({ "@value": value, "@type": datatype })
+        }
+        _ => json!({ "@value": value }),
+    }
+}
+
+fn is_list(value: &Value) -> bool {
+    value.get("@list").is_some()
+}
+
+fn into_array(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(items) => items,
+        Value::Null => vec![],
+        value => vec![value],
+    }
+}
@@ -0,0 +1,296 @@
+//! JSON-LD processing of DID documents: expansion, compaction, context validation and RDFC-1.0
+//! canonicalization. Remote contexts are resolved from an offline [ContextCache] only.
+
+mod cache;
+mod canonicalization;
+mod compaction;
+mod context;
+mod error;
+mod expansion;
+mod rdf;
+
+pub use cache::ContextCache;
+pub use canonicalization::canonicalize;
+pub use error::JsonLdError;
+pub use rdf::{to_nquads, Quad, Term};
+use serde_json::Value;
+
+use self::{compaction::Compactor, expansion::Expander};
+use crate::schema::did_doc::DidDocument;
+
+/// Properties of [DidDocument] holding sets of values
+const SET_PROPERTIES: [&str; 8] = [
+    "alsoKnownAs",
+    "verificationMethod",
+    "authentication",
+    "assertionMethod",
+    "keyAgreement",
+    "capabilityInvocation",
+    "capabilityDelegation",
+    "service",
+];
+
+/// Expands a JSON-LD document, relative IRIs are resolved against `base`.
+pub fn expand(
+    document: &Value,
+    base: Option<&str>,
+    cache: &ContextCache,
+) -> Result<Value, JsonLdError> {
+    Expander::new(cache).expand(document, base)
+}
+
+/// Compacts an expanded JSON-LD document against `context`, which is included in the result.
+pub fn compact(
+    expanded: &Value,
+    context: &Value,
+    cache: &ContextCache,
+) -> Result<Value, JsonLdError> {
+    Compactor::new(cache).compact(expanded, context)
+}
+
+/// RDF dataset of an expanded JSON-LD document
+pub fn to_rdf(expanded: &Value) -> Vec<Quad> {
+    rdf::to_rdf(expanded)
+}
+
+/// Canonical N-Quads of a JSON-LD document, as hashed by Data Integrity proofs
+pub fn canonical_nquads(
+    document: &Value,
+    base: Option<&str>,
+    cache: &ContextCache,
+) -> Result<String, JsonLdError> {
+    let expanded = expand(document, base, cache)?;
+    Ok(to_nquads(&canonicalize(&to_rdf(&expanded))?))
+}
+
+impl DidDocument {
+    fn context(&self) -> Result<&Value, JsonLdError> {
+        self.extra_field("@context")
+            .ok_or(JsonLdError::MissingContext)
+    }
+
+    /// Expands the document, relative DID URLs are resolved against the DID.
+    pub fn expand(&self, cache: &ContextCache) -> Result<Value, JsonLdError> {
+        self.context()?;
+        expand(&serde_json::to_value(self)?, Some(self.id().did()), cache)
+    }
+
+    /// Compacts an expanded DID document against `context`.
+    pub fn from_expanded(
+        expanded: &Value,
+        context: &Value,
+        cache: &ContextCache,
+    ) -> Result<Self, JsonLdError> {
+        let mut compacted = compact(expanded, context, cache)?;
+        // compaction unwraps single values, which the document model expects in sets
+        if let Value::Object(document) = &mut compacted {
+            for property in SET_PROPERTIES {
+                if let Some(value) = document.get_mut(property) {
+                    if !value.is_array() {
+                        *value = Value::Array(vec![value.take()]);
+                    }
+                }
+            }
+        }
+        Ok(serde_json::from_value(compacted)?)
+    }
+
+    /// Checks that the `@context` of the document defines every property and type used, in
+    /// particular verification method and service types. Undefined terms are silently dropped
+    /// by JSON-LD processing, so they would not be covered by a Data Integrity proof.
+    pub fn validate_context(&self, cache: &ContextCache) -> Result<(), JsonLdError> {
+        self.context()?;
+        let mut expander = Expander::new(cache);
+        expander.expand(&serde_json::to_value(self)?, Some(self.id().did()))?;
+        match expander.undefined() {
+            undefined if undefined.is_empty() => Ok(()),
+            undefined => Err(JsonLdError::UndefinedTerms(
+                undefined.iter().cloned().collect(),
+            )),
+        }
+    }
+
+    /// RDFC-1.0 canonical N-Quads of the document. Terms undefined by the `@context` are not
+    /// part of the result, see [DidDocument::validate_context].
+    pub fn to_canonical_nquads(&self, cache: &ContextCache) -> Result<String, JsonLdError> {
+        let expanded = self.expand(cache)?;
+        Ok(to_nquads(&canonicalize(&to_rdf(&expanded))?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::schema::contexts;
+
+    const SECURITY: &str = "https://w3id.org/security#";
+
+    fn did_doc() -> DidDocument {
+        serde_json::from_value(json!({
+            "@context": [
+                contexts::W3C_DID_V1,
+                contexts::W3C_SUITE_ED25519_2020,
+                contexts::W3C_SUITE_JWS_2020,
+            ],
+            "id": "did:example:123",
+            "verificationMethod": [
+                {
+                    "id": "did:example:123#key-1",
+                    "type": "Ed25519VerificationKey2020",
+                    "controller": "did:example:123",
+                    "publicKeyMultibase": "z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"
+                },
+                {
+                    "id": "did:example:123#key-2",
+                    "type": "JsonWebKey2020",
+                    "controller": "did:example:123",
+                    "publicKeyJwk": {
+                        "kty": "OKP",
+                        "crv": "Ed25519",
+                        "x": "VCpo2LMLhn6iWku8MKvSLg2ZAoC-nlOyPVQaO3FxVeQ"
+                    }
+                }
+            ],
+            "authentication": ["did:example:123#key-1"],
+            "assertionMethod": ["#key-2"],
+            "service": [
+                {
+                    "id": "did:example:123#linked-domain",
+                    "type": "LinkedDomains",
+                    "serviceEndpoint": "https://example.com/"
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_expand_did_document() {
+        let expanded = did_doc().expand(&ContextCache::bundled()).unwrap();
+        let node = &expanded[0];
+
+        assert_eq!(node["@id"], json!("did:example:123"));
+        assert_eq!(
+            node[format!("{SECURITY}authenticationMethod")],
+            json!([{ "@id": "did:example:123#key-1" }])
+        );
+        // relative DID URLs are resolved against the DID
+        assert_eq!(
+            node[format!("{SECURITY}assertionMethod")],
+            json!([{ "@id": "did:example:123#key-2" }])
+        );
+
+        let methods = node[format!("{SECURITY}verificationMethod")]
+            .as_array()
+            .unwrap();
+        assert_eq!(
+            methods[0]["@type"],
+            json!([format!("{SECURITY}Ed25519VerificationKey2020")])
+        );
+        assert_eq!(
+            methods[0][format!("{SECURITY}publicKeyMultibase")],
+            json!([{
+                "@value": "z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V",
+                "@type": format!("{SECURITY}multibase")
+            }])
+        );
+        assert_eq!(
+            methods[1][format!("{SECURITY}publicKeyJwk")][0]["@type"],
+            json!("@json")
+        );
+    }
+
+    #[test]
+    fn test_compact_expanded_did_document() {
+        let cache = ContextCache::bundled();
+        let did_doc = did_doc();
+        let expanded = did_doc.expand(&cache).unwrap();
+
+        let compacted =
+            DidDocument::from_expanded(&expanded, did_doc.context().unwrap(), &cache).unwrap();
+
+        let mut expected = serde_json::to_value(&did_doc).unwrap();
+        expected["assertionMethod"] = json!(["did:example:123#key-2"]);
+        assert_eq!(serde_json::to_value(compacted).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_validate_context() {
+        let cache = ContextCache::bundled();
+        let mut did_doc = did_doc();
+        assert!(matches!(
+            did_doc.validate_context(&cache),
+            Err(JsonLdError::UndefinedTerms(terms)) if terms == ["LinkedDomains"]
+        ));
+
+        did_doc.set_service(vec![]);
+        did_doc.validate_context(&cache).unwrap();
+
+        did_doc.set_extra_field("@context".to_string(), json!(contexts::W3C_DID_V1));
+        assert!(matches!(
+            did_doc.validate_context(&cache),
+            Err(JsonLdError::UndefinedTerms(terms)) if terms == [
+                "Ed25519VerificationKey2020",
+                "JsonWebKey2020",
+                "publicKeyJwk",
+                "publicKeyMultibase",
+            ]
+        ));
+
+        did_doc.remove_extra_field("@context");
+        assert!(matches!(
+            did_doc.validate_context(&cache),
+            Err(JsonLdError::MissingContext)
+        ));
+    }
+
+    #[test]
+    fn test_canonical_nquads() {
+        let did_doc: DidDocument = serde_json::from_value(json!({
+            "@context": [contexts::W3C_DID_V1, contexts::W3C_SUITE_ED25519_2020],
+            "id": "did:example:123",
+            "authentication": ["#key-1"],
+            "verificationMethod": [{
+                "id": "did:example:123#key-1",
+                "type": "Ed25519VerificationKey2020",
+                "controller": "did:example:123",
+                "publicKeyMultibase": "z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            did_doc
+                .to_canonical_nquads(&ContextCache::bundled())
+                .unwrap(),
+            "<did:example:123#key-1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://w3id.org/security#Ed25519VerificationKey2020> .\n\
+             <did:example:123#key-1> <https://w3id.org/security#controller> <did:example:123> .\n\
+             <did:example:123#key-1> <https://w3id.org/security#publicKeyMultibase> \"z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V\"^^<https://w3id.org/security#multibase> .\n\
+             <did:example:123> <https://w3id.org/security#authenticationMethod> <did:example:123#key-1> .\n\
+             <did:example:123> <https://w3id.org/security#verificationMethod> <did:example:123#key-1> .\n"
+        );
+    }
+
+    #[test]
+    fn test_canonical_nquads_with_blank_nodes() {
+        let cache = ContextCache::bundled();
+        let document = |first: &str, second: &str| {
+            json!({
+                "@context": { "@vocab": "https://example.com/" },
+                "@graph": [
+                    { "@id": first, "name": "a", "knows": { "@id": second } },
+                    { "@id": second, "name": "b", "knows": { "@id": first } }
+                ]
+            })
+        };
+
+        let nquads = canonical_nquads(&document("_:x", "_:y"), None, &cache).unwrap();
+        assert_eq!(
+            nquads,
+            canonical_nquads(&document("_:y", "_:x"), None, &cache).unwrap()
+        );
+        assert!(nquads.contains("_:c14n0 <https://example.com/knows> _:c14n1 .\n"));
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+};
+
+use serde_json::{Map, Number, Value};
+
+use super::context::is_absolute_iri;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Term {
+    Iri(String),
+    /// Blank node identifier, without the `_:` prefix
+    BlankNode(String),
+    Literal {
+        value: String,
+        datatype: String,
+    },
+}
+
+impl Display for Term {
+    /// Canonical N-Quads form of the term
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Iri(iri) => write!(f, "<{iri}>"),
+            Term::BlankNode(id) => write!(f, "_:{id}"),
+            Term::Literal { value, datatype } => {
+                write!(f, "\"")?;
+                for c in value.chars() {
+                    match c {
+                        '\u{8}' => write!(f, "\\b")?,
+                        '\t' => write!(f, "\\t")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\u{c}' => write!(f, "\\f")?,
+                        '\r' => write!(f, "\\r")?,
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\u{0}'..='\u{1f}' | '\u{7f}' => write!(f, "\\u{:04X}", c as u32)?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+                write!(f, "\"")?;
+                if datatype != XSD_STRING {
+                    write!(f, "^^<{datatype}>")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Quad {
+    pub subject: Term,
+    pub predicate: Term,
+    pub object: Term,
+    /// `None` for the default graph
+    pub graph: Option<Term>,
+}
+
+impl Display for Quad {
+    /// N-Quads statement, without the line terminator
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} ", self.subject, self.predicate, self.object)?;
+        if let Some(graph) = &self.graph {
+            write!(f, "{graph} ")?;
+        }
+        write!(f, ".")
+    }
+}
+
+/// Serializes the dataset as N-Quads, one statement per line.
+pub fn to_nquads(quads: &[Quad]) -> String {
+    quads.iter().map(|quad| format!("{quad}\n")).collect()
+}
+
+/// Deserializes an expanded JSON-LD document to an RDF dataset. Statements with relative IRIs
+/// can not be expressed in RDF and are dropped.
+pub(super) fn to_rdf(expanded: &Value) -> Vec<Quad> {
+    let mut builder = DatasetBuilder::default();
+    for node in expanded.as_array().into_iter().flatten() {
+        if let Value::Object(node) = node {
+            builder.node(node, &None);
+        }
+    }
+    builder.quads
+}
+
+#[derive(Default)]
+struct DatasetBuilder {
+    quads: Vec<Quad>,
+    emitted: HashSet<Quad>,
+    /// Blank node identifiers of the document, relabeled to not clash with generated ones
+    labels: HashMap<String, String>,
+    blank_nodes: usize,
+}
+
+impl DatasetBuilder {
+    fn push(&mut self, subject: Term, predicate: &str, object: Term, graph: &Option<Term>) {
+        let quad = Quad {
+            subject,
+            predicate: Term::Iri(predicate.to_string()),
+            object,
+            graph: graph.clone(),
+        };
+        if self.emitted.insert(quad.clone()) {
+            self.quads.push(quad);
+        }
+    }
+
+    fn blank_node(&mut self, label: Option<&str>) -> Term {
+        if let Some(id) = label.and_then(|label| self.labels.get(label)) {
+            return Term::BlankNode(id.clone());
+        }
+        let id = format!("b{}", self.blank_nodes);
+        self.blank_nodes += 1;
+        if let Some(label) = label {
+            self.labels.insert(label.to_string(), id.clone());
+        }
+        Term::BlankNode(id)
+    }
+
+    fn resource(&mut self, id: &str) -> Option<Term> {
+        if id.starts_with("_:") {
+            Some(self.blank_node(Some(id)))
+        } else if is_absolute_iri(id) {
+            Some(Term::Iri(id.to_string()))
+        } else {
+            None
+        }
+    }
+
+    fn node(&mut self, node: &Map<String, Value>, graph: &Option<Term>) -> Option<Term> {
+        let subject = match node.get("@id").and_then(Value::as_str) {
+            Some(id) => self.resource(id)?,
+            None => self.blank_node(None),
+        };
+        self.node_statements(node, subject.clone(), graph);
+        Some(subject)
+    }
+
+    fn node_statements(&mut self, node: &Map<String, Value>, subject: Term, graph: &Option<Term>) {
+        if let Some(Value::Array(nodes)) = node.get("@graph") {
+            let graph_name = Some(subject.clone());
+            for nested in nodes {
+                if let Value::Object(nested) = nested {
+                    self.node(nested, &graph_name);
+                }
+            }
+        }
+        for type_iri in node
+            .get("@type")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if let Some(object) = self.resource(type_iri) {
+                self.push(subject.clone(), RDF_TYPE, object, graph);
+            }
+        }
+        for (property, values) in node {
+            if property.starts_with('@') || !is_absolute_iri(property) {
+                continue;
+            }
+            for value in values.as_array().into_iter().flatten() {
+                if let Some(object) = self.object(value, graph) {
+                    self.push(subject.clone(), property, object, graph);
+                }
+            }
+        }
+    }
+
+    fn object(&mut self, value: &Value, graph: &Option<Term>) -> Option<Term> {
+        let object = value.as_object()?;
+        if let Some(literal) = object.get("@value") {
+            let datatype = object.get("@type").and_then(Value::as_str);
+            return literal_term(literal, datatype);
+        }
+        if let Some(Value::Array(items)) = object.get("@list") {
+            return Some(self.list(items, graph));
+        }
+        if object.contains_key("@graph") && !object.contains_key("@id") {
+            let graph_name = self.blank_node(None);
+            self.node_statements(object, graph_name.clone(), graph);
+            return Some(graph_name);
+        }
+        self.node(object, graph)
+    }
+
+    fn list(&mut self, items: &[Value], graph: &Option<Term>) -> Term {
+        let nodes = items
+            .iter()
+            .map(|_| self.blank_node(None))
+            .collect::<Vec<_>>();
+        for (position, item) in items.iter().enumerate() {
+            if let Some(object) = self.object(item, graph) {
+                self.push(nodes[position].clone(), RDF_FIRST, object, graph);
+            }
+            let rest = nodes
+                .get(position + 1)
+                .cloned()
+                .unwrap_or_else(|| Term::Iri(RDF_NIL.to_string()));
+            self.push(nodes[position].clone(), RDF_REST, rest, graph);
+        }
+        nodes
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| Term::Iri(RDF_NIL.to_string()))
+    }
+}
+
+fn literal_term(value: &Value, datatype: Option<&str>) -> Option<Term> {
+    if datatype.is_some_and(|datatype| datatype != "@json" && !is_absolute_iri(datatype)) {
+        return None;
+    }
+    let (value, default_datatype) = match (value, datatype) {
+        (value, Some("@json")) => (canonical_json(value), RDF_JSON),
+        (Value::String(value), _) => (value.clone(), XSD_STRING),
+        (Value::Bool(value), _) => (value.to_string(), XSD_BOOLEAN),
+        (Value::Number(number), datatype) => number_literal(number, datatype),
+        _ => return None,
+    };
+    let datatype = match datatype {
+        Some("@json") | None => default_datatype,
+        Some(datatype) => datatype,
+    };
+    Some(Term::Literal {
+        value,
+        datatype: datatype.to_string(),
+    })
+}
+
+/// Integral numbers below 10^21 are integers, other numbers doubles in canonical form.
+fn number_literal(number: &Number, datatype: Option<&str>) -> (String, &'static str) {
+    if datatype != Some(XSD_DOUBLE) {
+        if number.is_i64() || number.is_u64() {
+            return (number.to_string(), XSD_INTEGER);
+        }
+        if let Some(value) = number.as_f64() {
+            if value.fract() == 0.0 && value.abs() < 1e21 {
+                return (format!("{value:.0}"), XSD_INTEGER);
+            }
+        }
+    }
+    let formatted = format!("{:E}", number.as_f64().unwrap_or_default());
+    let formatted = match formatted.split_once('E') {
+        Some((mantissa, exponent)) if !mantissa.contains('.') => {
+            format!("{mantissa}.0E{exponent}")
+        }
+        _ => formatted,
+    };
+    (formatted, XSD_DOUBLE)
+}
+
+/// JSON Canonicalization Scheme (RFC 8785) serialization of `@json` literals
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Array(items) => {
+            let items = items.iter().map(canonical_json).collect::<Vec<_>>();
+            format!("[{}]", items.join(","))
+        }
+        Value::Object(entries) => {
+            let mut entries = entries.iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            let entries = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    format!("{}:{}", Value::from(key.as_str()), canonical_json(value))
+                })
+                .collect::<Vec<_>>();
+            format!("{{{}}}", entries.join(","))
+        }
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_literal_serialization() {
+        let literal = literal_term(&json!("line\n\"quoted\"\u{1}"), None).unwrap();
+        assert_eq!(literal.to_string(), r#""line\n\"quoted\"\u0001""#);
+
+        let literal = literal_term(&json!(1.5), None).unwrap();
+        assert_eq!(
+            literal.to_string(),
+            "\"1.5E0\"^^<http://www.w3.org/2001/XMLSchema#double>"
+        );
+        let literal = literal_term(&json!(2.0), None).unwrap();
+        assert_eq!(
+            literal.to_string(),
+            "\"2\"^^<http://www.w3.org/2001/XMLSchema#integer>"
+        );
+    }
+
+    #[test]
+    fn test_json_literal_is_canonical() {
+        let literal = literal_term(&json!({ "y": "Y", "x": [1, true] }), Some("@json")).unwrap();
+        assert_eq!(
+            literal,
+            Term::Literal {
+                value: r#"{"x":[1,true],"y":"Y"}"#.to_string(),
+                datatype: RDF_JSON.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_lists_and_blank_nodes() {
+        let expanded = json!([{
+            "@id": "https://example.com/a",
+            "https://example.com/list": [{ "@list": [{ "@value": "x" }] }],
+            "https://example.com/knows": [{ "https://example.com/name": [{ "@value": "b" }] }],
+        }]);
+
+        let nquads = to_nquads(&to_rdf(&expanded));
+        assert_eq!(
+            nquads,
+            "_:b0 <https://example.com/name> \"b\" .\n\
+             <https://example.com/a> <https://example.com/knows> _:b0 .\n\
+             _:b1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"x\" .\n\
+             _:b1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .\n\
+             <https://example.com/a> <https://example.com/list> _:b1 .\n"
+        );
+    }
+}
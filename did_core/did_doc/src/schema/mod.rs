@@ -1,4 +1,6 @@
 pub mod did_doc;
+#[cfg(feature = "jsonld")]
+pub mod jsonld;
 pub mod service;
 pub mod types;
 pub mod utils;
@@ -0,0 +1,366 @@
+use ::public_key::Key;
+use did_parser_nom::{Did, DidUrl};
+
+use super::{PublicKeyField, VerificationMethod, VerificationMethodType};
+use crate::error::DidDocumentBuilderError;
+
+impl PublicKeyField {
+    /// Encodes `key` in the representation defined by the suite of `vm_type`: multicodec
+    /// prefixed multibase for `Multikey` and the 2020 suites, JWK for `JsonWebKey2020` and
+    /// base58 for the remaining suites.
+    pub fn from_key(
+        key: &Key,
+        vm_type: VerificationMethodType,
+    ) -> Result<Self, DidDocumentBuilderError> {
+        if !vm_type.supports_key_type(key.key_type()) {
+            return Err(DidDocumentBuilderError::UnsupportedKeyType(
+                *key.key_type(),
+                vm_type,
+            ));
+        }
+        let field = match vm_type {
+            VerificationMethodType::Multikey
+            | VerificationMethodType::Ed25519VerificationKey2020
+            | VerificationMethodType::X25519KeyAgreementKey2020 => PublicKeyField::Multibase {
+                public_key_multibase: key.fingerprint(),
+            },
+            VerificationMethodType::JsonWebKey2020 => jwk_field(key)?,
+            _ => PublicKeyField::Base58 {
+                public_key_base58: key.base58(),
+            },
+        };
+        Ok(field)
+    }
+}
+
+#[cfg(feature = "jwk")]
+fn jwk_field(key: &Key) -> Result<PublicKeyField, DidDocumentBuilderError> {
+    Ok(PublicKeyField::Jwk {
+        public_key_jwk: serde_json::from_str(&key.to_jwk()?)?,
+    })
+}
+
+#[cfg(not(feature = "jwk"))]
+fn jwk_field(_key: &Key) -> Result<PublicKeyField, DidDocumentBuilderError> {
+    Err(DidDocumentBuilderError::CustomError(
+        "JWK encoding of public keys requires the jwk feature".to_string(),
+    ))
+}
+
+impl VerificationMethod {
+    /// Builds a verification method of type `vm_type` holding `key`.
+    pub fn from_key(
+        id: DidUrl,
+        controller: Did,
+        key: &Key,
+        vm_type: VerificationMethodType,
+    ) -> Result<Self, DidDocumentBuilderError> {
+        Ok(Self::builder()
+            .id(id)
+            .controller(controller)
+            .verification_method_type(vm_type)
+            .public_key(PublicKeyField::from_key(key, vm_type)?)
+            .build())
+    }
+
+    /// Re-encodes the key of this method as a verification method of type `vm_type`, keeping
+    /// its id and controller.
+    pub fn convert(
+        &self,
+        vm_type: VerificationMethodType,
+    ) -> Result<Self, DidDocumentBuilderError> {
+        Self::from_key(
+            self.id.clone(),
+            self.controller.clone(),
+            &self.public_key_strict()?,
+            vm_type,
+        )
+    }
+
+    pub fn to_multikey(&self) -> Result<Self, DidDocumentBuilderError> {
+        self.convert(VerificationMethodType::Multikey)
+    }
+
+    /// Derives the X25519 key agreement method corresponding to the Ed25519 key of this method.
+    /// `Ed25519VerificationKey2018` and `Ed25519VerificationKey2020` methods give
+    /// `X25519KeyAgreementKey2019` and `X25519KeyAgreementKey2020` methods respectively,
+    /// `Multikey` and `JsonWebKey2020` methods keep their type.
+    #[cfg(feature = "x25519")]
+    pub fn derive_x25519_key_agreement(&self, id: DidUrl) -> Result<Self, DidDocumentBuilderError> {
+        let vm_type = match self.verification_method_type {
+            VerificationMethodType::Ed25519VerificationKey2018 => {
+                VerificationMethodType::X25519KeyAgreementKey2019
+            }
+            VerificationMethodType::Ed25519VerificationKey2020 => {
+                VerificationMethodType::X25519KeyAgreementKey2020
+            }
+            vm_type => vm_type,
+        };
+        let key = self.public_key_strict()?.ed25519_to_x25519()?;
+        Self::from_key(id, self.controller.clone(), &key, vm_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::public_key::KeyType;
+
+    use super::*;
+
+    const ED25519_FINGERPRINT: &str = "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+    const X25519_FINGERPRINT: &str = "z6LShs9GGnqk85isEBzzshkuVWrVKsRp24GnDuHk8QWkARMW";
+    const P256_FINGERPRINT: &str = "zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169";
+    const P384_FINGERPRINT: &str =
+        "z82Lkytz3HqpWiBmt2853ZgNgNG8qVoUJnyoMvGw6ZEBktGcwUVdKpUNJHct1wvp9pXjr7Y";
+    // compressed generators of the BLS12-381 groups
+    const BLS12381_G1_HEX: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    const BLS12381_G2_HEX: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+    const VM_TYPES: [VerificationMethodType; 11] = [
+        VerificationMethodType::JsonWebKey2020,
+        VerificationMethodType::EcdsaSecp256k1VerificationKey2019,
+        VerificationMethodType::Ed25519VerificationKey2018,
+        VerificationMethodType::Ed25519VerificationKey2020,
+        VerificationMethodType::Bls12381G1Key2020,
+        VerificationMethodType::Bls12381G2Key2020,
+        VerificationMethodType::PgpVerificationKey2021,
+        VerificationMethodType::X25519KeyAgreementKey2019,
+        VerificationMethodType::X25519KeyAgreementKey2020,
+        VerificationMethodType::EcdsaSecp256k1RecoveryMethod2020,
+        VerificationMethodType::Multikey,
+    ];
+
+    fn keys() -> Vec<Key> {
+        let g1 = hex::decode(BLS12381_G1_HEX).unwrap();
+        let g2 = hex::decode(BLS12381_G2_HEX).unwrap();
+        vec![
+            Key::from_fingerprint(ED25519_FINGERPRINT).unwrap(),
+            Key::from_fingerprint(X25519_FINGERPRINT).unwrap(),
+            Key::from_fingerprint(P256_FINGERPRINT).unwrap(),
+            Key::from_fingerprint(P384_FINGERPRINT).unwrap(),
+            Key::new(vec![2; 67], KeyType::P521).unwrap(),
+            Key::new(g1.clone(), KeyType::Bls12381g1).unwrap(),
+            Key::new(g2.clone(), KeyType::Bls12381g2).unwrap(),
+            Key::new([g1, g2].concat(), KeyType::Bls12381g1g2).unwrap(),
+        ]
+    }
+
+    fn id() -> DidUrl {
+        DidUrl::parse("did:example:123#key-1".to_string()).unwrap()
+    }
+
+    fn controller() -> Did {
+        Did::parse("did:example:123".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_conversions_between_key_and_verification_method_types() {
+        for key in keys() {
+            let key_type = key.key_type();
+            for vm_type in VM_TYPES {
+                let result = VerificationMethod::from_key(id(), controller(), &key, vm_type);
+                if !vm_type.supports_key_type(key_type) {
+                    assert!(
+                        matches!(
+                            result,
+                            Err(DidDocumentBuilderError::UnsupportedKeyType(t, v))
+                                if &t == key_type && v == vm_type
+                        ),
+                        "{key_type} should not be representable as {vm_type}"
+                    );
+                    continue;
+                }
+                if vm_type == VerificationMethodType::JsonWebKey2020 && cfg!(not(feature = "jwk")) {
+                    assert!(result.is_err());
+                    continue;
+                }
+
+                let vm: VerificationMethod =
+                    serde_json::from_value(serde_json::to_value(result.unwrap()).unwrap()).unwrap();
+                assert_eq!(vm.public_key().unwrap(), key, "{key_type} as {vm_type}");
+
+                let multikey = vm.to_multikey().unwrap();
+                assert_eq!(
+                    multikey.verification_method_type(),
+                    &VerificationMethodType::Multikey
+                );
+                assert_eq!(
+                    multikey.public_key_field(),
+                    &PublicKeyField::Multibase {
+                        public_key_multibase: key.fingerprint()
+                    }
+                );
+                assert_eq!(multikey.id(), vm.id());
+                assert_eq!(multikey.controller(), vm.controller());
+            }
+        }
+    }
+
+    #[test]
+    fn test_for_key_type_supports_key_type() {
+        for key in keys() {
+            let vm_type = VerificationMethodType::for_key_type(key.key_type());
+            assert!(vm_type.supports_key_type(key.key_type()));
+        }
+    }
+
+    #[test]
+    fn test_legacy_encodings() {
+        let key = Key::from_fingerprint(ED25519_FINGERPRINT).unwrap();
+
+        let vm = VerificationMethod::from_key(
+            id(),
+            controller(),
+            &key,
+            VerificationMethodType::Ed25519VerificationKey2018,
+        )
+        .unwrap();
+        assert_eq!(
+            vm.public_key_field(),
+            &PublicKeyField::Base58 {
+                public_key_base58: key.base58()
+            }
+        );
+
+        let vm = vm
+            .convert(VerificationMethodType::Ed25519VerificationKey2020)
+            .unwrap();
+        assert_eq!(
+            vm.public_key_field(),
+            &PublicKeyField::Multibase {
+                public_key_multibase: ED25519_FINGERPRINT.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_public_key_from_unprefixed_multibase() {
+        let key = Key::from_fingerprint(ED25519_FINGERPRINT).unwrap();
+        let vm = VerificationMethod::builder()
+            .id(id())
+            .controller(controller())
+            .verification_method_type(VerificationMethodType::Ed25519VerificationKey2020)
+            .public_key(PublicKeyField::Multibase {
+                public_key_multibase: key.multibase58(),
+            })
+            .build();
+        assert_eq!(vm.public_key_strict().unwrap(), key);
+    }
+
+    #[test]
+    fn test_convert_to_incompatible_type_fails() {
+        let vm = VerificationMethod::from_key(
+            id(),
+            controller(),
+            &Key::from_fingerprint(X25519_FINGERPRINT).unwrap(),
+            VerificationMethodType::Multikey,
+        )
+        .unwrap();
+        assert!(matches!(
+            vm.convert(VerificationMethodType::Ed25519VerificationKey2020),
+            Err(DidDocumentBuilderError::UnsupportedKeyType(
+                KeyType::X25519,
+                VerificationMethodType::Ed25519VerificationKey2020
+            ))
+        ));
+    }
+
+    #[cfg(feature = "jwk")]
+    #[test]
+    fn test_p256_json_web_key_to_multikey() {
+        let vm: VerificationMethod = serde_json::from_value(serde_json::json!({
+            "id": "did:example:123#key-1",
+            "type": "JsonWebKey2020",
+            "controller": "did:example:123",
+            "publicKeyJwk": {
+                "kty": "EC",
+                "crv": "P-256",
+                "x": "fyNYMN0976ci7xqiSdag3buk-ZCwgXU4kz9XNkBlNUI",
+                "y": "hW2ojTNfH7Jbi8--CJUo3OCbH3y5n91g-IMA9MLMbTU"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(vm.to_multikey().unwrap()).unwrap(),
+            serde_json::json!({
+                "id": "did:example:123#key-1",
+                "type": "Multikey",
+                "controller": "did:example:123",
+                "publicKeyMultibase": P256_FINGERPRINT
+            })
+        );
+    }
+
+    #[cfg(feature = "jwk")]
+    #[test]
+    fn test_strict_public_key_of_other_key_type_fails() {
+        let vm: VerificationMethod = serde_json::from_value(serde_json::json!({
+            "id": "did:example:123#key-1",
+            "type": "Ed25519VerificationKey2018",
+            "controller": "did:example:123",
+            "publicKeyJwk": {
+                "kty": "OKP",
+                "crv": "X25519",
+                "x": "W_Vcc7guviK-gPNDBmevVw-uJVamQV5rMNQGUwCqlH0"
+            }
+        }))
+        .unwrap();
+        // the lenient accessor keeps returning the key whatever the verification method type
+        assert_eq!(vm.public_key().unwrap().key_type(), &KeyType::X25519);
+        assert!(matches!(
+            vm.public_key_strict(),
+            Err(DidDocumentBuilderError::UnsupportedKeyType(
+                KeyType::X25519,
+                VerificationMethodType::Ed25519VerificationKey2018
+            ))
+        ));
+    }
+
+    #[cfg(feature = "x25519")]
+    #[test]
+    fn test_derive_x25519_key_agreement() {
+        // vector from https://w3c-ccg.github.io/did-method-key/#ed25519-x25519
+        let x25519_fingerprint = "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p";
+        let key = Key::from_fingerprint(ED25519_FINGERPRINT).unwrap();
+        let key_agreement_id = DidUrl::parse("did:example:123#key-2".to_string()).unwrap();
+
+        let vm = VerificationMethod::from_key(
+            id(),
+            controller(),
+            &key,
+            VerificationMethodType::Ed25519VerificationKey2018,
+        )
+        .unwrap();
+        let key_agreement = vm
+            .derive_x25519_key_agreement(key_agreement_id.clone())
+            .unwrap();
+        assert_eq!(key_agreement.id(), &key_agreement_id);
+        assert_eq!(
+            key_agreement.verification_method_type(),
+            &VerificationMethodType::X25519KeyAgreementKey2019
+        );
+        assert_eq!(
+            key_agreement.public_key().unwrap().fingerprint(),
+            x25519_fingerprint
+        );
+
+        let key_agreement = vm
+            .to_multikey()
+            .unwrap()
+            .derive_x25519_key_agreement(key_agreement_id)
+            .unwrap();
+        assert_eq!(
+            key_agreement.public_key_field(),
+            &PublicKeyField::Multibase {
+                public_key_multibase: x25519_fingerprint.to_string()
+            }
+        );
+
+        let x25519_vm = key_agreement.derive_x25519_key_agreement(id());
+        assert!(matches!(
+            x25519_vm,
+            Err(DidDocumentBuilderError::PublicKeyError(_))
+        ));
+    }
+}
@@ -1,9 +1,10 @@
+mod conversion;
 pub mod error;
 pub mod public_key;
 mod verification_method_kind;
 mod verification_method_type;
 
-use ::public_key::Key;
+use ::public_key::{Key, KeyType};
 use did_parser_nom::{Did, DidUrl};
 use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
@@ -58,6 +59,50 @@ impl VerificationMethod {
 
         Ok(key)
     }
+
+    /// Like [VerificationMethod::public_key], but also reads multibase keys of key type specific
+    /// suites which lack the multicodec prefix, and fails if the key is of a type the
+    /// verification method type is not defined for.
+    pub fn public_key_strict(&self) -> Result<Key, DidDocumentBuilderError> {
+        let key = match (
+            &self.public_key,
+            KeyType::try_from(self.verification_method_type),
+        ) {
+            // key type specific suites do not always multicodec prefix their multibase keys
+            (
+                PublicKeyField::Multibase {
+                    public_key_multibase,
+                },
+                Ok(key_type),
+            ) => {
+                let (_, key) = multibase::decode(public_key_multibase)
+                    .map_err(::public_key::PublicKeyError::from)?;
+                Key::new(key, key_type)?
+            }
+            (
+                PublicKeyField::Multibase {
+                    public_key_multibase,
+                },
+                Err(_),
+            ) => Key::from_fingerprint(public_key_multibase)?,
+            #[cfg(feature = "jwk")]
+            (PublicKeyField::Jwk { public_key_jwk }, _) => {
+                Key::from_jwk(&public_key_jwk.to_string())?
+            }
+            (_, key_type) => Key::new(self.public_key.key_decoded()?, key_type?)?,
+        };
+
+        if !self
+            .verification_method_type
+            .supports_key_type(key.key_type())
+        {
+            return Err(DidDocumentBuilderError::UnsupportedKeyType(
+                *key.key_type(),
+                self.verification_method_type,
+            ));
+        }
+        Ok(key)
+    }
 }
 
 #[cfg(test)]
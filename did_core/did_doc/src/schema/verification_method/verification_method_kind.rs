@@ -21,4 +21,12 @@ impl VerificationMethodKind {
             VerificationMethodKind::Resolvable(_) => None,
         }
     }
+
+    /// Id of the embedded verification method, or the reference to it
+    pub fn id(&self) -> &DidUrl {
+        match self {
+            VerificationMethodKind::Resolved(verification_method) => verification_method.id(),
+            VerificationMethodKind::Resolvable(reference) => reference,
+        }
+    }
 }
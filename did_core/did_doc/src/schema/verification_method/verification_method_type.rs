@@ -53,6 +53,33 @@ impl VerificationMethodType {
             VerificationMethodType::Multikey => contexts::W3C_MULTIKEY_V1,
         }
     }
+
+    /// Return the key type specific verification method type for `key_type`. NIST curves have no
+    /// key type specific suite, `JsonWebKey2020` is used instead where possible, and `Multikey`
+    /// otherwise.
+    pub fn for_key_type(key_type: &KeyType) -> Self {
+        match key_type {
+            KeyType::Ed25519 => VerificationMethodType::Ed25519VerificationKey2020,
+            KeyType::X25519 => VerificationMethodType::X25519KeyAgreementKey2020,
+            KeyType::Bls12381g1 => VerificationMethodType::Bls12381G1Key2020,
+            KeyType::Bls12381g2 => VerificationMethodType::Bls12381G2Key2020,
+            KeyType::P256 | KeyType::P384 => VerificationMethodType::JsonWebKey2020,
+            KeyType::P521 | KeyType::Bls12381g1g2 => VerificationMethodType::Multikey,
+        }
+    }
+
+    /// Whether a verification method of this type can hold a key of `key_type`
+    pub fn supports_key_type(&self, key_type: &KeyType) -> bool {
+        match self {
+            VerificationMethodType::Multikey => true,
+            // JWK conversion is limited to the curves supported by `public_key`
+            VerificationMethodType::JsonWebKey2020 => matches!(
+                key_type,
+                KeyType::Ed25519 | KeyType::X25519 | KeyType::P256 | KeyType::P384
+            ),
+            vm_type => KeyType::try_from(*vm_type).is_ok_and(|supported| &supported == key_type),
+        }
+    }
 }
 
 impl Display for VerificationMethodType {
@@ -0,0 +1,157 @@
+//! RDFC-1.0 conformance vectors. Every `<name>-in.nq` dataset of `tests/rdf_canon` must
+//! canonicalize to `<name>-rdfc10.nq`, which includes the examples of the RDFC-1.0
+//! specification (https://www.w3.org/TR/rdf-canon/#canon-algo-examples) and the blank node
+//! topologies (cycles, diamonds, self links, blank node graph names) the algorithm has to
+//! tell apart.
+
+use std::{fs, path::Path};
+
+use did_doc::schema::jsonld::{canonicalize, to_nquads, Quad, Term};
+
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+const VECTORS: [&str; 15] = [
+    "spec-unique-hashes",
+    "spec-shared-hashes",
+    "no-blank-nodes",
+    "duplicate-statements",
+    "literals",
+    "self-link",
+    "disjoint-self-links",
+    "circle-of-2",
+    "double-circle-of-2",
+    "circle-of-3",
+    "double-circle-of-3",
+    "circle-with-labels",
+    "diamond",
+    "named-graphs",
+    "indistinguishable-graphs",
+];
+
+/// Minimal N-Quads parser for the vectors: IRIs, blank nodes and literals with a datatype.
+fn parse_nquads(nquads: &str) -> Vec<Quad> {
+    nquads
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut rest = line
+                .trim()
+                .strip_suffix('.')
+                .unwrap_or_else(|| panic!("statement without terminator: {line}"))
+                .trim_end();
+            let mut terms = vec![];
+            while !rest.is_empty() {
+                let (term, remaining) = parse_term(rest);
+                terms.push(term);
+                rest = remaining.trim_start();
+            }
+            let mut terms = terms.into_iter();
+            let quad = Quad {
+                subject: terms.next().unwrap(),
+                predicate: terms.next().unwrap(),
+                object: terms.next().unwrap(),
+                graph: terms.next(),
+            };
+            assert!(terms.next().is_none(), "too many terms: {line}");
+            quad
+        })
+        .collect()
+}
+
+fn parse_term(input: &str) -> (Term, &str) {
+    if let Some(rest) = input.strip_prefix('<') {
+        let (iri, rest) = rest.split_once('>').unwrap();
+        return (Term::Iri(iri.to_string()), rest);
+    }
+    if let Some(rest) = input.strip_prefix("_:") {
+        let end = rest.find(' ').unwrap_or(rest.len());
+        return (Term::BlankNode(rest[..end].to_string()), &rest[end..]);
+    }
+    let rest = input
+        .strip_prefix('"')
+        .unwrap_or_else(|| panic!("unexpected term: {input}"));
+    let mut value = String::new();
+    let mut chars = rest.char_indices();
+    let end = loop {
+        match chars.next().unwrap() {
+            (index, '"') => break index,
+            (_, '\\') => {
+                let unescaped = match chars.next().unwrap().1 {
+                    'b' => '\u{8}',
+                    't' => '\t',
+                    'n' => '\n',
+                    'f' => '\u{c}',
+                    'r' => '\r',
+                    'u' => {
+                        let hex: String = (0..4).map(|_| chars.next().unwrap().1).collect();
+                        char::from_u32(u32::from_str_radix(&hex, 16).unwrap()).unwrap()
+                    }
+                    escaped => escaped,
+                };
+                value.push(unescaped);
+            }
+            (_, c) => value.push(c),
+        }
+    };
+    let rest = &rest[end + 1..];
+    match rest.strip_prefix("^^<") {
+        Some(rest) => {
+            let (datatype, rest) = rest.split_once('>').unwrap();
+            (
+                Term::Literal {
+                    value,
+                    datatype: datatype.to_string(),
+                },
+                rest,
+            )
+        }
+        None => (
+            Term::Literal {
+                value,
+                datatype: XSD_STRING.to_string(),
+            },
+            rest,
+        ),
+    }
+}
+
+fn read_vector(name: &str, suffix: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/rdf_canon")
+        .join(format!("{name}-{suffix}.nq"));
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+}
+
+#[test]
+fn test_rdfc10_vectors() {
+    for name in VECTORS {
+        let input = parse_nquads(&read_vector(name, "in"));
+        let expected = read_vector(name, "rdfc10");
+
+        let canonical = to_nquads(&canonicalize(&input).unwrap());
+        assert_eq!(canonical, expected, "vector {name}");
+    }
+}
+
+#[test]
+fn test_rdfc10_vectors_do_not_depend_on_input_labels_or_order() {
+    for name in VECTORS {
+        let mut input = parse_nquads(&read_vector(name, "in"));
+        input.reverse();
+        let relabel = |term: &mut Term| {
+            if let Term::BlankNode(id) = term {
+                *id = format!("other{id}");
+            }
+        };
+        for quad in input.iter_mut() {
+            relabel(&mut quad.subject);
+            relabel(&mut quad.object);
+            if let Some(graph) = quad.graph.as_mut() {
+                relabel(graph);
+            }
+        }
+
+        let canonical = to_nquads(&canonicalize(&input).unwrap());
+        assert_eq!(canonical, read_vector(name, "rdfc10"), "vector {name}");
+    }
+}
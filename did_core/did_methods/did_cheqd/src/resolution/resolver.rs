@@ -11,7 +11,13 @@ use did_resolver::{
         did_document_metadata::DidDocumentMetadata,
         did_resource::{DidResource, DidResourceMetadata},
     },
-    traits::resolvable::{resolution_output::DidResolutionOutput, DidResolvable},
+    traits::{
+        dereferenceable::DidResourceDereferenceable,
+        resolvable::{
+            resolution_error::DidResolutionError, resolution_output::DidResolutionOutput,
+            DidResolvable,
+        },
+    },
 };
 use http_body_util::combinators::UnsyncBoxBody;
 use hyper_tls::HttpsConnector;
@@ -20,7 +26,7 @@ use hyper_util::{
     rt::TokioExecutor,
 };
 use tokio::sync::Mutex;
-use tonic::{transport::Uri, Status};
+use tonic::{transport::Uri, Code, Status};
 
 use super::transformer::CheqdResourceMetadataWithUri;
 use crate::{
@@ -108,7 +114,20 @@ impl DidResolvable for DidCheqdResolver {
         did: &Did,
         _: &Self::DidResolutionOptions,
     ) -> Result<DidResolutionOutput, GenericError> {
-        Ok(self.resolve_did(did).await?)
+        match self.resolve_did(did).await {
+            // reported as such so that callers, such as resolution caches, recognize missing DIDs
+            Err(DidCheqdError::NonSuccessResponse(status)) if status.code() == Code::NotFound => {
+                Err(Box::new(DidResolutionError::NotFound))
+            }
+            result => Ok(result?),
+        }
+    }
+}
+
+#[async_trait]
+impl DidResourceDereferenceable for DidCheqdResolver {
+    async fn dereference_resource(&self, did_url: &DidUrl) -> Result<DidResource, GenericError> {
+        Ok(self.resolve_resource(did_url).await?)
     }
 }
 
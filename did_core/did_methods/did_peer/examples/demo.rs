@@ -59,6 +59,7 @@ async fn demo_did_peer_2_and_3() -> Result<(), Box<dyn Error>> {
             peer_did_2.did(),
             &PeerDidResolutionOptions {
                 encoding: Some(PublicKeyEncoding::Base58),
+                ..Default::default()
             },
         )
         .await
@@ -7,13 +7,17 @@ use super::PeerDid;
 use crate::{
     error::DidPeerError,
     peer_did::{
-        numalgos::{kind::NumalgoKind, numalgo2::Numalgo2, numalgo3::Numalgo3, numalgo4::Numalgo4},
+        numalgos::{
+            kind::NumalgoKind, numalgo1::Numalgo1, numalgo2::Numalgo2, numalgo3::Numalgo3,
+            numalgo4::Numalgo4,
+        },
         parse::parse_numalgo,
     },
 };
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum AnyPeerDid {
+    Numalgo1(PeerDid<Numalgo1>),
     Numalgo2(PeerDid<Numalgo2>),
     Numalgo3(PeerDid<Numalgo3>),
     Numalgo4(PeerDid<Numalgo4>),
@@ -32,6 +36,10 @@ impl AnyPeerDid {
         let numalgo = parse_numalgo(&did)?;
         log::info!("AnyPeerDid >> parsed numalgo {}", numalgo.to_char());
         let parsed = match numalgo {
+            NumalgoKind::GenesisDoc(numalgo1) => AnyPeerDid::Numalgo1(PeerDid {
+                did,
+                numalgo: numalgo1,
+            }),
             NumalgoKind::MultipleInceptionKeys(numalgo2) => AnyPeerDid::Numalgo2(PeerDid {
                 did,
                 numalgo: numalgo2,
@@ -51,6 +59,7 @@ impl AnyPeerDid {
 
     pub fn numalgo(&self) -> NumalgoKind {
         match self {
+            AnyPeerDid::Numalgo1(peer_did) => NumalgoKind::GenesisDoc(peer_did.numalgo),
             AnyPeerDid::Numalgo2(peer_did) => NumalgoKind::MultipleInceptionKeys(peer_did.numalgo),
             AnyPeerDid::Numalgo3(peer_did) => NumalgoKind::DidShortening(peer_did.numalgo),
             AnyPeerDid::Numalgo4(peer_did) => NumalgoKind::DidPeer4(peer_did.numalgo),
@@ -64,6 +73,7 @@ impl Serialize for AnyPeerDid {
         S: Serializer,
     {
         match &self {
+            AnyPeerDid::Numalgo1(peer_did) => serializer.serialize_str(peer_did.did().did()),
             AnyPeerDid::Numalgo2(peer_did) => serializer.serialize_str(peer_did.did().did()),
             AnyPeerDid::Numalgo3(peer_did) => serializer.serialize_str(peer_did.did().did()),
             AnyPeerDid::Numalgo4(peer_did) => serializer.serialize_str(peer_did.did().did()),
@@ -1,4 +1,13 @@
-use crate::peer_did::numalgos::Numalgo;
+use did_doc::schema::did_doc::DidDocument;
+use did_parser_nom::Did;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::DidPeerError,
+    helpers::MULTIHASH_SHA2_256,
+    peer_did::{numalgos::Numalgo, FromDidDoc, PeerDid},
+};
 
 #[derive(Clone, Copy, Default, Debug, PartialEq)]
 pub struct Numalgo1;
@@ -6,3 +15,117 @@ pub struct Numalgo1;
 impl Numalgo for Numalgo1 {
     const NUMALGO_CHAR: char = '1';
 }
+
+impl FromDidDoc for Numalgo1 {
+    /// Implementation of did:peer:1 creation spec, the DID is derived from the hash of the
+    /// stored variant of the genesis document:
+    /// https://identity.foundation/peer-did-method-spec/#method-1-genesis-doc
+    fn from_did_doc(did_document: DidDocument) -> Result<PeerDid<Numalgo1>, DidPeerError> {
+        let hash = genesis_doc_hash(&did_document)?;
+        let did = Did::parse(format!("did:peer:1{hash}"))?;
+        Ok(PeerDid::from_parts(did, Numalgo1))
+    }
+}
+
+impl PeerDid<Numalgo1> {
+    /// Resolves the DID from its genesis document, which has to be obtained out of band, as
+    /// numalgo 1 DIDs do not encode the document themselves.
+    pub fn resolve_did_doc(&self, genesis_doc: &DidDocument) -> Result<DidDocument, DidPeerError> {
+        let hash = genesis_doc_hash(genesis_doc)?;
+        if self.did().id()[1..] != hash {
+            return Err(DidPeerError::DidValidationError(format!(
+                "Genesis document does not match peer did: {}",
+                self.did()
+            )));
+        }
+        let mut did_doc = genesis_doc.clone();
+        did_doc.set_id(self.did().clone());
+        Ok(did_doc)
+    }
+}
+
+/// Multibase encoded multihash of the genesis document in its stored variant, that is without
+/// the `id` property. Keys are serialized in lexicographic order so the hash does not depend on
+/// the order in which properties were added to the document.
+fn genesis_doc_hash(genesis_doc: &DidDocument) -> Result<String, DidPeerError> {
+    let mut stored_variant = serde_json::to_value(genesis_doc)?;
+    if let Value::Object(map) = &mut stored_variant {
+        map.remove("id");
+    }
+    let digest = {
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(&stored_variant)?);
+        hasher.finalize()
+    };
+    Ok(multibase::encode(
+        multibase::Base::Base58Btc,
+        [MULTIHASH_SHA2_256.as_slice(), &digest[..]].concat(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use did_doc::schema::{
+        did_doc::DidDocument,
+        verification_method::{PublicKeyField, VerificationMethod, VerificationMethodType},
+    };
+    use did_parser_nom::{Did, DidUrl};
+
+    use crate::{
+        error::DidPeerError,
+        peer_did::{numalgos::numalgo1::Numalgo1, PeerDid},
+    };
+
+    fn genesis_doc() -> DidDocument {
+        let did: Did = "did:peer:1".parse().unwrap();
+        let mut did_doc = DidDocument::new(did.clone());
+        did_doc.add_verification_method(
+            VerificationMethod::builder()
+                .id(DidUrl::parse("#key-1".to_string()).unwrap())
+                .controller(did)
+                .verification_method_type(VerificationMethodType::Ed25519VerificationKey2020)
+                .public_key(PublicKeyField::Multibase {
+                    public_key_multibase: "z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"
+                        .to_string(),
+                })
+                .build(),
+        );
+        did_doc.add_authentication_ref(DidUrl::parse("#key-1".to_string()).unwrap());
+        did_doc
+    }
+
+    #[test]
+    fn test_create_numalgo1_ignores_genesis_id() {
+        let did = PeerDid::<Numalgo1>::from_did_doc(genesis_doc()).unwrap();
+        assert!(did.did().id().starts_with("1z"));
+
+        let mut genesis_with_id = genesis_doc();
+        genesis_with_id.set_id(did.did().clone());
+        assert_eq!(
+            did,
+            PeerDid::<Numalgo1>::from_did_doc(genesis_with_id).unwrap()
+        );
+        assert_eq!(PeerDid::<Numalgo1>::parse(did.to_string()).unwrap(), did);
+    }
+
+    #[test]
+    fn test_resolve_numalgo1() {
+        let genesis = genesis_doc();
+        let did = PeerDid::<Numalgo1>::from_did_doc(genesis.clone()).unwrap();
+
+        let did_doc = did.resolve_did_doc(&genesis).unwrap();
+        assert_eq!(did_doc.id(), did.did());
+        assert_eq!(did_doc.verification_method(), genesis.verification_method());
+    }
+
+    #[test]
+    fn test_resolve_numalgo1_with_other_genesis_doc_fails() {
+        let did = PeerDid::<Numalgo1>::from_did_doc(genesis_doc()).unwrap();
+        let other_genesis = DidDocument::new("did:peer:1".parse().unwrap());
+
+        assert!(matches!(
+            did.resolve_did_doc(&other_genesis),
+            Err(DidPeerError::DidValidationError(_))
+        ));
+    }
+}
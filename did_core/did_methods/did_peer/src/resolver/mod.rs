@@ -25,10 +25,15 @@ impl PeerDidResolver {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PeerDidResolutionOptions {
     pub encoding: Option<PublicKeyEncoding>,
+    /// Genesis document of a numalgo 1 DID, which can not be resolved from the DID alone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_document: Option<DidDocument>,
 }
+
 #[async_trait]
 impl DidResolvable for PeerDidResolver {
     type DidResolutionOptions = PeerDidResolutionOptions;
@@ -40,6 +45,14 @@ impl DidResolvable for PeerDidResolver {
     ) -> Result<DidResolutionOutput, GenericError> {
         let peer_did = AnyPeerDid::parse(did.to_owned())?;
         let did_doc = match peer_did {
+            AnyPeerDid::Numalgo1(peer_did) => {
+                let genesis_document = options.genesis_document.as_ref().ok_or_else(|| {
+                    DidPeerError::DidValidationError(format!(
+                        "Genesis document is required to resolve peer did: {peer_did}"
+                    ))
+                })?;
+                peer_did.resolve_did_doc(genesis_document)?
+            }
             AnyPeerDid::Numalgo2(peer_did) => {
                 let encoding = options.encoding.unwrap_or(PublicKeyEncoding::Multibase);
                 let mut did_doc: DidDocument = peer_did.to_did_doc_builder(encoding)?;
@@ -10,6 +10,7 @@ use tokio::test;
 async fn resolve_error(peer_did: &str) -> DidPeerError {
     let options = PeerDidResolutionOptions {
         encoding: Some(PublicKeyEncoding::Multibase),
+        ..Default::default()
     };
     *PeerDidResolver
         .resolve(&peer_did.parse().unwrap(), &options)
@@ -104,3 +105,12 @@ async fn test_resolve_numalgo_2_invalid_7() {
         DidPeerError::PublicKeyError(_)
     ));
 }
+
+#[test]
+async fn test_resolve_numalgo_1_without_genesis_document() {
+    let peer_did = "did:peer:1zQmS19jtYDvGtKVrJhQnRFpBQAx3pJ9omx2HpNrcXFuRCz9";
+    assert!(matches!(
+        resolve_error(peer_did).await,
+        DidPeerError::DidValidationError(_)
+    ));
+}
@@ -1,7 +1,10 @@
 mod fixtures;
 
 use did_doc::schema::did_doc::DidDocument;
-use did_peer::resolver::{options::PublicKeyEncoding, PeerDidResolutionOptions, PeerDidResolver};
+use did_peer::{
+    peer_did::{numalgos::numalgo1::Numalgo1, PeerDid},
+    resolver::{options::PublicKeyEncoding, PeerDidResolutionOptions, PeerDidResolver},
+};
 use did_resolver::traits::resolvable::DidResolvable;
 use pretty_assertions::assert_eq;
 use tokio::test;
@@ -25,6 +28,7 @@ async fn resolve_positive_test(did_doc: &str, peer_did: &str, options: PeerDidRe
 async fn test_resolve_numalgo2_basic() {
     let options = PeerDidResolutionOptions {
         encoding: Some(PublicKeyEncoding::Base58),
+        ..Default::default()
     };
     resolve_positive_test(DID_DOC_BASIC, PEER_DID_NUMALGO_2_BASIC, options).await;
 }
@@ -33,6 +37,7 @@ async fn test_resolve_numalgo2_basic() {
 async fn test_resolve_numalgo2_no_routing_keys() {
     let options = PeerDidResolutionOptions {
         encoding: Some(PublicKeyEncoding::Multibase),
+        ..Default::default()
     };
     resolve_positive_test(
         DID_DOC_NO_ROUTING_KEYS,
@@ -46,6 +51,25 @@ async fn test_resolve_numalgo2_no_routing_keys() {
 async fn test_resolve_numalgo2_no_services() {
     let options = PeerDidResolutionOptions {
         encoding: Some(PublicKeyEncoding::Multibase),
+        ..Default::default()
     };
     resolve_positive_test(DID_DOC_NO_SERVICES, PEER_DID_NUMALGO_2_NO_SERVICES, options).await;
 }
+
+#[test]
+async fn test_resolve_numalgo1_from_genesis_document() {
+    let genesis_document = serde_json::from_str::<DidDocument>(DID_DOC_BASIC).unwrap();
+    let peer_did = PeerDid::<Numalgo1>::from_did_doc(genesis_document.clone()).unwrap();
+    let options = PeerDidResolutionOptions {
+        genesis_document: Some(genesis_document.clone()),
+        ..Default::default()
+    };
+    let resolution = PeerDidResolver
+        .resolve(peer_did.did(), &options)
+        .await
+        .unwrap();
+
+    let mut did_document_expected = genesis_document;
+    did_document_expected.set_id(peer_did.did().clone());
+    assert_eq!(resolution.did_document, did_document_expected);
+}
@@ -0,0 +1,21 @@
+use did_resolver::{
+    did_doc::schema::types::jsonwebkey::JsonWebKeyError, did_parser_nom::ParseError,
+};
+use public_key::{KeyType, PublicKeyError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DidKeyResolverError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Key type {0} can not be represented as {1}")]
+    UnsupportedKeyFormat(KeyType, String),
+    #[error("DID key error: {0}")]
+    DidKeyError(#[from] did_key::error::DidKeyError),
+    #[error("Public key error: {0}")]
+    PublicKeyError(#[from] PublicKeyError),
+    #[error("DID parser error: {0}")]
+    DidParserError(#[from] ParseError),
+    #[error("JWK error: {0}")]
+    JsonWebKeyError(#[from] JsonWebKeyError),
+}
@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use did_key::DidKey;
+use did_resolver::{
+    did_doc::schema::{
+        contexts,
+        did_doc::DidDocument,
+        types::jsonwebkey::JsonWebKey,
+        verification_method::{PublicKeyField, VerificationMethod, VerificationMethodType},
+    },
+    did_parser_nom::{Did, DidUrl},
+    error::GenericError,
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+use public_key::{Key, KeyType};
+use serde_json::json;
+
+use crate::{
+    error::DidKeyResolverError,
+    resolver::options::{DidKeyResolutionOptions, PublicKeyFormat},
+};
+
+pub mod options;
+
+const BLS12381_G1_KEY_LENGTH: usize = 48;
+
+#[derive(Default)]
+pub struct DidKeyResolver;
+
+impl DidKeyResolver {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidKeyResolver {
+    type DidResolutionOptions = DidKeyResolutionOptions;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        if did.method() != Some("key") {
+            return Err(Box::new(DidKeyResolverError::MethodNotSupported(
+                did.method().unwrap_or_default().to_string(),
+            )));
+        }
+        let did_key = DidKey::parse(did.did())?;
+        let did_doc = resolve_did_doc(&did_key, options)?;
+
+        let resolution_metadata = DidResolutionMetadata::builder()
+            .content_type("application/did+json".to_string())
+            .build();
+        let builder =
+            DidResolutionOutput::builder(did_doc).did_resolution_metadata(resolution_metadata);
+        Ok(builder.build())
+    }
+}
+
+/// Expands the key encoded in the did:key into a DID document.
+/// See https://w3c-ccg.github.io/did-method-key/#document-creation-algorithm
+pub fn resolve_did_doc(
+    did_key: &DidKey,
+    options: &DidKeyResolutionOptions,
+) -> Result<DidDocument, DidKeyResolverError> {
+    let did = did_key.did();
+    let key = did_key.key();
+    let format = options.public_key_format.unwrap_or_default();
+    let derive_encryption_key = options.enable_encryption_key_derivation.unwrap_or(true);
+
+    let mut did_doc = DidDocument::new(did.to_owned());
+    let mut contexts = vec![contexts::W3C_DID_V1.to_string()];
+
+    let signing_keys = match key.key_type() {
+        KeyType::X25519 => vec![],
+        KeyType::Bls12381g1g2 => split_bls12381g1g2(key)?,
+        _ => vec![key.to_owned()],
+    };
+    for signing_key in signing_keys.iter() {
+        let vm = build_verification_method(did, signing_key, format)?;
+        let vm_id = vm.id().to_owned();
+        push_context(&mut contexts, vm.verification_method_type());
+        did_doc.add_verification_method(vm);
+
+        did_doc.add_authentication_ref(vm_id.clone());
+        did_doc.add_assertion_method_ref(vm_id.clone());
+        did_doc.add_capability_invocation_ref(vm_id.clone());
+        did_doc.add_capability_delegation_ref(vm_id);
+    }
+
+    let agreement_key = match key.key_type() {
+        KeyType::X25519 => Some(key.to_owned()),
+        KeyType::Ed25519 if derive_encryption_key => Some(key.ed25519_to_x25519()?),
+        _ => None,
+    };
+    if let Some(agreement_key) = agreement_key {
+        let vm = build_verification_method(did, &agreement_key, format)?;
+        let vm_id = vm.id().to_owned();
+        push_context(&mut contexts, vm.verification_method_type());
+        did_doc.add_verification_method(vm);
+        did_doc.add_key_agreement_ref(vm_id);
+    }
+
+    did_doc.set_extra_field("@context".to_string(), json!(contexts));
+    Ok(did_doc)
+}
+
+fn build_verification_method(
+    did: &Did,
+    key: &Key,
+    format: PublicKeyFormat,
+) -> Result<VerificationMethod, DidKeyResolverError> {
+    let fingerprint = key.fingerprint();
+    let id = DidUrl::parse(format!("{did}#{fingerprint}"))?;
+
+    let (verification_method_type, public_key) = match (format, key.key_type()) {
+        (PublicKeyFormat::Multikey, _) => (
+            VerificationMethodType::Multikey,
+            PublicKeyField::Multibase {
+                public_key_multibase: fingerprint,
+            },
+        ),
+        (PublicKeyFormat::LegacyBase58, KeyType::Ed25519) => (
+            VerificationMethodType::Ed25519VerificationKey2018,
+            PublicKeyField::Base58 {
+                public_key_base58: key.base58(),
+            },
+        ),
+        (PublicKeyFormat::LegacyMultibase, KeyType::Ed25519) => (
+            VerificationMethodType::Ed25519VerificationKey2020,
+            PublicKeyField::Multibase {
+                public_key_multibase: fingerprint,
+            },
+        ),
+        (PublicKeyFormat::LegacyBase58, KeyType::X25519) => (
+            VerificationMethodType::X25519KeyAgreementKey2019,
+            PublicKeyField::Base58 {
+                public_key_base58: key.base58(),
+            },
+        ),
+        (PublicKeyFormat::LegacyMultibase, KeyType::X25519) => (
+            VerificationMethodType::X25519KeyAgreementKey2020,
+            PublicKeyField::Multibase {
+                public_key_multibase: fingerprint,
+            },
+        ),
+        // BLS suites only define the base58 representation
+        (PublicKeyFormat::LegacyBase58 | PublicKeyFormat::LegacyMultibase, KeyType::Bls12381g1) => {
+            (
+                VerificationMethodType::Bls12381G1Key2020,
+                PublicKeyField::Base58 {
+                    public_key_base58: key.base58(),
+                },
+            )
+        }
+        (PublicKeyFormat::LegacyBase58 | PublicKeyFormat::LegacyMultibase, KeyType::Bls12381g2) => {
+            (
+                VerificationMethodType::Bls12381G2Key2020,
+                PublicKeyField::Base58 {
+                    public_key_base58: key.base58(),
+                },
+            )
+        }
+        (PublicKeyFormat::JsonWebKey2020, key_type) => {
+            let jwk = key.to_jwk().map_err(|_| {
+                DidKeyResolverError::UnsupportedKeyFormat(
+                    key_type.to_owned(),
+                    VerificationMethodType::JsonWebKey2020.to_string(),
+                )
+            })?;
+            (
+                VerificationMethodType::JsonWebKey2020,
+                PublicKeyField::Jwk {
+                    public_key_jwk: JsonWebKey::new(&jwk)?,
+                },
+            )
+        }
+        // NIST curves have no key type specific suite
+        (format, key_type) => {
+            return Err(DidKeyResolverError::UnsupportedKeyFormat(
+                key_type.to_owned(),
+                format!("{format:?}"),
+            ))
+        }
+    };
+
+    Ok(VerificationMethod::builder()
+        .id(id)
+        .controller(did.to_owned())
+        .verification_method_type(verification_method_type)
+        .public_key(public_key)
+        .build())
+}
+
+fn split_bls12381g1g2(key: &Key) -> Result<Vec<Key>, DidKeyResolverError> {
+    if key.key().len() <= BLS12381_G1_KEY_LENGTH {
+        return Err(DidKeyResolverError::UnsupportedKeyFormat(
+            KeyType::Bls12381g1g2,
+            "Bls12381G1Key2020 and Bls12381G2Key2020".to_string(),
+        ));
+    }
+    let (g1, g2) = key.key().split_at(BLS12381_G1_KEY_LENGTH);
+
+    Ok(vec![
+        Key::new(g1.to_vec(), KeyType::Bls12381g1)?,
+        Key::new(g2.to_vec(), KeyType::Bls12381g2)?,
+    ])
+}
+
+fn push_context(contexts: &mut Vec<String>, vm_type: &VerificationMethodType) {
+    let context = vm_type.context_for_type().to_string();
+    if !contexts.contains(&context) {
+        contexts.push(context);
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Representation of the verification methods in the resolved DID document.
+/// See https://w3c-ccg.github.io/did-method-key/#options
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PublicKeyFormat {
+    /// `Multikey` with `publicKeyMultibase`
+    #[default]
+    Multikey,
+    /// `JsonWebKey2020` with `publicKeyJwk`
+    JsonWebKey2020,
+    /// Key type specific 2018/2019 suites (e.g. `Ed25519VerificationKey2018`) with
+    /// `publicKeyBase58`. Not available for NIST curve keys.
+    LegacyBase58,
+    /// Key type specific 2020 suites (e.g. `Ed25519VerificationKey2020`) with
+    /// `publicKeyMultibase`. Not available for NIST curve keys.
+    LegacyMultibase,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidKeyResolutionOptions {
+    pub public_key_format: Option<PublicKeyFormat>,
+    /// Whether an X25519 key agreement key is derived for Ed25519 keys, defaults to `true`
+    pub enable_encryption_key_derivation: Option<bool>,
+}
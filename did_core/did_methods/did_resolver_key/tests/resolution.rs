@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use did_resolver::{
+    did_doc::schema::did_doc::DidDocument, did_parser_nom::Did, traits::resolvable::DidResolvable,
+};
+use did_resolver_key::resolver::{
+    options::{DidKeyResolutionOptions, PublicKeyFormat},
+    DidKeyResolver,
+};
+use did_resolver_registry::ResolverRegistry;
+use serde_json::json;
+
+const ED25519_DID: &str = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+
+// https://w3c-ccg.github.io/did-method-key/#example-a-simple-ed25519-did-key-value
+#[tokio::test]
+async fn test_ed25519_multikey_resolution() {
+    let did = Did::parse(ED25519_DID.to_string()).unwrap();
+    let expected_json = json!({
+      "@context": [
+        "https://www.w3.org/ns/did/v1",
+        "https://w3id.org/security/multikey/v1"
+      ],
+      "id": "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+      "verificationMethod": [
+        {
+          "id": "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp#z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+          "type": "Multikey",
+          "controller": "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+          "publicKeyMultibase": "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"
+        },
+        {
+          "id": "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp#z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p",
+          "type": "Multikey",
+          "controller": "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+          "publicKeyMultibase": "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p"
+        }
+      ],
+      "authentication": ["did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp#z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"],
+      "assertionMethod": ["did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp#z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"],
+      "capabilityInvocation": ["did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp#z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"],
+      "capabilityDelegation": ["did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp#z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"],
+      "keyAgreement": ["did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp#z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p"]
+    });
+    let expected_did_doc: DidDocument = serde_json::from_value(expected_json).unwrap();
+
+    let output = DidKeyResolver::new()
+        .resolve(&did, &DidKeyResolutionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document, expected_did_doc);
+    assert_eq!(
+        output.did_resolution_metadata.content_type(),
+        Some(&"application/did+json".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_ed25519_legacy_resolution_without_key_agreement() {
+    let did = Did::parse(ED25519_DID.to_string()).unwrap();
+    let options = DidKeyResolutionOptions {
+        public_key_format: Some(PublicKeyFormat::LegacyBase58),
+        enable_encryption_key_derivation: Some(false),
+    };
+
+    let did_doc = DidKeyResolver::new()
+        .resolve(&did, &options)
+        .await
+        .unwrap()
+        .did_document;
+    let did_doc_json = serde_json::to_value(&did_doc).unwrap();
+
+    assert_eq!(
+        did_doc_json["@context"],
+        json!([
+            "https://www.w3.org/ns/did/v1",
+            "https://w3id.org/security/suites/ed25519-2018/v1"
+        ])
+    );
+    assert_eq!(did_doc.verification_method().len(), 1);
+    assert_eq!(
+        did_doc_json["verificationMethod"][0]["type"],
+        "Ed25519VerificationKey2018"
+    );
+    assert_eq!(
+        did_doc_json["verificationMethod"][0]["publicKeyBase58"],
+        "4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS"
+    );
+    assert!(did_doc.key_agreement().is_empty());
+}
+
+#[tokio::test]
+async fn test_x25519_resolution_key_agreement_only() {
+    let did =
+        Did::parse("did:key:z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p".to_string()).unwrap();
+
+    let did_doc = DidKeyResolver::new()
+        .resolve(&did, &DidKeyResolutionOptions::default())
+        .await
+        .unwrap()
+        .did_document;
+
+    assert_eq!(did_doc.verification_method().len(), 1);
+    assert_eq!(did_doc.key_agreement().len(), 1);
+    assert!(did_doc.authentication().is_empty());
+    assert!(did_doc.assertion_method().is_empty());
+}
+
+// https://w3c-ccg.github.io/did-method-key/#p-256
+#[tokio::test]
+async fn test_p256_json_web_key_resolution() {
+    let did = Did::parse("did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169".to_string())
+        .unwrap();
+    let expected_json = json!({
+      "@context": [
+        "https://www.w3.org/ns/did/v1",
+        "https://w3id.org/security/suites/jws-2020/v1"
+      ],
+      "id": "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+      "verificationMethod": [
+        {
+          "id": "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169#zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+          "type": "JsonWebKey2020",
+          "controller": "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+          "publicKeyJwk": {
+            "kty": "EC",
+            "crv": "P-256",
+            "x": "fyNYMN0976ci7xqiSdag3buk-ZCwgXU4kz9XNkBlNUI",
+            "y": "hW2ojTNfH7Jbi8--CJUo3OCbH3y5n91g-IMA9MLMbTU"
+          }
+        }
+      ],
+      "authentication": ["did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169#zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169"],
+      "assertionMethod": ["did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169#zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169"],
+      "capabilityInvocation": ["did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169#zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169"],
+      "capabilityDelegation": ["did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169#zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169"]
+    });
+    let expected_did_doc: DidDocument = serde_json::from_value(expected_json).unwrap();
+
+    let options = DidKeyResolutionOptions {
+        public_key_format: Some(PublicKeyFormat::JsonWebKey2020),
+        ..Default::default()
+    };
+    let output = DidKeyResolver::new().resolve(&did, &options).await.unwrap();
+    assert_eq!(output.did_document, expected_did_doc);
+}
+
+#[tokio::test]
+async fn test_p256_legacy_resolution_is_rejected() {
+    let did = Did::parse("did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169".to_string())
+        .unwrap();
+    let options = DidKeyResolutionOptions {
+        public_key_format: Some(PublicKeyFormat::LegacyBase58),
+        ..Default::default()
+    };
+
+    let result = DidKeyResolver::new().resolve(&did, &options).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_resolution_through_registry() {
+    let registry = ResolverRegistry::new().register_resolver("key".into(), DidKeyResolver::new());
+    let did = Did::parse(ED25519_DID.to_string()).unwrap();
+    let options = HashMap::from([("publicKeyFormat".to_string(), json!("JsonWebKey2020"))]);
+
+    let did_doc = registry.resolve(&did, &options).await.unwrap().did_document;
+    let did_doc_json = serde_json::to_value(&did_doc).unwrap();
+
+    assert_eq!(did_doc.verification_method().len(), 2);
+    assert_eq!(
+        did_doc_json["verificationMethod"][0]["type"],
+        "JsonWebKey2020"
+    );
+    assert_eq!(
+        did_doc_json["verificationMethod"][0]["publicKeyJwk"]["crv"],
+        "Ed25519"
+    );
+    assert_eq!(
+        did_doc_json["verificationMethod"][1]["publicKeyJwk"]["crv"],
+        "X25519"
+    );
+}
+
+#[tokio::test]
+async fn test_resolution_rejects_other_methods() {
+    let did =
+        Did::parse("did:example:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp".to_string())
+            .unwrap();
+
+    let result = DidKeyResolver::new()
+        .resolve(&did, &DidKeyResolutionOptions::default())
+        .await;
+    assert!(result.is_err());
+}
@@ -14,7 +14,10 @@ use did_resolver::{
 };
 
 use super::utils::dereference_did_document;
-use crate::{reader::AttrReader, resolution::DidSovResolver};
+use crate::{
+    reader::AttrReader,
+    resolution::{DidIndyResolver, DidSovResolver},
+};
 
 #[async_trait]
 impl<T, A> DidDereferenceable for DidSovResolver<T, A>
@@ -34,3 +37,24 @@ where
         dereference_did_document(&resolution_output, did_url).map_err(|err| err.into())
     }
 }
+
+#[async_trait]
+impl<T, A> DidDereferenceable for DidIndyResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    type Output = Cursor<Vec<u8>>;
+
+    async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        _options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Self::Output>, GenericError> {
+        let resolution_output = self
+            .resolve(&did_url.try_into()?, &Default::default())
+            .await?;
+
+        dereference_did_document(&resolution_output, did_url).map_err(|err| err.into())
+    }
+}
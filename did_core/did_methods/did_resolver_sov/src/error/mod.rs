@@ -8,6 +8,8 @@ use thiserror::Error;
 use self::parsing::ParsingErrorSource;
 use crate::error::DidSovError::ParsingError;
 
+pub(crate) use self::resolution::into_resolution_error;
+
 // TODO: DIDDocumentBuilderError should do key validation and the error
 // should me mapped accordingly
 // TODO: Perhaps split into input errors and external errors?
@@ -1,5 +1,8 @@
-use did_resolver::traits::resolvable::{
-    resolution_error::DidResolutionError, resolution_metadata::DidResolutionMetadata,
+use did_resolver::{
+    error::GenericError,
+    traits::resolvable::{
+        resolution_error::DidResolutionError, resolution_metadata::DidResolutionMetadata,
+    },
 };
 
 use super::DidSovError;
@@ -19,3 +22,12 @@ impl From<&DidSovError> for DidResolutionMetadata {
         DidResolutionMetadata::builder().error(err.into()).build()
     }
 }
+
+/// Reports DIDs missing from the ledger as [DidResolutionError::NotFound], so that callers such
+/// as resolution caches recognize them without knowing [DidSovError].
+pub(crate) fn into_resolution_error(err: DidSovError) -> GenericError {
+    match err {
+        DidSovError::NotFound(_) => Box::new(DidResolutionError::NotFound),
+        err => Box::new(err),
+    }
+}
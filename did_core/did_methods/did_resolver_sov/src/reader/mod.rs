@@ -1,4 +1,4 @@
-use aries_vcx_ledger::ledger::base_ledger::IndyLedgerRead;
+use aries_vcx_ledger::{errors::error::VcxLedgerError, ledger::base_ledger::IndyLedgerRead};
 use async_trait::async_trait;
 use did_resolver::did_parser_nom::Did;
 
@@ -9,6 +9,23 @@ use crate::error::DidSovError;
 pub trait AttrReader: Send + Sync {
     async fn get_attr(&self, target_did: &Did, attr_name: &str) -> Result<String, DidSovError>;
     async fn get_nym(&self, did: &Did) -> Result<String, DidSovError>;
+    async fn get_nym_version(
+        &self,
+        _did: &Did,
+        _seq_no: Option<i32>,
+        _timestamp: Option<u64>,
+    ) -> Result<String, DidSovError> {
+        Err(VcxLedgerError::UnimplementedFeature(
+            "get_nym_version is not supported by this implementation".into(),
+        )
+        .into())
+    }
+    async fn get_ledger_txn(&self, _seq_no: i32) -> Result<String, DidSovError> {
+        Err(VcxLedgerError::UnimplementedFeature(
+            "get_ledger_txn is not supported by this implementation".into(),
+        )
+        .into())
+    }
 }
 
 #[async_trait]
@@ -27,4 +44,21 @@ where
             .await
             .map_err(|err| err.into())
     }
+
+    async fn get_nym_version(
+        &self,
+        did: &Did,
+        seq_no: Option<i32>,
+        timestamp: Option<u64>,
+    ) -> Result<String, DidSovError> {
+        IndyLedgerRead::get_nym_version(self, did, seq_no, timestamp)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    async fn get_ledger_txn(&self, seq_no: i32) -> Result<String, DidSovError> {
+        IndyLedgerRead::get_ledger_txn(self, seq_no, None)
+            .await
+            .map_err(|err| err.into())
+    }
 }
@@ -0,0 +1,635 @@
+use std::{borrow::Borrow, collections::HashMap, marker::PhantomData};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use did_resolver::{
+    did_doc::schema::{
+        did_doc::DidDocument,
+        types::uri::Uri,
+        verification_method::{PublicKeyField, VerificationMethod, VerificationMethodType},
+    },
+    did_parser_nom::{Did, DidUrl},
+    error::GenericError,
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+        resolution_version::ResolutionVersion, DidResolvable,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::utils::{
+    endpoint_services, expand_abbreviated_verkey, get_data_from_response, is_valid_sovrin_did_id,
+    unix_to_datetime,
+};
+use crate::{
+    error::{into_resolution_error, parsing::ParsingErrorSource, DidSovError},
+    reader::AttrReader,
+};
+
+const NYM_TXN_TYPE: &str = "1";
+const VERKEY_FRAGMENT: &str = "verkey";
+const DID_CORE_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+const ED25519_2018_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2018/v1";
+
+/// See https://hyperledger.github.io/indy-did-method/#did-resolution
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidIndyResolutionOptions {
+    /// Sequence number of the NYM transaction which produced the requested version
+    pub version_id: Option<u32>,
+    /// Resolves the version which was current at the given time
+    pub version_time: Option<DateTime<Utc>>,
+}
+
+/// Resolver for `did:indy:<namespace>:<id>` DIDs. Every namespace is served by its own ledger
+/// reader, DIDs of namespaces without a reader are rejected as not supported.
+pub struct DidIndyResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    ledgers: HashMap<String, T>,
+    _marker: PhantomData<A>,
+}
+
+impl<T, A> Default for DidIndyResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A> DidIndyResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    pub fn new() -> Self {
+        DidIndyResolver {
+            ledgers: HashMap::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Registers the ledger reader of the indy network `namespace`, e.g. `sovrin:staging`
+    pub fn with_namespace(mut self, namespace: impl Into<String>, ledger: T) -> Self {
+        self.ledgers.insert(namespace.into(), ledger);
+        self
+    }
+
+    pub fn supports_namespace(&self, namespace: &str) -> bool {
+        self.ledgers.contains_key(namespace)
+    }
+
+    fn ledger(&self, parsed_did: &Did) -> Result<&A, DidSovError> {
+        let method = parsed_did.method().ok_or_else(|| {
+            DidSovError::InvalidDid("Attempted to resolve unqualified did".to_string())
+        })?;
+        if method != "indy" {
+            return Err(DidSovError::MethodNotSupported(method.to_string()));
+        }
+        let namespace = parsed_did.namespace().ok_or_else(|| {
+            DidSovError::InvalidDid(format!("Indy DID: {parsed_did} is missing a namespace"))
+        })?;
+        if !is_valid_sovrin_did_id(parsed_did.id()) {
+            return Err(DidSovError::InvalidDid(format!(
+                "Indy DID: {} contains invalid DID ID.",
+                parsed_did.id()
+            )));
+        }
+        self.ledgers
+            .get(namespace)
+            .map(|ledger| ledger.borrow())
+            .ok_or_else(|| DidSovError::MethodNotSupported(format!("did:indy:{namespace}")))
+    }
+
+    async fn resolve_nym(
+        &self,
+        parsed_did: &Did,
+        options: &DidIndyResolutionOptions,
+    ) -> Result<DidResolutionOutput, DidSovError> {
+        let ledger = self.ledger(parsed_did)?;
+        // ledger requests address the NYM by its unqualified identifier
+        let nym_did = Did::parse(parsed_did.id().to_string())?;
+
+        let nym_response = match (options.version_id, options.version_time) {
+            (Some(_), Some(_)) => {
+                return Err(DidSovError::InvalidConfiguration(
+                    "versionId and versionTime can not be combined".to_string(),
+                ))
+            }
+            (Some(version_id), None) => {
+                let seq_no = i32::try_from(version_id).map_err(|_| {
+                    DidSovError::InvalidConfiguration(format!("Invalid versionId: {version_id}"))
+                })?;
+                let txn_response = ledger.get_ledger_txn(seq_no).await?;
+                check_nym_txn(&txn_response, nym_did.id(), seq_no)?;
+                ledger.get_nym_version(&nym_did, Some(seq_no), None).await?
+            }
+            (None, Some(version_time)) => {
+                let timestamp = u64::try_from(version_time.timestamp()).map_err(|_| {
+                    DidSovError::InvalidConfiguration(format!(
+                        "Invalid versionTime: {version_time}"
+                    ))
+                })?;
+                ledger
+                    .get_nym_version(&nym_did, None, Some(timestamp))
+                    .await?
+            }
+            (None, None) => ledger.get_nym(&nym_did).await?,
+        };
+        let nym = NymState::from_response(&nym_response)?;
+
+        // the endpoint ATTRIB is only consulted for DIDs without diddocContent, it is always
+        // read in its latest state
+        let endpoint_response = match nym.diddoc_content {
+            None => Some(ledger.get_attr(&nym_did, "endpoint").await?),
+            Some(_) => None,
+        };
+
+        nym_to_resolution_output(parsed_did, nym, endpoint_response.as_deref())
+    }
+}
+
+#[async_trait]
+impl<T, A> DidResolvable for DidIndyResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    type DidResolutionOptions = DidIndyResolutionOptions;
+
+    async fn resolve(
+        &self,
+        parsed_did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        log::info!("DidIndyResolver::resolve >> Resolving did: {parsed_did}, options: {options:?}");
+        self.resolve_nym(parsed_did, options)
+            .await
+            .map_err(into_resolution_error)
+    }
+
+    fn select_version(
+        &self,
+        options: Self::DidResolutionOptions,
+        version: &ResolutionVersion,
+    ) -> Result<Self::DidResolutionOptions, GenericError> {
+        let version_id = match &version.version_id {
+            Some(version_id) => Some(version_id.parse().map_err(|_| {
+                DidSovError::InvalidConfiguration(format!("Invalid versionId: {version_id}"))
+            })?),
+            None => options.version_id,
+        };
+        Ok(DidIndyResolutionOptions {
+            version_id,
+            version_time: version.version_time.or(options.version_time),
+        })
+    }
+}
+
+/// State of a NYM as returned by GET_NYM
+#[derive(Debug)]
+struct NymState {
+    verkey: Option<String>,
+    diddoc_content: Option<Map<String, Value>>,
+    seq_no: Option<u64>,
+    txn_time: Option<i64>,
+}
+
+impl NymState {
+    fn from_response(resp: &str) -> Result<Self, DidSovError> {
+        let data = get_data_from_response(resp)?;
+        let resp: Value = serde_json::from_str(resp)?;
+        let result = &resp["result"];
+
+        let diddoc_content = match &data["diddocContent"] {
+            Value::Null => None,
+            Value::String(content) => Some(serde_json::from_str(content)?),
+            Value::Object(content) => Some(content.clone()),
+            content => {
+                return Err(DidSovError::ParsingError(
+                    ParsingErrorSource::LedgerResponseParsingError(format!(
+                        "Unexpected diddocContent format in NYM: {content}"
+                    )),
+                ))
+            }
+        };
+
+        Ok(Self {
+            verkey: data["verkey"].as_str().map(ToOwned::to_owned),
+            diddoc_content,
+            seq_no: data["seqNo"].as_u64().or_else(|| result["seqNo"].as_u64()),
+            txn_time: data["txnTime"]
+                .as_i64()
+                .or_else(|| result["txnTime"].as_i64()),
+        })
+    }
+}
+
+/// Makes sure transaction `seq_no` is a NYM of the DID with identifier `id`, so that versionId
+/// can not be used to resolve an unrelated transaction.
+fn check_nym_txn(resp: &str, id: &str, seq_no: i32) -> Result<(), DidSovError> {
+    let resp: Value = serde_json::from_str(resp)?;
+    let txn = &resp["result"]["data"]["txn"];
+    if txn.is_null() {
+        return Err(DidSovError::NotFound(format!(
+            "No transaction with sequence number {seq_no}"
+        )));
+    }
+    let is_nym = txn["type"].as_str() == Some(NYM_TXN_TYPE);
+    if !is_nym || txn["data"]["dest"].as_str() != Some(id) {
+        return Err(DidSovError::NotFound(format!(
+            "Transaction {seq_no} is not a NYM of {id}"
+        )));
+    }
+    Ok(())
+}
+
+fn nym_to_resolution_output(
+    did: &Did,
+    nym: NymState,
+    endpoint_response: Option<&str>,
+) -> Result<DidResolutionOutput, DidSovError> {
+    let mut ddo = DidDocument::new(did.clone());
+
+    // a NYM without verkey has been deactivated
+    let deactivated = nym.verkey.is_none();
+    if let Some(verkey) = &nym.verkey {
+        let expanded_verkey = expand_abbreviated_verkey(did.id(), verkey)?;
+        let verkey_id = DidUrl::parse(format!("{did}#{VERKEY_FRAGMENT}"))?;
+        let verification_method = VerificationMethod::builder()
+            .id(verkey_id.clone())
+            .controller(did.clone())
+            .verification_method_type(VerificationMethodType::Ed25519VerificationKey2018)
+            .public_key(PublicKeyField::Base58 {
+                public_key_base58: expanded_verkey,
+            })
+            .build();
+        ddo.add_verification_method(verification_method);
+        ddo.add_authentication_ref(verkey_id);
+    }
+
+    if let Some(endpoint_response) = endpoint_response {
+        let service_id = Uri::new(&format!("{did}#endpoint"))?;
+        ddo.set_service(endpoint_services(service_id, endpoint_response)?);
+    }
+
+    if let Some(content) = nym.diddoc_content {
+        ddo = merge_diddoc_content(ddo, content)?;
+    }
+
+    let ddo_metadata = {
+        let mut metadata_builder = DidDocumentMetadata::builder().deactivated(deactivated);
+        if let Some(datetime) = nym.txn_time.and_then(unix_to_datetime) {
+            metadata_builder = metadata_builder.updated(datetime);
+        }
+        if let Some(seq_no) = nym.seq_no {
+            metadata_builder = metadata_builder.version_id(seq_no.to_string());
+        }
+        metadata_builder.build()
+    };
+
+    let resolution_metadata = DidResolutionMetadata::builder()
+        .content_type("application/did+json".to_string())
+        .build();
+
+    Ok(DidResolutionOutput::builder(ddo)
+        .did_document_metadata(ddo_metadata)
+        .did_resolution_metadata(resolution_metadata)
+        .build())
+}
+
+/// Merges the `diddocContent` of a NYM into the base document derived from its verkey: arrays are
+/// appended to, `@context` is extended and any other field is taken over. Content may neither
+/// replace the document id nor redefine the verkey verification method.
+fn merge_diddoc_content(
+    ddo: DidDocument,
+    content: Map<String, Value>,
+) -> Result<DidDocument, DidSovError> {
+    let verkey_id = format!("{}#{VERKEY_FRAGMENT}", ddo.id());
+    let Value::Object(mut merged) = serde_json::to_value(&ddo)? else {
+        return Err(DidSovError::InternalError);
+    };
+
+    for (key, value) in content {
+        match (key.as_str(), value) {
+            ("id", _) => {
+                return Err(DidSovError::InvalidDid(
+                    "diddocContent must not contain an id".to_string(),
+                ))
+            }
+            ("@context", value) => {
+                let mut contexts = vec![
+                    Value::from(DID_CORE_CONTEXT),
+                    Value::from(ED25519_2018_CONTEXT),
+                ];
+                let additional = match value {
+                    Value::Array(values) => values,
+                    value => vec![value],
+                };
+                for context in additional {
+                    if !contexts.contains(&context) {
+                        contexts.push(context);
+                    }
+                }
+                merged.insert(key, Value::Array(contexts));
+            }
+            (_, Value::Array(values)) => {
+                let redefines_verkey = key == "verificationMethod"
+                    && values.iter().any(|vm| {
+                        let id = vm["id"].as_str().unwrap_or_default();
+                        id == verkey_id || id == format!("#{VERKEY_FRAGMENT}")
+                    });
+                if redefines_verkey {
+                    return Err(DidSovError::InvalidDid(format!(
+                        "diddocContent must not redefine {verkey_id}"
+                    )));
+                }
+                match merged.get_mut(&key) {
+                    Some(Value::Array(existing)) => existing.extend(values),
+                    _ => {
+                        merged.insert(key, Value::Array(values));
+                    }
+                }
+            }
+            (_, value) => {
+                merged.insert(key, value);
+            }
+        }
+    }
+
+    Ok(serde_json::from_value(Value::Object(merged))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use did_resolver::traits::resolvable::resolution_error::DidResolutionError;
+    use serde_json::json;
+
+    use super::*;
+    use crate::reader::MockAttrReader;
+
+    const DID: &str = "did:indy:sovrin:staging:6cgbu8ZPoWTnR5Rv5JcSMB";
+    const ID: &str = "6cgbu8ZPoWTnR5Rv5JcSMB";
+    const VERKEY: &str = "9wvq2i4xUa5umXoThe83CDgx1e5bsjZKJL4DEWvTP9qe";
+
+    fn nym_response(data: Value) -> String {
+        json!({
+            "op": "REPLY",
+            "result": {
+                "type": "105",
+                "dest": ID,
+                "data": data.to_string(),
+                "seqNo": 42,
+                "txnTime": 1629272938
+            }
+        })
+        .to_string()
+    }
+
+    fn attr_response() -> String {
+        json!({
+            "result": {
+                "data": json!({"endpoint": {"endpoint": "https://example.com"}}).to_string()
+            }
+        })
+        .to_string()
+    }
+
+    fn resolver(ledger: MockAttrReader) -> DidIndyResolver<MockAttrReader, MockAttrReader> {
+        DidIndyResolver::new().with_namespace("sovrin:staging", ledger)
+    }
+
+    #[tokio::test]
+    async fn test_resolve_nym_with_endpoint() {
+        let mut ledger = MockAttrReader::new();
+        ledger
+            .expect_get_nym()
+            .withf(|did| did.did() == ID)
+            .returning(|_| Ok(nym_response(json!({"dest": ID, "verkey": VERKEY}))));
+        ledger
+            .expect_get_attr()
+            .returning(|_, _| Ok(attr_response()));
+
+        let output = resolver(ledger)
+            .resolve(&Did::parse(DID.to_string()).unwrap(), &Default::default())
+            .await
+            .unwrap();
+
+        let ddo = &output.did_document;
+        assert_eq!(ddo.id().to_string(), DID);
+        assert_eq!(
+            ddo.verification_method()[0].id().to_string(),
+            format!("{DID}#verkey")
+        );
+        assert!(ddo.authentication_by_id("verkey").is_some());
+        assert_eq!(ddo.service()[0].id().to_string(), format!("{DID}#endpoint"));
+        assert_eq!(
+            output.did_document_metadata.version_id(),
+            Some(&"42".to_string())
+        );
+        assert_eq!(
+            output.did_document_metadata.updated().unwrap(),
+            Utc.timestamp_opt(1629272938, 0).unwrap()
+        );
+        assert_eq!(output.did_document_metadata.deactivated(), Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_nym_with_diddoc_content() {
+        let content = json!({
+            "@context": ["https://identity.foundation/didcomm-messaging/service-endpoint/v1"],
+            "verificationMethod": [{
+                "id": format!("{DID}#key-agreement-1"),
+                "type": "X25519KeyAgreementKey2019",
+                "controller": DID,
+                "publicKeyBase58": "FxfdY3DCQxVZddKGAtSjZdFW9bCCW7oRwZn1NFJ2Tbg2"
+            }],
+            "keyAgreement": [format!("{DID}#key-agreement-1")],
+            "service": [{
+                "id": format!("{DID}#did-communication"),
+                "type": "did-communication",
+                "serviceEndpoint": "https://example.com/"
+            }]
+        });
+        let mut ledger = MockAttrReader::new();
+        ledger.expect_get_nym().returning(move |_| {
+            Ok(nym_response(
+                json!({"dest": ID, "verkey": VERKEY, "diddocContent": content.to_string()}),
+            ))
+        });
+        ledger.expect_get_attr().never();
+
+        let output = resolver(ledger)
+            .resolve(&Did::parse(DID.to_string()).unwrap(), &Default::default())
+            .await
+            .unwrap();
+
+        let ddo = &output.did_document;
+        assert_eq!(ddo.verification_method().len(), 2);
+        assert!(ddo.key_agreement_by_id("key-agreement-1").is_some());
+        assert_eq!(
+            ddo.service()[0].id().to_string(),
+            format!("{DID}#did-communication")
+        );
+        assert_eq!(
+            ddo.extra_field("@context").unwrap(),
+            &json!([
+                DID_CORE_CONTEXT,
+                ED25519_2018_CONTEXT,
+                "https://identity.foundation/didcomm-messaging/service-endpoint/v1"
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_rejects_diddoc_content_redefining_verkey() {
+        let content = json!({
+            "verificationMethod": [{
+                "id": "#verkey",
+                "type": "Ed25519VerificationKey2018",
+                "controller": DID,
+                "publicKeyBase58": VERKEY
+            }]
+        });
+        let mut ledger = MockAttrReader::new();
+        ledger.expect_get_nym().returning(move |_| {
+            Ok(nym_response(
+                json!({"dest": ID, "verkey": VERKEY, "diddocContent": content}),
+            ))
+        });
+
+        let result = resolver(ledger)
+            .resolve(&Did::parse(DID.to_string()).unwrap(), &Default::default())
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_version_id() {
+        let mut ledger = MockAttrReader::new();
+        ledger
+            .expect_get_ledger_txn()
+            .withf(|seq_no| *seq_no == 7)
+            .returning(|_| {
+                Ok(json!({
+                "result": {"data": {"txn": {"type": "1", "data": {"dest": ID, "verkey": VERKEY}}}}
+            })
+            .to_string())
+            });
+        ledger
+            .expect_get_nym_version()
+            .withf(|_, seq_no, timestamp| *seq_no == Some(7) && timestamp.is_none())
+            .returning(|_, _, _| {
+                Ok(nym_response(
+                    json!({"dest": ID, "verkey": VERKEY, "seqNo": 7}),
+                ))
+            });
+        ledger
+            .expect_get_attr()
+            .returning(|_, _| Ok(attr_response()));
+
+        let options = DidIndyResolutionOptions {
+            version_id: Some(7),
+            ..Default::default()
+        };
+        let output = resolver(ledger)
+            .resolve(&Did::parse(DID.to_string()).unwrap(), &options)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            output.did_document_metadata.version_id(),
+            Some(&"7".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_version_id_of_unrelated_txn() {
+        let mut ledger = MockAttrReader::new();
+        ledger.expect_get_ledger_txn().returning(|_| {
+            Ok(json!({
+                "result": {"data": {"txn": {"type": "101", "data": {"data": {"name": "schema"}}}}}
+            })
+            .to_string())
+        });
+        ledger.expect_get_nym_version().never();
+
+        let options = DidIndyResolutionOptions {
+            version_id: Some(7),
+            ..Default::default()
+        };
+        let error = resolver(ledger)
+            .resolve(&Did::parse(DID.to_string()).unwrap(), &options)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<DidResolutionError>(),
+            Some(DidResolutionError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_missing_nym() {
+        let mut ledger = MockAttrReader::new();
+        ledger.expect_get_nym().returning(|_| {
+            Ok(json!({"op": "REPLY", "result": {"type": "105", "data": null}}).to_string())
+        });
+        ledger.expect_get_attr().never();
+
+        let error = resolver(ledger)
+            .resolve(&Did::parse(DID.to_string()).unwrap(), &Default::default())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<DidResolutionError>(),
+            Some(DidResolutionError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_version_time() {
+        let mut ledger = MockAttrReader::new();
+        ledger
+            .expect_get_nym_version()
+            .withf(|_, seq_no, timestamp| seq_no.is_none() && *timestamp == Some(1629272938))
+            .returning(|_, _, _| Ok(nym_response(json!({"dest": ID, "verkey": null}))));
+        ledger
+            .expect_get_attr()
+            .returning(|_, _| Ok(attr_response()));
+
+        let options = DidIndyResolutionOptions {
+            version_time: Some(Utc.timestamp_opt(1629272938, 0).unwrap()),
+            ..Default::default()
+        };
+        let output = resolver(ledger)
+            .resolve(&Did::parse(DID.to_string()).unwrap(), &options)
+            .await
+            .unwrap();
+
+        assert!(output.did_document.verification_method().is_empty());
+        assert_eq!(output.did_document_metadata.deactivated(), Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_unknown_namespace() {
+        let result = resolver(MockAttrReader::new())
+            .resolve(
+                &Did::parse("did:indy:sovrin:6cgbu8ZPoWTnR5Rv5JcSMB".to_string()).unwrap(),
+                &Default::default(),
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+}
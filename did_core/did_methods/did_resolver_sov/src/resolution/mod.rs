@@ -1,4 +1,6 @@
+mod indy_resolver;
 mod resolver;
 mod utils;
 
+pub use indy_resolver::{DidIndyResolutionOptions, DidIndyResolver};
 pub use resolver::DidSovResolver;
@@ -6,11 +6,10 @@ use did_resolver::{
     error::GenericError,
     traits::resolvable::{resolution_output::DidResolutionOutput, DidResolvable},
 };
-use serde_json::Value;
 
-use super::utils::{is_valid_sovrin_did_id, ledger_response_to_ddo};
+use super::utils::{get_data_from_response, is_valid_sovrin_did_id, ledger_response_to_ddo};
 use crate::{
-    error::{parsing::ParsingErrorSource, DidSovError},
+    error::{into_resolution_error, parsing::ParsingErrorSource, DidSovError},
     reader::AttrReader,
 };
 
@@ -53,19 +52,29 @@ where
         _options: &Self::DidResolutionOptions,
     ) -> Result<DidResolutionOutput, GenericError> {
         log::info!("DidSovResolver::resolve >> Resolving did: {parsed_did}");
+        self.resolve_did(parsed_did)
+            .await
+            .map_err(into_resolution_error)
+    }
+}
+
+impl<T, A> DidSovResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    async fn resolve_did(&self, parsed_did: &Did) -> Result<DidResolutionOutput, DidSovError> {
         let method = parsed_did.method().ok_or_else(|| {
             DidSovError::InvalidDid("Attempted to resolve unqualified did".to_string())
         })?;
         if method != "sov" {
-            return Err(Box::new(DidSovError::MethodNotSupported(
-                method.to_string(),
-            )));
+            return Err(DidSovError::MethodNotSupported(method.to_string()));
         }
         if !is_valid_sovrin_did_id(parsed_did.id()) {
-            return Err(Box::new(DidSovError::InvalidDid(format!(
+            return Err(DidSovError::InvalidDid(format!(
                 "Sovrin DID: {} contains invalid DID ID.",
                 parsed_did.id()
-            ))));
+            )));
         }
         let ledger_response = self
             .ledger
@@ -73,29 +82,14 @@ where
             .get_attr(parsed_did, "endpoint")
             .await?;
         let verkey = self.get_verkey(parsed_did).await?;
-        ledger_response_to_ddo(parsed_did.did(), &ledger_response, verkey)
-            .await
-            .map_err(|err| err.into())
+        ledger_response_to_ddo(parsed_did.did(), &ledger_response, verkey).await
     }
-}
 
-impl<T, A> DidSovResolver<T, A>
-where
-    T: Borrow<A> + Sync + Send,
-    A: AttrReader,
-{
     async fn get_verkey(&self, did: &Did) -> Result<String, DidSovError> {
         let nym_response = self.ledger.borrow().get_nym(did).await?;
         log::info!("get_verkey >> nym_response: {nym_response}");
-        let nym_json: Value = serde_json::from_str(&nym_response)?;
-        let nym_data = nym_json["result"]["data"]
-            .as_str()
-            .ok_or(DidSovError::ParsingError(
-                ParsingErrorSource::LedgerResponseParsingError(
-                    "Failed to parse nym data".to_string(),
-                ),
-            ))?;
-        let nym_data: Value = serde_json::from_str(nym_data)?;
+        // a DID missing from the ledger has no NYM data
+        let nym_data = get_data_from_response(&nym_response)?;
         let verkey = nym_data["verkey"]
             .as_str()
             .ok_or(DidSovError::ParsingError(
@@ -106,3 +100,35 @@ where
         Ok(verkey.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use did_resolver::traits::resolvable::resolution_error::DidResolutionError;
+    use serde_json::json;
+
+    use super::*;
+    use crate::reader::MockAttrReader;
+
+    #[tokio::test]
+    async fn test_resolve_missing_did_is_not_found() {
+        let missing = || -> Result<String, DidSovError> {
+            Ok(json!({"op": "REPLY", "result": {"data": null}}).to_string())
+        };
+        let mut ledger = MockAttrReader::new();
+        ledger.expect_get_attr().returning(move |_, _| missing());
+        ledger.expect_get_nym().returning(move |_| missing());
+        let resolver: DidSovResolver<MockAttrReader, MockAttrReader> = DidSovResolver::new(ledger);
+
+        let error = resolver
+            .resolve(
+                &Did::parse("did:sov:2wJPyULfLLnYTEFYzByfUR".to_string()).unwrap(),
+                &(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<DidResolutionError>(),
+            Some(DidResolutionError::NotFound)
+        ));
+    }
+}
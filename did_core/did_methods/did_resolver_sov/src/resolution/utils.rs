@@ -26,7 +26,7 @@ fn prepare_ids(did: &str) -> Result<(Uri, Did), DidSovError> {
     Ok((service_id, ddo_id))
 }
 
-fn get_data_from_response(resp: &str) -> Result<Value, DidSovError> {
+pub(super) fn get_data_from_response(resp: &str) -> Result<Value, DidSovError> {
     let resp: serde_json::Value = serde_json::from_str(resp)?;
     match &resp["result"]["data"] {
         Value::String(ref data) => serde_json::from_str(data).map_err(|err| err.into()),
@@ -49,11 +49,11 @@ fn get_txn_time_from_response(resp: &str) -> Result<i64, DidSovError> {
     Ok(txn_time)
 }
 
-fn unix_to_datetime(posix_timestamp: i64) -> Option<DateTime<Utc>> {
+pub(super) fn unix_to_datetime(posix_timestamp: i64) -> Option<DateTime<Utc>> {
     DateTime::from_timestamp(posix_timestamp, 0)
 }
 
-fn expand_abbreviated_verkey(nym: &str, verkey: &str) -> Result<String, DidSovError> {
+pub(super) fn expand_abbreviated_verkey(nym: &str, verkey: &str) -> Result<String, DidSovError> {
     if let Some(stripped_verkey) = verkey.strip_prefix('~') {
         let mut decoded_nym = bs58::decode(nym).into_vec().map_err(|e| {
             DidSovError::ParsingError(ParsingErrorSource::LedgerResponseParsingError(format!(
@@ -81,14 +81,9 @@ pub(super) fn is_valid_sovrin_did_id(id: &str) -> bool {
     id.chars().all(|c| base58_chars.contains(c))
 }
 
-pub(super) async fn ledger_response_to_ddo(
-    did: &str,
-    resp: &str,
-    verkey: String,
-) -> Result<DidResolutionOutput, DidSovError> {
-    log::info!("ledger_response_to_ddo >> did: {did}, verkey: {verkey}, resp: {resp}");
-    let (service_id, ddo_id) = prepare_ids(did)?;
-
+/// Converts the `endpoint` ATTRIB found in the ledger response `resp` into services, an absent
+/// ATTRIB yields no services.
+pub(super) fn endpoint_services(service_id: Uri, resp: &str) -> Result<Vec<Service>, DidSovError> {
     let service_data = match get_data_from_response(resp) {
         Ok(data) => data,
         Err(e) => {
@@ -97,8 +92,6 @@ pub(super) async fn ledger_response_to_ddo(
         }
     };
 
-    let mut ddo = DidDocument::new(ddo_id.clone());
-
     let mut services = Vec::new();
 
     if !service_data.is_null() {
@@ -123,7 +116,19 @@ pub(super) async fn ledger_response_to_ddo(
         services.push(service);
     }
 
-    ddo.set_service(services);
+    Ok(services)
+}
+
+pub(super) async fn ledger_response_to_ddo(
+    did: &str,
+    resp: &str,
+    verkey: String,
+) -> Result<DidResolutionOutput, DidSovError> {
+    log::info!("ledger_response_to_ddo >> did: {did}, verkey: {verkey}, resp: {resp}");
+    let (service_id, ddo_id) = prepare_ids(did)?;
+
+    let mut ddo = DidDocument::new(ddo_id.clone());
+    ddo.set_service(endpoint_services(service_id, resp)?);
 
     let txn_time_result = get_txn_time_from_response(resp);
     let datetime = match txn_time_result {
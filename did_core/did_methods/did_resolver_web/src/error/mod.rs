@@ -14,6 +14,12 @@ pub enum DidWebError {
     RepresentationNotSupported(String),
     #[error("Invalid DID: {0}")]
     InvalidDid(String),
+    #[error("DID parser error: {0}")]
+    DidParserError(#[from] did_resolver::did_parser_nom::ParseError),
+    #[error("Verification method not found: {0}")]
+    VerificationMethodNotFound(String),
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
     #[error("Parsing error: {0}")]
     ParsingError(#[from] ParsingErrorSource),
     #[error("Network error: {0}")]
@@ -22,6 +28,8 @@ pub enum DidWebError {
     NetworkClientError(#[from] hyper_util::client::legacy::Error),
     #[error("Non-success server response: {0}")]
     NonSuccessResponse(StatusCode),
+    #[error("Invalid publisher state file: {0}")]
+    InvalidStateFile(String),
     #[error(transparent)]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
@@ -1,2 +1,5 @@
 pub mod error;
+mod location;
+#[cfg(feature = "publisher")]
+pub mod publisher;
 pub mod resolution;
@@ -0,0 +1,74 @@
+use did_resolver::did_parser_nom::Did;
+
+use crate::error::DidWebError;
+
+const WELL_KNOWN_PATH: &str = "/.well-known/did.json";
+
+/// Where the DID document of a did:web is hosted.
+/// See https://w3c-ccg.github.io/did-method-web/#read-resolve
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct DidWebLocation {
+    pub(crate) authority: String,
+    pub(crate) path: String,
+}
+
+impl DidWebLocation {
+    pub(crate) fn from_did(did: &Did) -> Result<Self, DidWebError> {
+        let method = did.method().ok_or_else(|| {
+            DidWebError::InvalidDid("Attempted to resolve unqualified did".to_string())
+        })?;
+        if method != "web" {
+            return Err(DidWebError::MethodNotSupported(method.to_string()));
+        }
+
+        let did_parts: Vec<&str> = did.id().split(':').collect();
+
+        if did_parts.is_empty() || did_parts[0].is_empty() {
+            return Err(DidWebError::InvalidDid(did.id().to_string()));
+        }
+
+        let authority = did_parts[0].replace("%3A", ":");
+
+        let path_parts = &did_parts[1..];
+        let path = if path_parts.is_empty() {
+            WELL_KNOWN_PATH.to_string()
+        } else {
+            let path = path_parts.join("/");
+            format!("/{path}/did.json")
+        };
+
+        Ok(Self { authority, path })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_well_known() {
+        let did = Did::parse("did:web:example.com".to_string()).unwrap();
+        let location = DidWebLocation::from_did(&did).unwrap();
+        assert_eq!(location.authority, "example.com");
+        assert_eq!(location.path, "/.well-known/did.json");
+    }
+
+    #[test]
+    fn test_location_with_path_and_port() {
+        let did = Did::parse("did:web:localhost%3A8080:user:alice".to_string()).unwrap();
+        let location = DidWebLocation::from_did(&did).unwrap();
+        assert_eq!(location.authority, "localhost:8080");
+        assert_eq!(location.path, "/user/alice/did.json");
+    }
+
+    #[test]
+    fn test_location_rejects_other_methods() {
+        let did =
+            Did::parse("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp".to_string())
+                .unwrap();
+        assert!(matches!(
+            DidWebLocation::from_did(&did),
+            Err(DidWebError::MethodNotSupported(_))
+        ));
+    }
+}
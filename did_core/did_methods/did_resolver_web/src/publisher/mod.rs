@@ -0,0 +1,441 @@
+pub mod router;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use chrono::{DateTime, Utc};
+use did_resolver::{
+    did_doc::schema::{
+        contexts,
+        did_doc::DidDocument,
+        service::Service,
+        verification_method::{PublicKeyField, VerificationMethod, VerificationMethodType},
+    },
+    did_parser_nom::{Did, DidUrl},
+    shared_types::did_document_metadata::DidDocumentMetadata,
+};
+use public_key::{Key, KeyType};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{error::DidWebError, location::DidWebLocation};
+
+/// What a hosted key is published for in the DID document.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyPurpose {
+    /// Referenced from `authentication` and `assertionMethod`
+    Signing,
+    /// Referenced from `keyAgreement`
+    KeyAgreement,
+}
+
+impl KeyPurpose {
+    fn for_key_type(key_type: &KeyType) -> Self {
+        match key_type {
+            KeyType::X25519 => KeyPurpose::KeyAgreement,
+            _ => KeyPurpose::Signing,
+        }
+    }
+}
+
+/// A key which is, or was, published in the DID document.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyHistoryEntry {
+    id: DidUrl,
+    public_key_multibase: String,
+    purpose: KeyPurpose,
+    added: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revoked: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replaced_by: Option<DidUrl>,
+}
+
+impl KeyHistoryEntry {
+    pub fn id(&self) -> &DidUrl {
+        &self.id
+    }
+
+    pub fn public_key_multibase(&self) -> &str {
+        &self.public_key_multibase
+    }
+
+    pub fn purpose(&self) -> KeyPurpose {
+        self.purpose
+    }
+
+    pub fn added(&self) -> DateTime<Utc> {
+        self.added
+    }
+
+    pub fn revoked(&self) -> Option<DateTime<Utc>> {
+        self.revoked
+    }
+
+    pub fn replaced_by(&self) -> Option<&DidUrl> {
+        self.replaced_by.as_ref()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.revoked.is_none()
+    }
+}
+
+/// A version of the DID document, as it was served from the moment it was published.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedDocument {
+    version_id: u64,
+    created: DateTime<Utc>,
+    did_document: DidDocument,
+}
+
+impl PublishedDocument {
+    pub fn version_id(&self) -> u64 {
+        self.version_id
+    }
+
+    pub fn created(&self) -> DateTime<Utc> {
+        self.created
+    }
+
+    pub fn did_document(&self) -> &DidDocument {
+        &self.did_document
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct PublisherState {
+    keys: Vec<KeyHistoryEntry>,
+    services: Vec<Service>,
+    versions: Vec<PublishedDocument>,
+}
+
+#[derive(Serialize)]
+struct StateFileRef<'a> {
+    did: &'a Did,
+    #[serde(flatten)]
+    state: &'a PublisherState,
+}
+
+#[derive(Deserialize)]
+struct StateFile {
+    did: Did,
+    #[serde(flatten)]
+    state: PublisherState,
+}
+
+/// Maintains the DID document of a self hosted did:web. Every change to the published keys or
+/// services produces a new document version, while previous versions and the key rotation
+/// history are kept so they can still be served and audited. With a state file set, the
+/// versions and the key history are written to it on every change and survive restarts.
+/// See https://w3c-ccg.github.io/did-method-web/#create-register
+#[derive(Clone, Debug)]
+pub struct DidWebPublisher {
+    did: Did,
+    location: DidWebLocation,
+    state: Arc<RwLock<PublisherState>>,
+    state_file: Option<PathBuf>,
+}
+
+impl DidWebPublisher {
+    /// Creates the initial version of the document, publishing the given keys (typically
+    /// verkeys of DIDs created in the wallet). X25519 keys are published for key agreement,
+    /// any other key type for authentication and assertion.
+    pub fn new(did: Did, keys: Vec<Key>) -> Result<Self, DidWebError> {
+        let location = DidWebLocation::from_did(&did)?;
+        let publisher = Self {
+            did,
+            location,
+            state: Arc::new(RwLock::new(PublisherState {
+                keys: vec![],
+                services: vec![],
+                versions: vec![],
+            })),
+            state_file: None,
+        };
+
+        publisher.update(|publisher, state| {
+            let now = Utc::now();
+            for key in keys {
+                publisher.push_key(state, key, now)?;
+            }
+            publisher.publish(state, now);
+            Ok(())
+        })?;
+
+        Ok(publisher)
+    }
+
+    /// Restores a publisher from a state file written by a publisher with the state file set.
+    /// Further changes are written back to the same file.
+    pub fn load(state_file: impl AsRef<Path>) -> Result<Self, DidWebError> {
+        let state_file = state_file.as_ref().to_path_buf();
+        let StateFile { did, state } = serde_json::from_slice(&fs::read(&state_file)?)?;
+        if state.versions.is_empty() {
+            return Err(DidWebError::InvalidStateFile(format!(
+                "{} has no published document version",
+                state_file.display()
+            )));
+        }
+        let location = DidWebLocation::from_did(&did)?;
+
+        Ok(Self {
+            did,
+            location,
+            state: Arc::new(RwLock::new(state)),
+            state_file: Some(state_file),
+        })
+    }
+
+    /// Writes the versions and the key history to `state_file`, now and on every following
+    /// change, so the publisher can be restored with [`DidWebPublisher::load`].
+    pub fn with_state_file(mut self, state_file: impl AsRef<Path>) -> Result<Self, DidWebError> {
+        self.state_file = Some(state_file.as_ref().to_path_buf());
+        self.persist(&self.read_state())?;
+
+        Ok(self)
+    }
+
+    pub fn did(&self) -> &Did {
+        &self.did
+    }
+
+    /// HTTP path the document must be served from, e.g. `/.well-known/did.json`
+    pub fn document_path(&self) -> &str {
+        &self.location.path
+    }
+
+    pub fn add_key(&self, key: Key) -> Result<DidUrl, DidWebError> {
+        self.update(|publisher, state| {
+            let now = Utc::now();
+            let id = publisher.push_key(state, key, now)?;
+            publisher.publish(state, now);
+
+            Ok(id)
+        })
+    }
+
+    /// Replaces the active key `key_id` by `new_key`. The new key is published with the same
+    /// purpose, while the old key is kept in the history as revoked.
+    pub fn rotate_key(&self, key_id: &DidUrl, new_key: Key) -> Result<DidUrl, DidWebError> {
+        self.update(|publisher, state| {
+            let now = Utc::now();
+            let index = Self::active_key_index(state, key_id)?;
+            let new_id = publisher.push_key(state, new_key, now)?;
+
+            let entry = &mut state.keys[index];
+            entry.revoked = Some(now);
+            entry.replaced_by = Some(new_id.clone());
+            publisher.publish(state, now);
+
+            Ok(new_id)
+        })
+    }
+
+    pub fn revoke_key(&self, key_id: &DidUrl) -> Result<(), DidWebError> {
+        self.update(|publisher, state| {
+            let now = Utc::now();
+            let index = Self::active_key_index(state, key_id)?;
+
+            state.keys[index].revoked = Some(now);
+            publisher.publish(state, now);
+
+            Ok(())
+        })
+    }
+
+    pub fn add_service(&self, service: Service) -> Result<(), DidWebError> {
+        self.update(|publisher, state| {
+            state.services.push(service);
+            publisher.publish(state, Utc::now());
+
+            Ok(())
+        })
+    }
+
+    pub fn current(&self) -> PublishedDocument {
+        // there is always at least the initial version, state files without one are rejected
+        self.read_state()
+            .versions
+            .last()
+            .cloned()
+            .expect("publisher has no published document")
+    }
+
+    pub fn version(&self, version_id: u64) -> Option<PublishedDocument> {
+        self.read_state()
+            .versions
+            .iter()
+            .find(|version| version.version_id == version_id)
+            .cloned()
+    }
+
+    /// Resolution metadata of the given version, linking it to its successor if there is one.
+    pub fn document_metadata(&self, version_id: u64) -> Option<DidDocumentMetadata> {
+        let state = self.read_state();
+        let first = state.versions.first()?;
+        let version = state
+            .versions
+            .iter()
+            .find(|version| version.version_id == version_id)?;
+
+        let mut builder = DidDocumentMetadata::builder()
+            .created(first.created)
+            .updated(version.created)
+            .version_id(version.version_id.to_string());
+        if let Some(next) = state
+            .versions
+            .iter()
+            .find(|next| next.version_id == version_id + 1)
+        {
+            builder = builder
+                .next_update(next.created)
+                .next_version_id(next.version_id.to_string());
+        }
+
+        Some(builder.build())
+    }
+
+    pub fn key_history(&self) -> Vec<KeyHistoryEntry> {
+        self.read_state().keys.clone()
+    }
+
+    pub fn did_json(&self) -> Result<String, DidWebError> {
+        Ok(serde_json::to_string_pretty(self.current().did_document())?)
+    }
+
+    /// Writes the current document below `root` at the location a did:web resolver will look
+    /// for it (e.g. `<root>/.well-known/did.json`), so it can be served by any static web server.
+    pub fn write_to_dir(&self, root: impl AsRef<Path>) -> Result<PathBuf, DidWebError> {
+        let path = root
+            .as_ref()
+            .join(self.location.path.trim_start_matches('/'));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, self.did_json()?)?;
+
+        Ok(path)
+    }
+
+    /// Applies `change` to a copy of the state, which replaces the current state only once it is
+    /// persisted, so a failed write leaves both unchanged.
+    fn update<T>(
+        &self,
+        change: impl FnOnce(&Self, &mut PublisherState) -> Result<T, DidWebError>,
+    ) -> Result<T, DidWebError> {
+        let mut current = self.write_state();
+        let mut state = current.clone();
+        let output = change(self, &mut state)?;
+        self.persist(&state)?;
+        *current = state;
+
+        Ok(output)
+    }
+
+    fn persist(&self, state: &PublisherState) -> Result<(), DidWebError> {
+        let Some(state_file) = &self.state_file else {
+            return Ok(());
+        };
+        if let Some(parent) = state_file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // write and rename, so a crash never leaves a truncated state file behind
+        let tmp_file = state_file.with_extension("tmp");
+        fs::write(
+            &tmp_file,
+            serde_json::to_vec_pretty(&StateFileRef {
+                did: &self.did,
+                state,
+            })?,
+        )?;
+        fs::rename(tmp_file, state_file)?;
+
+        Ok(())
+    }
+
+    fn push_key(
+        &self,
+        state: &mut PublisherState,
+        key: Key,
+        added: DateTime<Utc>,
+    ) -> Result<DidUrl, DidWebError> {
+        let id = DidUrl::parse(format!("{}#key-{}", self.did, state.keys.len() + 1))?;
+        let entry = KeyHistoryEntry {
+            id: id.clone(),
+            public_key_multibase: key.fingerprint(),
+            purpose: KeyPurpose::for_key_type(key.key_type()),
+            added,
+            revoked: None,
+            replaced_by: None,
+        };
+        state.keys.push(entry);
+
+        Ok(id)
+    }
+
+    fn active_key_index(state: &PublisherState, key_id: &DidUrl) -> Result<usize, DidWebError> {
+        state
+            .keys
+            .iter()
+            .position(|entry| entry.is_active() && &entry.id == key_id)
+            .ok_or_else(|| DidWebError::VerificationMethodNotFound(key_id.to_string()))
+    }
+
+    fn publish(&self, state: &mut PublisherState, created: DateTime<Utc>) {
+        let did_document = self.build_did_document(state);
+        let version_id = state.versions.len() as u64 + 1;
+        state.versions.push(PublishedDocument {
+            version_id,
+            created,
+            did_document,
+        });
+    }
+
+    fn build_did_document(&self, state: &PublisherState) -> DidDocument {
+        let mut did_doc = DidDocument::new(self.did.clone());
+        did_doc.set_extra_field(
+            "@context".to_string(),
+            json!([contexts::W3C_DID_V1, contexts::W3C_MULTIKEY_V1]),
+        );
+
+        for entry in state.keys.iter().filter(|entry| entry.is_active()) {
+            let vm = VerificationMethod::builder()
+                .id(entry.id.clone())
+                .controller(self.did.clone())
+                .verification_method_type(VerificationMethodType::Multikey)
+                .public_key(PublicKeyField::Multibase {
+                    public_key_multibase: entry.public_key_multibase.clone(),
+                })
+                .build();
+            did_doc.add_verification_method(vm);
+
+            match entry.purpose {
+                KeyPurpose::Signing => {
+                    did_doc.add_authentication_ref(entry.id.clone());
+                    did_doc.add_assertion_method_ref(entry.id.clone());
+                }
+                KeyPurpose::KeyAgreement => did_doc.add_key_agreement_ref(entry.id.clone()),
+            }
+        }
+        for service in state.services.iter() {
+            did_doc.add_service(service.clone());
+        }
+
+        did_doc
+    }
+
+    fn read_state(&self) -> RwLockReadGuard<'_, PublisherState> {
+        self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_state(&self) -> RwLockWriteGuard<'_, PublisherState> {
+        self.state.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
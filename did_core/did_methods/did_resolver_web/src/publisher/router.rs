@@ -0,0 +1,63 @@
+use axum::{
+    extract::{Query, State},
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+
+use super::DidWebPublisher;
+
+const DID_JSON_CONTENT_TYPE: &str = "application/did+json";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DocumentQuery {
+    version_id: Option<u64>,
+}
+
+async fn did_document(
+    State(publisher): State<DidWebPublisher>,
+    Query(query): Query<DocumentQuery>,
+) -> Response {
+    let document = match query.version_id {
+        Some(version_id) => match publisher.version(version_id) {
+            Some(document) => document,
+            None => return StatusCode::NOT_FOUND.into_response(),
+        },
+        None => publisher.current(),
+    };
+
+    (
+        [(CONTENT_TYPE, DID_JSON_CONTENT_TYPE)],
+        Json(document.did_document().clone()),
+    )
+        .into_response()
+}
+
+async fn key_history(State(publisher): State<DidWebPublisher>) -> Response {
+    Json(publisher.key_history()).into_response()
+}
+
+/// Serves the published document at the path mandated by the did:web spec, with older versions
+/// available through the `versionId` query parameter, and the key rotation history next to it
+/// (e.g. `/.well-known/did.json` and `/.well-known/did-history.json`).
+///
+/// The returned router is stateless from the caller's point of view, so it can be merged into
+/// the router of an existing agent, such as the mediator.
+pub fn build_router<S>(publisher: DidWebPublisher) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let document_path = publisher.document_path().to_string();
+    let history_path = format!(
+        "{}did-history.json",
+        document_path.trim_end_matches("did.json")
+    );
+
+    Router::new()
+        .route(&document_path, get(did_document))
+        .route(&history_path, get(key_history))
+        .with_state(publisher)
+}
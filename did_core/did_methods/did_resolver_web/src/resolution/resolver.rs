@@ -4,15 +4,15 @@ use did_resolver::{
     error::GenericError,
     shared_types::did_document_metadata::DidDocumentMetadata,
     traits::resolvable::{
-        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
-        DidResolvable,
+        resolution_error::DidResolutionError, resolution_metadata::DidResolutionMetadata,
+        resolution_output::DidResolutionOutput, DidResolvable,
     },
 };
 use http_body_util::{combinators::BoxBody, BodyExt as _};
 use hyper::{
     body::Bytes,
     http::uri::{self, Scheme},
-    Uri,
+    StatusCode, Uri,
 };
 use hyper_tls::HttpsConnector;
 use hyper_util::{
@@ -23,7 +23,7 @@ use hyper_util::{
     rt::TokioExecutor,
 };
 
-use crate::error::DidWebError;
+use crate::{error::DidWebError, location::DidWebLocation};
 
 pub struct DidWebResolver<C>
 where
@@ -82,37 +82,21 @@ where
         did: &Did,
         _options: &Self::DidResolutionOptions,
     ) -> Result<DidResolutionOutput, GenericError> {
-        let method = did.method().ok_or_else(|| {
-            DidWebError::InvalidDid("Attempted to resolve unqualified did".to_string())
-        })?;
-        if method != "web" {
-            return Err(Box::new(DidWebError::MethodNotSupported(
-                method.to_string(),
-            )));
-        }
-
-        let did_parts: Vec<&str> = did.id().split(':').collect();
-
-        if did_parts.is_empty() {
-            return Err(Box::new(DidWebError::InvalidDid(did.id().to_string())));
-        }
+        let location = DidWebLocation::from_did(did)?;
 
-        let domain = did_parts[0].replace("%3A", ":");
-
-        let path_parts = &did_parts[1..];
-        let path_and_query = if path_parts.is_empty() {
-            "/.well-known/did.json".to_string()
-        } else {
-            let path = path_parts.join("/");
-            format!("/{path}/did.json")
-        };
         let url = uri::Builder::new()
             .scheme(self.scheme.clone())
-            .authority(domain.as_str())
-            .path_and_query(path_and_query.as_str())
+            .authority(location.authority.as_str())
+            .path_and_query(location.path.as_str())
             .build()?;
 
-        let did_document = serde_json::from_str(&self.fetch_did_document(url).await?)?;
+        let did_document = match self.fetch_did_document(url).await {
+            // reported as such so that callers, such as resolution caches, recognize missing DIDs
+            Err(DidWebError::NonSuccessResponse(status)) if status == StatusCode::NOT_FOUND => {
+                return Err(Box::new(DidResolutionError::NotFound))
+            }
+            did_document => serde_json::from_str(&did_document?)?,
+        };
 
         let did_resolution_output = DidResolutionOutput::builder(did_document)
             .did_resolution_metadata(DidResolutionMetadata::default())
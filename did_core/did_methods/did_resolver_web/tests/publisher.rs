@@ -0,0 +1,170 @@
+use did_resolver::{did_parser_nom::Did, traits::resolvable::DidResolvable};
+use did_resolver_web::{
+    error::DidWebError,
+    publisher::{router::build_router, DidWebPublisher, KeyPurpose},
+    resolution::resolver::DidWebResolver,
+};
+use public_key::Key;
+use tokio::net::TcpListener;
+
+const ED25519_KEY_1: &str = "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+const ED25519_KEY_2: &str = "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+const X25519_KEY: &str = "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p";
+
+fn key(fingerprint: &str) -> Key {
+    Key::from_fingerprint(fingerprint).unwrap()
+}
+
+async fn serve(publisher: DidWebPublisher, listener: TcpListener) {
+    tokio::spawn(async move {
+        axum::serve(listener, build_router(publisher))
+            .await
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_publisher_builds_document_from_keys() {
+    let did = Did::parse("did:web:example.com:user:alice".to_string()).unwrap();
+    let publisher = DidWebPublisher::new(did, vec![key(ED25519_KEY_1), key(X25519_KEY)]).unwrap();
+
+    assert_eq!(publisher.document_path(), "/user/alice/did.json");
+    let document = publisher.current();
+    assert_eq!(document.version_id(), 1);
+    let did_doc = document.did_document();
+    assert_eq!(did_doc.verification_method().len(), 2);
+    assert_eq!(did_doc.authentication().len(), 1);
+    assert_eq!(did_doc.assertion_method().len(), 1);
+    assert_eq!(did_doc.key_agreement().len(), 1);
+    assert!(did_doc.verification_method_by_id("key-1").is_some());
+}
+
+#[test]
+fn test_publisher_rotates_keys_and_keeps_history() {
+    let did = Did::parse("did:web:example.com".to_string()).unwrap();
+    let publisher = DidWebPublisher::new(did, vec![key(ED25519_KEY_1)]).unwrap();
+    let old_id = publisher.current().did_document().verification_method()[0]
+        .id()
+        .clone();
+
+    let new_id = publisher.rotate_key(&old_id, key(ED25519_KEY_2)).unwrap();
+
+    let current = publisher.current();
+    assert_eq!(current.version_id(), 2);
+    let vms = current.did_document().verification_method();
+    assert_eq!(vms.len(), 1);
+    assert_eq!(vms[0].id(), &new_id);
+
+    let history = publisher.key_history();
+    assert_eq!(history.len(), 2);
+    assert!(!history[0].is_active());
+    assert_eq!(history[0].replaced_by(), Some(&new_id));
+    assert!(history[1].is_active());
+    assert_eq!(history[1].purpose(), KeyPurpose::Signing);
+
+    // the previous version is still available
+    let previous = publisher.version(1).unwrap();
+    assert_eq!(
+        previous.did_document().verification_method()[0].id(),
+        &old_id
+    );
+    let metadata = publisher.document_metadata(1).unwrap();
+    assert_eq!(metadata.next_version_id(), Some(&"2".to_string()));
+
+    // a revoked key can not be rotated again
+    assert!(publisher.rotate_key(&old_id, key(ED25519_KEY_1)).is_err());
+}
+
+#[test]
+fn test_publisher_writes_did_json() {
+    let did = Did::parse("did:web:example.com".to_string()).unwrap();
+    let publisher = DidWebPublisher::new(did, vec![key(ED25519_KEY_1)]).unwrap();
+    let root = std::env::temp_dir().join(format!("did_web_publisher_{}", std::process::id()));
+
+    let path = publisher.write_to_dir(&root).unwrap();
+
+    assert_eq!(path, root.join(".well-known/did.json"));
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(written, publisher.did_json().unwrap());
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_publisher_restores_history_from_state_file() {
+    let did = Did::parse("did:web:example.com".to_string()).unwrap();
+    let state_file = std::env::temp_dir().join(format!(
+        "did_web_publisher_state_{}.json",
+        std::process::id()
+    ));
+    let publisher = DidWebPublisher::new(did.clone(), vec![key(ED25519_KEY_1)])
+        .unwrap()
+        .with_state_file(&state_file)
+        .unwrap();
+    let old_id = publisher.key_history()[0].id().clone();
+    publisher.rotate_key(&old_id, key(ED25519_KEY_2)).unwrap();
+
+    let restored = DidWebPublisher::load(&state_file).unwrap();
+
+    assert_eq!(restored.did(), &did);
+    assert_eq!(restored.key_history(), publisher.key_history());
+    assert_eq!(restored.current(), publisher.current());
+    assert_eq!(restored.version(1), publisher.version(1));
+
+    // changes made after the restart are persisted as well
+    restored.add_key(key(X25519_KEY)).unwrap();
+    let reloaded = DidWebPublisher::load(&state_file).unwrap();
+    assert_eq!(reloaded.key_history().len(), 3);
+    assert_eq!(reloaded.current().version_id(), 3);
+    std::fs::remove_file(state_file).unwrap();
+}
+
+#[test]
+fn test_publisher_rejects_state_file_without_versions() {
+    let did = Did::parse("did:web:example.com".to_string()).unwrap();
+    let state_file = std::env::temp_dir().join(format!(
+        "did_web_publisher_empty_state_{}.json",
+        std::process::id()
+    ));
+    DidWebPublisher::new(did, vec![key(ED25519_KEY_1)])
+        .unwrap()
+        .with_state_file(&state_file)
+        .unwrap();
+    let mut state: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&state_file).unwrap()).unwrap();
+    state["versions"] = serde_json::json!([]);
+    std::fs::write(&state_file, state.to_string()).unwrap();
+
+    assert!(matches!(
+        DidWebPublisher::load(&state_file),
+        Err(DidWebError::InvalidStateFile(_))
+    ));
+    std::fs::remove_file(state_file).unwrap();
+}
+
+#[tokio::test]
+async fn test_published_document_resolves() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let did = Did::parse(format!("did:web:127.0.0.1%3A{port}:issuer")).unwrap();
+    let publisher = DidWebPublisher::new(did.clone(), vec![key(ED25519_KEY_1)]).unwrap();
+    serve(publisher.clone(), listener).await;
+
+    let resolver = DidWebResolver::http();
+    let output = resolver.resolve(&did, &()).await.unwrap();
+    assert_eq!(&output.did_document, publisher.current().did_document());
+
+    let old_id = publisher.current().did_document().verification_method()[0]
+        .id()
+        .clone();
+    publisher.rotate_key(&old_id, key(ED25519_KEY_2)).unwrap();
+
+    let output = resolver.resolve(&did, &()).await.unwrap();
+    assert_eq!(&output.did_document, publisher.current().did_document());
+    assert_eq!(
+        output.did_document.verification_method()[0]
+            .public_key()
+            .unwrap()
+            .fingerprint(),
+        ED25519_KEY_2
+    );
+}
@@ -3,7 +3,9 @@ use std::convert::Infallible;
 use did_resolver::{
     did_doc::schema::did_doc::DidDocument,
     did_parser_nom::Did,
-    traits::resolvable::{resolution_output::DidResolutionOutput, DidResolvable},
+    traits::resolvable::{
+        resolution_error::DidResolutionError, resolution_output::DidResolutionOutput, DidResolvable,
+    },
 };
 use did_resolver_web::resolution::resolver::DidWebResolver;
 use http_body_util::{combinators::BoxBody, BodyExt, Full};
@@ -155,4 +157,14 @@ async fn test_did_web_resolver() {
         did_document: ddo2, ..
     } = assert_ok!(did_web_resolver.resolve(&did_example_2, &()).await);
     verify_did_document(&ddo2);
+
+    let did_missing = Did::parse(format!("did:web:{host}%3A{port}:user:bob")).unwrap();
+    let error = did_web_resolver
+        .resolve(&did_missing, &())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<DidResolutionError>(),
+        Some(DidResolutionError::NotFound)
+    ));
 }
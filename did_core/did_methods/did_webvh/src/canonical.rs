@@ -0,0 +1,100 @@
+//! JSON Canonicalization Scheme (RFC 8785) and the multihash based digests used throughout
+//! did:webvh.
+
+use public_key::Key;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// multicodec code of sha2-256 followed by the digest length
+const SHA2_256_MULTIHASH_PREFIX: [u8; 2] = [0x12, 0x20];
+
+/// Serializes `value` according to JCS: object members sorted by their UTF-16 code units, no
+/// insignificant whitespace.
+pub fn canonicalize(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, (key, value)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.to_owned()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+pub fn sha256(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data).to_vec()
+}
+
+/// base58btc encoded sha2-256 multihash of the canonical form of `value`, as used for the SCID
+/// and the entry hashes.
+pub fn multihash_b58(value: &Value) -> String {
+    multihash_b58_bytes(canonicalize(value).as_bytes())
+}
+
+fn multihash_b58_bytes(data: &[u8]) -> String {
+    let mut multihash = SHA2_256_MULTIHASH_PREFIX.to_vec();
+    multihash.extend(sha256(data));
+    bs58::encode(multihash).into_string()
+}
+
+/// Commitment to a future update key, for the `nextKeyHashes` parameter (pre-rotation).
+pub fn next_key_hash(key: &Key) -> String {
+    next_key_hash_from_multikey(&key.fingerprint())
+}
+
+pub fn next_key_hash_from_multikey(multikey: &str) -> String {
+    multihash_b58_bytes(multikey.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_sorts_members() {
+        let value = json!({
+            "b": [3, {"z": null, "a": true}],
+            "a": "x\ny",
+            "€": 1,
+            "\u{10000}": 2
+        });
+        assert_eq!(
+            canonicalize(&value),
+            r#"{"a":"x\ny","b":[3,{"a":true,"z":null}],"€":1,"𐀀":2}"#
+        );
+    }
+
+    #[test]
+    fn test_multihash_prefix() {
+        let hash = multihash_b58(&json!({}));
+        assert!(hash.starts_with("Qm"));
+        assert_eq!(bs58::decode(hash).into_vec().unwrap().len(), 34);
+    }
+}
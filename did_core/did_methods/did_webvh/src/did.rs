@@ -0,0 +1,133 @@
+use std::fmt::{self, Display};
+
+use did_resolver::did_parser_nom::Did;
+
+use crate::error::DidWebvhError;
+
+const LOG_FILE: &str = "did.jsonl";
+const WITNESS_FILE: &str = "did-witness.json";
+const WELL_KNOWN: &str = ".well-known";
+
+/// Represents did:webvh, a did:web with a self-certifying identifier (SCID) and a verifiable
+/// history of the DID document hosted next to it.
+/// See the spec: https://identity.foundation/didwebvh/
+#[derive(Clone, Debug, PartialEq)]
+pub struct DidWebvh {
+    did: Did,
+    scid: String,
+    authority: String,
+    path: Vec<String>,
+}
+
+impl DidWebvh {
+    pub fn parse<T>(did: T) -> Result<Self, DidWebvhError>
+    where
+        Did: TryFrom<T>,
+        <Did as TryFrom<T>>::Error: Into<DidWebvhError>,
+    {
+        let did: Did = did.try_into().map_err(Into::into)?;
+        match did.method() {
+            Some("webvh") => {}
+            Some(method) => return Err(DidWebvhError::MethodNotSupported(method.to_string())),
+            None => return Err(DidWebvhError::InvalidDid(did.to_string())),
+        }
+
+        let mut parts = did.id().split(':');
+        let scid = parts.next().unwrap_or_default().to_string();
+        let authority = parts.next().unwrap_or_default().replace("%3A", ":");
+        if scid.is_empty() || authority.is_empty() {
+            return Err(DidWebvhError::InvalidDid(did.to_string()));
+        }
+        let path = parts.map(ToString::to_string).collect();
+
+        Ok(Self {
+            did,
+            scid,
+            authority,
+            path,
+        })
+    }
+
+    pub fn did(&self) -> &Did {
+        &self.did
+    }
+
+    pub fn scid(&self) -> &str {
+        &self.scid
+    }
+
+    /// Host (and port) serving the DID log
+    pub fn authority(&self) -> &str {
+        &self.authority
+    }
+
+    /// HTTP path of the DID log, e.g. `/.well-known/did.jsonl`
+    pub fn log_path(&self) -> String {
+        self.file_path(LOG_FILE)
+    }
+
+    /// HTTP path of the witness proofs, e.g. `/.well-known/did-witness.json`
+    pub fn witness_path(&self) -> String {
+        self.file_path(WITNESS_FILE)
+    }
+
+    /// The did:web this DID is also known as
+    pub fn to_did_web(&self) -> String {
+        let id = self.did.id();
+        format!("did:web:{}", &id[self.scid.len() + 1..])
+    }
+
+    fn file_path(&self, file: &str) -> String {
+        if self.path.is_empty() {
+            format!("/{WELL_KNOWN}/{file}")
+        } else {
+            format!("/{}/{file}", self.path.join("/"))
+        }
+    }
+}
+
+impl Display for DidWebvh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.did)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_did_webvh() {
+        let did =
+            DidWebvh::parse("did:webvh:QmfGEUAcMpzo25kF2Rhn8L5FAXysfGnkzjwdKoNPi615XQ:example.com")
+                .unwrap();
+        assert_eq!(did.scid(), "QmfGEUAcMpzo25kF2Rhn8L5FAXysfGnkzjwdKoNPi615XQ");
+        assert_eq!(did.authority(), "example.com");
+        assert_eq!(did.log_path(), "/.well-known/did.jsonl");
+        assert_eq!(did.witness_path(), "/.well-known/did-witness.json");
+        assert_eq!(did.to_did_web(), "did:web:example.com");
+    }
+
+    #[test]
+    fn test_parse_did_webvh_with_path_and_port() {
+        let did = DidWebvh::parse(
+            "did:webvh:QmfGEUAcMpzo25kF2Rhn8L5FAXysfGnkzjwdKoNPi615XQ:localhost%3A8000:dids:issuer",
+        )
+        .unwrap();
+        assert_eq!(did.authority(), "localhost:8000");
+        assert_eq!(did.log_path(), "/dids/issuer/did.jsonl");
+        assert_eq!(did.to_did_web(), "did:web:localhost%3A8000:dids:issuer");
+    }
+
+    #[test]
+    fn test_parse_rejects_other_methods() {
+        assert!(matches!(
+            DidWebvh::parse("did:web:example.com"),
+            Err(DidWebvhError::MethodNotSupported(_))
+        ));
+        assert!(matches!(
+            DidWebvh::parse("did:webvh:QmfGEUAcMpzo25kF2Rhn8L5FAXysfGnkzjwdKoNPi615XQ"),
+            Err(DidWebvhError::InvalidDid(_))
+        ));
+    }
+}
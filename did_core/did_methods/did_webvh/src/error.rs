@@ -0,0 +1,48 @@
+use aries_vcx_wallet::errors::error::VcxWalletError;
+use did_resolver::did_parser_nom::ParseError;
+use hyper::StatusCode;
+use public_key::PublicKeyError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum DidWebvhError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Invalid DID: {0}")]
+    InvalidDid(String),
+    #[error("Invalid DID log: {0}")]
+    InvalidLog(String),
+    #[error("Invalid DID log entry {0}: {1}")]
+    InvalidLogEntry(String, String),
+    #[error("Invalid proof: {0}")]
+    InvalidProof(String),
+    #[error("Key {0} is not authorized to update the DID")]
+    UnauthorizedUpdateKey(String),
+    #[error("Witness threshold not met for version {0}")]
+    WitnessThresholdNotMet(String),
+    #[error("Version not found: {0}")]
+    VersionNotFound(String),
+    #[error("DID is deactivated")]
+    Deactivated,
+    #[error("Serde JSON error: {0}")]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("Public key error: {0}")]
+    PublicKeyError(#[from] PublicKeyError),
+    #[error("DID parser error: {0}")]
+    DidParserError(#[from] ParseError),
+    #[error("Wallet error: {0}")]
+    WalletError(#[from] VcxWalletError),
+    #[error("Base 58 decoding error: {0}")]
+    Base58DecodingError(#[from] bs58::decode::Error),
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(#[from] hyper::http::Error),
+    #[error("Network error: {0}")]
+    NetworkError(#[from] hyper::Error),
+    #[error("Network error: {0}")]
+    NetworkClientError(#[from] hyper_util::client::legacy::Error),
+    #[error("Non-success server response: {0}")]
+    NonSuccessResponse(StatusCode),
+    #[error("Invalid encoding: {0}")]
+    Utf8Error(#[from] std::string::FromUtf8Error),
+}
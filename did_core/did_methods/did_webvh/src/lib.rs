@@ -0,0 +1,16 @@
+pub mod canonical;
+pub mod error;
+pub mod log;
+pub mod parameters;
+pub mod proof;
+pub mod resolver;
+pub mod witness;
+
+mod did;
+
+pub use did::DidWebvh;
+
+/// Method version written to the `method` parameter of new logs
+pub const METHOD_VERSION: &str = "did:webvh:1.0";
+/// Placeholder for the SCID while it is being derived
+pub const SCID_PLACEHOLDER: &str = "{SCID}";
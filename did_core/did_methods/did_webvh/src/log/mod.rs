@@ -0,0 +1,326 @@
+pub mod update;
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use did_resolver::did_doc::schema::did_doc::DidDocument;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    canonical::multihash_b58,
+    error::DidWebvhError,
+    parameters::{ActiveParameters, DidWebvhParameters},
+    proof::DataIntegrityProof,
+    witness::WitnessProofs,
+    DidWebvh, SCID_PLACEHOLDER,
+};
+
+const VERSION_ID: &str = "versionId";
+const PROOF: &str = "proof";
+
+/// A single line of the `did.jsonl` log.
+/// See https://identity.foundation/didwebvh/#the-did-log-file
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidWebvhLogEntry {
+    version_id: String,
+    version_time: String,
+    parameters: Value,
+    state: Value,
+    proof: Vec<DataIntegrityProof>,
+}
+
+impl DidWebvhLogEntry {
+    /// `<version number>-<entry hash>`
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    pub fn version_number(&self) -> Result<u64, DidWebvhError> {
+        self.split_version_id()?
+            .0
+            .parse()
+            .map_err(|_| self.invalid("invalid version number"))
+    }
+
+    pub fn entry_hash(&self) -> Result<&str, DidWebvhError> {
+        Ok(self.split_version_id()?.1)
+    }
+
+    pub fn version_time(&self) -> Result<DateTime<Utc>, DidWebvhError> {
+        DateTime::parse_from_rfc3339(&self.version_time)
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|_| self.invalid("invalid versionTime"))
+    }
+
+    pub fn parameters(&self) -> Result<DidWebvhParameters, DidWebvhError> {
+        Ok(serde_json::from_value(self.parameters.clone())?)
+    }
+
+    /// The DID document as published in this version
+    pub fn state(&self) -> &Value {
+        &self.state
+    }
+
+    pub fn did_document(&self) -> Result<DidDocument, DidWebvhError> {
+        Ok(serde_json::from_value(self.state.clone())?)
+    }
+
+    pub fn proofs(&self) -> &[DataIntegrityProof] {
+        &self.proof
+    }
+
+    /// The entry without its proofs, which is what the proofs are made over.
+    fn unsigned(&self) -> Result<Value, DidWebvhError> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(entry) = value.as_object_mut() {
+            entry.remove(PROOF);
+        }
+        Ok(value)
+    }
+
+    /// Hash of the entry chained to its predecessor: the unsigned entry with its versionId
+    /// replaced by the versionId of the previous entry (the SCID for the first entry).
+    fn compute_entry_hash(&self, previous_version_id: &str) -> Result<String, DidWebvhError> {
+        let mut unsigned = self.unsigned()?;
+        unsigned[VERSION_ID] = Value::String(previous_version_id.to_string());
+        Ok(multihash_b58(&unsigned))
+    }
+
+    /// The SCID is the hash of the first entry with every occurrence of the SCID replaced by the
+    /// placeholder, including the versionId.
+    fn verify_scid(&self, scid: &str) -> Result<(), DidWebvhError> {
+        let unsigned = serde_json::to_string(&self.unsigned()?)?;
+        let mut preliminary: Value =
+            serde_json::from_str(&unsigned.replace(scid, SCID_PLACEHOLDER))?;
+        preliminary[VERSION_ID] = Value::String(SCID_PLACEHOLDER.to_string());
+
+        if multihash_b58(&preliminary) != scid {
+            return Err(self.invalid("SCID does not match the first entry"));
+        }
+        Ok(())
+    }
+
+    fn split_version_id(&self) -> Result<(&str, &str), DidWebvhError> {
+        self.version_id
+            .split_once('-')
+            .ok_or_else(|| self.invalid("invalid versionId"))
+    }
+
+    fn invalid(&self, reason: &str) -> DidWebvhError {
+        DidWebvhError::InvalidLogEntry(self.version_id.clone(), reason.to_string())
+    }
+}
+
+/// A version of the DID whose log entry, and every entry before it, was verified.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifiedVersion {
+    pub version_number: u64,
+    pub version_id: String,
+    pub version_time: DateTime<Utc>,
+    pub did_document: DidDocument,
+    pub parameters: ActiveParameters,
+}
+
+/// The verifiable history of a did:webvh, one entry per version of the DID document.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DidWebvhLog {
+    entries: Vec<DidWebvhLogEntry>,
+}
+
+impl DidWebvhLog {
+    /// Parses the JSON Lines content of a `did.jsonl` file.
+    pub fn parse(jsonl: &str) -> Result<Self, DidWebvhError> {
+        let entries = jsonl
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { entries })
+    }
+
+    pub fn to_jsonl(&self) -> Result<String, DidWebvhError> {
+        let mut jsonl = String::new();
+        for entry in self.entries.iter() {
+            jsonl.push_str(&serde_json::to_string(entry)?);
+            jsonl.push('\n');
+        }
+        Ok(jsonl)
+    }
+
+    pub fn entries(&self) -> &[DidWebvhLogEntry] {
+        &self.entries
+    }
+
+    pub fn latest(&self) -> Option<&DidWebvhLogEntry> {
+        self.entries.last()
+    }
+
+    /// The DID as identified by the latest version of the document
+    pub fn did(&self) -> Result<DidWebvh, DidWebvhError> {
+        let latest = self
+            .latest()
+            .ok_or_else(|| DidWebvhError::InvalidLog("log is empty".to_string()))?;
+        DidWebvh::parse(latest.did_document()?.id().to_string())
+    }
+
+    /// Parameters in effect after the latest entry, without verifying the log.
+    pub fn active_parameters(&self) -> Result<ActiveParameters, DidWebvhError> {
+        let mut entries = self.entries.iter();
+        let first = entries
+            .next()
+            .ok_or_else(|| DidWebvhError::InvalidLog("log is empty".to_string()))?;
+        let mut active = ActiveParameters::initial(&first.parameters()?)?;
+        for entry in entries {
+            active.apply(&entry.parameters()?)?;
+        }
+        Ok(active)
+    }
+
+    /// Whether any entry configures witnesses, in which case witness proofs are needed to verify
+    /// the log.
+    pub fn requires_witnesses(&self) -> bool {
+        self.entries.iter().any(|entry| {
+            entry
+                .parameters()
+                .ok()
+                .and_then(|parameters| parameters.witness)
+                .is_some_and(|witness| witness.threshold > 0)
+        })
+    }
+
+    /// Verifies the whole log of `did` and returns every version of the DID document.
+    /// See https://identity.foundation/didwebvh/#read-resolve
+    pub fn verify(
+        &self,
+        did: &DidWebvh,
+        witness_proofs: &WitnessProofs,
+    ) -> Result<Vec<VerifiedVersion>, DidWebvhError> {
+        if self.entries.is_empty() {
+            return Err(DidWebvhError::InvalidLog("log is empty".to_string()));
+        }
+        let witness_approvals = self.witness_approvals(witness_proofs);
+        let now = Utc::now();
+
+        let mut versions: Vec<VerifiedVersion> = Vec::with_capacity(self.entries.len());
+        let mut previous_version_id = did.scid().to_string();
+        for (index, entry) in self.entries.iter().enumerate() {
+            let version_number = index as u64 + 1;
+            if entry.version_number()? != version_number {
+                return Err(entry.invalid("unexpected version number"));
+            }
+
+            let version_time = entry.version_time()?;
+            let previous = versions.last();
+            if previous.is_some_and(|previous| previous.version_time > version_time)
+                || version_time > now
+            {
+                return Err(entry.invalid("versionTime out of order"));
+            }
+
+            let parameters = entry.parameters()?;
+            let (active, authorized_keys, witness) = match previous {
+                None => {
+                    let active = ActiveParameters::initial(&parameters)?;
+                    if active.scid != did.scid() {
+                        return Err(entry.invalid("scid does not match the DID"));
+                    }
+                    entry.verify_scid(&active.scid)?;
+                    let authorized_keys = active.update_keys.clone();
+                    let witness = active.witness.clone();
+                    (active, authorized_keys, witness)
+                }
+                Some(previous) => {
+                    let previous = &previous.parameters;
+                    if previous.deactivated {
+                        return Err(entry.invalid("DID was deactivated"));
+                    }
+                    let mut active = previous.clone();
+                    active.apply(&parameters)?;
+                    // with pre-rotation, the entry is signed by the keys it commits to
+                    let authorized_keys = if previous.is_pre_rotation_active() {
+                        previous.check_pre_rotation(parameters.update_keys.as_deref())?;
+                        active.update_keys.clone()
+                    } else {
+                        previous.update_keys.clone()
+                    };
+                    (active, authorized_keys, previous.witness.clone())
+                }
+            };
+
+            if entry.compute_entry_hash(&previous_version_id)? != entry.entry_hash()? {
+                return Err(entry.invalid("entry hash does not match"));
+            }
+
+            if entry.proof.is_empty() {
+                return Err(entry.invalid("entry is not signed"));
+            }
+            let unsigned = entry.unsigned()?;
+            for proof in entry.proof.iter() {
+                let multikey = proof.verify(&unsigned)?.fingerprint();
+                if !authorized_keys.contains(&multikey) {
+                    return Err(DidWebvhError::UnauthorizedUpdateKey(multikey));
+                }
+            }
+
+            if let Some(witness) = witness {
+                let approved_by = witness_approvals.get(&version_number);
+                let approvals = witness
+                    .witnesses
+                    .iter()
+                    .filter(|witness| approved_by.is_some_and(|ids| ids.contains(&witness.id)))
+                    .count();
+                if (approvals as u32) < witness.threshold {
+                    return Err(DidWebvhError::WitnessThresholdNotMet(
+                        entry.version_id.clone(),
+                    ));
+                }
+            }
+
+            let did_document = entry.did_document()?;
+            let document_did = DidWebvh::parse(did_document.id().to_string())?;
+            if document_did.scid() != did.scid()
+                || (!active.portable && document_did.did() != did.did())
+            {
+                return Err(entry.invalid("document id does not match the DID"));
+            }
+
+            previous_version_id = entry.version_id.clone();
+            versions.push(VerifiedVersion {
+                version_number,
+                version_id: entry.version_id.clone(),
+                version_time,
+                did_document,
+                parameters: active,
+            });
+        }
+
+        Ok(versions)
+    }
+
+    /// Witnesses approving each version number. A proof for a version approves all versions up
+    /// to it, proofs for version ids which are not part of this log are ignored.
+    fn witness_approvals(&self, witness_proofs: &WitnessProofs) -> HashMap<u64, HashSet<String>> {
+        let version_numbers: HashMap<&str, u64> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.version_id.as_str(), index as u64 + 1))
+            .collect();
+
+        let mut approvals: HashMap<u64, HashSet<String>> = HashMap::new();
+        for (version_id, witness_id) in witness_proofs.verified_witnesses() {
+            if let Some(approved) = version_numbers.get(version_id) {
+                for version_number in 1..=*approved {
+                    approvals
+                        .entry(version_number)
+                        .or_default()
+                        .insert(witness_id.clone());
+                }
+            }
+        }
+        approvals
+    }
+}
@@ -0,0 +1,226 @@
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use chrono::{SecondsFormat, Utc};
+use did_resolver::did_doc::schema::{contexts, did_doc::DidDocument};
+use public_key::{Key, KeyType};
+use serde_json::{json, Map, Value};
+
+use super::{DidWebvhLog, DidWebvhLogEntry, VERSION_ID};
+use crate::{
+    canonical::multihash_b58,
+    error::DidWebvhError,
+    parameters::{DidWebvhParameters, WitnessParameters},
+    proof::DataIntegrityProof,
+    METHOD_VERSION, SCID_PLACEHOLDER,
+};
+
+/// Configuration of a new did:webvh.
+#[derive(Clone, Debug, Default)]
+pub struct DidWebvhCreateOptions {
+    /// Wallet keys authorized to update the DID, the first one signs the initial entry
+    pub update_keys: Vec<Key>,
+    /// Keys published as verification methods of the initial document
+    pub verification_keys: Vec<Key>,
+    /// Commitments to the next update keys, see [crate::canonical::next_key_hash]
+    pub next_key_hashes: Vec<String>,
+    pub witness: Option<WitnessParameters>,
+    pub portable: bool,
+    pub ttl: Option<u64>,
+}
+
+/// Changes published by a new log entry. Fields left empty keep their current value.
+#[derive(Clone, Debug, Default)]
+pub struct DidWebvhUpdate {
+    pub did_document: Option<DidDocument>,
+    pub update_keys: Option<Vec<Key>>,
+    pub next_key_hashes: Option<Vec<String>>,
+    pub witness: Option<WitnessParameters>,
+    pub ttl: Option<u64>,
+}
+
+impl DidWebvhLog {
+    /// Creates the log of a new DID hosted at `location`, which is the method specific part of
+    /// the did:web the DID is derived from, e.g. `example.com%3A8080:dids:issuer`.
+    /// See https://identity.foundation/didwebvh/#create-register
+    pub async fn create(
+        wallet: &impl BaseWallet,
+        location: &str,
+        options: DidWebvhCreateOptions,
+    ) -> Result<Self, DidWebvhError> {
+        let signing_key = options.update_keys.first().ok_or_else(|| {
+            DidWebvhError::InvalidLog("at least one update key is required".to_string())
+        })?;
+        let did_template = format!("did:webvh:{SCID_PLACEHOLDER}:{location}");
+        let parameters = DidWebvhParameters {
+            method: Some(METHOD_VERSION.to_string()),
+            scid: Some(SCID_PLACEHOLDER.to_string()),
+            update_keys: Some(options.update_keys.iter().map(Key::fingerprint).collect()),
+            next_key_hashes: Some(options.next_key_hashes).filter(|hashes| !hashes.is_empty()),
+            witness: options.witness,
+            portable: options.portable.then_some(true),
+            ttl: options.ttl,
+            ..Default::default()
+        };
+        let version_time = now();
+        let preliminary = json!({
+            "versionId": SCID_PLACEHOLDER,
+            "versionTime": version_time,
+            "parameters": parameters,
+            "state": initial_state(&did_template, &options.verification_keys),
+        });
+
+        let scid = multihash_b58(&preliminary);
+        let mut entry: Value =
+            serde_json::from_str(&preliminary.to_string().replace(SCID_PLACEHOLDER, &scid))?;
+        entry[VERSION_ID] = Value::String(scid);
+
+        let mut log = Self::default();
+        log.push_signed_entry(wallet, signing_key, entry, 1).await?;
+        Ok(log)
+    }
+
+    /// Appends a new version signed by `signing_key`, which must be an authorized update key.
+    /// While pre-rotation is active, this is one of the new update keys being committed to.
+    /// See https://identity.foundation/didwebvh/#update-rotate
+    pub async fn update(
+        &mut self,
+        wallet: &impl BaseWallet,
+        signing_key: &Key,
+        update: DidWebvhUpdate,
+    ) -> Result<&DidWebvhLogEntry, DidWebvhError> {
+        let parameters = DidWebvhParameters {
+            update_keys: update
+                .update_keys
+                .map(|keys| keys.iter().map(Key::fingerprint).collect()),
+            next_key_hashes: update.next_key_hashes,
+            witness: update.witness,
+            ttl: update.ttl,
+            ..Default::default()
+        };
+        let state = update.did_document.map(serde_json::to_value).transpose()?;
+
+        self.append(wallet, signing_key, parameters, state).await
+    }
+
+    /// Deactivates the DID, no further updates are accepted afterwards. Pre-rotation has to be
+    /// turned off by an update first, as a deactivated DID has no update keys.
+    pub async fn deactivate(
+        &mut self,
+        wallet: &impl BaseWallet,
+        signing_key: &Key,
+    ) -> Result<&DidWebvhLogEntry, DidWebvhError> {
+        let parameters = DidWebvhParameters {
+            update_keys: Some(vec![]),
+            deactivated: Some(true),
+            ..Default::default()
+        };
+
+        self.append(wallet, signing_key, parameters, None).await
+    }
+
+    async fn append(
+        &mut self,
+        wallet: &impl BaseWallet,
+        signing_key: &Key,
+        parameters: DidWebvhParameters,
+        state: Option<Value>,
+    ) -> Result<&DidWebvhLogEntry, DidWebvhError> {
+        let active = self.active_parameters()?;
+        if active.deactivated {
+            return Err(DidWebvhError::Deactivated);
+        }
+        let authorized_keys = if active.is_pre_rotation_active() {
+            active.check_pre_rotation(parameters.update_keys.as_deref())?;
+            parameters.update_keys.clone().unwrap_or_default()
+        } else {
+            active.update_keys.clone()
+        };
+        let multikey = signing_key.fingerprint();
+        if !authorized_keys.contains(&multikey) {
+            return Err(DidWebvhError::UnauthorizedUpdateKey(multikey));
+        }
+
+        let previous = self
+            .latest()
+            .ok_or_else(|| DidWebvhError::InvalidLog("log is empty".to_string()))?;
+        let version_number = previous.version_number()? + 1;
+        let entry = json!({
+            "versionId": previous.version_id,
+            "versionTime": now(),
+            "parameters": parameters,
+            "state": state.unwrap_or_else(|| previous.state.clone()),
+        });
+
+        self.push_signed_entry(wallet, signing_key, entry, version_number)
+            .await
+    }
+
+    /// Derives the versionId of `entry`, whose versionId is still the one of its predecessor,
+    /// signs it and appends it to the log.
+    async fn push_signed_entry(
+        &mut self,
+        wallet: &impl BaseWallet,
+        signing_key: &Key,
+        mut entry: Value,
+        version_number: u64,
+    ) -> Result<&DidWebvhLogEntry, DidWebvhError> {
+        let entry_hash = multihash_b58(&entry);
+        entry[VERSION_ID] = Value::String(format!("{version_number}-{entry_hash}"));
+
+        let created = entry["versionTime"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let proof = DataIntegrityProof::sign(wallet, signing_key, &entry, &created).await?;
+        entry["proof"] = json!([proof]);
+
+        self.entries.push(serde_json::from_value(entry)?);
+        Ok(&self.entries[self.entries.len() - 1])
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Document listing `keys` as Multikey verification methods. X25519 keys are used for key
+/// agreement, any other key for authentication and assertion.
+fn initial_state(did: &str, keys: &[Key]) -> Value {
+    let mut verification_methods = vec![];
+    let mut signing_refs = vec![];
+    let mut key_agreement_refs = vec![];
+    for (index, key) in keys.iter().enumerate() {
+        let id = format!("{did}#key-{}", index + 1);
+        verification_methods.push(json!({
+            "id": id,
+            "type": "Multikey",
+            "controller": did,
+            "publicKeyMultibase": key.fingerprint(),
+        }));
+        match key.key_type() {
+            KeyType::X25519 => key_agreement_refs.push(id),
+            _ => signing_refs.push(id),
+        }
+    }
+
+    let mut state = Map::new();
+    state.insert(
+        "@context".to_string(),
+        json!([contexts::W3C_DID_V1, contexts::W3C_MULTIKEY_V1]),
+    );
+    state.insert("id".to_string(), json!(did));
+    if !verification_methods.is_empty() {
+        state.insert(
+            "verificationMethod".to_string(),
+            json!(verification_methods),
+        );
+    }
+    if !signing_refs.is_empty() {
+        state.insert("authentication".to_string(), json!(signing_refs));
+        state.insert("assertionMethod".to_string(), json!(signing_refs));
+    }
+    if !key_agreement_refs.is_empty() {
+        state.insert("keyAgreement".to_string(), json!(key_agreement_refs));
+    }
+
+    Value::Object(state)
+}
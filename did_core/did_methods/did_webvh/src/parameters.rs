@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{canonical::next_key_hash_from_multikey, error::DidWebvhError};
+
+const SUPPORTED_METHODS: [&str; 1] = [crate::METHOD_VERSION];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Witness {
+    pub id: String,
+}
+
+/// Witnesses which must approve a log entry before it is considered valid.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WitnessParameters {
+    pub threshold: u32,
+    pub witnesses: Vec<Witness>,
+}
+
+/// Parameters of a single log entry. Entries after the first only carry the parameters which
+/// changed, absent parameters keep their previous value.
+/// See https://identity.foundation/didwebvh/#didwebvh-did-method-parameters
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidWebvhParameters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_keys: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_key_hashes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness: Option<WitnessParameters>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watchers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub portable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deactivated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u64>,
+}
+
+/// Parameters in effect after applying a sequence of log entries.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ActiveParameters {
+    pub method: String,
+    pub scid: String,
+    pub update_keys: Vec<String>,
+    pub next_key_hashes: Vec<String>,
+    pub witness: Option<WitnessParameters>,
+    pub watchers: Vec<String>,
+    pub portable: bool,
+    pub deactivated: bool,
+    pub ttl: Option<u64>,
+}
+
+impl ActiveParameters {
+    /// Parameters of the first entry, which must define the method, SCID and update keys.
+    pub fn initial(parameters: &DidWebvhParameters) -> Result<Self, DidWebvhError> {
+        let missing = |name: &str| DidWebvhError::InvalidLog(format!("first entry has no {name}"));
+        let mut active = Self {
+            method: parameters.method.clone().ok_or_else(|| missing("method"))?,
+            scid: parameters.scid.clone().ok_or_else(|| missing("scid"))?,
+            update_keys: parameters
+                .update_keys
+                .clone()
+                .ok_or_else(|| missing("updateKeys"))?,
+            portable: parameters.portable.unwrap_or_default(),
+            ..Default::default()
+        };
+        active.apply(parameters)?;
+
+        Ok(active)
+    }
+
+    /// Applies the parameters of the next entry. Authorization of that entry is checked by the
+    /// caller against the parameters in effect before applying.
+    pub fn apply(&mut self, parameters: &DidWebvhParameters) -> Result<(), DidWebvhError> {
+        if let Some(method) = &parameters.method {
+            if !SUPPORTED_METHODS.contains(&method.as_str()) {
+                return Err(DidWebvhError::InvalidLog(format!(
+                    "unsupported method version {method}"
+                )));
+            }
+            self.method = method.clone();
+        }
+        if let Some(scid) = &parameters.scid {
+            if !self.scid.is_empty() && &self.scid != scid {
+                return Err(DidWebvhError::InvalidLog("scid can not change".to_string()));
+            }
+        }
+        if let Some(update_keys) = &parameters.update_keys {
+            self.update_keys = update_keys.clone();
+        }
+        if let Some(next_key_hashes) = &parameters.next_key_hashes {
+            self.next_key_hashes = next_key_hashes.clone();
+        }
+        if let Some(witness) = &parameters.witness {
+            self.witness = Some(witness.clone()).filter(|witness| witness.threshold > 0);
+        }
+        if let Some(watchers) = &parameters.watchers {
+            self.watchers = watchers.clone();
+        }
+        if let Some(portable) = parameters.portable {
+            if portable && !self.portable {
+                return Err(DidWebvhError::InvalidLog(
+                    "portable can only be enabled when the DID is created".to_string(),
+                ));
+            }
+            self.portable = portable;
+        }
+        if let Some(deactivated) = parameters.deactivated {
+            self.deactivated = deactivated;
+        }
+        if parameters.ttl.is_some() {
+            self.ttl = parameters.ttl;
+        }
+
+        Ok(())
+    }
+
+    pub fn is_pre_rotation_active(&self) -> bool {
+        !self.next_key_hashes.is_empty()
+    }
+
+    /// Checks that every update key of the next entry was committed to by `nextKeyHashes`.
+    pub fn check_pre_rotation(&self, update_keys: Option<&[String]>) -> Result<(), DidWebvhError> {
+        let update_keys = update_keys.ok_or_else(|| {
+            DidWebvhError::InvalidLog("pre-rotation requires new updateKeys".to_string())
+        })?;
+        for update_key in update_keys {
+            let hash = next_key_hash_from_multikey(update_key);
+            if !self.next_key_hashes.contains(&hash) {
+                return Err(DidWebvhError::UnauthorizedUpdateKey(update_key.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use aries_vcx_wallet::wallet::base_wallet::{did_wallet::DidWallet, BaseWallet};
+use askar_crypto::{alg::ed25519::Ed25519KeyPair, repr::KeyPublicBytes, sign::KeyVerify};
+use public_key::{Key, KeyType};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    canonical::{canonicalize, sha256},
+    error::DidWebvhError,
+};
+
+pub const PROOF_TYPE: &str = "DataIntegrityProof";
+pub const CRYPTOSUITE: &str = "eddsa-jcs-2022";
+pub const PROOF_PURPOSE: &str = "assertionMethod";
+
+const DID_KEY_PREFIX: &str = "did:key:";
+const MULTIBASE_BASE58BTC: char = 'z';
+
+/// Data Integrity proof using the `eddsa-jcs-2022` cryptosuite, which is the only suite
+/// did:webvh requires. Proofs are made by Ed25519 keys referenced as did:key.
+/// See https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataIntegrityProof {
+    #[serde(rename = "type")]
+    proof_type: String,
+    cryptosuite: String,
+    verification_method: String,
+    created: String,
+    proof_purpose: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof_value: Option<String>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl DataIntegrityProof {
+    /// Signs `document` with `key`, which must be an Ed25519 key held by the wallet.
+    pub async fn sign(
+        wallet: &impl BaseWallet,
+        key: &Key,
+        document: &Value,
+        created: &str,
+    ) -> Result<Self, DidWebvhError> {
+        key.validate_key_type(KeyType::Ed25519)?;
+        let fingerprint = key.fingerprint();
+        let mut proof = Self {
+            proof_type: PROOF_TYPE.to_string(),
+            cryptosuite: CRYPTOSUITE.to_string(),
+            verification_method: format!("{DID_KEY_PREFIX}{fingerprint}#{fingerprint}"),
+            created: created.to_string(),
+            proof_purpose: PROOF_PURPOSE.to_string(),
+            proof_value: None,
+            extra: Map::new(),
+        };
+
+        let signature = wallet.sign(key, &proof.hash_data(document)?).await?;
+        proof.proof_value = Some(format!(
+            "{MULTIBASE_BASE58BTC}{}",
+            bs58::encode(signature).into_string()
+        ));
+
+        Ok(proof)
+    }
+
+    /// Verifies the proof over `document` and returns the key which made it.
+    pub fn verify(&self, document: &Value) -> Result<Key, DidWebvhError> {
+        if self.proof_type != PROOF_TYPE || self.cryptosuite != CRYPTOSUITE {
+            return Err(DidWebvhError::InvalidProof(format!(
+                "unsupported proof {} with cryptosuite {}",
+                self.proof_type, self.cryptosuite
+            )));
+        }
+        if self.proof_purpose != PROOF_PURPOSE {
+            return Err(DidWebvhError::InvalidProof(format!(
+                "unexpected proof purpose {}",
+                self.proof_purpose
+            )));
+        }
+        let proof_value = self
+            .proof_value
+            .as_deref()
+            .and_then(|value| value.strip_prefix(MULTIBASE_BASE58BTC))
+            .ok_or_else(|| DidWebvhError::InvalidProof("missing proof value".to_string()))?;
+        let signature = bs58::decode(proof_value).into_vec()?;
+
+        let key = self.signing_key()?;
+        let hash_data = self.hash_data(document)?;
+        let verified = Ed25519KeyPair::from_public_bytes(key.key())
+            .and_then(|keypair| keypair.verify_signature(&hash_data, &signature, None))
+            .map_err(|err| DidWebvhError::InvalidProof(err.to_string()))?;
+        if !verified {
+            return Err(DidWebvhError::InvalidProof(format!(
+                "signature of {} does not match",
+                self.verification_method
+            )));
+        }
+
+        Ok(key)
+    }
+
+    /// The did:key verification method the proof was made with
+    pub fn verification_method(&self) -> &str {
+        &self.verification_method
+    }
+
+    pub fn created(&self) -> &str {
+        &self.created
+    }
+
+    pub fn signing_key(&self) -> Result<Key, DidWebvhError> {
+        let (did, fragment) = self
+            .verification_method
+            .split_once('#')
+            .ok_or_else(|| DidWebvhError::InvalidProof(self.verification_method.clone()))?;
+        match did.strip_prefix(DID_KEY_PREFIX) {
+            Some(fingerprint) if fingerprint == fragment => {
+                let key = Key::from_fingerprint(fingerprint)?;
+                key.validate_key_type(KeyType::Ed25519)?;
+                Ok(key)
+            }
+            _ => Err(DidWebvhError::InvalidProof(format!(
+                "verification method {} is not a did:key",
+                self.verification_method
+            ))),
+        }
+    }
+
+    /// `sha256(proof configuration) || sha256(document)`, both canonicalized with JCS
+    fn hash_data(&self, document: &Value) -> Result<Vec<u8>, DidWebvhError> {
+        let mut proof_config = serde_json::to_value(self)?;
+        if let Some(proof_config) = proof_config.as_object_mut() {
+            proof_config.remove("proofValue");
+        }
+
+        let mut hash_data = sha256(canonicalize(&proof_config).as_bytes());
+        hash_data.extend(sha256(canonicalize(document).as_bytes()));
+        Ok(hash_data)
+    }
+}
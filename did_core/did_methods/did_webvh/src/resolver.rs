@@ -0,0 +1,205 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use did_resolver::{
+    did_parser_nom::Did,
+    error::GenericError,
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+        resolution_version::ResolutionVersion, DidResolvable,
+    },
+};
+use http_body_util::{combinators::BoxBody, BodyExt as _};
+use hyper::{
+    body::Bytes,
+    http::uri::{self, Scheme},
+    StatusCode, Uri,
+};
+use hyper_tls::HttpsConnector;
+use hyper_util::{
+    client::legacy::{
+        connect::{Connect, HttpConnector},
+        Client,
+    },
+    rt::TokioExecutor,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::DidWebvhError,
+    log::{DidWebvhLog, VerifiedVersion},
+    witness::WitnessProofs,
+    DidWebvh,
+};
+
+/// Selects a historical version of the DID document. Without any option the latest version is
+/// resolved.
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidWebvhResolutionOptions {
+    pub version_id: Option<String>,
+    /// Resolves the version which was active at the given time
+    pub version_time: Option<DateTime<Utc>>,
+    pub version_number: Option<u64>,
+}
+
+pub struct DidWebvhResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    client: Client<C, BoxBody<Bytes, GenericError>>,
+    scheme: Scheme,
+}
+
+impl DidWebvhResolver<HttpConnector> {
+    pub fn http() -> DidWebvhResolver<HttpConnector> {
+        DidWebvhResolver {
+            client: Client::builder(TokioExecutor::new())
+                .build::<_, BoxBody<Bytes, GenericError>>(HttpConnector::new()),
+            scheme: Scheme::HTTP,
+        }
+    }
+}
+
+impl DidWebvhResolver<HttpsConnector<HttpConnector>> {
+    pub fn https() -> DidWebvhResolver<HttpsConnector<HttpConnector>> {
+        DidWebvhResolver {
+            client: Client::builder(TokioExecutor::new())
+                .build::<_, BoxBody<Bytes, GenericError>>(HttpsConnector::new()),
+            scheme: Scheme::HTTPS,
+        }
+    }
+}
+
+impl<C> DidWebvhResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    /// Fetches the file at `path`, `None` if it does not exist.
+    async fn fetch(&self, did: &DidWebvh, path: &str) -> Result<Option<String>, DidWebvhError> {
+        let url: Uri = uri::Builder::new()
+            .scheme(self.scheme.clone())
+            .authority(did.authority())
+            .path_and_query(path)
+            .build()?;
+        let res = self.client.get(url).await?;
+
+        if res.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !res.status().is_success() {
+            return Err(DidWebvhError::NonSuccessResponse(res.status()));
+        }
+
+        let body = res.into_body().collect().await?.to_bytes();
+        Ok(Some(String::from_utf8(body.to_vec())?))
+    }
+
+    pub async fn fetch_log(&self, did: &DidWebvh) -> Result<DidWebvhLog, DidWebvhError> {
+        let jsonl = self
+            .fetch(did, &did.log_path())
+            .await?
+            .ok_or_else(|| DidWebvhError::NonSuccessResponse(StatusCode::NOT_FOUND))?;
+        DidWebvhLog::parse(&jsonl)
+    }
+
+    pub async fn fetch_witness_proofs(
+        &self,
+        did: &DidWebvh,
+    ) -> Result<WitnessProofs, DidWebvhError> {
+        match self.fetch(did, &did.witness_path()).await? {
+            Some(json) => WitnessProofs::parse(&json),
+            None => Ok(WitnessProofs::default()),
+        }
+    }
+}
+
+#[async_trait]
+impl<C> DidResolvable for DidWebvhResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    type DidResolutionOptions = DidWebvhResolutionOptions;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let did = DidWebvh::parse(did.did())?;
+        let log = self.fetch_log(&did).await?;
+        let witness_proofs = if log.requires_witnesses() {
+            self.fetch_witness_proofs(&did).await?
+        } else {
+            WitnessProofs::default()
+        };
+
+        Ok(resolve_log(&did, &log, &witness_proofs, options)?)
+    }
+
+    fn select_version(
+        &self,
+        options: Self::DidResolutionOptions,
+        version: &ResolutionVersion,
+    ) -> Result<Self::DidResolutionOptions, GenericError> {
+        Ok(DidWebvhResolutionOptions {
+            version_id: version.version_id.clone().or(options.version_id),
+            version_time: version.version_time.or(options.version_time),
+            ..options
+        })
+    }
+}
+
+/// Verifies `log` and resolves the version of the DID document selected by `options`.
+pub fn resolve_log(
+    did: &DidWebvh,
+    log: &DidWebvhLog,
+    witness_proofs: &WitnessProofs,
+    options: &DidWebvhResolutionOptions,
+) -> Result<DidResolutionOutput, DidWebvhError> {
+    let versions = log.verify(did, witness_proofs)?;
+    let index = select_version(&versions, options)?;
+    let version = &versions[index];
+
+    let mut metadata = DidDocumentMetadata::builder()
+        .created(versions[0].version_time)
+        .updated(version.version_time)
+        .version_id(version.version_id.clone())
+        .deactivated(version.parameters.deactivated);
+    if let Some(next) = versions.get(index + 1) {
+        metadata = metadata
+            .next_update(next.version_time)
+            .next_version_id(next.version_id.clone());
+    }
+    let resolution_metadata = DidResolutionMetadata::builder()
+        .content_type("application/did+json".to_string())
+        .build();
+
+    Ok(DidResolutionOutput::builder(version.did_document.clone())
+        .did_resolution_metadata(resolution_metadata)
+        .did_document_metadata(metadata.build())
+        .build())
+}
+
+fn select_version(
+    versions: &[VerifiedVersion],
+    options: &DidWebvhResolutionOptions,
+) -> Result<usize, DidWebvhError> {
+    let position = if let Some(version_id) = &options.version_id {
+        versions
+            .iter()
+            .position(|version| &version.version_id == version_id)
+    } else if let Some(version_number) = options.version_number {
+        versions
+            .iter()
+            .position(|version| version.version_number == version_number)
+    } else if let Some(version_time) = options.version_time {
+        versions
+            .iter()
+            .rposition(|version| version.version_time <= version_time)
+    } else {
+        Some(versions.len() - 1)
+    };
+
+    position.ok_or_else(|| DidWebvhError::VersionNotFound(format!("{options:?}")))
+}
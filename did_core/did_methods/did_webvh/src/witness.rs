@@ -0,0 +1,96 @@
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use public_key::Key;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{error::DidWebvhError, proof::DataIntegrityProof};
+
+/// Proofs by witnesses approving the log entry `version_id`. Approving an entry implicitly
+/// approves every entry before it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WitnessProofEntry {
+    version_id: String,
+    proof: Vec<DataIntegrityProof>,
+}
+
+impl WitnessProofEntry {
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    pub fn proofs(&self) -> &[DataIntegrityProof] {
+        &self.proof
+    }
+}
+
+/// Content of the `did-witness.json` file published next to the DID log.
+/// See https://identity.foundation/didwebvh/#the-witness-proofs-file
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WitnessProofs(Vec<WitnessProofEntry>);
+
+impl WitnessProofs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(json: &str) -> Result<Self, DidWebvhError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn entries(&self) -> &[WitnessProofEntry] {
+        &self.0
+    }
+
+    /// Signs the approval of `version_id` with the witness key held by `wallet`.
+    pub async fn sign(
+        wallet: &impl BaseWallet,
+        witness_key: &Key,
+        version_id: &str,
+        created: &str,
+    ) -> Result<DataIntegrityProof, DidWebvhError> {
+        DataIntegrityProof::sign(
+            wallet,
+            witness_key,
+            &witnessed_document(version_id),
+            created,
+        )
+        .await
+    }
+
+    pub fn add_proof(&mut self, version_id: &str, proof: DataIntegrityProof) {
+        match self
+            .0
+            .iter_mut()
+            .find(|entry| entry.version_id == version_id)
+        {
+            Some(entry) => entry.proof.push(proof),
+            None => self.0.push(WitnessProofEntry {
+                version_id: version_id.to_string(),
+                proof: vec![proof],
+            }),
+        }
+    }
+
+    /// Witness DIDs (`did:key:...`) with a valid proof for each witnessed version id.
+    pub(crate) fn verified_witnesses(&self) -> Vec<(&str, String)> {
+        self.0
+            .iter()
+            .flat_map(|entry| {
+                let document = witnessed_document(&entry.version_id);
+                entry.proof.iter().filter_map(move |proof| {
+                    let key = proof.verify(&document).ok()?;
+                    Some((
+                        entry.version_id.as_str(),
+                        format!("did:key:{}", key.fingerprint()),
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+fn witnessed_document(version_id: &str) -> Value {
+    json!({ "versionId": version_id })
+}
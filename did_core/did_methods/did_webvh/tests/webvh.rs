@@ -0,0 +1,279 @@
+use aries_vcx_wallet::wallet::{askar::AskarWallet, base_wallet::did_wallet::DidWallet};
+use did_webvh::{
+    canonical::next_key_hash,
+    error::DidWebvhError,
+    log::{
+        update::{DidWebvhCreateOptions, DidWebvhUpdate},
+        DidWebvhLog,
+    },
+    parameters::{Witness, WitnessParameters},
+    resolver::{resolve_log, DidWebvhResolutionOptions},
+    witness::WitnessProofs,
+};
+use public_key::Key;
+use test_utils::devsetup::askar_wallet::dev_setup_wallet_askar;
+
+const LOCATION: &str = "example.com:dids:issuer";
+
+async fn setup_wallet() -> AskarWallet {
+    dev_setup_wallet_askar("000000000000000000000000Trustee1")
+        .await
+        .1
+}
+
+async fn new_key(wallet: &AskarWallet) -> Key {
+    wallet
+        .create_and_store_my_did(None, None)
+        .await
+        .unwrap()
+        .verkey()
+        .clone()
+}
+
+#[tokio::test]
+async fn test_create_and_resolve() {
+    let wallet = setup_wallet().await;
+    let update_key = new_key(&wallet).await;
+    let signing_key = new_key(&wallet).await;
+    let options = DidWebvhCreateOptions {
+        update_keys: vec![update_key],
+        verification_keys: vec![signing_key.clone()],
+        ..Default::default()
+    };
+
+    let log = DidWebvhLog::create(&wallet, LOCATION, options)
+        .await
+        .unwrap();
+    let did = log.did().unwrap();
+    assert!(did.to_string().starts_with("did:webvh:Qm"));
+    assert!(did.to_string().ends_with(":example.com:dids:issuer"));
+    assert_eq!(did.log_path(), "/dids/issuer/did.jsonl");
+
+    // the log survives serialization to did.jsonl
+    let log = DidWebvhLog::parse(&log.to_jsonl().unwrap()).unwrap();
+    let output = resolve_log(
+        &did,
+        &log,
+        &WitnessProofs::default(),
+        &DidWebvhResolutionOptions::default(),
+    )
+    .unwrap();
+
+    let did_doc = output.did_document;
+    assert_eq!(did_doc.id(), did.did());
+    assert_eq!(
+        did_doc.verification_method()[0].public_key().unwrap(),
+        signing_key
+    );
+    assert_eq!(did_doc.authentication().len(), 1);
+    assert_eq!(
+        output.did_document_metadata.version_id(),
+        Some(&log.entries()[0].version_id().to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_update_and_resolve_history() {
+    let wallet = setup_wallet().await;
+    let update_key_1 = new_key(&wallet).await;
+    let update_key_2 = new_key(&wallet).await;
+    let options = DidWebvhCreateOptions {
+        update_keys: vec![update_key_1.clone()],
+        verification_keys: vec![new_key(&wallet).await],
+        ..Default::default()
+    };
+    let mut log = DidWebvhLog::create(&wallet, LOCATION, options)
+        .await
+        .unwrap();
+    let did = log.did().unwrap();
+
+    // rotate the update key and publish the did:web alias
+    let mut did_doc = log.entries()[0].did_document().unwrap();
+    did_doc.add_also_known_as(did.to_did_web().parse().unwrap());
+    let update = DidWebvhUpdate {
+        did_document: Some(did_doc),
+        update_keys: Some(vec![update_key_2.clone()]),
+        ..Default::default()
+    };
+    log.update(&wallet, &update_key_1, update).await.unwrap();
+
+    // the previous update key is no longer authorized
+    let err = log
+        .update(&wallet, &update_key_1, DidWebvhUpdate::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DidWebvhError::UnauthorizedUpdateKey(_)));
+    log.update(&wallet, &update_key_2, DidWebvhUpdate::default())
+        .await
+        .unwrap();
+
+    let witness_proofs = WitnessProofs::default();
+    let versions = log.verify(&did, &witness_proofs).unwrap();
+    assert_eq!(versions.len(), 3);
+
+    let first_version_id = log.entries()[0].version_id().to_string();
+    let by_id = resolve_log(
+        &did,
+        &log,
+        &witness_proofs,
+        &DidWebvhResolutionOptions {
+            version_id: Some(first_version_id.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(by_id.did_document.also_known_as().is_empty());
+    assert_eq!(
+        by_id.did_document_metadata.next_version_id(),
+        Some(&log.entries()[1].version_id().to_string())
+    );
+
+    let by_number = resolve_log(
+        &did,
+        &log,
+        &witness_proofs,
+        &DidWebvhResolutionOptions {
+            version_number: Some(2),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(by_number.did_document.also_known_as().len(), 1);
+
+    let by_time = resolve_log(
+        &did,
+        &log,
+        &witness_proofs,
+        &DidWebvhResolutionOptions {
+            version_time: Some(versions[0].version_time - chrono::Duration::seconds(1)),
+            ..Default::default()
+        },
+    );
+    assert!(matches!(by_time, Err(DidWebvhError::VersionNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_pre_rotation() {
+    let wallet = setup_wallet().await;
+    let update_key = new_key(&wallet).await;
+    let next_key = new_key(&wallet).await;
+    let options = DidWebvhCreateOptions {
+        update_keys: vec![update_key.clone()],
+        next_key_hashes: vec![next_key_hash(&next_key)],
+        ..Default::default()
+    };
+    let mut log = DidWebvhLog::create(&wallet, LOCATION, options)
+        .await
+        .unwrap();
+    let did = log.did().unwrap();
+
+    // a key which was not committed to can not become an update key
+    let uncommitted = DidWebvhUpdate {
+        update_keys: Some(vec![update_key.clone()]),
+        ..Default::default()
+    };
+    let err = log
+        .update(&wallet, &update_key, uncommitted)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DidWebvhError::UnauthorizedUpdateKey(_)));
+
+    let rotation = DidWebvhUpdate {
+        update_keys: Some(vec![next_key.clone()]),
+        next_key_hashes: Some(vec![]),
+        ..Default::default()
+    };
+    log.update(&wallet, &next_key, rotation).await.unwrap();
+
+    let versions = log.verify(&did, &WitnessProofs::default()).unwrap();
+    assert!(!versions[1].parameters.is_pre_rotation_active());
+    assert_eq!(
+        versions[1].parameters.update_keys,
+        vec![next_key.fingerprint()]
+    );
+}
+
+#[tokio::test]
+async fn test_witness_threshold() {
+    let wallet = setup_wallet().await;
+    let update_key = new_key(&wallet).await;
+    let witness_key = new_key(&wallet).await;
+    let options = DidWebvhCreateOptions {
+        update_keys: vec![update_key],
+        witness: Some(WitnessParameters {
+            threshold: 1,
+            witnesses: vec![Witness {
+                id: format!("did:key:{}", witness_key.fingerprint()),
+            }],
+        }),
+        ..Default::default()
+    };
+    let log = DidWebvhLog::create(&wallet, LOCATION, options)
+        .await
+        .unwrap();
+    let did = log.did().unwrap();
+    assert!(log.requires_witnesses());
+
+    let mut witness_proofs = WitnessProofs::new();
+    let err = log.verify(&did, &witness_proofs).unwrap_err();
+    assert!(matches!(err, DidWebvhError::WitnessThresholdNotMet(_)));
+
+    let version_id = log.entries()[0].version_id();
+    let proof = WitnessProofs::sign(&wallet, &witness_key, version_id, "2025-01-01T00:00:00Z")
+        .await
+        .unwrap();
+    witness_proofs.add_proof(version_id, proof);
+    let witness_proofs =
+        WitnessProofs::parse(&serde_json::to_string(&witness_proofs).unwrap()).unwrap();
+
+    assert_eq!(log.verify(&did, &witness_proofs).unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_tampered_log_is_rejected() {
+    let wallet = setup_wallet().await;
+    let options = DidWebvhCreateOptions {
+        update_keys: vec![new_key(&wallet).await],
+        verification_keys: vec![new_key(&wallet).await],
+        ..Default::default()
+    };
+    let log = DidWebvhLog::create(&wallet, LOCATION, options)
+        .await
+        .unwrap();
+    let did = log.did().unwrap();
+
+    let tampered = log.to_jsonl().unwrap().replace("#key-1", "#key-2");
+    let tampered = DidWebvhLog::parse(&tampered).unwrap();
+
+    assert!(tampered.verify(&did, &WitnessProofs::default()).is_err());
+}
+
+#[tokio::test]
+async fn test_deactivate() {
+    let wallet = setup_wallet().await;
+    let update_key = new_key(&wallet).await;
+    let options = DidWebvhCreateOptions {
+        update_keys: vec![update_key.clone()],
+        ..Default::default()
+    };
+    let mut log = DidWebvhLog::create(&wallet, LOCATION, options)
+        .await
+        .unwrap();
+    let did = log.did().unwrap();
+
+    log.deactivate(&wallet, &update_key).await.unwrap();
+    let err = log
+        .update(&wallet, &update_key, DidWebvhUpdate::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DidWebvhError::Deactivated));
+
+    let output = resolve_log(
+        &did,
+        &log,
+        &WitnessProofs::default(),
+        &DidWebvhResolutionOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(output.did_document_metadata.deactivated(), Some(true));
+}
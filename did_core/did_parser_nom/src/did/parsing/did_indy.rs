@@ -0,0 +1,46 @@
+//! https://hyperledger.github.io/indy-did-method/#indy-did-method-identifiers
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::char,
+    combinator::{cut, recognize},
+    sequence::{delimited, terminated, tuple},
+    IResult,
+};
+
+use super::{did_sov::parse_unqualified_sovrin_did, DidPart};
+
+// namespace-char = %x61-7A / DIGIT / "_" / "-"
+fn namespace_segment(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')(
+        input,
+    )
+}
+
+// namespace = 1*namespace-char [":" 1*namespace-char] ":"
+fn did_indy_namespace(input: &str) -> IResult<&str, &str> {
+    alt((
+        terminated(
+            recognize(tuple((namespace_segment, char(':'), namespace_segment))),
+            char(':'),
+        ),
+        terminated(namespace_segment, char(':')),
+    ))(input)
+}
+
+// indy-did = "did:indy:" namespace namespace-identifier
+// namespace-identifier = 21*22(base58char)
+pub(super) fn parse_did_indy(input: &str) -> IResult<&str, DidPart> {
+    fn did_indy_method(input: &str) -> IResult<&str, &str> {
+        delimited(char(':'), tag("indy"), char(':'))(input)
+    }
+    let (input_left, (prefix, method, namespace, id)) = tuple((
+        tag("did"),
+        did_indy_method,
+        cut(did_indy_namespace),
+        cut(parse_unqualified_sovrin_did),
+    ))(input)?;
+
+    Ok((input_left, (prefix, method, Some(namespace), id)))
+}
@@ -1,5 +1,6 @@
 mod did_cheqd;
 mod did_core;
+mod did_indy;
 mod did_key;
 mod did_peer_4;
 mod did_sov;
@@ -14,6 +15,7 @@ use nom::{
 
 use self::{
     did_core::parse_qualified_did,
+    did_indy::parse_did_indy,
     did_key::parse_did_key,
     did_sov::{parse_qualified_sovrin_did, parse_unqualified_sovrin_did},
     did_web::parse_did_web,
@@ -70,6 +72,7 @@ pub fn parse_did_ranges(input: &str) -> IResult<&str, DidRanges> {
         map(parse_did_web, to_did_ranges),
         map(parse_did_key, to_did_ranges),
         map(parse_did_cheqd, to_did_ranges),
+        map(parse_did_indy, to_did_ranges),
         map(parse_qualified_sovrin_did, to_did_ranges),
         map(parse_qualified_did, to_did_ranges),
         map(parse_unqualified_sovrin_did, to_id_range),
@@ -24,6 +24,16 @@ test_cases_negative! {
         "did:indy:s@vrin:7Tqg6BwSSWapxgUDm9KKgg"
     indy_multiple_namespaces_invalid_char_in_method_specific_id:
         "did:indy:sovrin:alpha:%0zqg6BwS.Wapxg-Dm9K_gg"
+    indy_no_namespace:
+        "did:indy:7Tqg6BwSSWapxgUDm9KKgg"
+    indy_uppercase_namespace:
+        "did:indy:Sovrin:7Tqg6BwSSWapxgUDm9KKgg"
+    indy_too_many_namespaces:
+        "did:indy:sovrin:staging:extra:6cgbu8ZPoWTnR5Rv5JcSMB"
+    indy_invalid_id_len:
+        "did:indy:sovrin:7Tqg6BwSSWapxgUDm9K"
+    indy_non_base58_id_char:
+        "did:indy:sovrin:7Tqg6BwSSWapxgUDm9KKg0"
     sov_invalid_len:
         "did:sov:2wJPyULfLLnYTEFYzByf"
     sov_invalid_char:
@@ -59,8 +59,18 @@ test_cases_positive! {
     test_did_indy:
         "did:indy:sovrin:7Tqg6BwSSWapxgUDm9KKgg",
         Some("indy"),
-        None,
-        "sovrin:7Tqg6BwSSWapxgUDm9KKgg"
+        Some("sovrin"),
+        "7Tqg6BwSSWapxgUDm9KKgg"
+    test_did_indy_sub_namespace:
+        "did:indy:sovrin:staging:6cgbu8ZPoWTnR5Rv5JcSMB",
+        Some("indy"),
+        Some("sovrin:staging"),
+        "6cgbu8ZPoWTnR5Rv5JcSMB"
+    test_did_indy_namespace_with_separators:
+        "did:indy:bcovrin-test:Th7MpTaRZVRYnPiabds81Y",
+        Some("indy"),
+        Some("bcovrin-test"),
+        "Th7MpTaRZVRYnPiabds81Y"
     test_did_sov_namespaced:
         "did:sov:builder:VbPQNHsvoLZdaNU7fTBeFx",
         Some("sov"),
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub enum DidDereferencingError {
     InvalidDid,
+    InvalidDidUrl,
     NotFound,
 }
 
@@ -13,6 +14,7 @@ impl Display for DidDereferencingError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             DidDereferencingError::InvalidDid => write!(f, "invalidDid"),
+            DidDereferencingError::InvalidDidUrl => write!(f, "invalidDidUrl"),
             DidDereferencingError::NotFound => write!(f, "notFound"),
         }
     }
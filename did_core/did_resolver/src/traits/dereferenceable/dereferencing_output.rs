@@ -1,12 +1,15 @@
 use std::io::Read;
 
 use super::dereferencing_metadata::DidDereferencingMetadata;
-use crate::shared_types::did_document_metadata::DidDocumentMetadata;
+use crate::shared_types::{
+    did_document_metadata::DidDocumentMetadata, did_resource::DidResourceMetadata,
+};
 
 pub struct DidDereferencingOutput<R: Read + Send + Sync> {
     dereferencing_metadata: DidDereferencingMetadata,
     content_stream: R,
     content_metadata: DidDocumentMetadata,
+    resource_metadata: Option<DidResourceMetadata>,
 }
 
 impl<R> DidDereferencingOutput<R>
@@ -18,6 +21,7 @@ where
             dereferencing_metadata: None,
             content_stream,
             content_metadata: None,
+            resource_metadata: None,
         }
     }
 
@@ -32,12 +36,23 @@ where
     pub fn content_metadata(&self) -> &DidDocumentMetadata {
         &self.content_metadata
     }
+
+    /// Metadata of the dereferenced DID-Linked Resource, if the content is a resource rather
+    /// than (a part of) the DID document.
+    pub fn resource_metadata(&self) -> Option<&DidResourceMetadata> {
+        self.resource_metadata.as_ref()
+    }
+
+    pub fn into_content_stream(self) -> R {
+        self.content_stream
+    }
 }
 
 pub struct DidDDereferencingOutputBuilder<R: Read + Send + Sync> {
     dereferencing_metadata: Option<DidDereferencingMetadata>,
     content_stream: R,
     content_metadata: Option<DidDocumentMetadata>,
+    resource_metadata: Option<DidResourceMetadata>,
 }
 
 impl<R> DidDDereferencingOutputBuilder<R>
@@ -57,11 +72,17 @@ where
         self
     }
 
+    pub fn resource_metadata(mut self, resource_metadata: DidResourceMetadata) -> Self {
+        self.resource_metadata = Some(resource_metadata);
+        self
+    }
+
     pub fn build(self) -> DidDereferencingOutput<R> {
         DidDereferencingOutput {
             dereferencing_metadata: self.dereferencing_metadata.unwrap_or_default(),
             content_stream: self.content_stream,
             content_metadata: self.content_metadata.unwrap_or_default(),
+            resource_metadata: self.resource_metadata,
         }
     }
 }
@@ -0,0 +1,112 @@
+use std::io::Cursor;
+
+use did_doc::schema::{did_doc::DidDocument, service::Service};
+use did_parser_nom::DidUrl;
+
+use super::{
+    dereferencing_error::DidDereferencingError, dereferencing_metadata::DidDereferencingMetadata,
+    dereferencing_output::DidDereferencingOutput,
+};
+use crate::traits::resolvable::resolution_output::DidResolutionOutput;
+
+pub const SERVICE_QUERY: &str = "service";
+pub const RELATIVE_REF_QUERY: &str = "relativeRef";
+pub const VERSION_ID_QUERY: &str = "versionId";
+pub const VERSION_TIME_QUERY: &str = "versionTime";
+
+const DID_JSON_CONTENT_TYPE: &str = "application/did+json";
+const URI_LIST_CONTENT_TYPE: &str = "text/uri-list";
+
+/// Dereferences `did_url` against the already resolved DID document. Supported are
+/// * the DID itself, which yields the whole document
+/// * fragments, which select a verification method or a service
+/// * the `service` and `relativeRef` queries, which select a service endpoint URL
+///
+/// DID URL paths are method specific and have to be dereferenced by the method itself.
+/// See https://www.w3.org/TR/did-resolution/#dereferencing-algorithm
+pub fn dereference_did_document(
+    resolution_output: &DidResolutionOutput,
+    did_url: &DidUrl,
+) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, DidDereferencingError> {
+    if did_url.path().is_some() {
+        return Err(DidDereferencingError::InvalidDidUrl);
+    }
+    let did_document = &resolution_output.did_document;
+    let queries = did_url.queries();
+
+    let (content, content_type) = match queries.get(SERVICE_QUERY) {
+        Some(service_id) => {
+            let endpoint = service_endpoint(
+                did_document,
+                service_id,
+                queries.get(RELATIVE_REF_QUERY).map(String::as_str),
+                did_url.fragment(),
+            )?;
+            (endpoint.into_bytes(), URI_LIST_CONTENT_TYPE)
+        }
+        None => {
+            let value = match did_url.fragment() {
+                Some(fragment) => fragment_content(did_document, did_url, fragment)?,
+                None => serde_json::to_value(did_document)
+                    .map_err(|_| DidDereferencingError::InvalidDid)?,
+            };
+            (value.to_string().into_bytes(), DID_JSON_CONTENT_TYPE)
+        }
+    };
+
+    let dereferencing_metadata = DidDereferencingMetadata::builder()
+        .content_type(content_type.to_string())
+        .build();
+    Ok(DidDereferencingOutput::builder(Cursor::new(content))
+        .dereferencing_metadata(dereferencing_metadata)
+        .content_metadata(resolution_output.did_document_metadata.clone())
+        .build())
+}
+
+fn service_by_fragment<'a>(did_document: &'a DidDocument, fragment: &str) -> Option<&'a Service> {
+    let fragment = format!("#{fragment}");
+    did_document
+        .service()
+        .iter()
+        .find(|service| service.id().as_ref().ends_with(&fragment))
+}
+
+fn fragment_content(
+    did_document: &DidDocument,
+    did_url: &DidUrl,
+    fragment: &str,
+) -> Result<serde_json::Value, DidDereferencingError> {
+    let content = match (
+        did_document.dereference_key(did_url),
+        service_by_fragment(did_document, fragment),
+    ) {
+        (Some(verification_method), None) => serde_json::to_value(verification_method),
+        (None, Some(service)) => serde_json::to_value(service),
+        (None, None) => return Err(DidDereferencingError::NotFound),
+        (Some(_), Some(_)) => return Err(DidDereferencingError::InvalidDidUrl),
+    };
+    content.map_err(|_| DidDereferencingError::InvalidDid)
+}
+
+/// Service endpoint URL of the service `service_id`, with `relative_ref` resolved against it as
+/// per RFC 3986 and the DID URL fragment carried over.
+fn service_endpoint(
+    did_document: &DidDocument,
+    service_id: &str,
+    relative_ref: Option<&str>,
+    fragment: Option<&str>,
+) -> Result<String, DidDereferencingError> {
+    let service =
+        service_by_fragment(did_document, service_id).ok_or(DidDereferencingError::NotFound)?;
+    let mut endpoint = match relative_ref {
+        Some(relative_ref) => service
+            .service_endpoint()
+            .join(relative_ref)
+            .map_err(|_| DidDereferencingError::InvalidDidUrl)?,
+        None => service.service_endpoint().clone(),
+    };
+    if fragment.is_some() {
+        endpoint.set_fragment(fragment);
+    }
+    Ok(endpoint.to_string())
+}
@@ -2,6 +2,7 @@ pub mod dereferencing_error;
 pub mod dereferencing_metadata;
 pub mod dereferencing_options;
 pub mod dereferencing_output;
+pub mod document;
 
 use std::io::Read;
 
@@ -11,7 +12,9 @@ use did_parser_nom::DidUrl;
 use self::{
     dereferencing_options::DidDereferencingOptions, dereferencing_output::DidDereferencingOutput,
 };
-use crate::{error::GenericError, traits::resolvable::DidResolvable};
+use crate::{
+    error::GenericError, shared_types::did_resource::DidResource, traits::resolvable::DidResolvable,
+};
 
 #[async_trait]
 pub trait DidDereferenceable: DidResolvable {
@@ -23,3 +26,10 @@ pub trait DidDereferenceable: DidResolvable {
         options: &DidDereferencingOptions,
     ) -> Result<DidDereferencingOutput<Self::Output>, GenericError>;
 }
+
+/// Dereferences method specific resources, such as the DID-Linked Resources addressed by
+/// `did:cheqd:<network>:<id>/resources/<resource id>`.
+#[async_trait]
+pub trait DidResourceDereferenceable {
+    async fn dereference_resource(&self, did_url: &DidUrl) -> Result<DidResource, GenericError>;
+}
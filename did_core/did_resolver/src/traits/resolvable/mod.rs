@@ -1,11 +1,12 @@
 pub mod resolution_error;
 pub mod resolution_metadata;
 pub mod resolution_output;
+pub mod resolution_version;
 
 use async_trait::async_trait;
 use did_parser_nom::Did;
 
-use self::resolution_output::DidResolutionOutput;
+use self::{resolution_output::DidResolutionOutput, resolution_version::ResolutionVersion};
 use crate::error::GenericError;
 
 #[async_trait]
@@ -17,4 +18,15 @@ pub trait DidResolvable {
         did: &Did,
         options: &Self::DidResolutionOptions,
     ) -> Result<DidResolutionOutput, GenericError>;
+
+    /// Adds the selection of a historical version to `options`, used when dereferencing DID URLs
+    /// with a `versionId` or `versionTime` query. The default ignores the selection, so the
+    /// latest version is resolved and the dereferencing fails unless it is the requested one.
+    fn select_version(
+        &self,
+        options: Self::DidResolutionOptions,
+        _version: &ResolutionVersion,
+    ) -> Result<Self::DidResolutionOptions, GenericError> {
+        Ok(options)
+    }
 }
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub enum DidResolutionError {
     InvalidDid,
+    InvalidOptions,
     NotFound,
     RepresentationNotSupported,
     MethodNotSupported,
@@ -27,6 +28,7 @@ impl Display for DidResolutionError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             DidResolutionError::InvalidDid => write!(f, "invalidDid"),
+            DidResolutionError::InvalidOptions => write!(f, "invalidOptions"),
             DidResolutionError::NotFound => write!(f, "notFound"),
             DidResolutionError::RepresentationNotSupported => {
                 write!(f, "representationNotSupported")
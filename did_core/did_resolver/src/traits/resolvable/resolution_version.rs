@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Historical version of a DID document, as selected by the `versionId` or `versionTime` query
+/// of a DID URL. See https://www.w3.org/TR/did-core/#did-parameters
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionVersion {
+    pub version_id: Option<String>,
+    pub version_time: Option<DateTime<Utc>>,
+}
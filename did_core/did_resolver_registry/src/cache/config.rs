@@ -0,0 +1,119 @@
+use std::{collections::HashMap, error::Error, num::NonZeroUsize, sync::Arc, time::Duration};
+
+use did_resolver::traits::resolvable::resolution_error::DidResolutionError;
+
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(30);
+const DEFAULT_CAPACITY: NonZeroUsize = match NonZeroUsize::new(1000) {
+    Some(capacity) => capacity,
+    None => unreachable!(),
+};
+
+/// Decides whether a resolution error means the DID does not exist, in which case the error is
+/// cached for the negative TTL.
+pub type NotFoundPredicate =
+    Arc<dyn Fn(&(dyn Error + Send + Sync + 'static)) -> bool + Send + Sync>;
+
+#[derive(Clone)]
+pub struct DidResolutionCacheConfig {
+    ttl: Duration,
+    negative_ttl: Duration,
+    method_ttls: HashMap<String, Duration>,
+    capacity: NonZeroUsize,
+    is_not_found: NotFoundPredicate,
+}
+
+impl DidResolutionCacheConfig {
+    pub fn builder() -> DidResolutionCacheConfigBuilder {
+        DidResolutionCacheConfigBuilder::default()
+    }
+
+    /// TTL of documents of the DID method, a zero TTL disables caching for the method.
+    pub fn ttl(&self, method: &str) -> Duration {
+        self.method_ttls.get(method).copied().unwrap_or(self.ttl)
+    }
+
+    pub fn negative_ttl(&self) -> Duration {
+        self.negative_ttl
+    }
+
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    pub fn is_not_found(&self, error: &(dyn Error + Send + Sync + 'static)) -> bool {
+        (self.is_not_found)(error)
+    }
+}
+
+impl Default for DidResolutionCacheConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+pub struct DidResolutionCacheConfigBuilder {
+    ttl: Duration,
+    negative_ttl: Duration,
+    method_ttls: HashMap<String, Duration>,
+    capacity: NonZeroUsize,
+    is_not_found: NotFoundPredicate,
+}
+
+impl Default for DidResolutionCacheConfigBuilder {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_TTL,
+            negative_ttl: DEFAULT_NEGATIVE_TTL,
+            method_ttls: HashMap::new(),
+            capacity: DEFAULT_CAPACITY,
+            is_not_found: Arc::new(|error| {
+                matches!(
+                    error.downcast_ref::<DidResolutionError>(),
+                    Some(DidResolutionError::NotFound)
+                )
+            }),
+        }
+    }
+}
+
+impl DidResolutionCacheConfigBuilder {
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Overrides the TTL for DIDs of `method`, e.g. `"cheqd"`.
+    pub fn method_ttl(mut self, method: &str, ttl: Duration) -> Self {
+        self.method_ttls.insert(method.to_string(), ttl);
+        self
+    }
+
+    /// TTL of not found results, a zero TTL disables negative caching.
+    pub fn negative_ttl(mut self, negative_ttl: Duration) -> Self {
+        self.negative_ttl = negative_ttl;
+        self
+    }
+
+    pub fn capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Recognizes not found errors of resolvers which do not report them as
+    /// [DidResolutionError::NotFound].
+    pub fn not_found_predicate(mut self, is_not_found: NotFoundPredicate) -> Self {
+        self.is_not_found = is_not_found;
+        self
+    }
+
+    pub fn build(self) -> DidResolutionCacheConfig {
+        DidResolutionCacheConfig {
+            ttl: self.ttl,
+            negative_ttl: self.negative_ttl,
+            method_ttls: self.method_ttls,
+            capacity: self.capacity,
+            is_not_found: self.is_not_found,
+        }
+    }
+}
@@ -0,0 +1,386 @@
+mod config;
+
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+pub use config::*;
+use did_resolver::{
+    did_parser_nom::Did,
+    error::GenericError,
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::resolvable::{
+        resolution_error::DidResolutionError, resolution_output::DidResolutionOutput,
+        resolution_version::ResolutionVersion, DidResolvable,
+    },
+};
+use lru::LruCache;
+use serde::Serialize;
+
+/// Cache key: the DID and the serialized resolution options it was resolved with.
+type CacheKey = (String, String);
+
+#[derive(Clone, Debug)]
+enum CachedResolution {
+    Resolved(Box<DidResolutionOutput>),
+    NotFound,
+}
+
+struct CacheEntry {
+    resolution: CachedResolution,
+    /// `None` for documents which can not change anymore
+    expires_at: Option<Instant>,
+}
+
+impl CacheEntry {
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Instant::now())
+    }
+}
+
+/// Resolution results shared by any number of [CachingResolver]s, typically one per DID method
+/// registered in the [crate::ResolverRegistry]. Cloning the cache yields a handle to the same
+/// entries.
+///
+/// Entries expire after the TTL of their DID method. Deactivated documents and historical
+/// versions (with a `nextUpdate` in the past) can not change anymore and only leave the cache when
+/// evicted or invalidated, while a `nextUpdate` in the future shortens the TTL. A result never
+/// replaces a cached document which was `updated` more recently.
+#[derive(Clone)]
+pub struct DidResolutionCache {
+    entries: Arc<Mutex<LruCache<CacheKey, CacheEntry>>>,
+    config: Arc<DidResolutionCacheConfig>,
+}
+
+impl DidResolutionCache {
+    pub fn new(config: DidResolutionCacheConfig) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruCache::new(config.capacity()))),
+            config: Arc::new(config),
+        }
+    }
+
+    /// Removes every cached resolution of `did`, whatever the resolution options.
+    pub fn invalidate(&self, did: &Did) {
+        let mut entries = self.lock();
+        let keys = entries
+            .iter()
+            .filter(|((cached_did, _), _)| cached_did == did.did())
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in keys {
+            entries.pop(&key);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LruCache<CacheKey, CacheEntry>> {
+        // the cache holds no invariants a panicking thread could break
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<CachedResolution> {
+        let mut entries = self.lock();
+        match entries.get(key) {
+            Some(entry) if entry.is_expired() => {
+                entries.pop(key);
+                None
+            }
+            Some(entry) => Some(entry.resolution.clone()),
+            None => None,
+        }
+    }
+
+    fn put_resolved(&self, key: CacheKey, method: &str, output: &DidResolutionOutput) {
+        let metadata = &output.did_document_metadata;
+        let Some(expires_at) = self.expiry(method, metadata) else {
+            return;
+        };
+
+        let mut entries = self.lock();
+        if let Some(CacheEntry {
+            resolution: CachedResolution::Resolved(cached),
+            ..
+        }) = entries.peek(&key)
+        {
+            if cached.did_document_metadata.updated() > metadata.updated() {
+                return;
+            }
+        }
+        entries.put(
+            key,
+            CacheEntry {
+                resolution: CachedResolution::Resolved(Box::new(output.clone())),
+                expires_at,
+            },
+        );
+    }
+
+    fn put_not_found(&self, key: CacheKey) {
+        let negative_ttl = self.config.negative_ttl();
+        if negative_ttl.is_zero() {
+            return;
+        }
+        self.lock().put(
+            key,
+            CacheEntry {
+                resolution: CachedResolution::NotFound,
+                expires_at: Some(Instant::now() + negative_ttl),
+            },
+        );
+    }
+
+    /// `None` if the document must not be cached, `Some(None)` if it never expires.
+    fn expiry(&self, method: &str, metadata: &DidDocumentMetadata) -> Option<Option<Instant>> {
+        let ttl = self.config.ttl(method);
+        if ttl.is_zero() {
+            return None;
+        }
+        let now = Utc::now();
+        let ttl = match metadata.next_update() {
+            Some(next_update) if next_update <= now => return Some(None),
+            Some(next_update) => ttl.min((next_update - now).to_std().unwrap_or(Duration::ZERO)),
+            None if metadata.deactivated() == Some(true) => return Some(None),
+            None => ttl,
+        };
+        Some(Some(Instant::now() + ttl))
+    }
+}
+
+/// Serves resolutions of the wrapped resolver from a [DidResolutionCache].
+pub struct CachingResolver<T: DidResolvable> {
+    inner: T,
+    cache: DidResolutionCache,
+}
+
+impl<T: DidResolvable> CachingResolver<T> {
+    pub fn new(inner: T, cache: DidResolutionCache) -> Self {
+        Self { inner, cache }
+    }
+
+    pub fn cache(&self) -> &DidResolutionCache {
+        &self.cache
+    }
+}
+
+#[async_trait]
+impl<T> DidResolvable for CachingResolver<T>
+where
+    T: DidResolvable + Send + Sync,
+    T::DidResolutionOptions: Serialize + Send + Sync,
+{
+    type DidResolutionOptions = T::DidResolutionOptions;
+
+    /// Cached not found results are reported as [DidResolutionError::NotFound], whatever the
+    /// error originally returned by the wrapped resolver.
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let key = (did.did().to_string(), serde_json::to_string(options)?);
+        match self.cache.get(&key) {
+            Some(CachedResolution::Resolved(output)) => return Ok(*output),
+            Some(CachedResolution::NotFound) => return Err(Box::new(DidResolutionError::NotFound)),
+            None => {}
+        }
+
+        let method = did.method().unwrap_or_default();
+        match self.inner.resolve(did, options).await {
+            Ok(output) => {
+                self.cache.put_resolved(key, method, &output);
+                Ok(output)
+            }
+            Err(error) => {
+                if self.cache.config.is_not_found(error.as_ref()) {
+                    self.cache.put_not_found(key);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    fn select_version(
+        &self,
+        options: Self::DidResolutionOptions,
+        version: &ResolutionVersion,
+    ) -> Result<Self::DidResolutionOptions, GenericError> {
+        self.inner.select_version(options, version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        num::NonZeroUsize,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use chrono::{DateTime, TimeDelta, Utc};
+    use did_resolver::did_doc::schema::did_doc::DidDocument;
+
+    use super::*;
+
+    /// Resolves `did:example:<id>`, `did:example:missing` is not found
+    #[derive(Default)]
+    struct CountingResolver {
+        resolutions: Arc<AtomicUsize>,
+        metadata: DidDocumentMetadata,
+    }
+
+    #[async_trait]
+    impl DidResolvable for CountingResolver {
+        type DidResolutionOptions = ();
+
+        async fn resolve(
+            &self,
+            did: &Did,
+            _options: &Self::DidResolutionOptions,
+        ) -> Result<DidResolutionOutput, GenericError> {
+            self.resolutions.fetch_add(1, Ordering::SeqCst);
+            if did.id() == "missing" {
+                return Err(Box::new(DidResolutionError::NotFound));
+            }
+            Ok(DidResolutionOutput::builder(DidDocument::new(did.clone()))
+                .did_document_metadata(self.metadata.clone())
+                .build())
+        }
+    }
+
+    fn caching_resolver(
+        config: DidResolutionCacheConfig,
+        metadata: DidDocumentMetadata,
+    ) -> (CachingResolver<CountingResolver>, Arc<AtomicUsize>) {
+        let resolutions = Arc::new(AtomicUsize::new(0));
+        let resolver = CountingResolver {
+            resolutions: resolutions.clone(),
+            metadata,
+        };
+        (
+            CachingResolver::new(resolver, DidResolutionCache::new(config)),
+            resolutions,
+        )
+    }
+
+    fn did(did: &str) -> Did {
+        Did::parse(did.to_string()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_resolution_is_cached_until_invalidated() {
+        let (resolver, resolutions) =
+            caching_resolver(Default::default(), DidDocumentMetadata::default());
+
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        assert_eq!(resolutions.load(Ordering::SeqCst), 1);
+
+        resolver.cache().invalidate(&did("did:example:1"));
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        assert_eq!(resolutions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_method_ttl_expiry() {
+        let config = DidResolutionCacheConfig::builder()
+            .method_ttl("example", Duration::from_millis(1))
+            .build();
+        let (resolver, resolutions) = caching_resolver(config, DidDocumentMetadata::default());
+
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        assert_eq!(resolutions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_zero_method_ttl_disables_caching() {
+        let config = DidResolutionCacheConfig::builder()
+            .method_ttl("example", Duration::ZERO)
+            .build();
+        let (resolver, _) = caching_resolver(config, DidDocumentMetadata::default());
+
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        assert!(resolver.cache().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deactivated_document_does_not_expire() {
+        let config = DidResolutionCacheConfig::builder()
+            .ttl(Duration::from_millis(1))
+            .build();
+        let metadata = DidDocumentMetadata::builder().deactivated(true).build();
+        let (resolver, resolutions) = caching_resolver(config, metadata);
+
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        assert_eq!(resolutions.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_next_update_shortens_ttl() {
+        let next_update: DateTime<Utc> = Utc::now() + TimeDelta::milliseconds(1);
+        let metadata = DidDocumentMetadata::builder()
+            .next_update(next_update)
+            .build();
+        let (resolver, resolutions) = caching_resolver(Default::default(), metadata);
+
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+        resolver.resolve(&did("did:example:1"), &()).await.unwrap();
+        assert_eq!(resolutions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_cached() {
+        let (resolver, resolutions) =
+            caching_resolver(Default::default(), DidDocumentMetadata::default());
+
+        for _ in 0..2 {
+            let error = resolver
+                .resolve(&did("did:example:missing"), &())
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<DidResolutionError>(),
+                Some(DidResolutionError::NotFound)
+            ));
+        }
+        assert_eq!(resolutions.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_capacity() {
+        let config = DidResolutionCacheConfig::builder()
+            .capacity(NonZeroUsize::new(2).unwrap())
+            .build();
+        let (resolver, resolutions) = caching_resolver(config, DidDocumentMetadata::default());
+
+        for id in ["1", "2", "3", "1"] {
+            resolver
+                .resolve(&did(&format!("did:example:{id}")), &())
+                .await
+                .unwrap();
+        }
+        assert_eq!(resolver.cache().len(), 2);
+        assert_eq!(resolutions.load(Ordering::SeqCst), 4);
+    }
+}
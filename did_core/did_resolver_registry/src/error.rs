@@ -4,6 +4,8 @@ use std::error::Error;
 pub enum DidResolverRegistryError {
     UnsupportedMethod,
     UnqualifiedDid,
+    UnsupportedResource,
+    InvalidOptions(String),
 }
 
 impl std::fmt::Display for DidResolverRegistryError {
@@ -13,6 +15,12 @@ impl std::fmt::Display for DidResolverRegistryError {
             DidResolverRegistryError::UnqualifiedDid => {
                 write!(f, "Attempted to resolve unqualified DID")
             }
+            DidResolverRegistryError::UnsupportedResource => {
+                write!(f, "DID method does not support dereferencing resources")
+            }
+            DidResolverRegistryError::InvalidOptions(details) => {
+                write!(f, "Invalid resolution options: {details}")
+            }
         }
     }
 }
@@ -1,23 +1,63 @@
+pub mod cache;
 pub mod error;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "universal_resolver")]
+pub mod universal_resolver;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, io::Cursor};
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use did_resolver::{
     did_doc::schema::did_doc::DidDocument,
-    did_parser_nom::Did,
+    did_parser_nom::{Did, DidUrl},
     error::GenericError,
-    traits::resolvable::{resolution_output::DidResolutionOutput, DidResolvable},
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::{
+        dereferenceable::{
+            dereferencing_error::DidDereferencingError,
+            dereferencing_metadata::DidDereferencingMetadata,
+            dereferencing_output::DidDereferencingOutput,
+            document::{
+                dereference_did_document, RELATIVE_REF_QUERY, SERVICE_QUERY, VERSION_ID_QUERY,
+                VERSION_TIME_QUERY,
+            },
+            DidResourceDereferenceable,
+        },
+        resolvable::{
+            resolution_output::DidResolutionOutput, resolution_version::ResolutionVersion,
+            DidResolvable,
+        },
+    },
 };
 use error::DidResolverRegistryError;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// Media type of a complete DID resolution result, including the resolution and document
+/// metadata. See https://w3c-ccg.github.io/did-resolution/#did-resolution-result
+pub const RESOLUTION_RESULT_CONTENT_TYPE: &str =
+    "application/ld+json;profile=\"https://w3id.org/did-resolution\"";
+
 pub type GenericResolver = dyn DidResolvableAdaptorTrait + Send + Sync;
 
+pub type GenericResourceDereferencer = dyn DidResourceDereferenceable + Send + Sync;
+
+/// Query parameters with a meaning common to all DID methods, any other query or a DID URL path
+/// addresses a method specific resource.
+const GENERIC_QUERIES: [&str; 4] = [
+    SERVICE_QUERY,
+    RELATIVE_REF_QUERY,
+    VERSION_ID_QUERY,
+    VERSION_TIME_QUERY,
+];
+
 #[derive(Default)]
 pub struct ResolverRegistry {
     resolvers: HashMap<String, Box<GenericResolver>>,
+    fallback_resolver: Option<Box<GenericResolver>>,
+    resource_dereferencers: HashMap<String, Box<GenericResourceDereferencer>>,
 }
 
 pub struct DidResolvableAdaptor<T: DidResolvable> {
@@ -31,6 +71,15 @@ pub trait DidResolvableAdaptorTrait: Send + Sync {
         did: &Did,
         options: HashMap<String, Value>,
     ) -> Result<DidResolutionOutput, GenericError>;
+
+    /// Resolves the historical version of the DID document selected by `version`, if the
+    /// resolver supports it.
+    async fn resolve_version(
+        &self,
+        did: &Did,
+        version: &ResolutionVersion,
+        options: HashMap<String, Value>,
+    ) -> Result<DidResolutionOutput, GenericError>;
 }
 
 #[async_trait]
@@ -43,13 +92,57 @@ where
         did: &Did,
         options: HashMap<String, Value>,
     ) -> Result<DidResolutionOutput, GenericError> {
-        let options: T::DidResolutionOptions = if options.is_empty() {
-            Default::default()
-        } else {
-            let json_map = options.into_iter().collect();
-            serde_json::from_value(Value::Object(json_map))?
-        };
-        let result_inner = self.inner.resolve(did, &options).await?;
+        let options = Self::parse_options(options)?;
+        self.resolve_inner(did, &options).await
+    }
+
+    async fn resolve_version(
+        &self,
+        did: &Did,
+        version: &ResolutionVersion,
+        options: HashMap<String, Value>,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let options = self
+            .inner
+            .select_version(Self::parse_options(options)?, version)?;
+        self.resolve_inner(did, &options).await
+    }
+}
+
+impl<T: DidResolvable + Send + Sync> DidResolvableAdaptor<T>
+where
+    T::DidResolutionOptions: Send + Sync + Serialize + for<'de> Deserialize<'de>,
+{
+    /// Options received as query parameters are JSON scalars where the value looks like one, so
+    /// they are retried as strings for resolvers taking string options only.
+    fn parse_options(
+        options: HashMap<String, Value>,
+    ) -> Result<T::DidResolutionOptions, DidResolverRegistryError> {
+        if options.is_empty() {
+            return Ok(Default::default());
+        }
+        let stringified: serde_json::Map<String, Value> = options
+            .iter()
+            .map(|(name, value)| match value {
+                Value::Bool(_) | Value::Number(_) => {
+                    (name.clone(), Value::String(value.to_string()))
+                }
+                value => (name.clone(), value.clone()),
+            })
+            .collect();
+        let json_map = options.into_iter().collect();
+        serde_json::from_value(Value::Object(json_map)).or_else(|error| {
+            serde_json::from_value(Value::Object(stringified))
+                .map_err(|_| DidResolverRegistryError::InvalidOptions(error.to_string()))
+        })
+    }
+
+    async fn resolve_inner(
+        &self,
+        did: &Did,
+        options: &T::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let result_inner = self.inner.resolve(did, options).await?;
 
         let did_document_inner_hashmap = serde_json::to_value(result_inner.did_document)
             .unwrap()
@@ -88,19 +181,155 @@ impl ResolverRegistry {
         self
     }
 
+    /// Resolver for DIDs of any method without a registered resolver, such as the
+    /// `UniversalResolver` delegating to another resolver service.
+    pub fn register_fallback_resolver<T>(mut self, resolver: T) -> Self
+    where
+        T: DidResolvable + 'static + Send + Sync,
+        for<'de> <T as DidResolvable>::DidResolutionOptions:
+            Send + Sync + Serialize + Deserialize<'de>,
+    {
+        self.fallback_resolver = Some(Box::new(DidResolvableAdaptor { inner: resolver }));
+        self
+    }
+
+    pub fn register_resource_dereferencer<T>(mut self, method: String, dereferencer: T) -> Self
+    where
+        T: DidResourceDereferenceable + 'static + Send + Sync,
+    {
+        self.resource_dereferencers
+            .insert(method, Box::new(dereferencer));
+        self
+    }
+
     pub async fn resolve(
         &self,
         did: &Did,
         options: &HashMap<String, Value>,
     ) -> Result<DidResolutionOutput, GenericError> {
+        self.resolver(did)?.resolve(did, options.clone()).await
+    }
+
+    fn resolver(&self, did: &Did) -> Result<&GenericResolver, DidResolverRegistryError> {
         let method = did
             .method()
             .ok_or(DidResolverRegistryError::UnsupportedMethod)?;
-        match self.resolvers.get(method) {
-            Some(resolver) => resolver.resolve(did, options.clone()).await,
-            None => Err(Box::new(DidResolverRegistryError::UnsupportedMethod)),
+        self.resolvers
+            .get(method)
+            .or(self.fallback_resolver.as_ref())
+            .map(|resolver| resolver.as_ref())
+            .ok_or(DidResolverRegistryError::UnsupportedMethod)
+    }
+
+    /// Dereferences `did_url` to a part of the DID document, a service endpoint URL or a method
+    /// specific resource. The version selected by the `versionId` and `versionTime` queries is
+    /// passed on to the resolver apart from `options`, see [DidResolvable::select_version].
+    /// See https://www.w3.org/TR/did-resolution/#dereferencing-algorithm
+    pub async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        options: &HashMap<String, Value>,
+    ) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, GenericError> {
+        let did = Did::parse(
+            did_url
+                .did()
+                .ok_or(DidResolverRegistryError::UnqualifiedDid)?
+                .to_string(),
+        )?;
+        let queries = did_url.queries();
+
+        let is_resource = did_url.path().is_some()
+            || queries
+                .keys()
+                .any(|query| !GENERIC_QUERIES.contains(&query.as_str()));
+        if is_resource {
+            return self.dereference_resource(&did, did_url).await;
+        }
+
+        let resolver = self.resolver(&did)?;
+        let resolution_output = match requested_version(&queries)? {
+            Some(version) => {
+                let output = resolver
+                    .resolve_version(&did, &version, options.clone())
+                    .await?;
+                check_version(&output.did_document_metadata, &version)?;
+                output
+            }
+            None => resolver.resolve(&did, options.clone()).await?,
+        };
+
+        Ok(dereference_did_document(&resolution_output, did_url)?)
+    }
+
+    async fn dereference_resource(
+        &self,
+        did: &Did,
+        did_url: &DidUrl,
+    ) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, GenericError> {
+        let method = did
+            .method()
+            .ok_or(DidResolverRegistryError::UnsupportedMethod)?;
+        let dereferencer = self
+            .resource_dereferencers
+            .get(method)
+            .ok_or(DidResolverRegistryError::UnsupportedResource)?;
+        let resource = dereferencer.dereference_resource(did_url).await?;
+
+        let dereferencing_metadata = DidDereferencingMetadata::builder()
+            .content_type(resource.metadata.media_type.clone())
+            .build();
+        Ok(
+            DidDereferencingOutput::builder(Cursor::new(resource.content))
+                .dereferencing_metadata(dereferencing_metadata)
+                .resource_metadata(resource.metadata)
+                .build(),
+        )
+    }
+}
+
+/// Version selected by the `versionId` and `versionTime` queries, if any.
+fn requested_version(
+    queries: &HashMap<String, String>,
+) -> Result<Option<ResolutionVersion>, DidDereferencingError> {
+    let version_id = queries.get(VERSION_ID_QUERY).cloned();
+    let version_time = queries
+        .get(VERSION_TIME_QUERY)
+        .map(|version_time| {
+            DateTime::parse_from_rfc3339(version_time)
+                .map(|version_time| version_time.with_timezone(&Utc))
+                .map_err(|_| DidDereferencingError::InvalidDidUrl)
+        })
+        .transpose()?;
+    if version_id.is_none() && version_time.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(ResolutionVersion {
+        version_id,
+        version_time,
+    }))
+}
+
+/// Makes sure the resolver returned the selected version, as not every DID method supports
+/// resolving historical versions.
+fn check_version(
+    metadata: &DidDocumentMetadata,
+    version: &ResolutionVersion,
+) -> Result<(), DidDereferencingError> {
+    if let Some(version_id) = &version.version_id {
+        if metadata.version_id() != Some(version_id) {
+            return Err(DidDereferencingError::NotFound);
+        }
+    }
+    if let Some(version_time) = version.version_time {
+        let valid_from = metadata.updated().or(metadata.created());
+        let valid_until = metadata.next_update();
+        if !valid_from.is_some_and(|valid_from| valid_from <= version_time)
+            || valid_until.is_some_and(|valid_until| valid_until <= version_time)
+        {
+            return Err(DidDereferencingError::NotFound);
         }
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -108,7 +337,10 @@ mod tests {
     use std::{error::Error, pin::Pin};
 
     use async_trait::async_trait;
-    use did_resolver::did_doc::schema::did_doc::DidDocument;
+    use did_resolver::{
+        did_doc::schema::did_doc::DidDocument,
+        shared_types::did_resource::{DidResource, DidResourceMetadata},
+    };
     use mockall::automock;
 
     use super::*;
@@ -274,4 +506,211 @@ mod tests {
         let result_after = registry.resolve(&parsed_did, &HashMap::new()).await;
         assert!(result_after.is_ok());
     }
+
+    /// Resolves `did:example:versioned` in the version requested by the `versionId` option
+    struct VersionedDidResolver;
+
+    #[async_trait]
+    impl DidResolvable for VersionedDidResolver {
+        type DidResolutionOptions = HashMap<String, String>;
+
+        async fn resolve(
+            &self,
+            did: &Did,
+            options: &Self::DidResolutionOptions,
+        ) -> Result<DidResolutionOutput, GenericError> {
+            let version_id = options
+                .get(VERSION_ID_QUERY)
+                .cloned()
+                .unwrap_or_else(|| "2".to_string());
+            let did_document: DidDocument = serde_json::from_value(serde_json::json!({
+                "id": did.did(),
+                "verificationMethod": [{
+                    "id": format!("{did}#key-1"),
+                    "type": "Multikey",
+                    "controller": did.did(),
+                    "publicKeyMultibase": "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+                }],
+                "service": [{
+                    "id": format!("{did}#files"),
+                    "type": "LinkedDomains",
+                    "serviceEndpoint": "https://example.com/files/"
+                }]
+            }))?;
+            let metadata = DidDocumentMetadata::builder()
+                .version_id(version_id)
+                .created("2024-01-01T00:00:00Z".parse()?)
+                .build();
+
+            Ok(DidResolutionOutput::builder(did_document)
+                .did_document_metadata(metadata)
+                .build())
+        }
+
+        fn select_version(
+            &self,
+            mut options: Self::DidResolutionOptions,
+            version: &ResolutionVersion,
+        ) -> Result<Self::DidResolutionOptions, GenericError> {
+            if let Some(version_id) = &version.version_id {
+                options.insert(VERSION_ID_QUERY.to_string(), version_id.clone());
+            }
+            Ok(options)
+        }
+    }
+
+    struct DummyResourceDereferencer;
+
+    #[async_trait]
+    impl DidResourceDereferenceable for DummyResourceDereferencer {
+        async fn dereference_resource(
+            &self,
+            did_url: &DidUrl,
+        ) -> Result<DidResource, GenericError> {
+            Ok(DidResource {
+                content: did_url.path().unwrap_or_default().as_bytes().to_vec(),
+                metadata: DidResourceMetadata {
+                    media_type: "text/plain".to_string(),
+                    ..Default::default()
+                },
+            })
+        }
+    }
+
+    async fn dereference(did_url: &str) -> Result<(String, Option<String>), GenericError> {
+        let registry = ResolverRegistry::new()
+            .register_resolver("example".to_string(), VersionedDidResolver)
+            .register_resource_dereferencer("example".to_string(), DummyResourceDereferencer);
+        let output = registry
+            .dereference(&DidUrl::parse(did_url.to_string())?, &HashMap::new())
+            .await?;
+        let content_type = output.dereferencing_metadata().content_type().cloned();
+        let content = String::from_utf8(output.into_content_stream().into_inner())?;
+        Ok((content, content_type))
+    }
+
+    #[tokio::test]
+    async fn test_dereference_did() {
+        let (content, content_type) = dereference("did:example:versioned").await.unwrap();
+        let did_document: DidDocument = serde_json::from_str(&content).unwrap();
+        assert_eq!(did_document.id().did(), "did:example:versioned");
+        assert_eq!(content_type.as_deref(), Some("application/did+json"));
+    }
+
+    #[tokio::test]
+    async fn test_dereference_fragment() {
+        let (content, _) = dereference("did:example:versioned#key-1").await.unwrap();
+        let verification_method: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(
+            verification_method["id"],
+            "did:example:versioned#key-1".to_string()
+        );
+
+        let (content, _) = dereference("did:example:versioned#files").await.unwrap();
+        let service: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(service["type"], "LinkedDomains".to_string());
+
+        let error = dereference("did:example:versioned#unknown")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<DidDereferencingError>(),
+            Some(&DidDereferencingError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dereference_service_endpoint() {
+        let (content, content_type) =
+            dereference("did:example:versioned?service=files&relativeRef=%2Fresume.pdf%23intro")
+                .await
+                .unwrap();
+        assert_eq!(content, "https://example.com/resume.pdf#intro");
+        assert_eq!(content_type.as_deref(), Some("text/uri-list"));
+
+        let (content, _) = dereference("did:example:versioned?service=files&relativeRef=cv.pdf")
+            .await
+            .unwrap();
+        assert_eq!(content, "https://example.com/files/cv.pdf");
+    }
+
+    #[tokio::test]
+    async fn test_dereference_version() {
+        dereference("did:example:versioned?versionId=1#key-1")
+            .await
+            .unwrap();
+        dereference("did:example:versioned?versionTime=2024-06-01T00:00:00Z")
+            .await
+            .unwrap();
+
+        let error = dereference("did:example:versioned?versionTime=2023-06-01T00:00:00Z")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<DidDereferencingError>(),
+            Some(&DidDereferencingError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dereference_version_with_unit_options() {
+        let did = Did::parse("did:example:1234".to_string()).unwrap();
+        let did_cp = did.clone();
+        let mut mock_resolver = MockDummyDidResolver::new();
+        mock_resolver
+            .expect_resolve()
+            .times(1)
+            .return_once(move |_, _| {
+                let future = async move {
+                    let metadata = DidDocumentMetadata::builder()
+                        .version_id("1".to_string())
+                        .build();
+                    Ok::<DidResolutionOutput, GenericError>(
+                        DidResolutionOutput::builder(DidDocument::new(did_cp))
+                            .did_document_metadata(metadata)
+                            .build(),
+                    )
+                };
+                Pin::from(Box::new(future))
+            });
+        let registry = ResolverRegistry::new()
+            .register_resolver::<MockDummyDidResolver>("example".to_string(), mock_resolver);
+
+        // the version is not passed as an option, which `()` could not hold
+        let output = registry
+            .dereference(
+                &DidUrl::parse(format!("{did}?versionId=1")).unwrap(),
+                &HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            output.dereferencing_metadata().content_type().cloned(),
+            Some("application/did+json".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dereference_resource() {
+        let (content, content_type) = dereference("did:example:versioned/resources/1234")
+            .await
+            .unwrap();
+        assert_eq!(content, "/resources/1234");
+        assert_eq!(content_type.as_deref(), Some("text/plain"));
+
+        let registry =
+            ResolverRegistry::new().register_resolver("example".to_string(), VersionedDidResolver);
+        let error = registry
+            .dereference(
+                &DidUrl::parse("did:example:versioned/resources/1234".to_string()).unwrap(),
+                &HashMap::new(),
+            )
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error.downcast_ref::<DidResolverRegistryError>(),
+            Some(DidResolverRegistryError::UnsupportedResource)
+        ));
+    }
 }
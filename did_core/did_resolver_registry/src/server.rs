@@ -0,0 +1,227 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Query, RawPathParams, State},
+    http::{
+        header::{ACCEPT, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use did_resolver::{
+    did_doc::schema::contexts,
+    did_parser_nom::Did,
+    error::GenericError,
+    traits::resolvable::{
+        resolution_error::DidResolutionError, resolution_output::DidResolutionOutput,
+    },
+};
+use percent_encoding::percent_decode_str;
+use serde_json::{json, Value};
+
+use crate::{error::DidResolverRegistryError, ResolverRegistry, RESOLUTION_RESULT_CONTENT_TYPE};
+
+const DID_LD_JSON_CONTENT_TYPE: &str = "application/did+ld+json";
+const DID_JSON_CONTENT_TYPE: &str = "application/did+json";
+const RESOLUTION_CONTEXT: &str = "https://w3id.org/did-resolution/v1";
+
+/// Representation of the resolution result requested by the `Accept` header
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Representation {
+    ResolutionResult,
+    DidLdJson,
+    DidJson,
+}
+
+impl Representation {
+    /// Picks the first supported media type in the order listed by the client, defaulting to the
+    /// complete resolution result like the Universal Resolver does.
+    fn from_accept(headers: &HeaderMap) -> Option<Self> {
+        let Some(accept) = headers.get(ACCEPT).and_then(|accept| accept.to_str().ok()) else {
+            return Some(Representation::ResolutionResult);
+        };
+
+        accept.split(',').find_map(|media_range| {
+            let mut parameters = media_range.split(';').map(str::trim);
+            let media_type = parameters.next().unwrap_or_default();
+            let profile = parameters
+                .find_map(|parameter| parameter.strip_prefix("profile="))
+                .map(|profile| profile.trim_matches('"'));
+            match (media_type, profile) {
+                ("application/ld+json", Some("https://w3id.org/did-resolution"))
+                | ("*/*", _)
+                | ("application/*", _) => Some(Representation::ResolutionResult),
+                (DID_LD_JSON_CONTENT_TYPE, _) => Some(Representation::DidLdJson),
+                (DID_JSON_CONTENT_TYPE, _) => Some(Representation::DidJson),
+                _ => None,
+            }
+        })
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            Representation::ResolutionResult => RESOLUTION_RESULT_CONTENT_TYPE,
+            Representation::DidLdJson => DID_LD_JSON_CONTENT_TYPE,
+            Representation::DidJson => DID_JSON_CONTENT_TYPE,
+        }
+    }
+}
+
+/// The DID is taken from the raw path, as percent-encoded characters are part of DIDs such as
+/// `did:web:localhost%3A8080`. Clients may send the DID as is, or percent-encoded as a whole, in
+/// which case it no longer starts with `did:`.
+fn did_from_path(raw_did: &str) -> Option<Did> {
+    let did = if raw_did.starts_with("did:") {
+        raw_did.to_string()
+    } else {
+        percent_decode_str(raw_did).decode_utf8().ok()?.into_owned()
+    };
+    Did::parse(did).ok()
+}
+
+/// Query parameters are always strings, values such as `true` or `42` are passed on as JSON
+/// scalars so they deserialize into typed resolution options.
+fn option_value(value: String) -> Value {
+    match serde_json::from_str::<Value>(&value) {
+        Ok(scalar @ (Value::Bool(_) | Value::Number(_))) if scalar.to_string() == value => scalar,
+        _ => Value::String(value),
+    }
+}
+
+async fn resolve(
+    State(registry): State<Arc<ResolverRegistry>>,
+    path_params: RawPathParams,
+    Query(options): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(representation) = Representation::from_accept(&headers) else {
+        return error_response(DidResolutionError::RepresentationNotSupported);
+    };
+    let Some(did) = path_params
+        .iter()
+        .find(|(name, _)| *name == "did")
+        .and_then(|(_, raw_did)| did_from_path(raw_did))
+    else {
+        return error_response(DidResolutionError::InvalidDid);
+    };
+    let options = options
+        .into_iter()
+        .map(|(name, value)| (name, option_value(value)))
+        .collect();
+
+    match registry.resolve(&did, &options).await {
+        Ok(output) => resolution_response(output, representation),
+        Err(error) => error_response(resolution_error(&error)),
+    }
+}
+
+fn resolution_response(output: DidResolutionOutput, representation: Representation) -> Response {
+    let status = match output.did_document_metadata.deactivated() {
+        Some(true) => StatusCode::GONE,
+        _ => StatusCode::OK,
+    };
+    let mut did_document = json!(output.did_document);
+    if representation != Representation::DidJson {
+        if let Some(did_document) = did_document.as_object_mut() {
+            did_document
+                .entry("@context")
+                .or_insert_with(|| json!(contexts::W3C_DID_V1));
+        }
+    }
+
+    let body = match representation {
+        Representation::ResolutionResult => json!({
+            "@context": RESOLUTION_CONTEXT,
+            "didDocument": did_document,
+            "didResolutionMetadata": { "contentType": DID_LD_JSON_CONTENT_TYPE },
+            "didDocumentMetadata": output.did_document_metadata,
+        }),
+        Representation::DidLdJson | Representation::DidJson => did_document,
+    };
+    (
+        status,
+        [(CONTENT_TYPE, representation.content_type())],
+        Json(body),
+    )
+        .into_response()
+}
+
+/// Errors are always reported as a resolution result without a document.
+fn error_response(error: DidResolutionError) -> Response {
+    let status = match error {
+        DidResolutionError::InvalidDid | DidResolutionError::InvalidOptions => {
+            StatusCode::BAD_REQUEST
+        }
+        DidResolutionError::NotFound => StatusCode::NOT_FOUND,
+        DidResolutionError::RepresentationNotSupported => StatusCode::NOT_ACCEPTABLE,
+        DidResolutionError::MethodNotSupported => StatusCode::NOT_IMPLEMENTED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    let body = json!({
+        "@context": RESOLUTION_CONTEXT,
+        "didDocument": null,
+        "didResolutionMetadata": { "error": error },
+        "didDocumentMetadata": {},
+    });
+    (
+        status,
+        [(CONTENT_TYPE, RESOLUTION_RESULT_CONTENT_TYPE)],
+        Json(body),
+    )
+        .into_response()
+}
+
+fn resolution_error(error: &GenericError) -> DidResolutionError {
+    if let Some(error) = error.downcast_ref::<DidResolutionError>() {
+        return error.clone();
+    }
+    match error.downcast_ref::<DidResolverRegistryError>() {
+        Some(DidResolverRegistryError::UnsupportedMethod) => DidResolutionError::MethodNotSupported,
+        Some(DidResolverRegistryError::UnqualifiedDid) => DidResolutionError::InvalidDid,
+        Some(DidResolverRegistryError::InvalidOptions(_)) => DidResolutionError::InvalidOptions,
+        _ => {
+            log::warn!("DID resolution failed: {error}");
+            DidResolutionError::InternalError
+        }
+    }
+}
+
+/// Serves DID resolution by `registry` through the Universal Resolver API:
+/// `GET /1.0/identifiers/{did}`, with resolution options passed as query parameters and the
+/// representation of the result selected through the `Accept` header.
+/// See https://w3c-ccg.github.io/did-resolution/#bindings-https
+pub fn build_router<S>(registry: Arc<ResolverRegistry>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/1.0/identifiers/{did}", get(resolve))
+        .with_state(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_did_from_path() {
+        let did = Did::parse("did:web:localhost%3A8080".to_string()).unwrap();
+
+        assert_eq!(did_from_path("did:web:localhost%3A8080"), Some(did.clone()));
+        assert_eq!(did_from_path("did%3Aweb%3Alocalhost%253A8080"), Some(did));
+        assert_eq!(did_from_path("not-a-did"), None);
+    }
+
+    #[test]
+    fn test_option_value() {
+        assert_eq!(option_value("true".to_string()), json!(true));
+        assert_eq!(option_value("42".to_string()), json!(42));
+        assert_eq!(option_value("abc".to_string()), json!("abc"));
+        assert_eq!(option_value("007".to_string()), json!("007"));
+        assert_eq!(option_value("null".to_string()), json!("null"));
+    }
+}
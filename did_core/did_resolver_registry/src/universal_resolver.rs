@@ -0,0 +1,207 @@
+use std::{collections::HashMap, convert::Infallible};
+
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+use did_resolver::{
+    did_doc::schema::did_doc::DidDocument,
+    did_parser_nom::Did,
+    error::GenericError,
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::{
+        dereferenceable::document::{VERSION_ID_QUERY, VERSION_TIME_QUERY},
+        resolvable::{
+            resolution_error::DidResolutionError, resolution_metadata::DidResolutionMetadata,
+            resolution_output::DidResolutionOutput, resolution_version::ResolutionVersion,
+            DidResolvable,
+        },
+    },
+};
+use http_body_util::{combinators::BoxBody, BodyExt as _, Empty};
+use hyper::{
+    body::Bytes,
+    header::{ACCEPT, CONTENT_TYPE},
+    Request, StatusCode, Uri,
+};
+use hyper_tls::HttpsConnector;
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client},
+    rt::TokioExecutor,
+};
+use serde_json::Value;
+
+use crate::RESOLUTION_RESULT_CONTENT_TYPE;
+
+/// Path of the resolution endpoint, relative to the base URL of the resolver
+pub const IDENTIFIERS_PATH: &str = "/1.0/identifiers/";
+
+/// Resolution options are sent to the remote resolver as query parameters.
+pub type UniversalResolverOptions = HashMap<String, Value>;
+
+/// Delegates the resolution of DIDs of any method to a DIF Universal Resolver compatible HTTP
+/// endpoint. See https://github.com/decentralized-identity/universal-resolver
+pub struct UniversalResolver {
+    client: Client<HttpsConnector<HttpConnector>, BoxBody<Bytes, GenericError>>,
+    base_url: String,
+}
+
+impl UniversalResolver {
+    /// `base_url` is the URL the `/1.0/identifiers/{did}` API is served under, e.g.
+    /// `https://dev.uniresolver.io`. Plain HTTP URLs are supported as well.
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            client: Client::builder(TokioExecutor::new())
+                .build::<_, BoxBody<Bytes, GenericError>>(HttpsConnector::new()),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn resolution_uri(
+        &self,
+        did: &Did,
+        options: &UniversalResolverOptions,
+    ) -> Result<Uri, GenericError> {
+        let mut uri = format!(
+            "{}{IDENTIFIERS_PATH}{}",
+            self.base_url,
+            percent_encode(did.did())
+        );
+        let mut separator = '?';
+        for (name, value) in options {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            uri.push(separator);
+            uri.push_str(&percent_encode(name));
+            uri.push('=');
+            uri.push_str(&percent_encode(&value));
+            separator = '&';
+        }
+        Ok(uri.parse()?)
+    }
+}
+
+#[async_trait]
+impl DidResolvable for UniversalResolver {
+    type DidResolutionOptions = UniversalResolverOptions;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let request = Request::get(self.resolution_uri(did, options)?)
+            .header(ACCEPT, RESOLUTION_RESULT_CONTENT_TYPE)
+            .body(
+                Empty::<Bytes>::new()
+                    .map_err(|never: Infallible| match never {})
+                    .boxed(),
+            )?;
+        let response = self.client.request(request).await?;
+
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map(str::to_string);
+        let body = response.into_body().collect().await?.to_bytes();
+        let result: Option<Value> = serde_json::from_slice(&body).ok();
+
+        // a deactivated DID is still resolved, along with its metadata
+        if !status.is_success() && status != StatusCode::GONE {
+            let error = result
+                .as_ref()
+                .and_then(|result| result.pointer("/didResolutionMetadata/error"))
+                .and_then(|error| serde_json::from_value(error.clone()).ok())
+                .unwrap_or_else(|| resolution_error(status));
+            return Err(Box::new(error));
+        }
+        let result = result.ok_or(DidResolutionError::InternalError)?;
+
+        Ok(parse_resolution_result(result, content_type)?)
+    }
+
+    fn select_version(
+        &self,
+        mut options: Self::DidResolutionOptions,
+        version: &ResolutionVersion,
+    ) -> Result<Self::DidResolutionOptions, GenericError> {
+        if let Some(version_id) = &version.version_id {
+            options.insert(
+                VERSION_ID_QUERY.to_string(),
+                Value::from(version_id.clone()),
+            );
+        }
+        if let Some(version_time) = version.version_time {
+            options.insert(
+                VERSION_TIME_QUERY.to_string(),
+                Value::from(version_time.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            );
+        }
+        Ok(options)
+    }
+}
+
+/// Parses either a complete resolution result or a bare DID document, depending on what the
+/// remote resolver chose to return.
+fn parse_resolution_result(
+    mut result: Value,
+    content_type: Option<String>,
+) -> Result<DidResolutionOutput, serde_json::Error> {
+    let Some(did_document) = result.get_mut("didDocument").map(Value::take) else {
+        let metadata = DidResolutionMetadata::builder()
+            .content_type(content_type.unwrap_or_default())
+            .build();
+        return Ok(
+            DidResolutionOutput::builder(serde_json::from_value(result)?)
+                .did_resolution_metadata(metadata)
+                .build(),
+        );
+    };
+
+    let did_document: DidDocument = serde_json::from_value(did_document)?;
+    let did_document_metadata: DidDocumentMetadata = result
+        .get_mut("didDocumentMetadata")
+        .map(Value::take)
+        .map(serde_json::from_value)
+        .transpose()?
+        .unwrap_or_default();
+    let content_type = result
+        .pointer("/didResolutionMetadata/contentType")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or(content_type)
+        .unwrap_or_default();
+
+    Ok(DidResolutionOutput::builder(did_document)
+        .did_resolution_metadata(
+            DidResolutionMetadata::builder()
+                .content_type(content_type)
+                .build(),
+        )
+        .did_document_metadata(did_document_metadata)
+        .build())
+}
+
+fn resolution_error(status: StatusCode) -> DidResolutionError {
+    match status {
+        StatusCode::BAD_REQUEST => DidResolutionError::InvalidDid,
+        StatusCode::NOT_FOUND => DidResolutionError::NotFound,
+        StatusCode::NOT_ACCEPTABLE => DidResolutionError::RepresentationNotSupported,
+        StatusCode::NOT_IMPLEMENTED => DidResolutionError::MethodNotSupported,
+        _ => DidResolutionError::InternalError,
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
@@ -0,0 +1,60 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use did_resolver::{
+    did_parser_nom::Did,
+    traits::resolvable::{resolution_error::DidResolutionError, DidResolvable},
+};
+use did_resolver_registry::cache::{CachingResolver, DidResolutionCache, DidResolutionCacheConfig};
+use did_resolver_web::resolution::resolver::DidWebResolver;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+/// Answers every request with 404 Not Found, and returns the port it listens on along with the
+/// number of requests served
+async fn serve_not_found() -> (u16, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let requests = Arc::new(AtomicUsize::new(0));
+
+    let served = requests.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).await;
+            served.fetch_add(1, Ordering::SeqCst);
+            let _ = stream
+                .write_all(
+                    b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .await;
+        }
+    });
+
+    (port, requests)
+}
+
+#[tokio::test]
+async fn test_did_web_not_found_is_cached() {
+    let (port, requests) = serve_not_found().await;
+    let resolver = CachingResolver::new(
+        DidWebResolver::http(),
+        DidResolutionCache::new(DidResolutionCacheConfig::default()),
+    );
+    let did = Did::parse(format!("did:web:localhost%3A{port}:user:missing")).unwrap();
+
+    for _ in 0..2 {
+        let error = resolver.resolve(&did, &()).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<DidResolutionError>(),
+            Some(DidResolutionError::NotFound)
+        ));
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(resolver.cache().len(), 1);
+}
@@ -0,0 +1,216 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use did_resolver::{
+    did_doc::schema::did_doc::DidDocument,
+    did_parser_nom::Did,
+    error::GenericError,
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::resolvable::{
+        resolution_error::DidResolutionError, resolution_output::DidResolutionOutput, DidResolvable,
+    },
+};
+use did_resolver_registry::{
+    server::build_router, universal_resolver::UniversalResolver, ResolverRegistry,
+};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+
+/// Resolves `did:example:<id>`, `did:example:deactivated` is deactivated and
+/// `did:example:missing` does not exist
+struct ExampleResolver;
+
+#[async_trait]
+impl DidResolvable for ExampleResolver {
+    type DidResolutionOptions = HashMap<String, String>;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let mut metadata = DidDocumentMetadata::builder();
+        match did.id() {
+            "missing" => return Err(Box::new(DidResolutionError::NotFound)),
+            "deactivated" => metadata = metadata.deactivated(true),
+            _ => {}
+        }
+        if let Some(version_id) = options.get("versionId") {
+            metadata = metadata.version_id(version_id.clone());
+        }
+
+        Ok(DidResolutionOutput::builder(DidDocument::new(did.clone()))
+            .did_document_metadata(metadata.build())
+            .build())
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct TypedOptions {
+    no_cache: Option<bool>,
+    depth: Option<u32>,
+}
+
+/// Resolves `did:typed:<id>`, reporting the options it received as the version id
+struct TypedResolver;
+
+#[async_trait]
+impl DidResolvable for TypedResolver {
+    type DidResolutionOptions = TypedOptions;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let metadata = DidDocumentMetadata::builder()
+            .version_id(format!("{:?}/{:?}", options.no_cache, options.depth))
+            .build();
+
+        Ok(DidResolutionOutput::builder(DidDocument::new(did.clone()))
+            .did_document_metadata(metadata)
+            .build())
+    }
+}
+
+/// Serves a registry resolving `did:example` and `did:typed`, and returns its base URL
+async fn serve() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let registry = ResolverRegistry::new()
+        .register_resolver("example".into(), ExampleResolver)
+        .register_resolver("typed".into(), TypedResolver);
+    tokio::spawn(async move {
+        axum::serve(listener, build_router(Arc::new(registry)))
+            .await
+            .unwrap();
+    });
+    format!("http://{address}")
+}
+
+fn did(did: &str) -> Did {
+    Did::parse(did.to_string()).unwrap()
+}
+
+#[tokio::test]
+async fn test_resolve_through_server() {
+    let resolver = UniversalResolver::new(&serve().await);
+
+    let output = resolver
+        .resolve(&did("did:example:123"), &HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document.id(), &did("did:example:123"));
+    assert_eq!(
+        output.did_document.extra_field("@context"),
+        Some(&serde_json::json!("https://www.w3.org/ns/did/v1"))
+    );
+    assert_eq!(
+        output.did_resolution_metadata.content_type(),
+        Some(&"application/did+ld+json".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_resolution_options_are_forwarded() {
+    let resolver = UniversalResolver::new(&serve().await);
+    let options = HashMap::from([("versionId".to_string(), "2".into())]);
+
+    let output = resolver
+        .resolve(&did("did:example:123"), &options)
+        .await
+        .unwrap();
+    assert_eq!(
+        output.did_document_metadata.version_id(),
+        Some(&"2".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_typed_resolution_options_are_forwarded() {
+    let resolver = UniversalResolver::new(&serve().await);
+    let options = HashMap::from([
+        ("noCache".to_string(), true.into()),
+        ("depth".to_string(), 2.into()),
+    ]);
+
+    let output = resolver
+        .resolve(&did("did:typed:123"), &options)
+        .await
+        .unwrap();
+    assert_eq!(
+        output.did_document_metadata.version_id(),
+        Some(&"Some(true)/Some(2)".to_string())
+    );
+
+    let options = HashMap::from([("unknown".to_string(), "value".into())]);
+    let error = resolver
+        .resolve(&did("did:typed:123"), &options)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<DidResolutionError>(),
+        Some(&DidResolutionError::InvalidOptions)
+    );
+}
+
+#[tokio::test]
+async fn test_percent_encoded_did_is_preserved() {
+    let resolver = UniversalResolver::new(&serve().await);
+
+    let output = resolver
+        .resolve(&did("did:example:localhost%3A8080"), &HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(
+        output.did_document.id(),
+        &did("did:example:localhost%3A8080")
+    );
+}
+
+#[tokio::test]
+async fn test_deactivated_did_is_resolved() {
+    let resolver = UniversalResolver::new(&serve().await);
+
+    let output = resolver
+        .resolve(&did("did:example:deactivated"), &HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document_metadata.deactivated(), Some(true));
+}
+
+#[tokio::test]
+async fn test_resolution_errors() {
+    let resolver = UniversalResolver::new(&serve().await);
+
+    let error = resolver
+        .resolve(&did("did:example:missing"), &HashMap::new())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<DidResolutionError>(),
+        Some(&DidResolutionError::NotFound)
+    );
+
+    let error = resolver
+        .resolve(&did("did:other:123"), &HashMap::new())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<DidResolutionError>(),
+        Some(&DidResolutionError::MethodNotSupported)
+    );
+}
+
+#[tokio::test]
+async fn test_registry_falls_back_to_universal_resolver() {
+    let registry =
+        ResolverRegistry::new().register_fallback_resolver(UniversalResolver::new(&serve().await));
+
+    let output = registry
+        .resolve(&did("did:example:123"), &HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document.id(), &did("did:example:123"));
+}
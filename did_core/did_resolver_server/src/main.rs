@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use did_cheqd::resolution::resolver::{DidCheqdResolver, DidCheqdResolverConfiguration};
+use did_jwk::resolver::DidJwkResolver;
+use did_peer::resolver::PeerDidResolver;
+use did_resolver_key::resolver::DidKeyResolver;
+use did_resolver_registry::{
+    server::build_router, universal_resolver::UniversalResolver, ResolverRegistry,
+};
+use did_resolver_web::resolution::resolver::DidWebResolver;
+use did_webvh::resolver::DidWebvhResolver;
+use log::info;
+
+#[tokio::main]
+async fn main() {
+    load_dot_env();
+    setup_logging();
+    let address = std::env::var("DID_RESOLVER_ADDRESS").unwrap_or("127.0.0.1:8080".into());
+    // methods without a native resolver are delegated to a Universal Resolver, if configured
+    let fallback_url = std::env::var("DID_RESOLVER_FALLBACK_URL").ok();
+
+    let mut registry = ResolverRegistry::new()
+        .register_resolver("key".into(), DidKeyResolver::new())
+        .register_resolver("peer".into(), PeerDidResolver::new())
+        .register_resolver("jwk".into(), DidJwkResolver::new())
+        .register_resolver("web".into(), DidWebResolver::https())
+        .register_resolver("webvh".into(), DidWebvhResolver::https())
+        .register_resolver(
+            "cheqd".into(),
+            DidCheqdResolver::new(DidCheqdResolverConfiguration::default()),
+        );
+    if let Some(fallback_url) = fallback_url {
+        info!("Falling back to the Universal Resolver at {fallback_url}");
+        registry = registry.register_fallback_resolver(UniversalResolver::new(&fallback_url));
+    }
+
+    let router = build_router(Arc::new(registry));
+    info!("Serving DID resolution on {address}");
+    let listener = tokio::net::TcpListener::bind(&address).await.unwrap();
+    axum::serve(listener, router.into_make_service())
+        .await
+        .unwrap();
+}
+
+fn setup_logging() {
+    let env = env_logger::Env::default().default_filter_or("info");
+    env_logger::init_from_env(env);
+}
+
+fn load_dot_env() {
+    let _ = dotenvy::dotenv();
+}
@@ -22,6 +22,8 @@ pub enum PublicKeyError {
     UnsupportedKeyType(String),
     #[error("Invalid KeyType {0}, expected KeyType: {1}")]
     InvalidKeyType(KeyType, KeyType),
+    #[error("Key conversion error: {0}")]
+    KeyConversionError(String),
 }
 
 #[derive(Debug, Error)]
@@ -3,6 +3,8 @@ mod error;
 mod jwk;
 mod key;
 mod key_type;
+#[cfg(feature = "x25519")]
+mod x25519;
 
 pub use error::PublicKeyError;
 pub use key::Key;
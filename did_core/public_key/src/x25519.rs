@@ -0,0 +1,53 @@
+use askar_crypto::{
+    alg::ed25519::Ed25519KeyPair,
+    repr::{KeyPublicBytes, ToPublicBytes},
+};
+
+use crate::{Key, KeyType, PublicKeyError};
+
+impl Key {
+    /// Derives the X25519 key agreement key which corresponds to this Ed25519 verification key,
+    /// using the birational map between the Edwards and Montgomery forms of Curve25519.
+    pub fn ed25519_to_x25519(&self) -> Result<Key, PublicKeyError> {
+        self.validate_key_type(KeyType::Ed25519)?;
+
+        let ed25519_key = Ed25519KeyPair::from_public_bytes(self.key()).map_err(|e| {
+            PublicKeyError::KeyConversionError(format!("Invalid Ed25519 public key: {e}"))
+        })?;
+        let x25519_key = ed25519_key.to_x25519_keypair();
+        let public_bytes = x25519_key.to_public_bytes().map_err(|e| {
+            PublicKeyError::KeyConversionError(format!("Could not encode X25519 key: {e}"))
+        })?;
+
+        Key::new(public_bytes.to_vec(), KeyType::X25519)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // vector from https://w3c-ccg.github.io/did-method-key/#ed25519-x25519
+    const ED25519_FINGERPRINT: &str = "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+    const X25519_FINGERPRINT: &str = "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p";
+
+    #[test]
+    fn test_ed25519_to_x25519() {
+        let key = Key::from_fingerprint(ED25519_FINGERPRINT).unwrap();
+        let x25519_key = key.ed25519_to_x25519().unwrap();
+        assert_eq!(x25519_key.key_type(), &KeyType::X25519);
+        assert_eq!(x25519_key.fingerprint(), X25519_FINGERPRINT);
+    }
+
+    #[test]
+    fn test_x25519_to_x25519_fails() {
+        let key = Key::from_fingerprint(X25519_FINGERPRINT).unwrap();
+        assert!(matches!(
+            key.ed25519_to_x25519(),
+            Err(PublicKeyError::InvalidKeyType(
+                KeyType::X25519,
+                KeyType::Ed25519
+            ))
+        ));
+    }
+}